@@ -47,7 +47,29 @@ pub fn parse_import_key_property(value: &str) -> Result<String, String> {
 
 /// Extended help shown after `driggsby import create --help`.
 /// Contains workflow guidance, schema, and next-step instructions.
-pub const IMPORT_CREATE_AFTER_HELP: &str = "\
+pub const IMPORT_REPLACE_AFTER_HELP: &str = "\
+Replacing an import:
+  Preview the diff first. Rows are reported as unchanged, added, removed, or
+  modified (with the fields that changed):
+    driggsby import replace <import_id> <path> --dry-run
+  Then apply it. The old import is reverted and the corrected file is
+  imported in one transaction, so the ledger never shows a half-swapped state
+  and intelligence is refreshed once:
+    driggsby import replace <import_id> <path>
+  `import list` shows which import replaced which.
+";
+
+const IMPORT_UNDO_AFTER_HELP: &str = "\
+Undoing part of an import:
+  One import can span several accounts or statements. Pass --account,
+  --statement, or both to revert only the matching rows:
+    driggsby import undo <import_id> --account <account_key>
+    driggsby import undo <import_id> --statement <statement_id>
+  The import stays `partially_reverted` until its last rows are undone, and
+  only a fully reverted import can be restored with `driggsby import redo`.
+";
+
+const IMPORT_CREATE_AFTER_HELP: &str = "\
 How import works:
   Driggsby does not parse raw bank PDFs or provider-specific CSVs.
   You parse each statement into a normalized file, then import it.
@@ -98,6 +120,66 @@ Stability rule (important):
   If these drift over time, your ledger analysis will drift too.
   Before mapping new files, run `driggsby import keys uniq` and copy those canonical values.
 
+Remapping drifted values:
+  When dry-run suggests a canonical value (for example `account_key_possible_typo`
+  or `merchant_unseen`), rewrite it at import time instead of editing the file:
+    driggsby import create --remap account_key:chase_chk=chase_checking_1234 <path>
+  `--remap` is repeatable and works for `account_key`, `account_type`,
+  `merchant`, and `category`. Values must match exactly after trimming.
+  For many remaps, pass `--remap-file remaps.json`:
+    {\"merchant\": {\"AMZN Mktp US\": \"Amazon\"}, \"category\": {\"groceries\": \"Groceries\"}}
+  Applied remaps and their row counts are listed under `remaps` and stored
+  with the import.
+  For values that always drift the same way, store a durable alias instead:
+    driggsby alias add merchant \"AMZN Mktp US\" Amazon
+  Aliases are applied to every import before validation and reported under
+  `alias_rewrites`. `driggsby import keys uniq` shows them per canonical value.
+  To set merchant, category, or tags from the description, amount, account, or
+  mcc, add an ordered rule instead:
+    driggsby rules add --description \"^SQ \\*BLUE BOTTLE\" --set-merchant \"Blue Bottle Coffee\"
+  Rules run on every row during validation and are reported under `rule_changes`.
+
+Category suggestions:
+  Rows without `category` get a suggestion when earlier rows from the same
+  counterparty (merchant, or description when merchant is missing) and a
+  similar amount were consistently categorized. Dry-run lists them under
+  `category_suggestions` with a `confidence` and the number of ledger rows
+  behind it. Nothing is set unless you opt in:
+    driggsby import create --apply-category-suggestions <path>
+
+Retries and re-imports:
+  Each import stores a SHA-256 hash of its parsed rows (`source_hash`), so
+  whitespace, line endings and key order do not change it. Importing rows
+  that a committed import already used is refused with
+  `import_source_already_imported` (dry-run warns with `source_already_imported`);
+  undo that import first, or pass --allow-duplicate-source to import it again
+  and let dedupe decide row by row.
+  Agents that may retry after a timeout should pass a key of their choosing:
+    driggsby import create --idempotency-key <key> <path>
+  A retry with the same key and content returns the original `import_id` with
+  `replayed: true` and writes nothing. Reusing a key for different content, or
+  for an import that was undone, fails with `idempotency_key_conflict`.
+  To correct a committed import, swap it for the fixed file in one step:
+    driggsby import replace <import_id> <path> --dry-run
+
+Probable duplicates:
+  Rows that match a ledger row exactly are deduped. Rows with the same account,
+  currency and amount whose date is within 3 days and whose description is
+  similar are still inserted, but listed under `probable_duplicates` with a
+  score. Review them after the import:
+    driggsby import duplicates <import_id>
+    driggsby import duplicates accept <probable_id>
+    driggsby import duplicates reject <probable_id>
+  Accepting removes the incoming row; `import undo` restores it if the row it
+  matched is later reverted.
+  If an exact dedupe was wrong, insert the row anyway with
+    driggsby import duplicates promote <candidate_id>
+  If two ledger rows are the same transaction, remove one with
+    driggsby transaction mark-duplicate <txn_id> --of <kept_txn_id>
+  Every committed row keeps its original source record. Trace a ledger row
+  back to its file, import and row with
+    driggsby transaction show <txn_id>
+
 Field rules (very explicit):
   account_key (required):
     A stable account name. Pick one value and keep it the same forever.
@@ -120,6 +202,8 @@ Field rules (very explicit):
     - negative = money out (`spend`, `card charge`)
     - positive = money in (`refund`, `payment`, `credit`)
     Use exactly one sign convention everywhere. Do not flip signs between imports.
+    If dry-run reports `account_sign_profile_inverted`, fix the mapping or rerun
+    with `--auto-fix-signs` to invert that account's amounts at import time.
     Use at most 2 decimal places.
     Example charge: `-42.15`
     Example refund/payment: `42.15`
@@ -136,6 +220,9 @@ Field rules (very explicit):
     Use `statement_id` = `<account_key>_<statement_end_YYYY-MM-DD>` when available.
     Never reuse the same `statement_id` across different imports/statements.
     Imports with reused provided `statement_id` values are rejected.
+    Dry-run warns with `statement_overlap` when a new statement's dates overlap
+    an existing statement, and with `coverage_gap` when a calendar month has no
+    coverage. Covered ranges and gaps are queryable via `v1_account_coverage`.
     Example: `chase_checking_1234_2026-01-31`
 
   external_id (optional):
@@ -150,6 +237,71 @@ Field rules (very explicit):
   category (optional):
     Clean category label if you know it.
     If you do not know it, omit it.
+
+  balance (optional):
+    Running account balance after this row, as printed on the statement.
+    Import checks each row against the previous one (previous balance + amount)
+    and reports `balance_gaps` with the likely missing or duplicated row.
+    Gaps are reported, not rejected. The latest balance shows in `account list`.
+
+  mcc (optional):
+    Four-digit merchant category code, when your card export provides one.
+    Stored as text so leading zeros survive; `driggsby rules add --mcc` matches on it.
+    Example: `5814`
+
+  statement_opening_balance / statement_closing_balance (optional, together):
+    Statement balances for the row's `statement_id`, as numbers.
+    Rows of the same statement may repeat them or leave them blank,
+    but repeated values must match.
+    Import checks opening + sum(amounts) = closing for every statement with
+    balances and rejects the import with `statement_balance_mismatch` when a
+    row was dropped, duplicated, or sign-flipped.
+
+Statement balances as a sidecar (JSON only):
+  Instead of per-row balance fields, a JSON import may be an object with
+  `statements` and `transactions` arrays:
+  {
+    \"statements\": [
+      {\"statement_id\": \"chase_checking_1234_2026-01-31\", \"opening_balance\": 1000.00, \"closing_balance\": 957.85}
+    ],
+    \"transactions\": [ ...transaction objects as above... ]
+  }
+  `account_key` is optional in each statement entry; add it when the same
+  statement_id is used by more than one account.
+  Reconciled statements are queryable via the `v1_statements` view.
+
+Import policy (optional):
+  Put per-ledger rules in `import_policy.json` in the ledger home
+  (`~/.driggsby` or `DRIGGSBY_HOME`). Every import is checked against them.
+  {
+    \"rules\": [
+      {\"rule\": \"posted_at_not_in_future\"},
+      {\"rule\": \"posted_at_not_before\", \"date\": \"2000-01-01\"},
+      {\"rule\": \"max_abs_amount\", \"limit\": 10000, \"account_type\": \"credit_card\", \"severity\": \"warning\"},
+      {\"rule\": \"require_field\", \"field\": \"merchant\", \"account_type\": \"credit_card\"},
+      {\"rule\": \"currency_allowlist\", \"currencies\": [\"USD\", \"EUR\"]}
+    ]
+  }
+  `severity` is `error` (default; rejects the import) or `warning` (reported
+  under `policy_warnings`). Violations use `policy_*` issue codes.
+  `account_type` scopes a rule to accounts declared with that type in the import.
+
+  The same file can set a dedupe policy per account_key:
+  {
+    \"dedupe\": {
+      \"chase_checking_1234\": {
+        \"key_fields\": [\"external_id\", \"posted_at\", \"amount\"],
+        \"trust_external_id\": false,
+        \"date_tolerance_days\": 2
+      }
+    }
+  }
+  `key_fields` (posted_at, amount, currency, description, statement_id,
+  external_id, merchant) must all match for rows to be duplicates. With
+  `trust_external_id` (default true) a present external_id is enough on its own.
+  `date_tolerance_days` (0-31) lets posted_at differ by that many days.
+  Accounts without an entry keep the default policy. Each import reports the
+  policy used per account under `dedupe_policies`.
 ";
 
 #[derive(Debug, Parser)]
@@ -172,6 +324,12 @@ pub enum Commands {
         #[command(subcommand)]
         command: AccountCommand,
     },
+    /// Manage durable aliases that rewrite values on every import
+    #[command(arg_required_else_help = true)]
+    Alias {
+        #[command(subcommand)]
+        command: AliasCommand,
+    },
     /// Database discovery and query commands
     #[command(arg_required_else_help = true)]
     Db {
@@ -184,6 +342,24 @@ pub enum Commands {
         #[command(subcommand)]
         command: ImportCommand,
     },
+    /// Correct individual ledger transactions
+    #[command(arg_required_else_help = true)]
+    Transaction {
+        #[command(subcommand)]
+        command: TransactionCommand,
+    },
+    /// Label transactions with tags such as #vacation-2026 or #reimbursable
+    #[command(arg_required_else_help = true)]
+    Tag {
+        #[command(subcommand)]
+        command: TagCommand,
+    },
+    /// Manage ordered rules that set merchant, category, or tags
+    #[command(arg_required_else_help = true)]
+    Rules {
+        #[command(subcommand)]
+        command: RulesCommand,
+    },
     /// Internal intelligence maintenance commands
     #[command(hide = true, arg_required_else_help = true)]
     Intelligence {
@@ -204,6 +380,9 @@ pub enum Commands {
         /// End date filter (YYYY-MM-DD)
         #[arg(long, value_parser = parse_iso_date)]
         to: Option<IsoDate>,
+        /// Only show anomalies on transactions carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
         /// Emit structured JSON object output for machine parsing
         #[arg(long)]
         json: bool,
@@ -216,6 +395,9 @@ pub enum Commands {
         /// End date filter (YYYY-MM-DD)
         #[arg(long, value_parser = parse_iso_date)]
         to: Option<IsoDate>,
+        /// Only show patterns that include a transaction carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
         /// Emit structured JSON object output for machine parsing
         #[arg(long)]
         json: bool,
@@ -254,6 +436,262 @@ pub enum AccountCommand {
     },
 }
 
+#[derive(Debug, Clone, Subcommand)]
+pub enum AliasCommand {
+    /// Rewrite an alias to its canonical value on every future import
+    Add {
+        /// Property the alias applies to: account_key, merchant, or category
+        property: String,
+        /// Value as it appears in source files (e.g. "AMZN Mktp US")
+        alias: String,
+        /// Canonical value to store instead (e.g. "Amazon")
+        canonical: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// List stored aliases
+    List {
+        /// Only list aliases for this property
+        #[arg(long)]
+        property: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Stop rewriting an alias on future imports
+    Remove {
+        /// Property the alias applies to: account_key, merchant, or category
+        property: String,
+        /// Alias value to remove
+        alias: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TransactionCommand {
+    /// Show a transaction and the import, file and source row that produced it
+    Show {
+        /// The transaction to show (e.g. txn_abc123)
+        txn_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Record a cash or off-ledger transaction by hand
+    Add {
+        /// Account to record it against (e.g. cash_wallet)
+        #[arg(long = "account", value_name = "ACCOUNT_KEY")]
+        account_key: String,
+        /// Date it happened as YYYY-MM-DD
+        #[arg(long)]
+        posted_at: String,
+        /// Signed amount; spending is negative (e.g. -12.50)
+        #[arg(long, allow_hyphen_values = true)]
+        amount: String,
+        /// ISO currency code
+        #[arg(long, default_value = "USD")]
+        currency: String,
+        /// What it was for
+        #[arg(long)]
+        description: String,
+        /// Merchant name
+        #[arg(long)]
+        merchant: Option<String>,
+        /// Category name
+        #[arg(long)]
+        category: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Override merchant, category or note without touching imported data
+    Edit {
+        /// The transaction to edit (e.g. txn_abc123)
+        txn_id: String,
+        /// Merchant to show instead of the imported one; pass "" to clear
+        #[arg(long)]
+        merchant: Option<String>,
+        /// Category to show instead of the imported one; pass "" to clear
+        #[arg(long)]
+        category: Option<String>,
+        /// Free-form note; pass "" to clear
+        #[arg(long)]
+        note: Option<String>,
+        /// New date as YYYY-MM-DD (manual transactions only)
+        #[arg(long)]
+        posted_at: Option<String>,
+        /// New signed amount (manual transactions only)
+        #[arg(long, allow_hyphen_values = true)]
+        amount: Option<String>,
+        /// New description (manual transactions only)
+        #[arg(long)]
+        description: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete a manual transaction; `import redo` brings it back
+    Delete {
+        /// The manual transaction to delete (e.g. txn_abc123)
+        txn_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Split a transaction into parts with their own amount and category
+    Split {
+        /// The transaction to split (e.g. txn_abc123)
+        txn_id: String,
+        /// A part as AMOUNT:CATEGORY (e.g. 42.10:Groceries); parts must add up to the amount
+        #[arg(
+            long = "part",
+            value_name = "AMOUNT:CATEGORY",
+            allow_hyphen_values = true,
+            conflicts_with = "clear"
+        )]
+        parts: Vec<String>,
+        /// Remove the split and use the transaction's own category again
+        #[arg(long)]
+        clear: bool,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Keep a transaction out of intelligence and reporting, e.g. a transfer
+    Exclude {
+        /// The transaction to exclude (e.g. txn_abc123)
+        txn_id: String,
+        /// Why it is excluded (e.g. transfer, reimbursed, refund)
+        #[arg(long)]
+        reason: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Count an excluded transaction in intelligence and reporting again
+    Include {
+        /// The transaction to include (e.g. txn_abc123)
+        txn_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove a transaction from the ledger as a duplicate of another one
+    MarkDuplicate {
+        /// The transaction to remove (e.g. txn_abc123)
+        txn_id: String,
+        /// The transaction it duplicates, which stays in the ledger
+        #[arg(long = "of", value_name = "TXN_ID")]
+        duplicate_of: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum RulesCommand {
+    /// Add a rule; every condition given must match for its actions to apply
+    Add {
+        /// Regex matched against the raw description (e.g. "^SQ \\*BLUE BOTTLE")
+        #[arg(long, value_name = "REGEX")]
+        description: Option<String>,
+        /// Smallest signed amount that matches (e.g. -20)
+        #[arg(long, allow_hyphen_values = true)]
+        amount_min: Option<f64>,
+        /// Largest signed amount that matches (e.g. 0 for charges only)
+        #[arg(long, allow_hyphen_values = true)]
+        amount_max: Option<f64>,
+        /// Only match rows of this account_key
+        #[arg(long = "account", value_name = "ACCOUNT_KEY")]
+        account_key: Option<String>,
+        /// Only match rows with this four-digit merchant category code
+        #[arg(long)]
+        mcc: Option<String>,
+        /// Merchant to set on matching rows
+        #[arg(long)]
+        set_merchant: Option<String>,
+        /// Category to set on matching rows
+        #[arg(long)]
+        set_category: Option<String>,
+        /// Tag to add to matching rows (repeatable)
+        #[arg(long = "add-tag", value_name = "TAG")]
+        add_tags: Vec<String>,
+        /// Place in evaluation order, starting at 1 (default: last)
+        #[arg(long)]
+        position: Option<i64>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// List rules in evaluation order
+    List {
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove a rule; rows it already changed keep their values
+    Remove {
+        /// The rule to remove (e.g. rule_abc123)
+        rule_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run the rules over transactions already in the ledger
+    Apply {
+        /// Report what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TagCommand {
+    /// Add a tag to transactions selected by id, by SQL filter, or both
+    Add {
+        /// Tag to add (e.g. vacation-2026; a leading # is optional)
+        tag: String,
+        /// Transactions to tag (e.g. txn_abc123)
+        txn_ids: Vec<String>,
+        /// WHERE expression over v1_transactions selecting more transactions
+        #[arg(long = "where", value_name = "SQL")]
+        filter: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove a tag from transactions selected by id, by SQL filter, or both
+    Remove {
+        /// Tag to remove
+        tag: String,
+        /// Transactions to untag (e.g. txn_abc123)
+        txn_ids: Vec<String>,
+        /// WHERE expression over v1_transactions selecting more transactions
+        #[arg(long = "where", value_name = "SQL")]
+        filter: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// List tags in use and how many transactions carry each
+    List {
+        /// Only list the tags of this transaction
+        #[arg(long = "txn", value_name = "TXN_ID")]
+        txn_id: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Debug, Clone, Subcommand)]
 pub enum SchemaCommand {
     /// Show column details for a specific public view
@@ -271,6 +709,24 @@ pub enum ImportCommand {
         /// Validate import data without writing to the ledger
         #[arg(long)]
         dry_run: bool,
+        /// Invert amounts for accounts whose incoming signs mirror ledger history
+        #[arg(long)]
+        auto_fix_signs: bool,
+        /// Fill missing categories from ledger history for this counterparty
+        #[arg(long)]
+        apply_category_suggestions: bool,
+        /// Rewrite a value before validation, as `property:from=to` (repeatable)
+        #[arg(long = "remap", value_name = "PROPERTY:FROM=TO")]
+        remaps: Vec<String>,
+        /// JSON file of remaps shaped like {"account_key": {"from": "to"}}
+        #[arg(long, value_name = "PATH")]
+        remap_file: Option<String>,
+        /// Caller-chosen key that makes retries return the original import
+        #[arg(long, value_name = "KEY", conflicts_with = "dry_run")]
+        idempotency_key: Option<String>,
+        /// Import rows a committed import already brought in from the same source
+        #[arg(long)]
+        allow_duplicate_source: bool,
         /// Emit machine-readable JSON output
         #[arg(long)]
         json: bool,
@@ -284,12 +740,16 @@ pub enum ImportCommand {
         json: bool,
     },
     /// Inspect rows this import was deduped against, with match context
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Duplicates {
         /// The import ID to inspect (e.g. imp_abc123)
-        import_id: String,
+        #[arg(required = true)]
+        import_id: Option<String>,
         /// Emit machine-readable JSON output
         #[arg(long)]
         json: bool,
+        #[command(subcommand)]
+        command: Option<ImportDuplicatesCommand>,
     },
     /// List canonical unique values for high-drift import properties
     #[command(arg_required_else_help = true)]
@@ -297,10 +757,127 @@ pub enum ImportCommand {
         #[command(subcommand)]
         command: ImportKeysCommand,
     },
+    /// Swap a committed import for a corrected file in one step
+    #[command(after_long_help = IMPORT_REPLACE_AFTER_HELP)]
+    Replace {
+        /// The import ID to replace (e.g. imp_abc123)
+        import_id: String,
+        /// Path to the corrected JSON or CSV file (use `-` for stdin)
+        path: String,
+        /// Preview the diff against the old import without writing
+        #[arg(long)]
+        dry_run: bool,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
     /// Revert a previously committed import and restore overwritten transactions
+    #[command(after_long_help = IMPORT_UNDO_AFTER_HELP)]
     Undo {
         /// The import ID to revert (e.g. imp_abc123)
         import_id: String,
+        /// Only revert this account's rows
+        #[arg(long = "account", value_name = "ACCOUNT_KEY")]
+        account_key: Option<String>,
+        /// Only revert rows from this statement
+        #[arg(long = "statement", value_name = "STATEMENT_ID")]
+        statement_id: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Restore an undone import unless newer imports conflict with it
+    Redo {
+        /// The reverted import ID to restore (e.g. imp_abc123)
+        import_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Validate and analyze a file, then hold it for review without writing to the ledger
+    Stage {
+        /// Invert amounts for accounts whose incoming signs mirror ledger history
+        #[arg(long)]
+        auto_fix_signs: bool,
+        /// Fill missing categories from ledger history for this counterparty
+        #[arg(long)]
+        apply_category_suggestions: bool,
+        /// Rewrite a value before validation, as `property:from=to` (repeatable)
+        #[arg(long = "remap", value_name = "PROPERTY:FROM=TO")]
+        remaps: Vec<String>,
+        /// JSON file of remaps shaped like {"account_key": {"from": "to"}}
+        #[arg(long, value_name = "PATH")]
+        remap_file: Option<String>,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+        /// Path to a normalized JSON or CSV file (use `-` for stdin)
+        path: String,
+    },
+    /// Inspect imports waiting for approval
+    #[command(arg_required_else_help = true)]
+    Staged {
+        #[command(subcommand)]
+        command: ImportStagedCommand,
+    },
+    /// Commit a staged import after rechecking it against the ledger
+    Approve {
+        /// The staged import ID to commit (e.g. imp_abc123)
+        import_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Discard a staged import without writing to the ledger
+    Reject {
+        /// The staged import ID to discard (e.g. imp_abc123)
+        import_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ImportStagedCommand {
+    /// List imports waiting for approval
+    List {
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the analysis recorded when an import was staged
+    Show {
+        /// The staged import ID to inspect (e.g. imp_abc123)
+        import_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ImportDuplicatesCommand {
+    /// Insert a deduped row as a real transaction
+    Promote {
+        /// The dedupe candidate ID to insert (e.g. cand_abc123)
+        candidate_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Confirm a probable duplicate and remove the incoming transaction
+    Accept {
+        /// The probable duplicate ID to accept (e.g. prob_abc123)
+        probable_id: String,
+        /// Emit machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Keep both transactions of a probable duplicate
+    Reject {
+        /// The probable duplicate ID to reject (e.g. prob_abc123)
+        probable_id: String,
         /// Emit machine-readable JSON output
         #[arg(long)]
         json: bool,
@@ -353,11 +930,14 @@ where
 mod tests {
     use clap::error::ErrorKind;
 
-    use super::{AccountCommand, Commands, DemoCommand, ImportCommand, parse_from};
+    use super::{
+        AccountCommand, Commands, DemoCommand, ImportCommand, ImportDuplicatesCommand,
+        ImportStagedCommand, RulesCommand, TagCommand, TransactionCommand, parse_from,
+    };
 
     #[test]
     fn parse_command_paths() {
-        let cases: [Vec<&str>; 28] = [
+        let cases: [Vec<&str>; 58] = [
             vec!["driggsby", "account", "list"],
             vec!["driggsby", "account", "list", "--json"],
             vec!["driggsby", "db", "schema"],
@@ -378,9 +958,106 @@ mod tests {
             vec!["driggsby", "import", "list", "--json"],
             vec!["driggsby", "import", "undo", "imp_1", "--json"],
             vec!["driggsby", "import", "undo", "imp_1"],
+            vec![
+                "driggsby",
+                "import",
+                "undo",
+                "imp_1",
+                "--account",
+                "chase_checking_1234",
+                "--statement",
+                "stmt_jan",
+            ],
+            vec!["driggsby", "import", "redo", "imp_1", "--json"],
+            vec![
+                "driggsby",
+                "import",
+                "replace",
+                "imp_1",
+                "./fixed.csv",
+                "--dry-run",
+            ],
             vec!["driggsby", "import", "duplicates", "imp_1"],
             vec!["driggsby", "import", "duplicates", "imp_1", "--json"],
+            vec!["driggsby", "import", "duplicates", "accept", "prob_1"],
+            vec!["driggsby", "import", "duplicates", "promote", "cand_1"],
+            vec!["driggsby", "transaction", "show", "txn_1"],
+            vec!["driggsby", "transaction", "split", "txn_1", "--clear"],
+            vec![
+                "driggsby",
+                "transaction",
+                "exclude",
+                "txn_1",
+                "--reason",
+                "transfer",
+            ],
+            vec!["driggsby", "transaction", "include", "txn_1", "--json"],
+            vec![
+                "driggsby",
+                "transaction",
+                "add",
+                "--account",
+                "cash_wallet",
+                "--posted-at",
+                "2026-03-14",
+                "--amount",
+                "-12.50",
+                "--description",
+                "Farmers market",
+            ],
+            vec![
+                "driggsby",
+                "transaction",
+                "edit",
+                "txn_1",
+                "--amount",
+                "-13.00",
+            ],
+            vec!["driggsby", "transaction", "delete", "txn_1", "--json"],
+            vec![
+                "driggsby",
+                "transaction",
+                "edit",
+                "txn_1",
+                "--note",
+                "split",
+            ],
+            vec!["driggsby", "tag", "list", "--txn", "txn_1"],
+            vec!["driggsby", "rules", "list", "--json"],
+            vec!["driggsby", "rules", "remove", "rule_1"],
+            vec!["driggsby", "rules", "apply", "--dry-run"],
+            vec![
+                "driggsby",
+                "rules",
+                "add",
+                "--mcc",
+                "5814",
+                "--add-tag",
+                "coffee",
+            ],
+            vec!["driggsby", "recurring", "--tag", "reimbursable"],
+            vec![
+                "driggsby",
+                "transaction",
+                "mark-duplicate",
+                "txn_2",
+                "--of",
+                "txn_1",
+            ],
+            vec![
+                "driggsby",
+                "import",
+                "duplicates",
+                "reject",
+                "prob_1",
+                "--json",
+            ],
             vec!["driggsby", "import", "list"],
+            vec!["driggsby", "import", "stage", "./statement.csv", "--json"],
+            vec!["driggsby", "import", "staged", "list"],
+            vec!["driggsby", "import", "staged", "show", "imp_1", "--json"],
+            vec!["driggsby", "import", "approve", "imp_1"],
+            vec!["driggsby", "import", "reject", "imp_1", "--json"],
             vec!["driggsby", "import", "keys", "uniq"],
             vec![
                 "driggsby",
@@ -390,6 +1067,24 @@ mod tests {
                 "account_key",
                 "--json",
             ],
+            vec![
+                "driggsby",
+                "alias",
+                "add",
+                "merchant",
+                "AMZN Mktp US",
+                "Amazon",
+            ],
+            vec![
+                "driggsby",
+                "alias",
+                "list",
+                "--property",
+                "merchant",
+                "--json",
+            ],
+            vec!["driggsby", "alias", "list"],
+            vec!["driggsby", "alias", "remove", "merchant", "AMZN Mktp US"],
             vec!["driggsby", "intelligence", "refresh"],
             vec!["driggsby", "intelligence", "refresh", "--json"],
             vec!["driggsby", "demo", "dash"],
@@ -447,9 +1142,62 @@ mod tests {
             ));
         }
 
+        let parsed_redo = parse_from(["driggsby", "import", "redo", "imp_1"]);
+        assert!(parsed_redo.is_ok());
+        if let Ok(cli) = parsed_redo {
+            assert!(matches!(
+                cli.command,
+                Commands::Import {
+                    command: ImportCommand::Redo { json: false, .. },
+                }
+            ));
+        }
+
         let parsed_list = parse_from(["driggsby", "import", "list"]);
         assert!(parsed_list.is_ok());
 
+        let parsed_staged_show = parse_from(["driggsby", "import", "staged", "show", "imp_1"]);
+        assert!(parsed_staged_show.is_ok());
+        if let Ok(cli) = parsed_staged_show {
+            assert!(matches!(
+                cli.command,
+                Commands::Import {
+                    command: ImportCommand::Staged {
+                        command: ImportStagedCommand::Show { json: false, .. },
+                    },
+                }
+            ));
+        }
+        assert!(parse_from(["driggsby", "import", "stage"]).is_err());
+
+        let parsed_replace = parse_from([
+            "driggsby",
+            "import",
+            "replace",
+            "imp_1",
+            "./fixed.csv",
+            "--dry-run",
+            "--json",
+        ]);
+        assert!(parsed_replace.is_ok());
+        if let Ok(cli) = parsed_replace
+            && let Commands::Import {
+                command:
+                    ImportCommand::Replace {
+                        import_id,
+                        path,
+                        dry_run,
+                        json,
+                    },
+            } = cli.command
+        {
+            assert_eq!(import_id, "imp_1");
+            assert_eq!(path, "./fixed.csv");
+            assert!(dry_run);
+            assert!(json);
+        }
+        assert!(parse_from(["driggsby", "import", "replace", "imp_1"]).is_err());
+
         let parsed_duplicates = parse_from(["driggsby", "import", "duplicates", "imp_1"]);
         assert!(parsed_duplicates.is_ok());
 
@@ -599,6 +1347,197 @@ mod tests {
         let duplicates = parse_from(["driggsby", "import", "duplicates", "imp_1", "--json"]);
         assert!(duplicates.is_ok());
 
+        let accept = parse_from(["driggsby", "import", "duplicates", "accept", "prob_1"]);
+        assert!(accept.is_ok());
+        if let Ok(cli) = accept {
+            assert!(matches!(
+                cli.command,
+                Commands::Import {
+                    command: ImportCommand::Duplicates {
+                        import_id: None,
+                        command: Some(ImportDuplicatesCommand::Accept { .. }),
+                        ..
+                    },
+                }
+            ));
+        }
+
+        let mark = parse_from([
+            "driggsby",
+            "transaction",
+            "mark-duplicate",
+            "txn_2",
+            "--of",
+            "txn_1",
+            "--json",
+        ]);
+        assert!(mark.is_ok());
+        if let Ok(cli) = mark
+            && let Commands::Transaction {
+                command:
+                    TransactionCommand::MarkDuplicate {
+                        txn_id,
+                        duplicate_of,
+                        json,
+                    },
+            } = cli.command
+        {
+            assert_eq!(txn_id, "txn_2");
+            assert_eq!(duplicate_of, "txn_1");
+            assert!(json);
+        }
+        assert!(parse_from(["driggsby", "transaction", "mark-duplicate", "txn_2"]).is_err());
+        assert!(parse_from(["driggsby", "transaction", "exclude", "txn_1"]).is_err());
+
+        let show = parse_from(["driggsby", "transaction", "show", "txn_1", "--json"]);
+        assert!(show.is_ok());
+        if let Ok(cli) = show
+            && let Commands::Transaction {
+                command: TransactionCommand::Show { txn_id, json },
+            } = cli.command
+        {
+            assert_eq!(txn_id, "txn_1");
+            assert!(json);
+        }
+        assert!(parse_from(["driggsby", "transaction", "show"]).is_err());
+
+        let edit = parse_from([
+            "driggsby",
+            "transaction",
+            "edit",
+            "txn_1",
+            "--merchant",
+            "Blue Bottle",
+            "--category",
+            "",
+        ]);
+        assert!(edit.is_ok());
+        if let Ok(cli) = edit
+            && let Commands::Transaction {
+                command:
+                    TransactionCommand::Edit {
+                        txn_id,
+                        merchant,
+                        category,
+                        note,
+                        json,
+                        ..
+                    },
+            } = cli.command
+        {
+            assert_eq!(txn_id, "txn_1");
+            assert_eq!(merchant.as_deref(), Some("Blue Bottle"));
+            assert_eq!(category.as_deref(), Some(""));
+            assert_eq!(note, None);
+            assert!(!json);
+        }
+
+        let split = parse_from([
+            "driggsby",
+            "transaction",
+            "split",
+            "txn_1",
+            "--part",
+            "-80.00:Groceries",
+            "--part",
+            "-40.00:Gifts",
+        ]);
+        assert!(split.is_ok());
+        if let Ok(cli) = split
+            && let Commands::Transaction {
+                command: TransactionCommand::Split { parts, clear, .. },
+            } = cli.command
+        {
+            assert_eq!(parts, vec!["-80.00:Groceries", "-40.00:Gifts"]);
+            assert!(!clear);
+        }
+        assert!(
+            parse_from([
+                "driggsby",
+                "transaction",
+                "split",
+                "txn_1",
+                "--part",
+                "80:Groceries",
+                "--clear",
+            ])
+            .is_err()
+        );
+
+        let tag = parse_from([
+            "driggsby",
+            "tag",
+            "add",
+            "#vacation-2026",
+            "txn_1",
+            "txn_2",
+            "--where",
+            "effective_category = 'Travel'",
+        ]);
+        assert!(tag.is_ok());
+        if let Ok(cli) = tag
+            && let Commands::Tag {
+                command:
+                    TagCommand::Add {
+                        tag,
+                        txn_ids,
+                        filter,
+                        json,
+                    },
+            } = cli.command
+        {
+            assert_eq!(tag, "#vacation-2026");
+            assert_eq!(txn_ids, vec!["txn_1".to_string(), "txn_2".to_string()]);
+            assert_eq!(filter.as_deref(), Some("effective_category = 'Travel'"));
+            assert!(!json);
+        }
+        assert!(parse_from(["driggsby", "tag", "remove"]).is_err());
+
+        let rule = parse_from([
+            "driggsby",
+            "rules",
+            "add",
+            "--description",
+            "^SQ \\*BLUE BOTTLE",
+            "--amount-min",
+            "-20",
+            "--amount-max",
+            "0",
+            "--set-merchant",
+            "Blue Bottle Coffee",
+            "--add-tag",
+            "coffee",
+            "--add-tag",
+            "treats",
+            "--position",
+            "1",
+        ]);
+        assert!(rule.is_ok());
+        if let Ok(cli) = rule
+            && let Commands::Rules {
+                command:
+                    RulesCommand::Add {
+                        description,
+                        amount_min,
+                        amount_max,
+                        set_merchant,
+                        add_tags,
+                        position,
+                        ..
+                    },
+            } = cli.command
+        {
+            assert_eq!(description.as_deref(), Some("^SQ \\*BLUE BOTTLE"));
+            assert_eq!(amount_min, Some(-20.0));
+            assert_eq!(amount_max, Some(0.0));
+            assert_eq!(set_merchant.as_deref(), Some("Blue Bottle Coffee"));
+            assert_eq!(add_tags, vec!["coffee".to_string(), "treats".to_string()]);
+            assert_eq!(position, Some(1));
+        }
+
+        let missing_id = parse_from(["driggsby", "import", "duplicates"]);
+        assert!(missing_id.is_err());
+
         let keys_uniq = parse_from(["driggsby", "import", "keys", "uniq", "--json"]);
         assert!(keys_uniq.is_ok());
     }
@@ -724,4 +1663,97 @@ mod tests {
             ));
         }
     }
+
+    #[test]
+    fn auto_fix_signs_flag_parses_on_import_create() {
+        let parsed = parse_from([
+            "driggsby",
+            "import",
+            "create",
+            "--auto-fix-signs",
+            "--dry-run",
+            "rows.csv",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert!(matches!(
+                cli.command,
+                Commands::Import {
+                    command: ImportCommand::Create {
+                        dry_run: true,
+                        auto_fix_signs: true,
+                        ..
+                    },
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn remap_flags_parse_on_import_create() {
+        let parsed = parse_from([
+            "driggsby",
+            "import",
+            "create",
+            "--remap",
+            "account_key:chase_chk=chase_checking_1234",
+            "--remap",
+            "merchant:AMZN=Amazon",
+            "--remap-file",
+            "remaps.json",
+            "rows.csv",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed
+            && let Commands::Import {
+                command:
+                    ImportCommand::Create {
+                        remaps, remap_file, ..
+                    },
+            } = cli.command
+        {
+            assert_eq!(
+                remaps,
+                vec![
+                    "account_key:chase_chk=chase_checking_1234".to_string(),
+                    "merchant:AMZN=Amazon".to_string(),
+                ]
+            );
+            assert_eq!(remap_file.as_deref(), Some("remaps.json"));
+        }
+    }
+
+    #[test]
+    fn idempotency_key_parses_and_conflicts_with_dry_run() {
+        let parsed = parse_from([
+            "driggsby",
+            "import",
+            "create",
+            "--idempotency-key",
+            "agent-run-42",
+            "rows.csv",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed
+            && let Commands::Import {
+                command:
+                    ImportCommand::Create {
+                        idempotency_key, ..
+                    },
+            } = cli.command
+        {
+            assert_eq!(idempotency_key.as_deref(), Some("agent-run-42"));
+        }
+
+        let conflicting = parse_from([
+            "driggsby",
+            "import",
+            "create",
+            "--dry-run",
+            "--idempotency-key",
+            "agent-run-42",
+            "rows.csv",
+        ]);
+        assert!(conflicting.is_err());
+    }
 }
@@ -2,8 +2,9 @@ use driggsby_client::commands;
 use driggsby_client::{ClientResult, SuccessEnvelope};
 
 use crate::cli::{
-    AccountCommand, Cli, Commands, DbCommand, DemoCommand, ImportCommand, ImportKeysCommand,
-    IntelligenceCommand, SchemaCommand,
+    AccountCommand, AliasCommand, Cli, Commands, DbCommand, DemoCommand, ImportCommand,
+    ImportDuplicatesCommand, ImportKeysCommand, ImportStagedCommand, IntelligenceCommand,
+    RulesCommand, SchemaCommand, TagCommand, TransactionCommand,
 };
 
 pub fn dispatch(cli: &Cli) -> ClientResult<SuccessEnvelope> {
@@ -11,6 +12,18 @@ pub fn dispatch(cli: &Cli) -> ClientResult<SuccessEnvelope> {
         Commands::Account { command } => match command {
             AccountCommand::List { .. } => commands::accounts::run(),
         },
+        Commands::Alias { command } => match command {
+            AliasCommand::Add {
+                property,
+                alias,
+                canonical,
+                ..
+            } => commands::aliases::add(property.clone(), alias.clone(), canonical.clone()),
+            AliasCommand::List { property, .. } => commands::aliases::list(property.clone()),
+            AliasCommand::Remove {
+                property, alias, ..
+            } => commands::aliases::remove(property.clone(), alias.clone()),
+        },
         Commands::Db { command } => match command {
             DbCommand::Schema { command } => match command {
                 Some(SchemaCommand::View { view_name }) => commands::schema::view(view_name),
@@ -21,17 +34,181 @@ pub fn dispatch(cli: &Cli) -> ClientResult<SuccessEnvelope> {
         Commands::Import { command } => match command {
             ImportCommand::Create {
                 dry_run,
+                auto_fix_signs,
+                apply_category_suggestions,
+                remaps,
+                remap_file,
+                idempotency_key,
+                allow_duplicate_source,
                 json: _,
                 path,
-            } => commands::import::run(path.clone(), *dry_run),
+            } => commands::import::run(commands::import::ImportRunOptions {
+                path: path.clone(),
+                dry_run: *dry_run,
+                auto_fix_signs: *auto_fix_signs,
+                apply_category_suggestions: *apply_category_suggestions,
+                remaps: remaps.clone(),
+                remap_file: remap_file.clone(),
+                idempotency_key: idempotency_key.clone(),
+                allow_duplicate_source: *allow_duplicate_source,
+                ..Default::default()
+            }),
             ImportCommand::List { .. } => commands::import::list(),
-            ImportCommand::Duplicates { import_id, .. } => commands::import::duplicates(import_id),
+            ImportCommand::Duplicates {
+                import_id, command, ..
+            } => match command {
+                Some(ImportDuplicatesCommand::Promote { candidate_id, .. }) => {
+                    commands::import::duplicates_promote(candidate_id)
+                }
+                Some(ImportDuplicatesCommand::Accept { probable_id, .. }) => {
+                    commands::import::duplicates_accept(probable_id)
+                }
+                Some(ImportDuplicatesCommand::Reject { probable_id, .. }) => {
+                    commands::import::duplicates_reject(probable_id)
+                }
+                None => commands::import::duplicates(import_id.as_deref().unwrap_or_default()),
+            },
             ImportCommand::Keys { command } => match command {
                 ImportKeysCommand::Uniq { property, .. } => {
                     commands::import::keys_uniq(property.clone())
                 }
             },
-            ImportCommand::Undo { import_id, .. } => commands::import::undo(import_id),
+            ImportCommand::Replace {
+                import_id,
+                path,
+                dry_run,
+                ..
+            } => commands::import::replace(import_id.clone(), path.clone(), *dry_run),
+            ImportCommand::Undo {
+                import_id,
+                account_key,
+                statement_id,
+                ..
+            } => commands::import::undo(import_id, account_key.clone(), statement_id.clone()),
+            ImportCommand::Redo { import_id, .. } => commands::import::redo(import_id),
+            ImportCommand::Stage {
+                auto_fix_signs,
+                apply_category_suggestions,
+                remaps,
+                remap_file,
+                json: _,
+                path,
+            } => commands::import::stage(
+                path.clone(),
+                *auto_fix_signs,
+                *apply_category_suggestions,
+                remaps.clone(),
+                remap_file.clone(),
+            ),
+            ImportCommand::Staged { command } => match command {
+                ImportStagedCommand::List { .. } => commands::import::staged_list(),
+                ImportStagedCommand::Show { import_id, .. } => {
+                    commands::import::staged_show(import_id)
+                }
+            },
+            ImportCommand::Approve { import_id, .. } => commands::import::approve(import_id),
+            ImportCommand::Reject { import_id, .. } => commands::import::reject(import_id),
+        },
+        Commands::Transaction { command } => match command {
+            TransactionCommand::MarkDuplicate {
+                txn_id,
+                duplicate_of,
+                ..
+            } => commands::transactions::mark_duplicate(txn_id.clone(), duplicate_of.clone()),
+            TransactionCommand::Show { txn_id, .. } => commands::transactions::show(txn_id.clone()),
+            TransactionCommand::Add {
+                account_key,
+                posted_at,
+                amount,
+                currency,
+                description,
+                merchant,
+                category,
+                ..
+            } => commands::transactions::add(
+                account_key.clone(),
+                posted_at.clone(),
+                amount.clone(),
+                currency.clone(),
+                description.clone(),
+                merchant.clone(),
+                category.clone(),
+            ),
+            TransactionCommand::Edit {
+                txn_id,
+                merchant,
+                category,
+                note,
+                posted_at,
+                amount,
+                description,
+                ..
+            } => commands::transactions::edit(
+                txn_id.clone(),
+                merchant.clone(),
+                category.clone(),
+                note.clone(),
+                posted_at.clone(),
+                amount.clone(),
+                description.clone(),
+            ),
+            TransactionCommand::Delete { txn_id, .. } => {
+                commands::transactions::delete(txn_id.clone())
+            }
+            TransactionCommand::Split {
+                txn_id,
+                parts,
+                clear,
+                ..
+            } => commands::transactions::split(txn_id.clone(), parts.clone(), *clear),
+            TransactionCommand::Exclude { txn_id, reason, .. } => {
+                commands::transactions::exclude(txn_id.clone(), reason.clone())
+            }
+            TransactionCommand::Include { txn_id, .. } => {
+                commands::transactions::include(txn_id.clone())
+            }
+        },
+        Commands::Tag { command } => match command {
+            TagCommand::Add {
+                tag,
+                txn_ids,
+                filter,
+                ..
+            } => commands::tags::add(tag.clone(), txn_ids.clone(), filter.clone()),
+            TagCommand::Remove {
+                tag,
+                txn_ids,
+                filter,
+                ..
+            } => commands::tags::remove(tag.clone(), txn_ids.clone(), filter.clone()),
+            TagCommand::List { txn_id, .. } => commands::tags::list(txn_id.clone()),
+        },
+        Commands::Rules { command } => match command {
+            RulesCommand::Add {
+                description,
+                amount_min,
+                amount_max,
+                account_key,
+                mcc,
+                set_merchant,
+                set_category,
+                add_tags,
+                position,
+                ..
+            } => commands::rules::add(commands::rules::RuleDefinition {
+                description: description.clone(),
+                amount_min: *amount_min,
+                amount_max: *amount_max,
+                account_key: account_key.clone(),
+                mcc: mcc.clone(),
+                set_merchant: set_merchant.clone(),
+                set_category: set_category.clone(),
+                add_tags: add_tags.clone(),
+                position: *position,
+            }),
+            RulesCommand::List { .. } => commands::rules::list(),
+            RulesCommand::Remove { rule_id, .. } => commands::rules::remove(rule_id.clone()),
+            RulesCommand::Apply { dry_run, .. } => commands::rules::apply(*dry_run),
         },
         Commands::Intelligence { command } => match command {
             IntelligenceCommand::Refresh { .. } => commands::intelligence::refresh(),
@@ -40,15 +217,15 @@ pub fn dispatch(cli: &Cli) -> ClientResult<SuccessEnvelope> {
             let topic = demo_command_to_str(command);
             commands::demo::run(topic)
         }
-        Commands::Anomalies { from, to, .. } => {
+        Commands::Anomalies { from, to, tag, .. } => {
             let from_value = from.as_ref().map(|value| value.as_str());
             let to_value = to.as_ref().map(|value| value.as_str());
-            commands::anomalies::run(from_value, to_value)
+            commands::anomalies::run(from_value, to_value, tag.as_deref())
         }
-        Commands::Recurring { from, to, .. } => {
+        Commands::Recurring { from, to, tag, .. } => {
             let from_value = from.as_ref().map(|value| value.as_str());
             let to_value = to.as_ref().map(|value| value.as_str());
-            commands::recurring::run(from_value, to_value)
+            commands::recurring::run(from_value, to_value, tag.as_deref())
         }
         Commands::Dash => commands::dash::run(),
     }
@@ -47,7 +47,22 @@ Other commands:
   driggsby account list                                   Show account-level ledger orientation
   driggsby import list                                    List past imports
   driggsby import keys uniq                               List canonical import identifiers
+  driggsby alias add <property> <alias> <canonical>       Rewrite a drifting value on every import
+  driggsby alias list                                     List stored aliases
   driggsby import undo <import-id>                        Undo an import
+  driggsby import redo <import-id>                        Restore an undone import
+  driggsby import stage <path>                            Hold an import for review before committing
+  driggsby import approve <import-id>                     Commit a staged import
+  driggsby import replace <import-id> <path>              Swap an import for a corrected file
+  driggsby transaction show <txn-id>                      Show a transaction and the source row it came from
+  driggsby transaction add --account <key> --amount <n>   Record a cash or off-ledger transaction
+  driggsby transaction edit <txn-id> --category <name>    Override merchant, category or note
+  driggsby transaction split <txn-id> --part <amt:cat>    Split a transaction across categories
+  driggsby transaction exclude <txn-id> --reason <why>    Leave a transfer or refund out of reports
+  driggsby transaction mark-duplicate <txn-id> --of <id>  Remove a duplicate transaction
+  driggsby tag add <tag> <txn-id>... [--where <sql>]      Tag transactions, e.g. #reimbursable
+  driggsby rules add --description <regex> ...            Set merchant, category or tags by rule
+  driggsby rules apply --dry-run                          Preview rules against existing transactions
 
 Want to ensure a clean first run, or having issues/errors?
   Run `driggsby import create --help` for import workflow guidance,
@@ -186,10 +201,39 @@ fn command_path_from_args(raw_args: &[String]) -> Option<String> {
         ["import", "keys", "uniq", ..] => Some("import keys uniq"),
         ["import", "create", ..] => Some("import create"),
         ["import", "list", ..] => Some("import list"),
+        ["import", "duplicates", "promote", ..] => Some("import duplicates promote"),
+        ["import", "duplicates", "accept", ..] => Some("import duplicates accept"),
+        ["import", "duplicates", "reject", ..] => Some("import duplicates reject"),
         ["import", "duplicates", ..] => Some("import duplicates"),
         ["import", "undo", ..] => Some("import undo"),
+        ["import", "redo", ..] => Some("import redo"),
+        ["import", "stage", ..] => Some("import stage"),
+        ["import", "staged", "list", ..] => Some("import staged list"),
+        ["import", "staged", "show", ..] => Some("import staged show"),
+        ["import", "staged", ..] => Some("import staged"),
+        ["import", "approve", ..] => Some("import approve"),
+        ["import", "reject", ..] => Some("import reject"),
+        ["import", "replace", ..] => Some("import replace"),
         ["import", "keys", ..] => Some("import keys"),
         ["import", ..] => Some("import"),
+        ["transaction", "mark-duplicate", ..] => Some("transaction mark-duplicate"),
+        ["transaction", "show", ..] => Some("transaction show"),
+        ["transaction", "add", ..] => Some("transaction add"),
+        ["transaction", "edit", ..] => Some("transaction edit"),
+        ["transaction", "delete", ..] => Some("transaction delete"),
+        ["transaction", "split", ..] => Some("transaction split"),
+        ["transaction", "exclude", ..] => Some("transaction exclude"),
+        ["transaction", "include", ..] => Some("transaction include"),
+        ["transaction", ..] => Some("transaction"),
+        ["tag", "add", ..] => Some("tag add"),
+        ["tag", "remove", ..] => Some("tag remove"),
+        ["tag", "list", ..] => Some("tag list"),
+        ["tag", ..] => Some("tag"),
+        ["rules", "add", ..] => Some("rules add"),
+        ["rules", "list", ..] => Some("rules list"),
+        ["rules", "remove", ..] => Some("rules remove"),
+        ["rules", "apply", ..] => Some("rules apply"),
+        ["rules", ..] => Some("rules"),
         ["intelligence", "refresh", ..] => Some("intelligence refresh"),
         ["intelligence", ..] => Some("intelligence"),
         ["demo", "dash", ..] => Some("demo dash"),
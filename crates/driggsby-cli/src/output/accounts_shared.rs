@@ -133,7 +133,8 @@ pub(super) fn render_accounts_table(rows: &[Value], mode: AccountTableMode) -> V
             )
         }
         AccountTableMode::WithDateRange => {
-            let columns = [
+            let show_balance = rows.iter().any(|row| row.get("latest_balance").is_some());
+            let mut columns = vec![
                 Column {
                     name: "Account Key",
                     align: Align::Left,
@@ -163,10 +164,16 @@ pub(super) fn render_accounts_table(rows: &[Value], mode: AccountTableMode) -> V
                     align: Align::Right,
                 },
             ];
+            if show_balance {
+                columns.push(Column {
+                    name: "Balance",
+                    align: Align::Right,
+                });
+            }
             let table_rows = rows
                 .iter()
                 .map(|row| {
-                    vec![
+                    let mut cells = vec![
                         row.get("account_key")
                             .and_then(Value::as_str)
                             .unwrap_or("unknown")
@@ -195,7 +202,16 @@ pub(super) fn render_accounts_table(rows: &[Value], mode: AccountTableMode) -> V
                             "{:.2}",
                             row.get("net_amount").and_then(Value::as_f64).unwrap_or(0.0)
                         ),
-                    ]
+                    ];
+                    if show_balance {
+                        cells.push(
+                            row.get("latest_balance")
+                                .and_then(Value::as_f64)
+                                .map(|balance| format!("{balance:.2}"))
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                    }
+                    cells
                 })
                 .collect::<Vec<Vec<String>>>();
             format::render_table_or_blocks(
@@ -0,0 +1,156 @@
+use std::io;
+
+use serde_json::Value;
+
+use super::format::{self, Align, Column};
+
+pub fn render_alias_add(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("alias add output requires message"))?;
+    let alias = data
+        .get("alias")
+        .ok_or_else(|| io::Error::other("alias add output requires alias"))?;
+
+    let mut lines = vec![message.to_string(), String::new()];
+    lines.extend(render_alias_entry(alias));
+    Ok(lines.join("\n"))
+}
+
+pub fn render_alias_list(data: &Value) -> io::Result<String> {
+    let rows = data
+        .get("rows")
+        .and_then(Value::as_array)
+        .ok_or_else(|| io::Error::other("alias list output requires rows"))?;
+
+    if rows.is_empty() {
+        return Ok([
+            "No aliases stored yet.",
+            "",
+            "Add one when the same value keeps drifting between imports:",
+            "  driggsby alias add merchant \"AMZN Mktp US\" Amazon",
+        ]
+        .join("\n"));
+    }
+
+    let count_label = if rows.len() == 1 {
+        "1 alias stored.".to_string()
+    } else {
+        format!("{} aliases stored.", rows.len())
+    };
+    let columns = [
+        Column {
+            name: "Property",
+            align: Align::Left,
+        },
+        Column {
+            name: "Alias",
+            align: Align::Left,
+        },
+        Column {
+            name: "Canonical",
+            align: Align::Left,
+        },
+    ];
+    let table_rows = rows
+        .iter()
+        .map(|row| {
+            ["property", "alias", "canonical"]
+                .iter()
+                .map(|field| {
+                    row.get(*field)
+                        .and_then(Value::as_str)
+                        .unwrap_or("")
+                        .to_string()
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    let mut lines = vec![count_label, String::new(), "Aliases:".to_string()];
+    lines.extend(format::render_table_or_blocks(
+        &columns,
+        &table_rows,
+        format::terminal_width(),
+        "Alias",
+    ));
+    Ok(lines.join("\n"))
+}
+
+pub fn render_alias_remove(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("alias remove output requires message"))?;
+    Ok(message.to_string())
+}
+
+fn render_alias_entry(alias: &Value) -> Vec<String> {
+    let field = |name: &str| {
+        alias
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string()
+    };
+    format::key_value_rows(
+        &[
+            ("Property:", field("property")),
+            ("Alias:", field("alias")),
+            ("Canonical:", field("canonical")),
+        ],
+        2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{render_alias_add, render_alias_list};
+
+    #[test]
+    fn alias_list_renders_table_and_empty_guidance() {
+        let empty = render_alias_list(&json!({ "rows": [] }));
+        assert!(empty.is_ok());
+        if let Ok(text) = empty {
+            assert!(text.contains("No aliases stored yet."));
+        }
+
+        let listed = render_alias_list(&json!({
+            "rows": [
+                {
+                    "property": "merchant",
+                    "alias": "AMZN Mktp US",
+                    "canonical": "Amazon",
+                    "created_at": "2026-01-01T00:00:00Z"
+                }
+            ]
+        }));
+        assert!(listed.is_ok());
+        if let Ok(text) = listed {
+            assert!(text.contains("1 alias stored."));
+            assert!(text.contains("AMZN Mktp US"));
+            assert!(text.contains("Amazon"));
+        }
+    }
+
+    #[test]
+    fn alias_add_renders_message_and_mapping() {
+        let rendered = render_alias_add(&json!({
+            "message": "Alias `chase-chk` maps to `chase_checking_1234`. Future imports rewrite it automatically.",
+            "alias": {
+                "property": "account_key",
+                "alias": "chase-chk",
+                "canonical": "chase_checking_1234",
+                "created_at": "2026-01-01T00:00:00Z"
+            }
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Alias `chase-chk` maps to"));
+            assert!(text.contains("Canonical:"));
+        }
+    }
+}
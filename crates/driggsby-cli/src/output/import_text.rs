@@ -2,7 +2,7 @@ use std::cmp;
 use std::io;
 
 use chrono::{Local, TimeZone};
-use serde_json::Value;
+use serde_json::{Value, json};
 
 use super::accounts_shared::{AccountTableMode, render_accounts_summary, render_accounts_table};
 use super::format::{self, Align, Column};
@@ -12,19 +12,225 @@ pub fn render_import_run(data: &Value) -> io::Result<String> {
         .get("dry_run")
         .and_then(Value::as_bool)
         .unwrap_or(false);
+    let staged = data.get("staged").and_then(Value::as_bool).unwrap_or(false);
 
+    let replayed = data
+        .get("replayed")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let replaced = data
+        .get("replacement")
+        .is_some_and(|value| !value.is_null());
+    let header = if dry_run {
+        "Dry-run validation completed successfully.".to_string()
+    } else if staged {
+        "Import staged for review. No rows were written.".to_string()
+    } else if replayed || replaced {
+        data.get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("This import was already committed. No rows were written.")
+            .to_string()
+    } else {
+        "Import completed successfully.".to_string()
+    };
+    render_import_sections(data, header, dry_run, staged)
+}
+
+/// The summary and analysis sections shared by import runs and staged imports.
+pub fn render_import_staged_list(data: &Value) -> io::Result<String> {
+    let rows = data
+        .get("rows")
+        .and_then(Value::as_array)
+        .ok_or_else(|| io::Error::other("import staged list output requires rows"))?;
+
+    if rows.is_empty() {
+        return Ok([
+            "No imports are waiting for approval.",
+            "",
+            "Stage one with driggsby import stage <path>.",
+        ]
+        .join("\n"));
+    }
+
+    let count_label = if rows.len() == 1 {
+        "1 staged import found.".to_string()
+    } else {
+        format!("{} staged imports found.", rows.len())
+    };
+    let columns = [
+        Column {
+            name: "Import ID",
+            align: Align::Left,
+        },
+        Column {
+            name: "Staged (local)",
+            align: Align::Left,
+        },
+        Column {
+            name: "Source",
+            align: Align::Left,
+        },
+        Column {
+            name: "Rows Read",
+            align: Align::Right,
+        },
+    ];
+    let table_rows = rows
+        .iter()
+        .map(|row| {
+            vec![
+                row.get("import_id")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown")
+                    .to_string(),
+                format_epoch_local(row, "staged_at"),
+                row.get("source_ref")
+                    .and_then(Value::as_str)
+                    .or_else(|| row.get("source_kind").and_then(Value::as_str))
+                    .unwrap_or("unknown")
+                    .to_string(),
+                row.get("rows_read")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0)
+                    .to_string(),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    let mut lines = vec![count_label, String::new(), "Staged imports:".to_string()];
+    lines.extend(format::render_table_or_blocks(
+        &columns,
+        &table_rows,
+        format::terminal_width(),
+        "Import",
+    ));
+    lines.push(String::new());
+    lines.push("Review one with driggsby import staged show <import-id>.".to_string());
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_import_staged_show(data: &Value) -> io::Result<String> {
+    let import_id = data
+        .get("import_id")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    // The stored analysis uses the import run field names, so the staged
+    // import renders through the same sections as a dry run.
+    let mut merged = data.get("analysis").cloned().unwrap_or_default();
+    if let Some(object) = merged.as_object_mut() {
+        for field in ["import_id", "summary"] {
+            if let Some(value) = data.get(field) {
+                object.insert(field.to_string(), value.clone());
+            }
+        }
+        object.insert(
+            "next_step".to_string(),
+            json!({
+                "label": "Approve this import",
+                "command": format!("driggsby import approve {import_id}"),
+            }),
+        );
+        object.insert(
+            "other_actions".to_string(),
+            json!([{
+                "label": "Reject this import (destructive)",
+                "command": format!("driggsby import reject {import_id}"),
+                "risk": "destructive",
+            }]),
+        );
+    }
+    let header = format!(
+        "Staged import {import_id} (staged {}).",
+        format_epoch_local(data, "staged_at")
+    );
+    render_import_sections(&merged, header, false, true)
+}
+
+pub fn render_import_approve(data: &Value) -> io::Result<String> {
+    let import_id = data
+        .get("import_id")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
     let summary = data
         .get("summary")
         .and_then(Value::as_object)
-        .ok_or_else(|| io::Error::other("import output requires summary"))?;
+        .ok_or_else(|| io::Error::other("import approve output requires summary"))?;
+    let duplicates_now = data
+        .get("duplicate_summary")
+        .and_then(|value| value.get("total"))
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    let duplicates_staged = data
+        .get("staged_duplicate_summary")
+        .and_then(|value| value.get("total"))
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
 
-    let mut lines = Vec::new();
-    if dry_run {
-        lines.push("Dry-run validation completed successfully.".to_string());
-    } else {
-        lines.push("Import completed successfully.".to_string());
-    }
+    let mut lines = vec![
+        data.get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("Staged import approved and committed.")
+            .to_string(),
+        String::new(),
+        "Summary:".to_string(),
+    ];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Import ID:", import_id.to_string()),
+            ("Rows read:", get_i64(summary, "rows_read").to_string()),
+            ("Inserted:", get_i64(summary, "inserted").to_string()),
+            ("Duplicates:", duplicates_now.to_string()),
+            ("Duplicates when staged:", duplicates_staged.to_string()),
+        ],
+        2,
+    ));
+    lines.push(String::new());
+    lines.push(format!(
+        "Run driggsby import undo {import_id} to revert this import."
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_import_reject(data: &Value) -> io::Result<String> {
+    let import_id = data
+        .get("import_id")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let rows_discarded = data
+        .get("rows_discarded")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+
+    let mut lines = vec![
+        "Staged import rejected. No rows were written.".to_string(),
+        String::new(),
+        "Summary:".to_string(),
+    ];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Import ID:", import_id.to_string()),
+            ("Rows discarded:", rows_discarded.to_string()),
+        ],
+        2,
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+fn render_import_sections(
+    data: &Value,
+    header: String,
+    dry_run: bool,
+    staged: bool,
+) -> io::Result<String> {
+    let summary = data
+        .get("summary")
+        .and_then(Value::as_object)
+        .ok_or_else(|| io::Error::other("import output requires summary"))?;
 
+    let mut lines = vec![header];
     lines.push(String::new());
     lines.push("Summary:".to_string());
 
@@ -46,6 +252,11 @@ pub fn render_import_run(data: &Value) -> io::Result<String> {
     entries.push(("Inserted:", get_i64(summary, "inserted").to_string()));
 
     lines.extend(format::key_value_rows(&entries, 2));
+    let replacement = render_replacement_section(data);
+    if !replacement.is_empty() {
+        lines.push(String::new());
+        lines.extend(replacement);
+    }
     let source_warnings = render_source_warnings(data);
     lines.push(String::new());
     if !source_warnings.is_empty() {
@@ -53,6 +264,56 @@ pub fn render_import_run(data: &Value) -> io::Result<String> {
         lines.push(String::new());
     }
     lines.extend(render_duplicate_summary_and_preview(data));
+    let dedupe_policies = render_dedupe_policies_section(data);
+    if !dedupe_policies.is_empty() {
+        lines.push(String::new());
+        lines.extend(dedupe_policies);
+    }
+    let remaps = render_rewrites_section(data, "remaps", "Remaps:");
+    if !remaps.is_empty() {
+        lines.push(String::new());
+        lines.extend(remaps);
+    }
+    let alias_rewrites = render_rewrites_section(data, "alias_rewrites", "Aliases applied:");
+    if !alias_rewrites.is_empty() {
+        lines.push(String::new());
+        lines.extend(alias_rewrites);
+    }
+    let rule_changes = render_rule_changes_section(data);
+    if !rule_changes.is_empty() {
+        lines.push(String::new());
+        lines.extend(rule_changes);
+    }
+    let category_suggestions = render_category_suggestions_section(data);
+    if !category_suggestions.is_empty() {
+        lines.push(String::new());
+        lines.extend(category_suggestions);
+    }
+    let sign_corrections = render_sign_corrections_section(data);
+    if !sign_corrections.is_empty() {
+        lines.push(String::new());
+        lines.extend(sign_corrections);
+    }
+    let reconciled_statements = render_reconciled_statements_section(data);
+    if !reconciled_statements.is_empty() {
+        lines.push(String::new());
+        lines.extend(reconciled_statements);
+    }
+    let policy_warnings = render_policy_warnings_section(data);
+    if !policy_warnings.is_empty() {
+        lines.push(String::new());
+        lines.extend(policy_warnings);
+    }
+    let balance_gaps = render_balance_gaps_section(data);
+    if !balance_gaps.is_empty() {
+        lines.push(String::new());
+        lines.extend(balance_gaps);
+    }
+    let probable_duplicates = render_probable_duplicates_section(data);
+    if !probable_duplicates.is_empty() {
+        lines.push(String::new());
+        lines.extend(probable_duplicates);
+    }
     if !dry_run {
         let ledger_now = render_ledger_accounts_section(data);
         if !ledger_now.is_empty() {
@@ -61,7 +322,7 @@ pub fn render_import_run(data: &Value) -> io::Result<String> {
         }
     }
 
-    if dry_run {
+    if dry_run || staged {
         lines.push(String::new());
         lines.push("Canonical existing values:".to_string());
         lines.extend(render_inventory_sections(data));
@@ -74,8 +335,21 @@ pub fn render_import_run(data: &Value) -> io::Result<String> {
         lines.push("Drift warnings:".to_string());
         lines.extend(render_drift_warnings_section(data));
 
+        if let Some(impact) = data.get("intelligence_impact") {
+            lines.push(String::new());
+            lines.push("Intelligence impact:".to_string());
+            lines.extend(render_intelligence_impact_section(impact));
+        }
+
         lines.push(String::new());
-        lines.push("No rows were written because this was a dry run.".to_string());
+        if staged {
+            lines.push(
+                "No rows were written. Approval rechecks this import against the ledger."
+                    .to_string(),
+            );
+        } else {
+            lines.push("No rows were written because this was a dry run.".to_string());
+        }
     }
 
     lines.push(String::new());
@@ -175,6 +449,20 @@ pub fn render_import_list(data: &Value) -> io::Result<String> {
         "Import",
     ));
 
+    let replacements = ordered_rows
+        .iter()
+        .filter_map(|row| {
+            let import_id = row.get("import_id").and_then(Value::as_str)?;
+            let replaced = row.get("replaces_import_id").and_then(Value::as_str)?;
+            Some(format!("  {import_id} replaced {replaced}"))
+        })
+        .collect::<Vec<String>>();
+    if !replacements.is_empty() {
+        lines.push(String::new());
+        lines.push("Replacements:".to_string());
+        lines.extend(replacements);
+    }
+
     let account_coverage = render_import_list_account_coverage(&ordered_rows);
     if !account_coverage.is_empty() {
         lines.push(String::new());
@@ -193,9 +481,74 @@ pub fn render_import_undo(data: &Value) -> io::Result<String> {
         .get("summary")
         .and_then(Value::as_object)
         .ok_or_else(|| io::Error::other("import undo output requires summary"))?;
+    let partial = data.get("status").and_then(Value::as_str) == Some("partially_reverted");
+
+    let mut lines = vec![
+        if partial {
+            "Import partially reverted.".to_string()
+        } else {
+            "Import reverted successfully.".to_string()
+        },
+        String::new(),
+        "Summary:".to_string(),
+    ];
+
+    let mut rows = vec![("Import ID:", import_id.to_string())];
+    if let Some(account_key) = data.get("account_key").and_then(Value::as_str) {
+        rows.push(("Account:", account_key.to_string()));
+    }
+    if let Some(statement_id) = data.get("statement_id").and_then(Value::as_str) {
+        rows.push(("Statement:", statement_id.to_string()));
+    }
+    rows.extend([
+        (
+            "Rows reverted:",
+            get_i64(summary, "rows_reverted").to_string(),
+        ),
+        (
+            "Rows promoted:",
+            get_i64(summary, "rows_promoted").to_string(),
+        ),
+        (
+            "Intelligence refreshed:",
+            if data
+                .get("intelligence_refreshed")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+            {
+                "yes".to_string()
+            } else {
+                "no".to_string()
+            },
+        ),
+    ]);
+    lines.extend(format::key_value_rows(&rows, 2));
+    lines.push(String::new());
+    if partial {
+        lines.push(format!(
+            "Run driggsby import undo {import_id} to revert the rest of this import."
+        ));
+    } else {
+        lines.push(format!(
+            "Run driggsby import redo {import_id} to restore this import."
+        ));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_import_redo(data: &Value) -> io::Result<String> {
+    let import_id = data
+        .get("import_id")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let summary = data
+        .get("summary")
+        .and_then(Value::as_object)
+        .ok_or_else(|| io::Error::other("import redo output requires summary"))?;
 
     let mut lines = vec![
-        "Import reverted successfully.".to_string(),
+        "Import restored successfully.".to_string(),
         String::new(),
         "Summary:".to_string(),
     ];
@@ -204,12 +557,12 @@ pub fn render_import_undo(data: &Value) -> io::Result<String> {
         &[
             ("Import ID:", import_id.to_string()),
             (
-                "Rows reverted:",
-                get_i64(summary, "rows_reverted").to_string(),
+                "Rows restored:",
+                get_i64(summary, "rows_restored").to_string(),
             ),
             (
-                "Rows promoted:",
-                get_i64(summary, "rows_promoted").to_string(),
+                "Rows deduped:",
+                get_i64(summary, "rows_deduped").to_string(),
             ),
             (
                 "Intelligence refreshed:",
@@ -282,13 +635,10 @@ pub fn render_import_duplicates(data: &Value) -> io::Result<String> {
         format!("Total duplicates: {total}"),
     ];
 
+    lines.push(String::new());
     if rows.is_empty() {
-        lines.push(String::new());
         lines.push("No duplicate rows were recorded for this import.".to_string());
-        return Ok(lines.join("\n"));
     }
-
-    lines.push(String::new());
     for (index, row) in rows.iter().enumerate() {
         lines.extend(render_duplicate_row(row, index + 1));
         if index + 1 < rows.len() {
@@ -296,51 +646,169 @@ pub fn render_import_duplicates(data: &Value) -> io::Result<String> {
         }
     }
 
+    let probable_duplicates = render_probable_duplicates_section(data);
+    if !probable_duplicates.is_empty() {
+        lines.push(String::new());
+        lines.extend(probable_duplicates);
+    }
+
     Ok(lines.join("\n"))
 }
 
-pub fn render_import_keys_uniq(data: &Value) -> io::Result<String> {
-    let inventories = data
-        .get("inventories")
-        .and_then(Value::as_array)
-        .ok_or_else(|| io::Error::other("import keys uniq output requires inventories"))?;
+pub fn render_duplicate_promote(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("duplicate promote output requires message"))?;
+    let field = |name: &str| {
+        data.get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string()
+    };
 
-    if let Some(property) = data.get("property").and_then(Value::as_str) {
-        let inventory = inventories
-            .iter()
-            .find_map(|item| item.as_object())
-            .ok_or_else(|| io::Error::other("import keys uniq output requires one inventory"))?;
-        let mut lines = vec![
-            format!("Canonical unique values for {property}."),
-            String::new(),
-            "Summary:".to_string(),
-        ];
-        lines.extend(format::key_value_rows(
-            &[
-                ("Property:", property.to_string()),
-                (
-                    "Unique count:",
-                    get_i64(inventory, "unique_count").to_string(),
-                ),
-                ("Null count:", get_i64(inventory, "null_count").to_string()),
-                ("Total rows:", get_i64(inventory, "total_rows").to_string()),
-            ],
-            2,
-        ));
-        lines.push(String::new());
-        lines.extend(render_counted_values_list(inventory));
-        return Ok(lines.join("\n"));
-    }
+    let mut lines = vec![message.to_string(), String::new(), "Summary:".to_string()];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Candidate ID:", field("candidate_id")),
+            ("Import ID:", field("import_id")),
+            ("Transaction:", field("promoted_txn_id")),
+            (
+                "Intelligence refreshed:",
+                yes_no(data, "intelligence_refreshed"),
+            ),
+        ],
+        2,
+    ));
 
-    let mut lines = vec![
-        "Canonical unique values from committed ledger transactions.".to_string(),
-        String::new(),
-    ];
+    Ok(lines.join("\n"))
+}
 
-    if inventories.is_empty() {
-        lines.push("No committed transaction rows found yet.".to_string());
-        return Ok(lines.join("\n"));
-    }
+pub fn render_mark_duplicate(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("mark duplicate output requires message"))?;
+    let field = |name: &str| {
+        data.get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string()
+    };
+
+    let mut lines = vec![message.to_string(), String::new(), "Summary:".to_string()];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Transaction:", field("txn_id")),
+            ("Import ID:", field("import_id")),
+            ("Duplicate of:", field("duplicate_of_txn_id")),
+            ("Duplicate of import:", field("duplicate_of_import_id")),
+            ("Candidate ID:", field("candidate_id")),
+            (
+                "Intelligence refreshed:",
+                yes_no(data, "intelligence_refreshed"),
+            ),
+        ],
+        2,
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+fn yes_no(data: &Value, name: &str) -> String {
+    if data.get(name).and_then(Value::as_bool).unwrap_or(false) {
+        "yes".to_string()
+    } else {
+        "no".to_string()
+    }
+}
+
+pub fn render_probable_resolution(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("probable duplicate output requires message"))?;
+    let probable = data
+        .get("probable_duplicate")
+        .ok_or_else(|| io::Error::other("probable duplicate output requires probable_duplicate"))?;
+    let field = |name: &str| {
+        probable
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string()
+    };
+
+    let mut lines = vec![message.to_string(), String::new(), "Summary:".to_string()];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Probable ID:", field("probable_id")),
+            ("Status:", field("status")),
+            ("Import ID:", field("import_id")),
+            ("Transaction:", field("txn_id")),
+            ("Matched transaction:", field("matched_txn_id")),
+            (
+                "Intelligence refreshed:",
+                if data
+                    .get("intelligence_refreshed")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+                {
+                    "yes".to_string()
+                } else {
+                    "no".to_string()
+                },
+            ),
+        ],
+        2,
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_import_keys_uniq(data: &Value) -> io::Result<String> {
+    let inventories = data
+        .get("inventories")
+        .and_then(Value::as_array)
+        .ok_or_else(|| io::Error::other("import keys uniq output requires inventories"))?;
+
+    if let Some(property) = data.get("property").and_then(Value::as_str) {
+        let inventory = inventories
+            .iter()
+            .find_map(|item| item.as_object())
+            .ok_or_else(|| io::Error::other("import keys uniq output requires one inventory"))?;
+        let mut lines = vec![
+            format!("Canonical unique values for {property}."),
+            String::new(),
+            "Summary:".to_string(),
+        ];
+        lines.extend(format::key_value_rows(
+            &[
+                ("Property:", property.to_string()),
+                (
+                    "Unique count:",
+                    get_i64(inventory, "unique_count").to_string(),
+                ),
+                ("Null count:", get_i64(inventory, "null_count").to_string()),
+                ("Total rows:", get_i64(inventory, "total_rows").to_string()),
+            ],
+            2,
+        ));
+        lines.push(String::new());
+        lines.extend(render_counted_values_list(inventory));
+        lines.extend(render_canonical_aliases(inventory));
+        return Ok(lines.join("\n"));
+    }
+
+    let mut lines = vec![
+        "Canonical unique values from committed ledger transactions.".to_string(),
+        String::new(),
+    ];
+
+    if inventories.is_empty() {
+        lines.push("No committed transaction rows found yet.".to_string());
+        return Ok(lines.join("\n"));
+    }
 
     for (index, inventory) in inventories.iter().enumerate() {
         let Some(inventory_map) = inventory.as_object() else {
@@ -370,6 +838,7 @@ pub fn render_import_keys_uniq(data: &Value) -> io::Result<String> {
         ));
         lines.push(String::new());
         lines.extend(render_counted_values_list(inventory_map));
+        lines.extend(render_canonical_aliases(inventory_map));
 
         if index + 1 < inventories.len() {
             lines.push(String::new());
@@ -580,6 +1049,17 @@ fn render_duplicate_row(row: &Value, ordinal: usize) -> Vec<String> {
         }
     }
 
+    if let Some(candidate_id) = row.get("candidate_id").and_then(Value::as_str) {
+        match row.get("promoted_txn_id").and_then(Value::as_str) {
+            Some(promoted_txn_id) => {
+                lines.push(format!("   Promoted: {promoted_txn_id} ({candidate_id})"));
+            }
+            None => lines.push(format!(
+                "   Promote: driggsby import duplicates promote {candidate_id}"
+            )),
+        }
+    }
+
     lines
 }
 
@@ -646,7 +1126,11 @@ fn compare_import_rows(left: &Value, right: &Value) -> cmp::Ordering {
 }
 
 fn parse_created_at(row: &Value) -> Option<i64> {
-    if let Some(raw) = row.get("created_at") {
+    parse_epoch(row, "created_at")
+}
+
+fn parse_epoch(row: &Value, field: &str) -> Option<i64> {
+    if let Some(raw) = row.get(field) {
         if let Some(value) = raw.as_i64() {
             return Some(value);
         }
@@ -658,10 +1142,14 @@ fn parse_created_at(row: &Value) -> Option<i64> {
 }
 
 fn format_created_local(row: &Value) -> String {
-    let Some(created_at) = parse_created_at(row) else {
+    format_epoch_local(row, "created_at")
+}
+
+fn format_epoch_local(row: &Value, field: &str) -> String {
+    let Some(epoch) = parse_epoch(row, field) else {
         return "unknown".to_string();
     };
-    let Some(local_dt) = Local.timestamp_opt(created_at, 0).single() else {
+    let Some(local_dt) = Local.timestamp_opt(epoch, 0).single() else {
         return "unknown".to_string();
     };
     local_dt.format("%Y-%m-%d %H:%M:%S %:z").to_string()
@@ -868,398 +1356,1474 @@ fn render_sign_profile_section(data: &Value) -> Vec<String> {
     format::render_table_or_blocks(&columns, &rows, format::terminal_width(), "Sign profile")
 }
 
-fn render_drift_warnings_section(data: &Value) -> Vec<String> {
-    let Some(warnings) = data.get("drift_warnings").and_then(Value::as_array) else {
-        return vec!["  None.".to_string()];
+fn render_sign_corrections_section(data: &Value) -> Vec<String> {
+    let Some(corrections) = data.get("sign_corrections").and_then(Value::as_array) else {
+        return Vec::new();
     };
 
-    if warnings.is_empty() {
-        return vec!["  None.".to_string()];
+    let mut lines = vec!["Sign corrections:".to_string()];
+    if corrections.is_empty() {
+        lines.push("  None. No account looked sign-inverted.".to_string());
+        return lines;
     }
 
-    let mut lines = Vec::new();
-    for warning in warnings {
-        let severity = warning
-            .get("severity")
-            .and_then(Value::as_str)
-            .unwrap_or("unknown")
-            .to_uppercase();
-        let property = warning
-            .get("property")
-            .and_then(Value::as_str)
-            .unwrap_or("unknown");
-        let incoming_value = warning
-            .get("incoming_value")
+    for correction in corrections {
+        let account_key = correction
+            .get("account_key")
             .and_then(Value::as_str)
             .unwrap_or("unknown");
-        let message = warning
-            .get("message")
-            .and_then(Value::as_str)
-            .unwrap_or("No warning message provided.");
-        lines.push(format!("  [{severity}] {property} = {incoming_value}"));
-        lines.push(format!("    {message}"));
-        if let Some(suggestions) = warning.get("suggestions").and_then(Value::as_array)
-            && !suggestions.is_empty()
-        {
-            let values = suggestions
-                .iter()
-                .filter_map(Value::as_str)
-                .collect::<Vec<&str>>()
-                .join(", ");
-            lines.push(format!("    Suggestions: {values}"));
-        }
+        let rows_inverted = correction
+            .get("rows_inverted")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        let historical = correction
+            .get("historical_negative_ratio")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        let incoming = correction
+            .get("incoming_negative_ratio")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        lines.push(format!(
+            "  {account_key}: inverted {rows_inverted} rows (historical neg {:.2}%, incoming neg {:.2}%)",
+            historical * 100.0,
+            incoming * 100.0
+        ));
     }
 
     lines
 }
 
-fn render_counted_values_list(inventory_map: &serde_json::Map<String, Value>) -> Vec<String> {
-    if let Some(value_counts) = inventory_map.get("value_counts").and_then(Value::as_array) {
-        if value_counts.is_empty() {
-            return vec!["  No values.".to_string()];
-        }
+fn render_reconciled_statements_section(data: &Value) -> Vec<String> {
+    let Some(statements) = data.get("reconciled_statements").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    if statements.is_empty() {
+        return Vec::new();
+    }
 
-        let rows = value_counts
-            .iter()
-            .map(|entry| {
-                let value = entry
-                    .get("value")
+    let columns = [
+        Column {
+            name: "Statement ID",
+            align: Align::Left,
+        },
+        Column {
+            name: "Account Key",
+            align: Align::Left,
+        },
+        Column {
+            name: "Opening",
+            align: Align::Right,
+        },
+        Column {
+            name: "Net",
+            align: Align::Right,
+        },
+        Column {
+            name: "Closing",
+            align: Align::Right,
+        },
+        Column {
+            name: "Rows",
+            align: Align::Right,
+        },
+    ];
+
+    let rows = statements
+        .iter()
+        .map(|statement| {
+            let text = |key: &str| {
+                statement
+                    .get(key)
                     .and_then(Value::as_str)
-                    .unwrap_or("unknown");
-                let count = entry.get("count").and_then(Value::as_i64).unwrap_or(0);
-                (value.to_string(), count.to_string())
-            })
-            .collect::<Vec<(String, String)>>();
+                    .unwrap_or("unknown")
+                    .to_string()
+            };
+            let money = |key: &str| {
+                format!(
+                    "{:.2}",
+                    statement.get(key).and_then(Value::as_f64).unwrap_or(0.0)
+                )
+            };
+            vec![
+                text("statement_id"),
+                text("account_key"),
+                money("opening_balance"),
+                money("net_amount"),
+                money("closing_balance"),
+                statement
+                    .get("txn_count")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0)
+                    .to_string(),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
 
-        return render_value_count_table(&rows);
+    let mut lines = vec!["Reconciled statements:".to_string()];
+    lines.extend(format::render_table_or_blocks(
+        &columns,
+        &rows,
+        format::terminal_width(),
+        "Statement",
+    ));
+    lines
+}
+
+fn render_rewrites_section(data: &Value, key: &str, title: &str) -> Vec<String> {
+    let Some(remaps) = data.get(key).and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    if remaps.is_empty() {
+        return Vec::new();
     }
 
-    let fallback = render_values_list_with_indent(inventory_map.get("existing_values"), 2);
-    if fallback.len() == 1 && fallback[0].trim() == "None" {
-        return vec!["  No values.".to_string()];
+    let mut lines = vec![title.to_string()];
+    for remap in remaps {
+        let property = remap
+            .get("property")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let from = remap.get("from").and_then(Value::as_str).unwrap_or("");
+        let to = remap.get("to").and_then(Value::as_str).unwrap_or("");
+        let rows = remap
+            .get("rows_rewritten")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        let row_label = if rows == 1 { "row" } else { "rows" };
+        lines.push(format!("  {property}: {from} -> {to} ({rows} {row_label})"));
     }
-    fallback
+    lines
 }
 
-fn render_value_count_table(rows: &[(String, String)]) -> Vec<String> {
-    if rows.is_empty() {
-        return vec!["  No values.".to_string()];
+fn render_rule_changes_section(data: &Value) -> Vec<String> {
+    let Some(changes) = data.get("rule_changes").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    if changes.is_empty() {
+        return Vec::new();
     }
 
-    let count_width = rows
-        .iter()
-        .map(|(_, count)| count.len())
-        .max()
-        .unwrap_or(5)
-        .max("Count".len());
-
-    let terminal_width = format::terminal_width();
-    let static_width = 2 + 2 + count_width;
-    let mut value_width = terminal_width
-        .saturating_sub(static_width)
-        .max("Value".len())
-        .max(8);
-
-    let natural_value_width = rows
-        .iter()
-        .map(|(value, _)| value.len())
-        .max()
-        .unwrap_or(8)
-        .max("Value".len());
-    value_width = value_width.min(natural_value_width);
-
-    let mut lines = Vec::new();
-    lines.push(format!(
-        "  {:<value_width$}  {:>count_width$}",
-        "Value", "Count"
-    ));
-    for (value, count) in rows {
-        lines.push(format!(
-            "  {:<value_width$}  {:>count_width$}",
-            truncate_ascii(value, value_width),
-            count
-        ));
+    let mut lines = vec!["Rules applied:".to_string()];
+    for change in changes {
+        let rule_id = change
+            .get("rule_id")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let fields = change
+            .get("fields")
+            .and_then(Value::as_array)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        let rows = change
+            .get("rows_changed")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        let row_label = if rows == 1 { "row" } else { "rows" };
+        lines.push(format!("  {rule_id}: set {fields} ({rows} {row_label})"));
     }
     lines
 }
 
-fn truncate_ascii(value: &str, width: usize) -> String {
-    let char_count = value.chars().count();
-    if char_count <= width {
-        return value.to_string();
+fn render_category_suggestions_section(data: &Value) -> Vec<String> {
+    let Some(suggestions) = data.get("category_suggestions").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    if suggestions.is_empty() {
+        return Vec::new();
     }
-    if width <= 3 {
-        return ".".repeat(width);
+
+    let applied = suggestions
+        .iter()
+        .all(|suggestion| suggestion.get("applied").and_then(Value::as_bool) == Some(true));
+    let mut lines = vec![if applied {
+        "Category suggestions applied:".to_string()
+    } else {
+        "Category suggestions:".to_string()
+    }];
+    for suggestion in suggestions {
+        let row = suggestion
+            .get("source_row_index")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        let counterparty = suggestion
+            .get("counterparty")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let category = suggestion
+            .get("category")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let confidence = suggestion
+            .get("confidence")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        let support = suggestion
+            .get("support")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        let support_label = if support == 1 { "row" } else { "rows" };
+        lines.push(format!(
+            "  row {row} {counterparty}: {category} (confidence {confidence:.2}, {support} ledger {support_label})"
+        ));
     }
-    let keep = width - 3;
-    let prefix = value.chars().take(keep).collect::<String>();
-    format!("{prefix}...")
+    if !applied {
+        lines
+            .push("  Rerun with --apply-category-suggestions to set these categories.".to_string());
+    }
+    lines
 }
 
-fn render_values_list_with_indent(values: Option<&Value>, indent: usize) -> Vec<String> {
-    let padding = " ".repeat(indent);
-    let Some(existing_values) = values.and_then(Value::as_array) else {
-        return vec![format!("{padding}None")];
+/// Lists accounts deduped under a configured policy; accounts on the default
+/// policy are left out.
+fn render_replacement_section(data: &Value) -> Vec<String> {
+    let Some(replacement) = data.get("replacement").filter(|value| !value.is_null()) else {
+        return Vec::new();
+    };
+    let replaced_import_id = replacement
+        .get("replaced_import_id")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let summary_count = |name: &str| {
+        replacement
+            .get("summary")
+            .and_then(|summary| summary.get(name))
+            .and_then(Value::as_i64)
+            .unwrap_or(0)
+            .to_string()
+    };
+    let count = |name: &str| {
+        replacement
+            .get(name)
+            .and_then(Value::as_i64)
+            .unwrap_or(0)
+            .to_string()
     };
 
-    if existing_values.is_empty() {
-        return vec![format!("{padding}None")];
+    let mut lines = vec![format!("Replacing {replaced_import_id}:")];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Unchanged:", summary_count("unchanged")),
+            ("Added:", summary_count("added")),
+            ("Removed:", summary_count("removed")),
+            ("Modified:", summary_count("modified")),
+            ("Rows reverted:", count("rows_reverted")),
+            ("Rows promoted:", count("rows_promoted")),
+        ],
+        2,
+    ));
+
+    let changes = replacement
+        .get("changes")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if changes.is_empty() {
+        return lines;
     }
+    lines.push(String::new());
+    lines.push("Changes:".to_string());
+    for change in &changes {
+        let status = change
+            .get("status")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let side = if status == "removed" { "old" } else { "new" };
+        let row = change.get(side).cloned().unwrap_or(Value::Null);
+        let mut line = format!("  {status:<8} {}", describe_replace_row(&row));
+        if status == "modified" {
+            if let Some(old) = change.get("old") {
+                line.push_str(&format!("  (was {})", describe_replace_row(old)));
+            }
+            let fields = change
+                .get("changed_fields")
+                .and_then(Value::as_array)
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_default();
+            line.push_str(&format!("  changed: {fields}"));
+        }
+        lines.push(line);
+    }
+    lines
+}
 
-    existing_values
+fn describe_replace_row(row: &Value) -> String {
+    let field = |name: &str| row.get(name).and_then(Value::as_str).unwrap_or("");
+    let location = match row.get("source_row_index").and_then(Value::as_i64) {
+        Some(index) if row.get("txn_id").is_none() => format!("row {index}"),
+        _ => field("txn_id").to_string(),
+    };
+    format!(
+        "{location} {} {} {:.2} {} {}",
+        field("account_key"),
+        field("posted_at"),
+        row.get("amount").and_then(Value::as_f64).unwrap_or(0.0),
+        field("currency"),
+        field("description")
+    )
+}
+
+fn render_dedupe_policies_section(data: &Value) -> Vec<String> {
+    let Some(policies) = data.get("dedupe_policies").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let configured = policies
         .iter()
-        .filter_map(Value::as_str)
-        .map(|value| format!("{padding}{value}"))
-        .collect()
+        .filter(|policy| policy.get("source").and_then(Value::as_str) == Some("import_policy"))
+        .collect::<Vec<_>>();
+    if configured.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec!["Dedupe policies:".to_string()];
+    for policy in configured {
+        let account_key = policy
+            .get("account_key")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let key_fields = policy
+            .get("key_fields")
+            .and_then(Value::as_array)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        let external_id = if policy
+            .get("trust_external_id")
+            .and_then(Value::as_bool)
+            .unwrap_or(true)
+        {
+            "trusted"
+        } else {
+            "not trusted"
+        };
+        let tolerance = policy
+            .get("date_tolerance_days")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        lines.push(format!(
+            "  {account_key}: key [{key_fields}], external_id {external_id}, date tolerance {tolerance}d"
+        ));
+    }
+    lines
 }
 
-#[cfg(test)]
-mod tests {
-    use serde_json::json;
+fn render_policy_warnings_section(data: &Value) -> Vec<String> {
+    let Some(warnings) = data.get("policy_warnings").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    if warnings.is_empty() {
+        return Vec::new();
+    }
 
-    use super::{
-        render_import_keys_uniq, render_import_list, render_import_run, render_import_undo,
-        render_intelligence_refresh,
+    let mut lines = vec![format!(
+        "Import policy warnings ({}; rows were not rejected):",
+        warnings.len()
+    )];
+    for warning in warnings {
+        lines.push(format!(
+            "  - row {} [{}] {}",
+            warning.get("row").and_then(Value::as_i64).unwrap_or(0),
+            warning
+                .get("code")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown"),
+            warning
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or("")
+        ));
+    }
+    lines
+}
+
+fn render_balance_gaps_section(data: &Value) -> Vec<String> {
+    let Some(gaps) = data.get("balance_gaps").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    if gaps.is_empty() {
+        return vec![
+            "Running balances:".to_string(),
+            "  Every row's balance follows from the previous row.".to_string(),
+        ];
+    }
+
+    let mut lines = vec![format!(
+        "Running balance gaps ({}; rows were not rejected):",
+        gaps.len()
+    )];
+    for gap in gaps {
+        let money =
+            |key: &str| format!("{:.2}", gap.get(key).and_then(Value::as_f64).unwrap_or(0.0));
+        let cause = match gap.get("likely_cause").and_then(Value::as_str) {
+            Some("duplicated_transaction") => "likely duplicated row",
+            _ => "likely missing transaction",
+        };
+        lines.push(format!(
+            "  - row {} ({}): expected {}, got {} (off by {}, {cause} after row {})",
+            gap.get("row").and_then(Value::as_i64).unwrap_or(0),
+            gap.get("account_key")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown"),
+            money("expected_balance"),
+            money("balance"),
+            money("difference"),
+            gap.get("previous_row").and_then(Value::as_i64).unwrap_or(0),
+        ));
+    }
+    lines
+}
+
+fn render_probable_duplicates_section(data: &Value) -> Vec<String> {
+    let Some(probables) = data.get("probable_duplicates").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    if probables.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![format!(
+        "Probable duplicates ({}; inserted, not auto-deduped):",
+        probables.len()
+    )];
+    let mut has_pending = false;
+    for probable in probables {
+        let text = |key: &str| {
+            probable
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or("unknown")
+                .to_string()
+        };
+        let days = probable
+            .get("date_distance_days")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        let status = match probable.get("probable_id").and_then(Value::as_str) {
+            Some(probable_id) => {
+                has_pending |= text("status") == "pending";
+                format!(" [{probable_id}, {}]", text("status"))
+            }
+            None => String::new(),
+        };
+        lines.push(format!(
+            "  - row {} ({}): {} {:.2} {} \"{}\"{status}",
+            probable
+                .get("source_row_index")
+                .and_then(Value::as_i64)
+                .unwrap_or(0),
+            text("account_key"),
+            text("posted_at"),
+            probable
+                .get("amount")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0),
+            text("currency"),
+            text("description"),
+        ));
+        lines.push(format!(
+            "    looks like {} on {} \"{}\" (score {:.2}, {days} day{} apart)",
+            text("matched_txn_id"),
+            text("matched_posted_at"),
+            text("matched_description"),
+            probable.get("score").and_then(Value::as_f64).unwrap_or(0.0),
+            if days == 1 { "" } else { "s" },
+        ));
+    }
+    if has_pending {
+        lines.push(
+            "  Review with: driggsby import duplicates accept|reject <probable_id>".to_string(),
+        );
+    }
+    lines
+}
+
+fn render_drift_warnings_section(data: &Value) -> Vec<String> {
+    let Some(warnings) = data.get("drift_warnings").and_then(Value::as_array) else {
+        return vec!["  None.".to_string()];
+    };
+
+    if warnings.is_empty() {
+        return vec!["  None.".to_string()];
+    }
+
+    let mut lines = Vec::new();
+    for warning in warnings {
+        let severity = warning
+            .get("severity")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_uppercase();
+        let property = warning
+            .get("property")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let incoming_value = warning
+            .get("incoming_value")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let message = warning
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("No warning message provided.");
+        lines.push(format!("  [{severity}] {property} = {incoming_value}"));
+        lines.push(format!("    {message}"));
+        if let Some(suggestions) = warning.get("suggestions").and_then(Value::as_array)
+            && !suggestions.is_empty()
+        {
+            let values = suggestions
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<&str>>()
+                .join(", ");
+            lines.push(format!("    Suggestions: {values}"));
+        }
+    }
+
+    lines
+}
+
+fn render_intelligence_impact_section(impact: &Value) -> Vec<String> {
+    let list = |key: &str| {
+        impact
+            .get(key)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let appearing = list("recurring_appearing");
+    let disappearing = list("recurring_disappearing");
+    let anomalies = list("anomalies_triggered");
+    if appearing.is_empty() && disappearing.is_empty() && anomalies.is_empty() {
+        return vec!["  No change to recurring or anomalies.".to_string()];
+    }
+
+    let mut lines = Vec::new();
+    for (label, rows) in [
+        ("New recurring", &appearing),
+        ("No longer recurring", &disappearing),
+    ] {
+        for row in rows {
+            let merchant = row.get("merchant").and_then(Value::as_str).unwrap_or("-");
+            let cadence = row.get("cadence").and_then(Value::as_str).unwrap_or("-");
+            let account_key = row
+                .get("account_key")
+                .and_then(Value::as_str)
+                .unwrap_or("-");
+            let amount = row
+                .get("typical_amount")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0);
+            let currency = row.get("currency").and_then(Value::as_str).unwrap_or("USD");
+            lines.push(format!(
+                "  {label}: {merchant} | {cadence} | {amount:.2} {currency} | {account_key}"
+            ));
+        }
+    }
+    for row in &anomalies {
+        let origin = match row.get("source_row_index").and_then(Value::as_i64) {
+            Some(index) => format!("row {index}"),
+            None => row
+                .get("txn_id")
+                .and_then(Value::as_str)
+                .unwrap_or("-")
+                .to_string(),
+        };
+        let posted_at = row.get("posted_at").and_then(Value::as_str).unwrap_or("-");
+        let amount = row.get("amount").and_then(Value::as_f64).unwrap_or(0.0);
+        let currency = row.get("currency").and_then(Value::as_str).unwrap_or("USD");
+        let reason = row.get("reason").and_then(Value::as_str).unwrap_or("-");
+        lines.push(format!(
+            "  New anomaly ({origin}): {posted_at} | {amount:.2} {currency} | {reason}"
+        ));
+    }
+
+    lines
+}
+
+fn render_canonical_aliases(inventory_map: &serde_json::Map<String, Value>) -> Vec<String> {
+    let Some(entries) = inventory_map.get("aliases").and_then(Value::as_array) else {
+        return Vec::new();
     };
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![String::new(), "  Aliases:".to_string()];
+    for entry in entries {
+        let canonical = entry
+            .get("canonical")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let aliases = entry
+            .get("aliases")
+            .and_then(Value::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        lines.push(format!("    {canonical} <- {aliases}"));
+    }
+    lines
+}
+
+fn render_counted_values_list(inventory_map: &serde_json::Map<String, Value>) -> Vec<String> {
+    if let Some(value_counts) = inventory_map.get("value_counts").and_then(Value::as_array) {
+        if value_counts.is_empty() {
+            return vec!["  No values.".to_string()];
+        }
+
+        let rows = value_counts
+            .iter()
+            .map(|entry| {
+                let value = entry
+                    .get("value")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown");
+                let count = entry.get("count").and_then(Value::as_i64).unwrap_or(0);
+                (value.to_string(), count.to_string())
+            })
+            .collect::<Vec<(String, String)>>();
+
+        return render_value_count_table(&rows);
+    }
+
+    let fallback = render_values_list_with_indent(inventory_map.get("existing_values"), 2);
+    if fallback.len() == 1 && fallback[0].trim() == "None" {
+        return vec!["  No values.".to_string()];
+    }
+    fallback
+}
+
+fn render_value_count_table(rows: &[(String, String)]) -> Vec<String> {
+    if rows.is_empty() {
+        return vec!["  No values.".to_string()];
+    }
+
+    let count_width = rows
+        .iter()
+        .map(|(_, count)| count.len())
+        .max()
+        .unwrap_or(5)
+        .max("Count".len());
+
+    let terminal_width = format::terminal_width();
+    let static_width = 2 + 2 + count_width;
+    let mut value_width = terminal_width
+        .saturating_sub(static_width)
+        .max("Value".len())
+        .max(8);
+
+    let natural_value_width = rows
+        .iter()
+        .map(|(value, _)| value.len())
+        .max()
+        .unwrap_or(8)
+        .max("Value".len());
+    value_width = value_width.min(natural_value_width);
+
+    let mut lines = Vec::new();
+    lines.push(format!(
+        "  {:<value_width$}  {:>count_width$}",
+        "Value", "Count"
+    ));
+    for (value, count) in rows {
+        lines.push(format!(
+            "  {:<value_width$}  {:>count_width$}",
+            truncate_ascii(value, value_width),
+            count
+        ));
+    }
+    lines
+}
+
+fn truncate_ascii(value: &str, width: usize) -> String {
+    let char_count = value.chars().count();
+    if char_count <= width {
+        return value.to_string();
+    }
+    if width <= 3 {
+        return ".".repeat(width);
+    }
+    let keep = width - 3;
+    let prefix = value.chars().take(keep).collect::<String>();
+    format!("{prefix}...")
+}
+
+fn render_values_list_with_indent(values: Option<&Value>, indent: usize) -> Vec<String> {
+    let padding = " ".repeat(indent);
+    let Some(existing_values) = values.and_then(Value::as_array) else {
+        return vec![format!("{padding}None")];
+    };
+
+    if existing_values.is_empty() {
+        return vec![format!("{padding}None")];
+    }
+
+    existing_values
+        .iter()
+        .filter_map(Value::as_str)
+        .map(|value| format!("{padding}{value}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        render_import_approve, render_import_duplicates, render_import_keys_uniq,
+        render_import_list, render_import_redo, render_import_run, render_import_staged_show,
+        render_import_undo, render_intelligence_refresh,
+    };
+
+    #[test]
+    fn import_run_renders_plaintext_summary() {
+        let payload = json!({
+            "dry_run": false,
+            "import_id": "imp_1",
+            "next_step": {
+                "label": "Connect and query your data",
+                "command": "driggsby db schema"
+            },
+            "other_actions": [
+                {
+                    "label": "View import list",
+                    "command": "driggsby import list"
+                },
+                {
+                    "label": "Undo this import (destructive)",
+                    "command": "driggsby import undo imp_1",
+                    "risk": "destructive"
+                }
+            ],
+            "summary": {
+                "rows_read": 10,
+                "rows_valid": 10,
+                "rows_invalid": 0,
+                "inserted": 9,
+                "deduped": 1
+            }
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Import completed successfully."));
+            assert!(text.contains("Import ID:"));
+            assert!(!text.contains("Undo ID:"));
+            assert!(text.contains("Next step:"));
+            assert!(text.contains("Other actions:"));
+            assert!(text.contains("(destructive)"));
+        }
+    }
+
+    #[test]
+    fn committed_import_run_renders_ledger_accounts_section() {
+        let payload = json!({
+            "dry_run": false,
+            "import_id": "imp_1",
+            "next_step": {
+                "label": "Connect and query your data",
+                "command": "driggsby db schema"
+            },
+            "other_actions": [],
+            "summary": {
+                "rows_read": 2,
+                "rows_valid": 2,
+                "rows_invalid": 0,
+                "inserted": 2
+            },
+            "duplicate_summary": {
+                "total": 0,
+                "batch": 0,
+                "existing_ledger": 0
+            },
+            "duplicates_preview": {
+                "returned": 0,
+                "truncated": false,
+                "rows": []
+            },
+            "ledger_accounts": {
+                "summary": {
+                    "account_count": 1,
+                    "transaction_count": 2,
+                    "earliest_posted_at": "2026-01-01",
+                    "latest_posted_at": "2026-01-05",
+                    "typed_account_count": 1,
+                    "untyped_account_count": 0,
+                    "net_amount": -20.0
+                },
+                "rows": [
+                    {
+                        "account_key": "acct_1",
+                        "account_type": "checking",
+                        "currency": "USD",
+                        "txn_count": 2,
+                        "first_posted_at": "2026-01-01",
+                        "last_posted_at": "2026-01-05",
+                        "net_amount": -20.0
+                    }
+                ]
+            }
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Your ledger now:"));
+            assert!(text.contains("Account count:"));
+            assert!(text.contains("acct_1"));
+            assert!(text.contains("checking"));
+        }
+    }
+
+    #[test]
+    fn dry_run_import_run_does_not_render_ledger_accounts_section() {
+        let payload = json!({
+            "dry_run": true,
+            "summary": {
+                "rows_read": 1,
+                "rows_valid": 1,
+                "rows_invalid": 0,
+                "inserted": 0
+            },
+            "duplicate_summary": {
+                "total": 0,
+                "batch": 0,
+                "existing_ledger": 0
+            },
+            "duplicates_preview": {
+                "returned": 0,
+                "truncated": false,
+                "rows": []
+            },
+            "next_step": {
+                "label": "Commit this import",
+                "command": "driggsby import create <path>"
+            },
+            "other_actions": [],
+            "ledger_accounts": {
+                "summary": {"account_count": 99},
+                "rows": []
+            }
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(!text.contains("Your ledger now:"));
+        }
+    }
+
+    #[test]
+    fn import_list_empty_guides_user() {
+        let payload = json!({ "rows": [] });
+        let rendered = render_import_list(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("No imports found yet."));
+            assert!(text.contains("driggsby import create --dry-run <path>"));
+        }
+    }
+
+    #[test]
+    fn import_undo_renders_summary() {
+        let payload = json!({
+            "import_id": "imp_1",
+            "summary": {
+                "rows_reverted": 4,
+                "rows_promoted": 1
+            }
+        });
+
+        let rendered = render_import_undo(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Import reverted successfully."));
+            assert!(text.contains("Rows reverted:"));
+            assert!(text.contains("Rows promoted:"));
+            assert!(text.contains("driggsby import redo imp_1"));
+        }
+    }
+
+    #[test]
+    fn import_undo_renders_partial_scope() {
+        let payload = json!({
+            "import_id": "imp_1",
+            "status": "partially_reverted",
+            "account_key": "chase_checking_1234",
+            "statement_id": null,
+            "summary": {
+                "rows_reverted": 2,
+                "rows_promoted": 0
+            }
+        });
+
+        let rendered = render_import_undo(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Import partially reverted."));
+            assert!(text.contains("chase_checking_1234"));
+            assert!(!text.contains("Statement:"));
+            assert!(text.contains("driggsby import undo imp_1 to revert the rest"));
+        }
+    }
+
+    #[test]
+    fn import_redo_renders_summary() {
+        let payload = json!({
+            "import_id": "imp_1",
+            "summary": {
+                "rows_restored": 3,
+                "rows_deduped": 1
+            },
+            "intelligence_refreshed": true
+        });
+
+        let rendered = render_import_redo(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Import restored successfully."));
+            assert!(text.contains("Rows restored:"));
+            assert!(text.contains("Rows deduped:"));
+        }
+    }
+
+    #[test]
+    fn staged_import_show_renders_analysis_and_approval_hint() {
+        let payload = json!({
+            "import_id": "imp_1",
+            "status": "staged",
+            "staged_at": "1767225600",
+            "summary": {
+                "rows_read": 3,
+                "rows_valid": 3,
+                "rows_invalid": 0,
+                "inserted": 0
+            },
+            "analysis": {
+                "duplicate_summary": {"total": 1, "batch": 0, "existing_ledger": 1},
+                "duplicates_preview": {"returned": 0, "truncated": false, "rows": []},
+                "warnings": [],
+                "drift_warnings": []
+            }
+        });
+
+        let rendered = render_import_staged_show(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Staged import imp_1 (staged "));
+            assert!(text.contains("Drift warnings:"));
+            assert!(text.contains("Approval rechecks this import against the ledger."));
+            assert!(text.contains("driggsby import approve imp_1"));
+            assert!(text.contains("driggsby import reject imp_1"));
+        }
+    }
+
+    #[test]
+    fn import_approve_renders_staged_and_current_duplicates() {
+        let payload = json!({
+            "import_id": "imp_1",
+            "message": "Staged import approved and committed.",
+            "summary": {"rows_read": 3, "rows_valid": 3, "rows_invalid": 0, "inserted": 2},
+            "duplicate_summary": {"total": 1, "batch": 0, "existing_ledger": 1},
+            "staged_duplicate_summary": {"total": 0, "batch": 0, "existing_ledger": 0},
+            "intelligence_refreshed": true
+        });
+
+        let rendered = render_import_approve(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Staged import approved and committed."));
+            assert!(text.contains("Duplicates when staged:"));
+            assert!(text.contains("driggsby import undo imp_1"));
+        }
+    }
+
+    #[test]
+    fn intelligence_refresh_renders_summary() {
+        let payload = json!({
+            "recurring_rows": 3,
+            "anomaly_rows": 1,
+            "completed_at": "2026-02-27T18:22:33Z"
+        });
+
+        let rendered = render_intelligence_refresh(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Intelligence refresh completed."));
+            assert!(text.contains("Recurring rows:"));
+            assert!(text.contains("Anomaly rows:"));
+            assert!(text.contains("Completed at:"));
+        }
+    }
+
+    #[test]
+    fn dry_run_renders_inventory_sign_profile_and_drift_warning_sections() {
+        let payload = json!({
+            "dry_run": true,
+            "summary": {
+                "rows_read": 5,
+                "rows_valid": 5,
+                "rows_invalid": 0,
+                "inserted": 0,
+                "deduped": 0
+            },
+            "key_inventory": {
+                "account_key": {
+                    "property": "account_key",
+                    "existing_values": ["chase_checking_1234"],
+                    "unique_count": 1,
+                    "null_count": 0,
+                    "total_rows": 10
+                }
+            },
+            "sign_profiles": [
+                {
+                    "account_key": "chase_checking_1234",
+                    "negative_count": 24,
+                    "positive_count": 1,
+                    "negative_ratio": 0.96,
+                    "positive_ratio": 0.04,
+                    "total_count": 25
+                }
+            ],
+            "drift_warnings": [
+                {
+                    "code": "account_key_unseen",
+                    "severity": "high",
+                    "property": "account_key",
+                    "incoming_value": "chase_checkng_1234",
+                    "message": "Incoming account_key was not found in existing ledger history.",
+                    "suggestions": ["chase_checking_1234"]
+                }
+            ]
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Canonical existing values:"));
+            assert!(text.contains("Per-account sign profile:"));
+            assert!(text.contains("Drift warnings:"));
+            assert!(text.contains("account_key"));
+            assert!(text.contains("chase_checking_1234"));
+            assert!(text.contains("HIGH"));
+        }
+    }
+
+    #[test]
+    fn import_dry_run_renders_intelligence_impact() {
+        let payload = json!({
+            "dry_run": true,
+            "summary": {"rows_read": 2, "rows_valid": 2, "rows_invalid": 0, "inserted": 0},
+            "intelligence_impact": {
+                "recurring_appearing": [
+                    {
+                        "group_key": "chase_checking_1234|USD|netflix",
+                        "account_key": "chase_checking_1234",
+                        "merchant": "Netflix",
+                        "cadence": "monthly",
+                        "typical_amount": -15.99,
+                        "currency": "USD"
+                    }
+                ],
+                "recurring_disappearing": [],
+                "anomalies_triggered": [
+                    {
+                        "source_row_index": 2,
+                        "account_key": "chase_checking_1234",
+                        "posted_at": "2026-04-09",
+                        "amount": -400.0,
+                        "currency": "USD",
+                        "reason": "Amount is far above this merchant's usual spend."
+                    }
+                ]
+            }
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Intelligence impact:"));
+            assert!(text.contains("New recurring: Netflix | monthly | -15.99 USD"));
+            assert!(text.contains("New anomaly (row 2): 2026-04-09 | -400.00 USD"));
+        }
+
+        let unchanged = json!({
+            "dry_run": true,
+            "summary": {"rows_read": 1, "rows_valid": 1, "rows_invalid": 0, "inserted": 0},
+            "intelligence_impact": {
+                "recurring_appearing": [],
+                "recurring_disappearing": [],
+                "anomalies_triggered": []
+            }
+        });
+        let rendered = render_import_run(&unchanged);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("No change to recurring or anomalies."));
+        }
+    }
+
+    #[test]
+    fn import_run_renders_sign_corrections_when_present() {
+        let payload = json!({
+            "dry_run": false,
+            "import_id": "imp_1",
+            "summary": {
+                "rows_read": 6,
+                "rows_valid": 6,
+                "rows_invalid": 0,
+                "inserted": 6
+            },
+            "sign_corrections": [
+                {
+                    "account_key": "chase_checking_1234",
+                    "rows_inverted": 6,
+                    "historical_negative_ratio": 0.88,
+                    "incoming_negative_ratio": 0.17
+                }
+            ]
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Sign corrections:"));
+            assert!(text.contains("chase_checking_1234: inverted 6 rows"));
+        }
+    }
 
     #[test]
-    fn import_run_renders_plaintext_summary() {
+    fn dry_run_renders_category_suggestions_with_apply_hint() {
         let payload = json!({
-            "dry_run": false,
-            "import_id": "imp_1",
-            "next_step": {
-                "label": "Connect and query your data",
-                "command": "driggsby db schema"
+            "dry_run": true,
+            "summary": {
+                "rows_read": 2,
+                "rows_valid": 2,
+                "rows_invalid": 0,
+                "inserted": 0
             },
-            "other_actions": [
-                {
-                    "label": "View import list",
-                    "command": "driggsby import list"
-                },
+            "category_suggestions": [
                 {
-                    "label": "Undo this import (destructive)",
-                    "command": "driggsby import undo imp_1",
-                    "risk": "destructive"
+                    "source_row_index": 2,
+                    "counterparty": "BLUE BOTTLE",
+                    "category": "Coffee",
+                    "confidence": 0.8333,
+                    "support": 5,
+                    "applied": false
                 }
-            ],
-            "summary": {
-                "rows_read": 10,
-                "rows_valid": 10,
-                "rows_invalid": 0,
-                "inserted": 9,
-                "deduped": 1
-            }
+            ]
         });
 
         let rendered = render_import_run(&payload);
         assert!(rendered.is_ok());
         if let Ok(text) = rendered {
-            assert!(text.starts_with("Import completed successfully."));
-            assert!(text.contains("Import ID:"));
-            assert!(!text.contains("Undo ID:"));
-            assert!(text.contains("Next step:"));
-            assert!(text.contains("Other actions:"));
-            assert!(text.contains("(destructive)"));
+            assert!(text.contains("Category suggestions:"));
+            assert!(text.contains("row 2 BLUE BOTTLE: Coffee (confidence 0.83, 5 ledger rows)"));
+            assert!(text.contains("--apply-category-suggestions"));
         }
     }
 
     #[test]
-    fn committed_import_run_renders_ledger_accounts_section() {
+    fn import_run_leads_with_the_message_for_replayed_imports() {
         let payload = json!({
             "dry_run": false,
             "import_id": "imp_1",
-            "next_step": {
-                "label": "Connect and query your data",
-                "command": "driggsby db schema"
-            },
-            "other_actions": [],
+            "message": "Import `imp_1` already committed this source with idempotency key `k1`. No rows were written.",
+            "replayed": true,
             "summary": {
                 "rows_read": 2,
                 "rows_valid": 2,
                 "rows_invalid": 0,
                 "inserted": 2
-            },
-            "duplicate_summary": {
-                "total": 0,
-                "batch": 0,
-                "existing_ledger": 0
-            },
-            "duplicates_preview": {
-                "returned": 0,
-                "truncated": false,
-                "rows": []
-            },
-            "ledger_accounts": {
-                "summary": {
-                    "account_count": 1,
-                    "transaction_count": 2,
-                    "earliest_posted_at": "2026-01-01",
-                    "latest_posted_at": "2026-01-05",
-                    "typed_account_count": 1,
-                    "untyped_account_count": 0,
-                    "net_amount": -20.0
-                },
-                "rows": [
-                    {
-                        "account_key": "acct_1",
-                        "account_type": "checking",
-                        "currency": "USD",
-                        "txn_count": 2,
-                        "first_posted_at": "2026-01-01",
-                        "last_posted_at": "2026-01-05",
-                        "net_amount": -20.0
-                    }
-                ]
             }
         });
 
         let rendered = render_import_run(&payload);
         assert!(rendered.is_ok());
         if let Ok(text) = rendered {
-            assert!(text.contains("Your ledger now:"));
-            assert!(text.contains("Account count:"));
-            assert!(text.contains("acct_1"));
-            assert!(text.contains("checking"));
+            assert!(text.starts_with("Import `imp_1` already committed this source"));
+            assert!(!text.contains("Import completed successfully."));
         }
     }
 
     #[test]
-    fn dry_run_import_run_does_not_render_ledger_accounts_section() {
+    fn import_run_renders_replacement_diff() {
         let payload = json!({
             "dry_run": true,
             "summary": {
-                "rows_read": 1,
-                "rows_valid": 1,
+                "rows_read": 2,
+                "rows_valid": 2,
                 "rows_invalid": 0,
                 "inserted": 0
             },
-            "duplicate_summary": {
-                "total": 0,
-                "batch": 0,
-                "existing_ledger": 0
-            },
-            "duplicates_preview": {
-                "returned": 0,
-                "truncated": false,
-                "rows": []
-            },
-            "next_step": {
-                "label": "Commit this import",
-                "command": "driggsby import create <path>"
-            },
-            "other_actions": [],
-            "ledger_accounts": {
-                "summary": {"account_count": 99},
-                "rows": []
+            "replacement": {
+                "replaced_import_id": "imp_old",
+                "summary": { "unchanged": 1, "added": 0, "removed": 0, "modified": 1 },
+                "rows_reverted": 2,
+                "rows_promoted": 0,
+                "changes": [
+                    {
+                        "status": "modified",
+                        "old": {
+                            "txn_id": "txn_1",
+                            "account_key": "chase_checking_1234",
+                            "posted_at": "2026-01-05",
+                            "amount": -42.1,
+                            "currency": "USD",
+                            "description": "WHOLE FOODS"
+                        },
+                        "new": {
+                            "source_row_index": 2,
+                            "account_key": "chase_checking_1234",
+                            "posted_at": "2026-01-05",
+                            "amount": -42.01,
+                            "currency": "USD",
+                            "description": "WHOLE FOODS"
+                        },
+                        "changed_fields": ["amount"]
+                    }
+                ]
             }
         });
 
         let rendered = render_import_run(&payload);
         assert!(rendered.is_ok());
         if let Ok(text) = rendered {
-            assert!(!text.contains("Your ledger now:"));
+            assert!(text.contains("Replacing imp_old:"));
+            assert!(text.contains("Modified:"));
+            assert!(text.contains("modified row 2 chase_checking_1234 2026-01-05 -42.01 USD"));
+            assert!(text.contains("(was txn_1"));
+            assert!(text.contains("changed: amount"));
         }
     }
 
     #[test]
-    fn import_list_empty_guides_user() {
-        let payload = json!({ "rows": [] });
-        let rendered = render_import_list(&payload);
+    fn import_run_renders_only_configured_dedupe_policies() {
+        let payload = json!({
+            "dry_run": true,
+            "summary": {
+                "rows_read": 2,
+                "rows_valid": 2,
+                "rows_invalid": 0,
+                "inserted": 0
+            },
+            "dedupe_policies": [
+                {
+                    "account_key": "amex_gold_1001",
+                    "key_fields": ["posted_at", "amount", "currency", "description"],
+                    "trust_external_id": true,
+                    "date_tolerance_days": 0,
+                    "source": "default"
+                },
+                {
+                    "account_key": "chase_checking_1234",
+                    "key_fields": ["external_id", "posted_at", "amount"],
+                    "trust_external_id": false,
+                    "date_tolerance_days": 2,
+                    "source": "import_policy"
+                }
+            ]
+        });
+
+        let rendered = render_import_run(&payload);
         assert!(rendered.is_ok());
         if let Ok(text) = rendered {
-            assert!(text.starts_with("No imports found yet."));
-            assert!(text.contains("driggsby import create --dry-run <path>"));
+            assert!(text.contains("Dedupe policies:"));
+            assert!(text.contains(
+                "chase_checking_1234: key [external_id, posted_at, amount], external_id not trusted, date tolerance 2d"
+            ));
+            assert!(!text.contains("amex_gold_1001"));
         }
     }
 
     #[test]
-    fn import_undo_renders_summary() {
+    fn import_run_renders_reconciled_statements_when_present() {
         let payload = json!({
-            "import_id": "imp_1",
+            "dry_run": true,
             "summary": {
-                "rows_reverted": 4,
-                "rows_promoted": 1
-            }
+                "rows_read": 2,
+                "rows_valid": 2,
+                "rows_invalid": 0,
+                "inserted": 0
+            },
+            "reconciled_statements": [
+                {
+                    "statement_id": "chase_checking_1234_2026-01-31",
+                    "account_key": "chase_checking_1234",
+                    "currency": "USD",
+                    "opening_balance": 1000.0,
+                    "closing_balance": 957.85,
+                    "net_amount": -42.15,
+                    "txn_count": 2
+                }
+            ]
         });
 
-        let rendered = render_import_undo(&payload);
+        let rendered = render_import_run(&payload);
         assert!(rendered.is_ok());
         if let Ok(text) = rendered {
-            assert!(text.starts_with("Import reverted successfully."));
-            assert!(text.contains("Rows reverted:"));
-            assert!(text.contains("Rows promoted:"));
+            assert!(text.contains("Reconciled statements:"));
+            assert!(text.contains("chase_checking_1234_2026-01-31"));
+            assert!(text.contains("957.85"));
         }
     }
 
     #[test]
-    fn intelligence_refresh_renders_summary() {
+    fn import_run_renders_policy_warnings_when_present() {
         let payload = json!({
-            "recurring_rows": 3,
-            "anomaly_rows": 1,
-            "completed_at": "2026-02-27T18:22:33Z"
+            "dry_run": true,
+            "summary": {
+                "rows_read": 1,
+                "rows_valid": 1,
+                "rows_invalid": 0,
+                "inserted": 0
+            },
+            "policy_warnings": [
+                {
+                    "row": 1,
+                    "field": "merchant",
+                    "code": "policy_required_field_missing",
+                    "description": "merchant is required by the import policy for credit_card accounts."
+                }
+            ]
         });
 
-        let rendered = render_intelligence_refresh(&payload);
+        let rendered = render_import_run(&payload);
         assert!(rendered.is_ok());
         if let Ok(text) = rendered {
-            assert!(text.starts_with("Intelligence refresh completed."));
-            assert!(text.contains("Recurring rows:"));
-            assert!(text.contains("Anomaly rows:"));
-            assert!(text.contains("Completed at:"));
+            assert!(text.contains("Import policy warnings (1; rows were not rejected):"));
+            assert!(text.contains("row 1 [policy_required_field_missing] merchant is required"));
         }
     }
 
     #[test]
-    fn dry_run_renders_inventory_sign_profile_and_drift_warning_sections() {
+    fn import_run_renders_applied_remaps() {
         let payload = json!({
             "dry_run": true,
             "summary": {
-                "rows_read": 5,
-                "rows_valid": 5,
+                "rows_read": 2,
+                "rows_valid": 2,
                 "rows_invalid": 0,
-                "inserted": 0,
-                "deduped": 0
+                "inserted": 0
             },
-            "key_inventory": {
-                "account_key": {
+            "remaps": [
+                {
                     "property": "account_key",
-                    "existing_values": ["chase_checking_1234"],
-                    "unique_count": 1,
-                    "null_count": 0,
-                    "total_rows": 10
+                    "from": "chase_chk",
+                    "to": "chase_checking_1234",
+                    "rows_rewritten": 2
                 }
+            ]
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Remaps:"));
+            assert!(text.contains("account_key: chase_chk -> chase_checking_1234 (2 rows)"));
+        }
+    }
+
+    #[test]
+    fn import_run_renders_balance_gaps_when_present() {
+        let payload = json!({
+            "dry_run": true,
+            "summary": {
+                "rows_read": 3,
+                "rows_valid": 3,
+                "rows_invalid": 0,
+                "inserted": 0
             },
-            "sign_profiles": [
+            "balance_gaps": [
                 {
                     "account_key": "chase_checking_1234",
-                    "negative_count": 24,
-                    "positive_count": 1,
-                    "negative_ratio": 0.96,
-                    "positive_ratio": 0.04,
-                    "total_count": 25
+                    "row": 3,
+                    "previous_row": 2,
+                    "previous_balance": 957.85,
+                    "amount": -20.0,
+                    "balance": 917.85,
+                    "expected_balance": 937.85,
+                    "difference": -20.0,
+                    "likely_cause": "missing_transaction"
                 }
-            ],
-            "drift_warnings": [
+            ]
+        });
+
+        let rendered = render_import_run(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Running balance gaps (1; rows were not rejected):"));
+            assert!(text.contains(
+                "row 3 (chase_checking_1234): expected 937.85, got 917.85 (off by -20.00, likely missing transaction after row 2)"
+            ));
+        }
+    }
+
+    #[test]
+    fn import_duplicates_lists_probable_duplicates_with_review_hint() {
+        let payload = json!({
+            "import_id": "imp_2",
+            "total": 0,
+            "rows": [],
+            "probable_duplicates": [
                 {
-                    "code": "account_key_unseen",
-                    "severity": "high",
-                    "property": "account_key",
-                    "incoming_value": "chase_checkng_1234",
-                    "message": "Incoming account_key was not found in existing ledger history.",
-                    "suggestions": ["chase_checking_1234"]
+                    "probable_id": "prob_1",
+                    "import_id": "imp_2",
+                    "txn_id": "txn_2",
+                    "status": "pending",
+                    "source_row_index": 1,
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-01-04",
+                    "amount": -15.49,
+                    "currency": "USD",
+                    "description": "NETFLIX.COM",
+                    "matched_txn_id": "txn_1",
+                    "matched_import_id": "imp_1",
+                    "matched_posted_at": "2026-01-03",
+                    "matched_description": "NETFLIX.COM",
+                    "score": 0.94,
+                    "date_distance_days": 1,
+                    "description_similarity": 1.0
                 }
             ]
         });
 
-        let rendered = render_import_run(&payload);
+        let rendered = render_import_duplicates(&payload);
         assert!(rendered.is_ok());
         if let Ok(text) = rendered {
-            assert!(text.contains("Canonical existing values:"));
-            assert!(text.contains("Per-account sign profile:"));
-            assert!(text.contains("Drift warnings:"));
-            assert!(text.contains("account_key"));
-            assert!(text.contains("chase_checking_1234"));
-            assert!(text.contains("HIGH"));
+            assert!(text.contains("No duplicate rows were recorded for this import."));
+            assert!(text.contains("Probable duplicates (1; inserted, not auto-deduped):"));
+            assert!(text.contains(
+                "row 1 (chase_checking_1234): 2026-01-04 -15.49 USD \"NETFLIX.COM\" [prob_1, pending]"
+            ));
+            assert!(text.contains(
+                "looks like txn_1 on 2026-01-03 \"NETFLIX.COM\" (score 0.94, 1 day apart)"
+            ));
+            assert!(text.contains("driggsby import duplicates accept|reject <probable_id>"));
         }
     }
 
@@ -1294,6 +2858,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn import_keys_uniq_lists_aliases_under_their_canonical_values() {
+        let payload = json!({
+            "property": "merchant",
+            "inventories": [
+                {
+                    "property": "merchant",
+                    "unique_count": 1,
+                    "null_count": 0,
+                    "total_rows": 4,
+                    "existing_values": ["Amazon"],
+                    "value_counts": [{"value": "Amazon", "count": 4}],
+                    "aliases": [
+                        {"canonical": "Amazon", "aliases": ["AMZN Mktp US", "Amazon.com"]}
+                    ]
+                }
+            ]
+        });
+
+        let rendered = render_import_keys_uniq(&payload);
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("  Aliases:"));
+            assert!(text.contains("    Amazon <- AMZN Mktp US, Amazon.com"));
+        }
+    }
+
     #[test]
     fn import_keys_uniq_empty_values_state_is_explicit() {
         let payload = json!({
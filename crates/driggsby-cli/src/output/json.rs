@@ -12,11 +12,30 @@ const JSON_VERSION: &str = "v1";
 pub fn render_success_json(success: &SuccessEnvelope) -> io::Result<String> {
     let value = match success.command.as_str() {
         "account list" => render_accounts_json(&success.data),
-        "import" => render_import_json(&success.data),
+        "alias add" | "alias remove" => render_edit_success_envelope(&success.data),
+        "alias list" => render_alias_list_json(&success.data),
+        "import" | "import replace" | "import stage" => render_import_json(&success.data),
         "import list" => render_import_list_json(&success.data),
         "import duplicates" => render_import_duplicates_json(&success.data),
+        "import duplicates promote"
+        | "import duplicates accept"
+        | "import duplicates reject"
+        | "transaction mark-duplicate"
+        | "transaction add"
+        | "transaction edit"
+        | "transaction delete"
+        | "transaction split"
+        | "transaction exclude"
+        | "transaction include" => render_edit_success_envelope(&success.data),
+        "transaction show" => success.data.clone(),
+        "tag add" | "tag remove" => render_edit_success_envelope(&success.data),
+        "tag list" => success.data.clone(),
+        "rules add" | "rules remove" | "rules apply" => render_edit_success_envelope(&success.data),
+        "rules list" => success.data.clone(),
         "import keys uniq" => render_import_keys_uniq_json(&success.data),
-        "import undo" => render_import_undo_json(&success.data),
+        "import undo" | "import redo" => render_import_undo_json(&success.data),
+        "import approve" | "import reject" => render_edit_success_envelope(&success.data),
+        "import staged list" | "import staged show" => success.data.clone(),
         "intelligence refresh" => render_intelligence_refresh_json(&success.data),
         "db sql" => render_db_sql_json(&success.data),
         "anomalies" => render_anomalies_json(&success.data),
@@ -88,6 +107,10 @@ fn render_intelligence_refresh_json(data: &Value) -> Value {
     render_edit_success_envelope(data)
 }
 
+fn render_alias_list_json(data: &Value) -> Value {
+    data.clone()
+}
+
 fn render_import_duplicates_json(data: &Value) -> Value {
     data.clone()
 }
@@ -511,5 +534,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn alias_json_wraps_edits_and_returns_raw_list() {
+        let added = render_success_json(&success(
+            "alias add",
+            json!({
+                "message": "Alias `AMZN` maps to `Amazon`.",
+                "alias": {"property": "merchant", "alias": "AMZN", "canonical": "Amazon"}
+            }),
+        ));
+        assert!(added.is_ok());
+        if let Ok(text) = added
+            && let Ok(value) = serde_json::from_str::<Value>(&text)
+        {
+            assert_eq!(value["ok"], Value::Bool(true));
+            assert_eq!(value["data"]["alias"]["canonical"], "Amazon");
+        }
+
+        let listed = render_success_json(&success("alias list", json!({ "rows": [] })));
+        assert!(listed.is_ok());
+        if let Ok(text) = listed
+            && let Ok(value) = serde_json::from_str::<Value>(&text)
+        {
+            assert!(value["rows"].is_array());
+            assert!(value.get("ok").is_none());
+        }
+    }
+
     use serde_json::Value;
 }
@@ -1,5 +1,6 @@
 mod accounts_shared;
 mod accounts_text;
+mod alias_text;
 mod demo_text;
 mod error_text;
 mod format;
@@ -7,8 +8,11 @@ mod import_text;
 mod intelligence_text;
 mod json;
 mod mode;
+mod rules_text;
 mod schema_text;
 mod sql_text;
+mod tag_text;
+mod transaction_text;
 
 use crate::stdout_io::write_stdout_line;
 use std::io;
@@ -36,14 +40,42 @@ pub fn print_failure(error: &ClientError, mode: OutputMode) -> io::Result<()> {
 fn render_text_success(success: &SuccessEnvelope) -> io::Result<String> {
     match success.command.as_str() {
         "account list" => accounts_text::render_accounts(&success.data),
+        "alias add" => alias_text::render_alias_add(&success.data),
+        "alias list" => alias_text::render_alias_list(&success.data),
+        "alias remove" => alias_text::render_alias_remove(&success.data),
         "db schema" => schema_text::render_schema_summary(&success.data),
         "db schema view" => schema_text::render_schema_view(&success.data),
         "db sql" => sql_text::render_sql_result(&success.data),
-        "import" => import_text::render_import_run(&success.data),
+        "import" | "import replace" => import_text::render_import_run(&success.data),
         "import list" => import_text::render_import_list(&success.data),
         "import duplicates" => import_text::render_import_duplicates(&success.data),
+        "import duplicates promote" => import_text::render_duplicate_promote(&success.data),
+        "import duplicates accept" | "import duplicates reject" => {
+            import_text::render_probable_resolution(&success.data)
+        }
         "import keys uniq" => import_text::render_import_keys_uniq(&success.data),
         "import undo" => import_text::render_import_undo(&success.data),
+        "import redo" => import_text::render_import_redo(&success.data),
+        "import stage" => import_text::render_import_run(&success.data),
+        "import staged list" => import_text::render_import_staged_list(&success.data),
+        "import staged show" => import_text::render_import_staged_show(&success.data),
+        "import approve" => import_text::render_import_approve(&success.data),
+        "import reject" => import_text::render_import_reject(&success.data),
+        "transaction mark-duplicate" => import_text::render_mark_duplicate(&success.data),
+        "transaction show" => transaction_text::render_transaction_show(&success.data),
+        "transaction add" => transaction_text::render_transaction_add(&success.data),
+        "transaction edit" => transaction_text::render_transaction_edit(&success.data),
+        "transaction delete" => transaction_text::render_transaction_delete(&success.data),
+        "transaction split" => transaction_text::render_transaction_split(&success.data),
+        "transaction exclude" | "transaction include" => {
+            transaction_text::render_transaction_exclude(&success.data)
+        }
+        "tag add" | "tag remove" => tag_text::render_tag_change(&success.data),
+        "tag list" => tag_text::render_tag_list(&success.data),
+        "rules add" => rules_text::render_rule_add(&success.data),
+        "rules list" => rules_text::render_rule_list(&success.data),
+        "rules remove" => rules_text::render_rule_remove(&success.data),
+        "rules apply" => rules_text::render_rules_apply(&success.data),
         "intelligence refresh" => import_text::render_intelligence_refresh(&success.data),
         "demo" | "dash" => demo_text::render_demo_or_dash(&success.command, &success.data),
         "anomalies" => intelligence_text::render_anomalies(&success.data),
@@ -1,5 +1,7 @@
 use crate::cli::{
-    AccountCommand, Commands, DbCommand, ImportCommand, ImportKeysCommand, IntelligenceCommand,
+    AccountCommand, AliasCommand, Commands, DbCommand, ImportCommand, ImportDuplicatesCommand,
+    ImportKeysCommand, ImportStagedCommand, IntelligenceCommand, RulesCommand, TagCommand,
+    TransactionCommand,
 };
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -19,14 +21,85 @@ pub fn mode_for_command(command: &Commands) -> OutputMode {
                 }
             }
         },
+        Commands::Alias { command } => match command {
+            AliasCommand::Add { json, .. }
+            | AliasCommand::List { json, .. }
+            | AliasCommand::Remove { json, .. } => {
+                if *json {
+                    OutputMode::Json
+                } else {
+                    OutputMode::Text
+                }
+            }
+        },
         Commands::Import { command } => match command {
             ImportCommand::Create { json, .. }
             | ImportCommand::List { json }
-            | ImportCommand::Duplicates { json, .. }
+            | ImportCommand::Duplicates {
+                command: None,
+                json,
+                ..
+            }
+            | ImportCommand::Duplicates {
+                command:
+                    Some(
+                        ImportDuplicatesCommand::Promote { json, .. }
+                        | ImportDuplicatesCommand::Accept { json, .. }
+                        | ImportDuplicatesCommand::Reject { json, .. },
+                    ),
+                ..
+            }
             | ImportCommand::Keys {
                 command: ImportKeysCommand::Uniq { json, .. },
             }
-            | ImportCommand::Undo { json, .. } => {
+            | ImportCommand::Replace { json, .. }
+            | ImportCommand::Undo { json, .. }
+            | ImportCommand::Redo { json, .. }
+            | ImportCommand::Stage { json, .. }
+            | ImportCommand::Staged {
+                command: ImportStagedCommand::List { json } | ImportStagedCommand::Show { json, .. },
+            }
+            | ImportCommand::Approve { json, .. }
+            | ImportCommand::Reject { json, .. } => {
+                if *json {
+                    OutputMode::Json
+                } else {
+                    OutputMode::Text
+                }
+            }
+        },
+        Commands::Transaction { command } => match command {
+            TransactionCommand::MarkDuplicate { json, .. }
+            | TransactionCommand::Show { json, .. }
+            | TransactionCommand::Add { json, .. }
+            | TransactionCommand::Edit { json, .. }
+            | TransactionCommand::Delete { json, .. }
+            | TransactionCommand::Split { json, .. }
+            | TransactionCommand::Exclude { json, .. }
+            | TransactionCommand::Include { json, .. } => {
+                if *json {
+                    OutputMode::Json
+                } else {
+                    OutputMode::Text
+                }
+            }
+        },
+        Commands::Tag { command } => match command {
+            TagCommand::Add { json, .. }
+            | TagCommand::Remove { json, .. }
+            | TagCommand::List { json, .. } => {
+                if *json {
+                    OutputMode::Json
+                } else {
+                    OutputMode::Text
+                }
+            }
+        },
+        Commands::Rules { command } => match command {
+            RulesCommand::Add { json, .. }
+            | RulesCommand::List { json, .. }
+            | RulesCommand::Remove { json, .. }
+            | RulesCommand::Apply { json, .. } => {
                 if *json {
                     OutputMode::Json
                 } else {
@@ -112,6 +185,195 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mode_uses_json_for_import_redo_with_json_flag() {
+        let parsed = parse_from(["driggsby", "import", "redo", "imp_1", "--json"]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_import_staged_show_with_json_flag() {
+        let parsed = parse_from(["driggsby", "import", "staged", "show", "imp_1", "--json"]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_probable_duplicate_resolution_with_json_flag() {
+        let parsed = parse_from([
+            "driggsby",
+            "import",
+            "duplicates",
+            "accept",
+            "prob_1",
+            "--json",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_transaction_mark_duplicate_with_json_flag() {
+        let parsed = parse_from([
+            "driggsby",
+            "transaction",
+            "mark-duplicate",
+            "txn_2",
+            "--of",
+            "txn_1",
+            "--json",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_transaction_edit_with_json_flag() {
+        let parsed = parse_from([
+            "driggsby",
+            "transaction",
+            "edit",
+            "txn_1",
+            "--category",
+            "Coffee",
+            "--json",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_transaction_split_with_json_flag() {
+        let parsed = parse_from([
+            "driggsby",
+            "transaction",
+            "split",
+            "txn_1",
+            "--part",
+            "80:Groceries",
+            "--part",
+            "40:Gifts",
+            "--json",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_transaction_exclude_and_include_with_json_flag() {
+        let exclude = parse_from([
+            "driggsby",
+            "transaction",
+            "exclude",
+            "txn_1",
+            "--reason",
+            "transfer",
+            "--json",
+        ]);
+        assert!(exclude.is_ok());
+        if let Ok(cli) = exclude {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+
+        let include = parse_from(["driggsby", "transaction", "include", "txn_1"]);
+        assert!(include.is_ok());
+        if let Ok(cli) = include {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Text);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_manual_transaction_commands_with_json_flag() {
+        let add = parse_from([
+            "driggsby",
+            "transaction",
+            "add",
+            "--account",
+            "cash_wallet",
+            "--posted-at",
+            "2026-03-14",
+            "--amount",
+            "-12.50",
+            "--description",
+            "Farmers market",
+            "--json",
+        ]);
+        assert!(add.is_ok());
+        if let Ok(cli) = add {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+
+        let delete = parse_from(["driggsby", "transaction", "delete", "txn_1"]);
+        assert!(delete.is_ok());
+        if let Ok(cli) = delete {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Text);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_tag_commands_with_json_flag() {
+        let parsed = parse_from([
+            "driggsby",
+            "tag",
+            "add",
+            "reimbursable",
+            "--where",
+            "effective_category = 'Travel'",
+            "--json",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_rules_commands_with_json_flag() {
+        let parsed = parse_from(["driggsby", "rules", "apply", "--dry-run", "--json"]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_import_replace_with_json_flag() {
+        let parsed = parse_from([
+            "driggsby",
+            "import",
+            "replace",
+            "imp_1",
+            "./fixed.csv",
+            "--json",
+        ]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
+    #[test]
+    fn mode_uses_json_for_transaction_show_with_json_flag() {
+        let parsed = parse_from(["driggsby", "transaction", "show", "txn_1", "--json"]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
     #[test]
     fn mode_uses_json_for_import_duplicates_with_json_flag() {
         let parsed = parse_from(["driggsby", "import", "duplicates", "imp_1", "--json"]);
@@ -130,6 +392,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mode_uses_json_for_alias_commands_with_json_flag() {
+        let parsed = parse_from(["driggsby", "alias", "list", "--json"]);
+        assert!(parsed.is_ok());
+        if let Ok(cli) = parsed {
+            assert_eq!(mode_for_command(&cli.command), OutputMode::Json);
+        }
+    }
+
     #[test]
     fn mode_uses_json_for_accounts_with_json_flag() {
         let parsed = parse_from(["driggsby", "account", "list", "--json"]);
@@ -0,0 +1,270 @@
+use std::io;
+
+use serde_json::Value;
+
+use super::format::{self, Align, Column};
+
+pub fn render_rule_add(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("rules add output requires message"))?;
+    let rule = data
+        .get("rule")
+        .ok_or_else(|| io::Error::other("rules add output requires rule"))?;
+
+    let mut lines = vec![message.to_string(), String::new()];
+    lines.extend(format::key_value_rows(
+        &[
+            (
+                "Rule:",
+                rule.get("rule_id")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown")
+                    .to_string(),
+            ),
+            ("When:", rule_conditions(rule)),
+            ("Then:", rule_actions(rule)),
+        ],
+        2,
+    ));
+    Ok(lines.join("\n"))
+}
+
+pub fn render_rule_list(data: &Value) -> io::Result<String> {
+    let rows = data
+        .get("rows")
+        .and_then(Value::as_array)
+        .ok_or_else(|| io::Error::other("rules list output requires rows"))?;
+
+    if rows.is_empty() {
+        return Ok([
+            "No rules stored yet.",
+            "",
+            "Add one to clean up rows you keep fixing by hand:",
+            "  driggsby rules add --description \"^SQ \\*BLUE BOTTLE\" --set-merchant \"Blue Bottle Coffee\" --set-category Coffee",
+        ]
+        .join("\n"));
+    }
+
+    let count_label = if rows.len() == 1 {
+        "1 rule, applied in this order.".to_string()
+    } else {
+        format!("{} rules, applied in this order.", rows.len())
+    };
+    let columns = [
+        Column {
+            name: "#",
+            align: Align::Right,
+        },
+        Column {
+            name: "Rule",
+            align: Align::Left,
+        },
+        Column {
+            name: "When",
+            align: Align::Left,
+        },
+        Column {
+            name: "Then",
+            align: Align::Left,
+        },
+    ];
+    let table_rows = rows
+        .iter()
+        .map(|row| {
+            vec![
+                row.get("position")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0)
+                    .to_string(),
+                row.get("rule_id")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                rule_conditions(row),
+                rule_actions(row),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    let mut lines = vec![count_label, String::new(), "Rules:".to_string()];
+    lines.extend(format::render_table_or_blocks(
+        &columns,
+        &table_rows,
+        format::terminal_width(),
+        "Rule",
+    ));
+    Ok(lines.join("\n"))
+}
+
+pub fn render_rule_remove(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("rules remove output requires message"))?;
+    Ok(message.to_string())
+}
+
+pub fn render_rules_apply(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("rules apply output requires message"))?;
+    let changes = data
+        .get("changes")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut lines = vec![message.to_string()];
+    if changes.is_empty() {
+        return Ok(lines.join("\n"));
+    }
+    let columns = [
+        Column {
+            name: "Transaction",
+            align: Align::Left,
+        },
+        Column {
+            name: "Rule",
+            align: Align::Left,
+        },
+        Column {
+            name: "Field",
+            align: Align::Left,
+        },
+        Column {
+            name: "From",
+            align: Align::Left,
+        },
+        Column {
+            name: "To",
+            align: Align::Left,
+        },
+    ];
+    let table_rows = changes
+        .iter()
+        .map(|change| {
+            ["txn_id", "rule_id", "field", "from", "to"]
+                .iter()
+                .map(|field| {
+                    change
+                        .get(*field)
+                        .and_then(Value::as_str)
+                        .unwrap_or("-")
+                        .to_string()
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    lines.push(String::new());
+    lines.push("Changes:".to_string());
+    lines.extend(format::render_table_or_blocks(
+        &columns,
+        &table_rows,
+        format::terminal_width(),
+        "Transaction",
+    ));
+    Ok(lines.join("\n"))
+}
+
+fn rule_conditions(rule: &Value) -> String {
+    let text = |name: &str| rule.get(name).and_then(Value::as_str);
+    let number = |name: &str| rule.get(name).and_then(Value::as_f64);
+    let mut parts = Vec::new();
+    if let Some(pattern) = text("description_pattern") {
+        parts.push(format!("description ~ /{pattern}/"));
+    }
+    match (number("amount_min"), number("amount_max")) {
+        (Some(min), Some(max)) => parts.push(format!("amount {min:.2}..{max:.2}")),
+        (Some(min), None) => parts.push(format!("amount >= {min:.2}")),
+        (None, Some(max)) => parts.push(format!("amount <= {max:.2}")),
+        (None, None) => {}
+    }
+    if let Some(account_key) = text("account_key") {
+        parts.push(format!("account {account_key}"));
+    }
+    if let Some(mcc) = text("mcc") {
+        parts.push(format!("mcc {mcc}"));
+    }
+    parts.join(", ")
+}
+
+fn rule_actions(rule: &Value) -> String {
+    let mut parts = Vec::new();
+    if let Some(merchant) = rule.get("set_merchant").and_then(Value::as_str) {
+        parts.push(format!("merchant = {merchant}"));
+    }
+    if let Some(category) = rule.get("set_category").and_then(Value::as_str) {
+        parts.push(format!("category = {category}"));
+    }
+    let tags = rule
+        .get("add_tags")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for tag in tags.iter().filter_map(Value::as_str) {
+        parts.push(format!("#{tag}"));
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{render_rule_list, render_rules_apply};
+
+    #[test]
+    fn rule_list_describes_conditions_and_actions() {
+        let listed = render_rule_list(&json!({
+            "rows": [{
+                "rule_id": "rule_1",
+                "position": 1,
+                "description_pattern": "^SQ \\*BLUE BOTTLE",
+                "amount_min": null,
+                "amount_max": 0.0,
+                "account_key": null,
+                "mcc": "5814",
+                "set_merchant": "Blue Bottle Coffee",
+                "set_category": "Coffee",
+                "add_tags": ["coffee"],
+                "created_at": "1760000000"
+            }]
+        }));
+        assert!(listed.is_ok());
+        if let Ok(text) = listed {
+            assert!(text.starts_with("1 rule, applied in this order."));
+            assert!(text.contains("description ~ /^SQ \\*BLUE BOTTLE/"));
+            assert!(text.contains("amount <= 0.00"));
+            assert!(text.contains("merchant = Blue Bottle Coffee"));
+            assert!(text.contains("#coffee"));
+        }
+
+        let empty = render_rule_list(&json!({ "rows": [] }));
+        assert!(empty.is_ok());
+        if let Ok(text) = empty {
+            assert!(text.contains("driggsby rules add"));
+        }
+    }
+
+    #[test]
+    fn rules_apply_lists_changes() {
+        let rendered = render_rules_apply(&json!({
+            "message": "Rules would change 1 transaction(s). No rows were written.",
+            "dry_run": true,
+            "rows_changed": 1,
+            "changes": [
+                { "txn_id": "txn_1", "rule_id": "rule_1", "field": "merchant", "from": null, "to": "Blue Bottle Coffee" }
+            ],
+            "intelligence_refreshed": false
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Changes:"));
+            assert!(text.contains("Blue Bottle Coffee"));
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::io;
+
+use serde_json::Value;
+
+use super::format::{self, Align, Column};
+
+pub fn render_tag_change(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("tag output requires message"))?;
+    let txn_ids = data
+        .get("txn_ids")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut lines = vec![message.to_string()];
+    if !txn_ids.is_empty() {
+        lines.push(String::new());
+        lines.push("Selected transactions:".to_string());
+        lines.extend(
+            txn_ids
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|txn_id| format!("  {txn_id}")),
+        );
+    }
+    Ok(lines.join("\n"))
+}
+
+pub fn render_tag_list(data: &Value) -> io::Result<String> {
+    let rows = data
+        .get("rows")
+        .and_then(Value::as_array)
+        .ok_or_else(|| io::Error::other("tag list output requires rows"))?;
+    let txn_id = data.get("txn_id").and_then(Value::as_str);
+
+    if rows.is_empty() {
+        let heading = match txn_id {
+            Some(txn_id) => format!("Transaction {txn_id} has no tags."),
+            None => "No tags in use yet.".to_string(),
+        };
+        return Ok([
+            heading.as_str(),
+            "",
+            "Tag transactions by id or by SQL filter:",
+            "  driggsby tag add reimbursable <txn-id>",
+            "  driggsby tag add vacation-2026 --where \"posted_at BETWEEN '2026-07-01' AND '2026-07-14'\"",
+        ]
+        .join("\n"));
+    }
+
+    let count_label = match (txn_id, rows.len()) {
+        (Some(txn_id), 1) => format!("Transaction {txn_id} has 1 tag."),
+        (Some(txn_id), count) => format!("Transaction {txn_id} has {count} tags."),
+        (None, 1) => "1 tag in use.".to_string(),
+        (None, count) => format!("{count} tags in use."),
+    };
+    let columns = [
+        Column {
+            name: "Tag",
+            align: Align::Left,
+        },
+        Column {
+            name: "Transactions",
+            align: Align::Right,
+        },
+    ];
+    let table_rows = rows
+        .iter()
+        .map(|row| {
+            vec![
+                format!(
+                    "#{}",
+                    row.get("tag").and_then(Value::as_str).unwrap_or_default()
+                ),
+                row.get("txn_count")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0)
+                    .to_string(),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    let mut lines = vec![count_label, String::new(), "Tags:".to_string()];
+    lines.extend(format::render_table_or_blocks(
+        &columns,
+        &table_rows,
+        format::terminal_width(),
+        "Tag",
+    ));
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{render_tag_change, render_tag_list};
+
+    #[test]
+    fn tag_list_renders_counts_and_empty_guidance() {
+        let empty = render_tag_list(&json!({ "rows": [] }));
+        assert!(empty.is_ok());
+        if let Ok(text) = empty {
+            assert!(text.contains("No tags in use yet."));
+            assert!(text.contains("--where"));
+        }
+
+        let listed = render_tag_list(&json!({
+            "rows": [
+                { "tag": "reimbursable", "txn_count": 3, "last_tagged_at": "1760000000" },
+                { "tag": "vacation-2026", "txn_count": 12, "last_tagged_at": "1760000000" }
+            ]
+        }));
+        assert!(listed.is_ok());
+        if let Ok(text) = listed {
+            assert!(text.starts_with("2 tags in use."));
+            assert!(text.contains("#reimbursable"));
+            assert!(text.contains("12"));
+        }
+    }
+
+    #[test]
+    fn tag_change_lists_selected_transactions() {
+        let rendered = render_tag_change(&json!({
+            "message": "Tagged 2 of 2 selected transaction(s) with #reimbursable.",
+            "tag": "reimbursable",
+            "txn_ids": ["txn_1", "txn_2"],
+            "changed": 2
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Tagged 2 of 2"));
+            assert!(text.contains("  txn_2"));
+        }
+    }
+}
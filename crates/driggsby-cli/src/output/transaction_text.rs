@@ -0,0 +1,463 @@
+use std::io;
+
+use serde_json::Value;
+
+use super::format::{self, Align, Column};
+
+pub fn render_transaction_show(data: &Value) -> io::Result<String> {
+    let txn_id = data
+        .get("txn_id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("transaction show output requires txn_id"))?;
+    let field = |value: &Value, name: &str| {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("-")
+            .to_string()
+    };
+    let amount = data.get("amount").and_then(Value::as_f64).unwrap_or(0.0);
+
+    let mut lines = vec![format!("Transaction {txn_id}"), String::new()];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Account:", field(data, "account_key")),
+            ("Posted at:", field(data, "posted_at")),
+            (
+                "Amount:",
+                format!("{amount:.2} {}", field(data, "currency")),
+            ),
+            ("Description:", field(data, "description")),
+            ("Merchant:", effective_field(data, "merchant")),
+            ("Category:", effective_field(data, "category")),
+            ("External ID:", field(data, "external_id")),
+            ("Statement ID:", field(data, "statement_id")),
+        ],
+        2,
+    ));
+    if let Some(note) = data.get("note").and_then(Value::as_str) {
+        lines.extend(format::key_value_rows(&[("Note:", note.to_string())], 2));
+    }
+    if let Some(exclusion) = data.get("exclusion").filter(|value| !value.is_null()) {
+        lines.extend(format::key_value_rows(
+            &[(
+                "Excluded:",
+                format!(
+                    "{} (left out of intelligence and reporting)",
+                    field(exclusion, "reason")
+                ),
+            )],
+            2,
+        ));
+    }
+    let splits = data
+        .get("splits")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if !splits.is_empty() {
+        lines.push(String::new());
+        lines.push("Split:".to_string());
+        lines.extend(split_rows(&splits, &field(data, "currency")));
+    }
+    let rule_hits = data
+        .get("rule_hits")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if !rule_hits.is_empty() {
+        lines.push(String::new());
+        lines.push("Changed by rules:".to_string());
+        for hit in &rule_hits {
+            let fields = hit
+                .get("fields")
+                .and_then(Value::as_array)
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                })
+                .unwrap_or_default();
+            lines.push(format!("  {}: {fields}", field(hit, "rule_id")));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("Source:".to_string());
+    match data.get("provenance").filter(|value| !value.is_null()) {
+        Some(provenance) => {
+            let source = match provenance.get("source_ref").and_then(Value::as_str) {
+                Some(source_ref) => source_ref.to_string(),
+                None => field(provenance, "source_kind"),
+            };
+            let row = provenance
+                .get("source_row_index")
+                .and_then(Value::as_i64)
+                .map(|index| index.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            lines.extend(format::key_value_rows(
+                &[
+                    ("Import ID:", field(provenance, "import_id")),
+                    ("File:", source),
+                    ("Row:", row),
+                    ("Committed at:", field(provenance, "committed_at")),
+                ],
+                2,
+            ));
+            lines.push(String::new());
+            lines.push("Original record:".to_string());
+            lines.extend(
+                field(provenance, "source_record")
+                    .lines()
+                    .map(|line| format!("  {line}")),
+            );
+        }
+        None => {
+            lines.extend(format::key_value_rows(
+                &[("Import ID:", field(data, "import_id"))],
+                2,
+            ));
+            lines.push(
+                "  The source row was not recorded for this transaction; it was imported before row provenance was kept."
+                    .to_string(),
+            );
+        }
+    }
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_transaction_add(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("transaction add output requires message"))?;
+    let field = |name: &str| {
+        data.get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("-")
+            .to_string()
+    };
+    let amount = data.get("amount").and_then(Value::as_f64).unwrap_or(0.0);
+
+    let mut lines = vec![message.to_string(), String::new()];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Transaction:", field("txn_id")),
+            ("Account:", field("account_key")),
+            ("Posted at:", field("posted_at")),
+            ("Amount:", format!("{amount:.2} {}", field("currency"))),
+            ("Description:", field("description")),
+            ("Merchant:", field("effective_merchant")),
+            ("Category:", field("effective_category")),
+        ],
+        2,
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_transaction_delete(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("transaction delete output requires message"))?;
+    Ok(message.to_string())
+}
+
+pub fn render_transaction_edit(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("transaction edit output requires message"))?;
+    let note = data
+        .get("note")
+        .and_then(Value::as_str)
+        .unwrap_or("-")
+        .to_string();
+    let refreshed = if data
+        .get("intelligence_refreshed")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        "yes"
+    } else {
+        "no"
+    };
+
+    let mut lines = vec![message.to_string(), String::new()];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Merchant:", effective_field(data, "merchant")),
+            ("Category:", effective_field(data, "category")),
+            ("Note:", note),
+            ("Intelligence refreshed:", refreshed.to_string()),
+        ],
+        2,
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_transaction_exclude(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("transaction exclude output requires message"))?;
+    let reason = data
+        .get("exclusion")
+        .and_then(|exclusion| exclusion.get("reason"))
+        .and_then(Value::as_str)
+        .unwrap_or("-")
+        .to_string();
+    let excluded = if data
+        .get("excluded")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        "yes"
+    } else {
+        "no"
+    };
+    let refreshed = if data
+        .get("intelligence_refreshed")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        "yes"
+    } else {
+        "no"
+    };
+
+    let mut lines = vec![message.to_string(), String::new()];
+    lines.extend(format::key_value_rows(
+        &[
+            ("Excluded:", excluded.to_string()),
+            ("Reason:", reason),
+            ("Intelligence refreshed:", refreshed.to_string()),
+        ],
+        2,
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+pub fn render_transaction_split(data: &Value) -> io::Result<String> {
+    let message = data
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or_else(|| io::Error::other("transaction split output requires message"))?;
+    let splits = data
+        .get("splits")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let currency = data.get("currency").and_then(Value::as_str).unwrap_or("-");
+
+    let mut lines = vec![message.to_string()];
+    if !splits.is_empty() {
+        let amount = data.get("amount").and_then(Value::as_f64).unwrap_or(0.0);
+        lines.push(String::new());
+        lines.extend(format::key_value_rows(
+            &[("Amount:", format!("{amount:.2} {currency}"))],
+            0,
+        ));
+        lines.push(String::new());
+        lines.push("Split:".to_string());
+        lines.extend(split_rows(&splits, currency));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn split_rows(splits: &[Value], currency: &str) -> Vec<String> {
+    let columns = [
+        Column {
+            name: "Part",
+            align: Align::Right,
+        },
+        Column {
+            name: "Amount",
+            align: Align::Right,
+        },
+        Column {
+            name: "Category",
+            align: Align::Left,
+        },
+    ];
+    let rows = splits
+        .iter()
+        .map(|split| {
+            vec![
+                split
+                    .get("split_index")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0)
+                    .to_string(),
+                format!(
+                    "{:.2} {currency}",
+                    split.get("amount").and_then(Value::as_f64).unwrap_or(0.0)
+                ),
+                split
+                    .get("category")
+                    .and_then(Value::as_str)
+                    .unwrap_or("-")
+                    .to_string(),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+    format::render_table_or_blocks(&columns, &rows, format::terminal_width(), "Part")
+}
+
+/// Shows the effective value, with the imported one alongside when an
+/// override replaced it.
+fn effective_field(data: &Value, name: &str) -> String {
+    let original = data.get(name).and_then(Value::as_str);
+    let effective = match data.get(format!("effective_{name}").as_str()) {
+        Some(value) => value.as_str(),
+        None => original,
+    };
+    match effective {
+        Some(value) if effective != original => {
+            format!("{value} (imported: {})", original.unwrap_or("-"))
+        }
+        Some(value) => value.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        render_transaction_add, render_transaction_edit, render_transaction_exclude,
+        render_transaction_show, render_transaction_split,
+    };
+
+    #[test]
+    fn transaction_add_renders_entered_values() {
+        let rendered = render_transaction_add(&json!({
+            "message": "Manual transaction `txn_1` was added as import `imp_1`.",
+            "txn_id": "txn_1",
+            "import_id": "imp_1",
+            "account_key": "cash_wallet",
+            "posted_at": "2026-03-14",
+            "amount": -12.5,
+            "currency": "USD",
+            "description": "Farmers market",
+            "effective_merchant": null,
+            "effective_category": "Groceries",
+            "intelligence_refreshed": true
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Manual transaction `txn_1` was added"));
+            assert!(text.contains("cash_wallet"));
+            assert!(text.contains("-12.50 USD"));
+            assert!(text.contains("Groceries"));
+        }
+    }
+
+    #[test]
+    fn transaction_show_renders_source_row_and_legacy_fallback() {
+        let rendered = render_transaction_show(&json!({
+            "txn_id": "txn_1",
+            "import_id": "imp_1",
+            "account_key": "chase_checking_1234",
+            "posted_at": "2026-01-02",
+            "amount": -12.5,
+            "currency": "USD",
+            "description": "Coffee",
+            "provenance": {
+                "import_id": "imp_1",
+                "source_kind": "file",
+                "source_ref": "/tmp/statement.csv",
+                "source_row_index": 3,
+                "source_record": "chase_checking_1234,2026-01-02,-12.5,USD,Coffee",
+                "committed_at": "1760000000"
+            }
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Transaction txn_1"));
+            assert!(text.contains("-12.50 USD"));
+            assert!(text.contains("/tmp/statement.csv"));
+            assert!(text.contains("Row:"));
+            assert!(text.contains("  chase_checking_1234,2026-01-02,-12.5,USD,Coffee"));
+        }
+
+        let legacy = render_transaction_show(&json!({
+            "txn_id": "txn_2",
+            "import_id": "imp_0",
+            "amount": 1.0,
+            "provenance": null
+        }));
+        assert!(legacy.is_ok());
+        if let Ok(text) = legacy {
+            assert!(text.contains("imp_0"));
+            assert!(text.contains("source row was not recorded"));
+        }
+    }
+
+    #[test]
+    fn transaction_edit_renders_effective_values_next_to_imported_ones() {
+        let rendered = render_transaction_edit(&json!({
+            "message": "Transaction `txn_1` was updated.",
+            "txn_id": "txn_1",
+            "merchant": "SQ *BLUE BOTTLE",
+            "category": null,
+            "effective_merchant": "Blue Bottle",
+            "effective_category": "Coffee",
+            "note": "Team offsite",
+            "intelligence_refreshed": true
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.contains("Blue Bottle (imported: SQ *BLUE BOTTLE)"));
+            assert!(text.contains("Coffee (imported: -)"));
+            assert!(text.contains("Team offsite"));
+            assert!(text.contains("Intelligence refreshed:"));
+        }
+    }
+
+    #[test]
+    fn transaction_split_renders_parts_with_currency() {
+        let rendered = render_transaction_split(&json!({
+            "message": "Transaction `txn_1` was split into 2 parts.",
+            "txn_id": "txn_1",
+            "amount": -120.0,
+            "currency": "USD",
+            "splits": [
+                { "split_index": 1, "amount": -80.0, "category": "Groceries" },
+                { "split_index": 2, "amount": -40.0, "category": "Gifts" }
+            ]
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Transaction `txn_1` was split into 2 parts."));
+            assert!(text.contains("-120.00 USD"));
+            assert!(text.contains("-80.00 USD"));
+            assert!(text.contains("Gifts"));
+        }
+    }
+
+    #[test]
+    fn transaction_exclude_renders_reason_and_refresh_state() {
+        let rendered = render_transaction_exclude(&json!({
+            "message": "Transaction `txn_1` is excluded from intelligence and reporting as `transfer`.",
+            "txn_id": "txn_1",
+            "excluded": true,
+            "exclusion": { "reason": "transfer", "excluded_at": "1760000000" },
+            "intelligence_refreshed": true
+        }));
+        assert!(rendered.is_ok());
+        if let Ok(text) = rendered {
+            assert!(text.starts_with("Transaction `txn_1` is excluded"));
+            assert!(text.contains("Reason:"));
+            assert!(text.contains("transfer"));
+            assert!(text.contains("yes"));
+        }
+    }
+}
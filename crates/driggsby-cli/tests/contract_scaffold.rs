@@ -33,7 +33,22 @@ Other commands:
   driggsby account list                                   Show account-level ledger orientation
   driggsby import list                                    List past imports
   driggsby import keys uniq                               List canonical import identifiers
+  driggsby alias add <property> <alias> <canonical>       Rewrite a drifting value on every import
+  driggsby alias list                                     List stored aliases
   driggsby import undo <import-id>                        Undo an import
+  driggsby import redo <import-id>                        Restore an undone import
+  driggsby import stage <path>                            Hold an import for review before committing
+  driggsby import approve <import-id>                     Commit a staged import
+  driggsby import replace <import-id> <path>              Swap an import for a corrected file
+  driggsby transaction show <txn-id>                      Show a transaction and the source row it came from
+  driggsby transaction add --account <key> --amount <n>   Record a cash or off-ledger transaction
+  driggsby transaction edit <txn-id> --category <name>    Override merchant, category or note
+  driggsby transaction split <txn-id> --part <amt:cat>    Split a transaction across categories
+  driggsby transaction exclude <txn-id> --reason <why>    Leave a transfer or refund out of reports
+  driggsby transaction mark-duplicate <txn-id> --of <id>  Remove a duplicate transaction
+  driggsby tag add <tag> <txn-id>... [--where <sql>]      Tag transactions, e.g. #reimbursable
+  driggsby rules add --description <regex> ...            Set merchant, category or tags by rule
+  driggsby rules apply --dry-run                          Preview rules against existing transactions
 
 Want to ensure a clean first run, or having issues/errors?
   Run `driggsby import create --help` for import workflow guidance,
@@ -60,19 +60,69 @@ pub(crate) fn query_accounts_data(
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
+    // The latest balance is the last row of the newest day. Rows posted on
+    // the same day follow their import's file order, reversed when the file
+    // lists the account newest-first, as in `import::balances`; the newest
+    // import wins a day that several imports cover.
     let mut statement = connection
         .prepare(
-            "SELECT
+            "WITH file_direction AS (
+                SELECT DISTINCT
+                    s.import_id,
+                    x.account_key,
+                    FIRST_VALUE(x.posted_at) OVER file_rows
+                        > LAST_VALUE(x.posted_at) OVER file_rows AS newest_first
+                FROM internal_import_source_rows s
+                JOIN internal_transactions x ON x.txn_id = s.txn_id
+                WINDOW file_rows AS (
+                    PARTITION BY s.import_id, x.account_key
+                    ORDER BY s.source_row_index
+                    ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING
+                )
+             ),
+             ranked_balances AS (
+                SELECT
+                    b.account_key,
+                    b.currency,
+                    b.balance,
+                    b.posted_at,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY b.account_key, b.currency
+                        ORDER BY
+                            b.posted_at DESC,
+                            r.created_at DESC,
+                            b.import_id DESC,
+                            CASE
+                                WHEN d.newest_first THEN s.source_row_index
+                                ELSE -s.source_row_index
+                            END ASC,
+                            b.txn_id DESC
+                    ) AS position
+                FROM internal_transactions b
+                LEFT JOIN internal_import_runs r ON r.import_id = b.import_id
+                LEFT JOIN internal_import_source_rows s ON s.txn_id = b.txn_id
+                LEFT JOIN file_direction d
+                    ON d.import_id = b.import_id
+                   AND d.account_key = b.account_key
+                WHERE b.balance IS NOT NULL
+             )
+             SELECT
                 t.account_key,
                 a.account_type,
                 t.currency,
                 COUNT(*) AS txn_count,
                 MIN(t.posted_at) AS first_posted_at,
                 MAX(t.posted_at) AS last_posted_at,
-                ROUND(COALESCE(SUM(t.amount), 0), 2) AS net_amount
+                ROUND(COALESCE(SUM(t.amount), 0), 2) AS net_amount,
+                l.balance AS latest_balance,
+                l.posted_at AS latest_balance_at
              FROM internal_transactions t
              LEFT JOIN internal_accounts a ON a.account_key = t.account_key
-             GROUP BY t.account_key, a.account_type, t.currency
+             LEFT JOIN ranked_balances l
+                ON l.account_key = t.account_key
+               AND l.currency = t.currency
+               AND l.position = 1
+             GROUP BY t.account_key, a.account_type, t.currency, l.balance, l.posted_at
              ORDER BY t.account_key ASC, t.currency ASC",
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
@@ -87,6 +137,8 @@ pub(crate) fn query_accounts_data(
                 first_posted_at: row.get(4)?,
                 last_posted_at: row.get(5)?,
                 net_amount: row.get(6)?,
+                latest_balance: row.get(7)?,
+                latest_balance_at: row.get(8)?,
             })
         })
         .map_err(|error| map_sqlite_error(db_path, &error))?;
@@ -0,0 +1,286 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::contracts::envelope::{SuccessEnvelope, success};
+use crate::contracts::types::{AliasAddData, AliasListData, AliasRemoveData, AliasRow};
+use crate::import::inventory::TrackedProperty;
+use crate::import::persist::now_timestamp;
+use crate::import::remap::RemapRule;
+use crate::setup::{ensure_initialized, ensure_initialized_at};
+use crate::state::{map_sqlite_error, open_connection};
+use crate::{ClientError, ClientResult};
+
+const ALIAS_PROPERTIES: [TrackedProperty; 3] = [
+    TrackedProperty::AccountKey,
+    TrackedProperty::Merchant,
+    TrackedProperty::Category,
+];
+
+#[derive(Debug, Default)]
+pub struct AliasAddOptions<'a> {
+    pub property: String,
+    pub alias: String,
+    pub canonical: String,
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct AliasListOptions<'a> {
+    pub property: Option<String>,
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct AliasRemoveOptions<'a> {
+    pub property: String,
+    pub alias: String,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn add(property: String, alias: String, canonical: String) -> ClientResult<SuccessEnvelope> {
+    add_with_options(AliasAddOptions {
+        property,
+        alias,
+        canonical,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn add_with_options(options: AliasAddOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let property = parse_alias_property(&options.property, "alias add")?;
+    let alias = options.alias.trim().to_string();
+    let canonical = options.canonical.trim().to_string();
+    if alias.is_empty() || canonical.is_empty() {
+        return Err(ClientError::invalid_argument_for_command(
+            "Alias and canonical values must both be non-empty.",
+            Some("alias add"),
+        ));
+    }
+    if alias == canonical {
+        return Err(ClientError::invalid_argument_for_command(
+            &format!("Alias `{alias}` cannot point at itself."),
+            Some("alias add"),
+        ));
+    }
+
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+
+    if let Some(target) = find_canonical(&connection, &db_path, property, &canonical)? {
+        return Err(ClientError::invalid_argument_with_recovery(
+            &format!(
+                "`{canonical}` is itself an alias of {} `{target}`, so aliases cannot point at it.",
+                property.as_str()
+            ),
+            vec![format!(
+                "Run driggsby alias add {} \"{alias}\" \"{target}\" instead.",
+                property.as_str()
+            )],
+        ));
+    }
+    let dependent_count = connection
+        .query_row(
+            "SELECT COUNT(*) FROM internal_aliases WHERE property = ?1 AND canonical = ?2",
+            params![property.as_str(), &alias],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+    if dependent_count > 0 {
+        return Err(ClientError::invalid_argument_with_recovery(
+            &format!(
+                "`{alias}` is already the canonical value for {dependent_count} {} alias(es), so it cannot become an alias.",
+                property.as_str()
+            ),
+            vec![format!(
+                "Run driggsby alias list --property {} to review existing aliases.",
+                property.as_str()
+            )],
+        ));
+    }
+
+    let replaced_canonical = find_canonical(&connection, &db_path, property, &alias)?
+        .filter(|existing| *existing != canonical);
+    let created_at = now_timestamp();
+    connection
+        .execute(
+            "INSERT INTO internal_aliases (property, alias, canonical, created_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (property, alias)
+             DO UPDATE SET canonical = excluded.canonical, created_at = excluded.created_at",
+            params![property.as_str(), &alias, &canonical, &created_at],
+        )
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+
+    let message = match replaced_canonical.as_deref() {
+        Some(previous) => format!(
+            "Alias `{alias}` now maps to `{canonical}` (was `{previous}`). Future imports rewrite it automatically."
+        ),
+        None => format!(
+            "Alias `{alias}` maps to `{canonical}`. Future imports rewrite it automatically."
+        ),
+    };
+    success(
+        "alias add",
+        AliasAddData {
+            message,
+            alias: AliasRow {
+                property: property.as_str().to_string(),
+                alias,
+                canonical,
+                created_at,
+            },
+            replaced_canonical,
+        },
+    )
+}
+
+pub fn list(property: Option<String>) -> ClientResult<SuccessEnvelope> {
+    list_with_options(AliasListOptions {
+        property,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn list_with_options(options: AliasListOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let property = match options.property.as_deref() {
+        Some(raw) => Some(parse_alias_property(raw, "alias list")?),
+        None => None,
+    };
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+
+    let rows = query_alias_rows(&connection, &db_path)?
+        .into_iter()
+        .filter(|row| property.is_none_or(|requested| row.property == requested.as_str()))
+        .collect::<Vec<AliasRow>>();
+
+    success(
+        "alias list",
+        AliasListData {
+            property: property.map(|requested| requested.as_str().to_string()),
+            rows,
+        },
+    )
+}
+
+pub fn remove(property: String, alias: String) -> ClientResult<SuccessEnvelope> {
+    remove_with_options(AliasRemoveOptions {
+        property,
+        alias,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn remove_with_options(options: AliasRemoveOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let property = parse_alias_property(&options.property, "alias remove")?;
+    let alias = options.alias.trim().to_string();
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+
+    let existing = query_alias_rows(&connection, &db_path)?
+        .into_iter()
+        .find(|row| row.property == property.as_str() && row.alias == alias);
+    let Some(existing) = existing else {
+        return Err(ClientError::alias_not_found(property.as_str(), &alias));
+    };
+    connection
+        .execute(
+            "DELETE FROM internal_aliases WHERE property = ?1 AND alias = ?2",
+            params![property.as_str(), &alias],
+        )
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+
+    success(
+        "alias remove",
+        AliasRemoveData {
+            message: format!(
+                "Alias `{alias}` was removed. Rows already imported keep `{}`.",
+                existing.canonical
+            ),
+            alias: existing,
+        },
+    )
+}
+
+/// Stored aliases as remap rules, applied to every import before validation.
+pub(crate) fn alias_rules(connection: &Connection, db_path: &Path) -> ClientResult<Vec<RemapRule>> {
+    Ok(query_alias_rows(connection, db_path)?
+        .into_iter()
+        .filter_map(|row| {
+            Some(RemapRule {
+                property: TrackedProperty::parse(&row.property)?,
+                from: row.alias,
+                to: row.canonical,
+            })
+        })
+        .collect())
+}
+
+fn query_alias_rows(connection: &Connection, db_path: &Path) -> ClientResult<Vec<AliasRow>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT property, alias, canonical, created_at
+             FROM internal_aliases
+             ORDER BY property ASC, canonical ASC, alias ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok(AliasRow {
+                property: row.get(0)?,
+                alias: row.get(1)?,
+                canonical: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut aliases = Vec::new();
+    for row in rows {
+        aliases.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(aliases)
+}
+
+fn find_canonical(
+    connection: &Connection,
+    db_path: &Path,
+    property: TrackedProperty,
+    alias: &str,
+) -> ClientResult<Option<String>> {
+    connection
+        .query_row(
+            "SELECT canonical FROM internal_aliases WHERE property = ?1 AND alias = ?2",
+            params![property.as_str(), alias],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+fn parse_alias_property(raw: &str, command: &str) -> ClientResult<TrackedProperty> {
+    TrackedProperty::parse(raw)
+        .filter(|property| ALIAS_PROPERTIES.contains(property))
+        .ok_or_else(|| {
+            ClientError::invalid_argument_for_command(
+                &format!(
+                    "Invalid alias property `{raw}`. Supported values: account_key, merchant, category."
+                ),
+                Some(command),
+            )
+        })
+}
+
+fn load_setup(home_override: Option<&Path>) -> ClientResult<crate::setup::SetupContext> {
+    if let Some(path) = home_override {
+        return ensure_initialized_at(path);
+    }
+    ensure_initialized()
+}
@@ -10,18 +10,25 @@ use crate::intelligence::date::{build_filter, format_iso_date};
 use crate::intelligence::policy::ANOMALIES_POLICY_VERSION;
 use crate::setup::{SetupContext, ensure_initialized, ensure_initialized_at};
 use crate::state::{map_sqlite_error, open_connection};
+use crate::tags::normalize_tag;
 
 #[derive(Debug, Default)]
 pub struct AnomaliesRunOptions<'a> {
     pub from: Option<String>,
     pub to: Option<String>,
+    pub tag: Option<String>,
     pub home_override: Option<&'a Path>,
 }
 
-pub fn run(from: Option<&str>, to: Option<&str>) -> ClientResult<SuccessEnvelope> {
+pub fn run(
+    from: Option<&str>,
+    to: Option<&str>,
+    tag: Option<&str>,
+) -> ClientResult<SuccessEnvelope> {
     run_with_options(AnomaliesRunOptions {
         from: from.map(std::string::ToString::to_string),
         to: to.map(std::string::ToString::to_string),
+        tag: tag.map(std::string::ToString::to_string),
         home_override: None,
     })
 }
@@ -30,6 +37,7 @@ pub fn run(from: Option<&str>, to: Option<&str>) -> ClientResult<SuccessEnvelope
 pub fn run_with_options(options: AnomaliesRunOptions<'_>) -> ClientResult<SuccessEnvelope> {
     let setup = load_setup(options.home_override)?;
     let filter = build_filter(options.from.as_deref(), options.to.as_deref(), "anomalies")?;
+    let tag = options.tag.as_deref().map(normalize_tag).transpose()?;
     let db_path = std::path::PathBuf::from(&setup.db_path);
     let connection = open_connection(&db_path)?;
     let from_bound = filter.from.as_ref().map(format_iso_date);
@@ -50,11 +58,12 @@ pub fn run_with_options(options: AnomaliesRunOptions<'_>) -> ClientResult<Succes
              FROM v1_anomalies
              WHERE (?1 IS NULL OR posted_at >= ?1)
                AND (?2 IS NULL OR posted_at <= ?2)
+               AND (?3 IS NULL OR txn_id IN (SELECT txn_id FROM v1_transaction_tags WHERE tag = ?3))
              ORDER BY posted_at ASC, merchant ASC, txn_id ASC",
         )
         .map_err(|error| map_sqlite_error(&db_path, &error))?;
     let rows_iter = statement
-        .query_map(params![from_bound, to_bound], |row| {
+        .query_map(params![from_bound, to_bound, &tag], |row| {
             Ok(AnomalyRow {
                 txn_id: row.get(0)?,
                 account_key: row.get(1)?,
@@ -79,6 +88,7 @@ pub fn run_with_options(options: AnomaliesRunOptions<'_>) -> ClientResult<Succes
         policy_version: ANOMALIES_POLICY_VERSION.to_string(),
         from: filter.from.as_ref().map(format_iso_date),
         to: filter.to.as_ref().map(format_iso_date),
+        tag,
         rows,
         data_range_hint: data_range_hint(&setup.data_range),
     };
@@ -8,12 +8,16 @@ const REQUIRED_IMPORT_FIELDS: [(&str, &str); 5] = [
     ("description", "string"),
 ];
 
-const OPTIONAL_IMPORT_FIELDS: [(&str, &str); 5] = [
+const OPTIONAL_IMPORT_FIELDS: [(&str, &str); 9] = [
     ("statement_id", "string"),
     ("account_type", "string"),
     ("external_id", "string"),
     ("merchant", "string|null"),
     ("category", "string|null"),
+    ("statement_opening_balance", "number|null"),
+    ("statement_closing_balance", "number|null"),
+    ("balance", "number|null"),
+    ("mcc", "string|null"),
 ];
 
 pub(crate) fn required_import_field_names() -> Vec<&'static str> {
@@ -47,6 +51,13 @@ pub fn public_view_contracts() -> Vec<PublicView> {
                 view_column("external_id", "text|null"),
                 view_column("merchant", "text|null"),
                 view_column("category", "text|null"),
+                view_column("effective_merchant", "text|null"),
+                view_column("effective_category", "text|null"),
+                view_column("note", "text|null"),
+                view_column("balance", "real|null"),
+                view_column("mcc", "text|null"),
+                view_column("excluded", "integer"),
+                view_column("exclusion_reason", "text|null"),
             ],
         },
         PublicView {
@@ -109,6 +120,72 @@ pub fn public_view_contracts() -> Vec<PublicView> {
                 view_column("severity", "text"),
             ],
         },
+        PublicView {
+            name: "v1_statements".to_string(),
+            columns: vec![
+                view_column("statement_id", "text"),
+                view_column("account_key", "text"),
+                view_column("account_type", "text|null"),
+                view_column("import_id", "text"),
+                view_column("currency", "text"),
+                view_column("opening_balance", "real"),
+                view_column("closing_balance", "real"),
+                view_column("net_amount", "real"),
+                view_column("txn_count", "integer"),
+                view_column("first_posted_at", "date"),
+                view_column("last_posted_at", "date"),
+                view_column("reconciled_at", "text"),
+            ],
+        },
+        PublicView {
+            name: "v1_account_coverage".to_string(),
+            columns: vec![
+                view_column("account_key", "text"),
+                view_column("range_kind", "text"),
+                view_column("range_start", "date"),
+                view_column("range_end", "date"),
+                view_column("day_count", "integer"),
+                view_column("import_count", "integer"),
+            ],
+        },
+        PublicView {
+            name: "v1_transaction_tags".to_string(),
+            columns: vec![
+                view_column("txn_id", "text"),
+                view_column("tag", "text"),
+                view_column("tagged_at", "text"),
+            ],
+        },
+        PublicView {
+            name: "v1_transaction_splits".to_string(),
+            columns: vec![
+                view_column("txn_id", "text"),
+                view_column("split_index", "integer"),
+                view_column("amount", "real"),
+                view_column("currency", "text"),
+                view_column("category", "text"),
+                view_column("split_at", "text"),
+            ],
+        },
+        PublicView {
+            name: "v1_category_rollup".to_string(),
+            columns: vec![
+                view_column("month", "text"),
+                view_column("currency", "text"),
+                view_column("category", "text|null"),
+                view_column("txn_count", "integer"),
+                view_column("amount", "real"),
+            ],
+        },
+        PublicView {
+            name: "v1_transaction_rule_hits".to_string(),
+            columns: vec![
+                view_column("txn_id", "text"),
+                view_column("rule_id", "text"),
+                view_column("fields", "text"),
+                view_column("applied_at", "text"),
+            ],
+        },
     ]
 }
 
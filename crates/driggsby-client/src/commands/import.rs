@@ -4,9 +4,11 @@ use rusqlite::{OptionalExtension, params};
 
 use crate::contracts::envelope::{SuccessEnvelope, success};
 use crate::contracts::types::{
-    ImportData, ImportDuplicateRow, ImportDuplicatesData, ImportKeysUniqData,
-    ImportListAccountStat, ImportListData, ImportListItem, ImportPropertyInventory, ImportUndoData,
-    ImportUndoSummary, QueryContext,
+    ImportApproveData, ImportCreateSummary, ImportData, ImportDuplicatePromoteData,
+    ImportDuplicateRow, ImportDuplicatesData, ImportKeysUniqData, ImportListAccountStat,
+    ImportListData, ImportListItem, ImportProbableResolutionData, ImportPropertyInventory,
+    ImportRedoData, ImportRedoSummary, ImportRejectData, ImportStagedListData,
+    ImportStagedShowData, ImportUndoData, ImportUndoSummary, QueryContext,
 };
 use crate::import;
 use crate::setup::{ensure_initialized, ensure_initialized_at};
@@ -19,6 +21,35 @@ pub struct ImportRunOptions<'a> {
     pub dry_run: bool,
     pub home_override: Option<&'a Path>,
     pub stdin_override: Option<String>,
+    pub auto_fix_signs: bool,
+    pub apply_category_suggestions: bool,
+    pub remaps: Vec<String>,
+    pub remap_file: Option<String>,
+    pub idempotency_key: Option<String>,
+    pub allow_duplicate_source: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReplaceOptions<'a> {
+    pub import_id: String,
+    pub path: String,
+    pub dry_run: bool,
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportStageOptions<'a> {
+    pub path: String,
+    pub home_override: Option<&'a Path>,
+    pub auto_fix_signs: bool,
+    pub apply_category_suggestions: bool,
+    pub remaps: Vec<String>,
+    pub remap_file: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportStagedOptions<'a> {
+    pub home_override: Option<&'a Path>,
 }
 
 #[derive(Debug, Default)]
@@ -28,6 +59,13 @@ pub struct ImportListOptions<'a> {
 
 #[derive(Debug, Default)]
 pub struct ImportUndoOptions<'a> {
+    pub account_key: Option<String>,
+    pub statement_id: Option<String>,
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportRedoOptions<'a> {
     pub home_override: Option<&'a Path>,
 }
 
@@ -42,28 +80,236 @@ pub struct ImportKeysUniqOptions<'a> {
     pub home_override: Option<&'a Path>,
 }
 
-pub fn run(path: Option<String>, dry_run: bool) -> ClientResult<SuccessEnvelope> {
+/// Runs an import from the CLI. Test-only overrides in `options` are
+/// ignored.
+pub fn run(options: ImportRunOptions<'_>) -> ClientResult<SuccessEnvelope> {
     run_with_options(ImportRunOptions {
-        path,
-        dry_run,
         home_override: None,
         stdin_override: None,
+        ..options
     })
 }
 
 #[doc(hidden)]
 pub fn run_with_options(options: ImportRunOptions<'_>) -> ClientResult<SuccessEnvelope> {
-    let setup = load_setup(options.home_override)?;
-    let execution = import::execute(
-        &setup,
-        options.path.clone(),
-        options.dry_run,
-        options.stdin_override,
+    let data = execute_import(
+        options.home_override,
+        import::ImportRequest {
+            path: options.path,
+            dry_run: options.dry_run,
+            stdin_override: options.stdin_override,
+            auto_fix_signs: options.auto_fix_signs,
+            apply_category_suggestions: options.apply_category_suggestions,
+            remaps: options.remaps,
+            remap_file: options.remap_file,
+            idempotency_key: options.idempotency_key,
+            allow_duplicate_source: options.allow_duplicate_source,
+            replace_import_id: None,
+            stage: false,
+        },
     )?;
-    let context_setup = if options.dry_run {
+
+    success("import", data)
+}
+
+pub fn replace(import_id: String, path: String, dry_run: bool) -> ClientResult<SuccessEnvelope> {
+    replace_with_options(ImportReplaceOptions {
+        import_id,
+        path,
+        dry_run,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn replace_with_options(options: ImportReplaceOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let data = execute_import(
+        options.home_override,
+        import::ImportRequest {
+            path: Some(options.path),
+            dry_run: options.dry_run,
+            replace_import_id: Some(options.import_id.trim().to_string()),
+            ..Default::default()
+        },
+    )?;
+
+    success("import replace", data)
+}
+
+pub fn stage(
+    path: String,
+    auto_fix_signs: bool,
+    apply_category_suggestions: bool,
+    remaps: Vec<String>,
+    remap_file: Option<String>,
+) -> ClientResult<SuccessEnvelope> {
+    stage_with_options(ImportStageOptions {
+        path,
+        home_override: None,
+        auto_fix_signs,
+        apply_category_suggestions,
+        remaps,
+        remap_file,
+    })
+}
+
+#[doc(hidden)]
+pub fn stage_with_options(options: ImportStageOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let data = execute_import(
+        options.home_override,
+        import::ImportRequest {
+            path: Some(options.path),
+            auto_fix_signs: options.auto_fix_signs,
+            apply_category_suggestions: options.apply_category_suggestions,
+            remaps: options.remaps,
+            remap_file: options.remap_file,
+            stage: true,
+            ..Default::default()
+        },
+    )?;
+
+    success("import stage", data)
+}
+
+pub fn staged_list() -> ClientResult<SuccessEnvelope> {
+    staged_list_with_options(ImportStagedOptions {
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn staged_list_with_options(options: ImportStagedOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+    let rows = import::staging::list_staged(&connection, &db_path)?;
+    success("import staged list", ImportStagedListData { rows })
+}
+
+pub fn staged_show(import_id: &str) -> ClientResult<SuccessEnvelope> {
+    staged_show_with_options(
+        import_id,
+        ImportStagedOptions {
+            home_override: None,
+        },
+    )
+}
+
+#[doc(hidden)]
+pub fn staged_show_with_options(
+    import_id: &str,
+    options: ImportStagedOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+    let run = import::staging::load_staged_run(&connection, &db_path, import_id)?;
+    if run.status != "staged" {
+        return Err(ClientError::import_not_staged(import_id, &run.status));
+    }
+    success(
+        "import staged show",
+        ImportStagedShowData {
+            import_id: run.import_id,
+            status: run.status,
+            staged_at: run.staged_at,
+            source_kind: run.source_kind,
+            source_ref: run.source_ref,
+            summary: ImportCreateSummary {
+                rows_read: run.rows_read,
+                rows_valid: run.rows_valid,
+                rows_invalid: run.rows_invalid,
+                inserted: 0,
+            },
+            analysis: run.analysis,
+        },
+    )
+}
+
+pub fn approve(import_id: &str) -> ClientResult<SuccessEnvelope> {
+    approve_with_options(
+        import_id,
+        ImportStagedOptions {
+            home_override: None,
+        },
+    )
+}
+
+#[doc(hidden)]
+pub fn approve_with_options(
+    import_id: &str,
+    options: ImportStagedOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result = import::staging::approve_staged(&mut connection, &db_path, import_id)?;
+    let staged_duplicate_summary = import::staging::staged_duplicate_summary(&result.run.analysis);
+    let message = if result.duplicate_summary.total == staged_duplicate_summary.total {
+        "Staged import approved and committed.".to_string()
+    } else {
+        format!(
+            "Staged import approved and committed. The ledger changed since staging: {} duplicate row(s) now versus {} when staged.",
+            result.duplicate_summary.total, staged_duplicate_summary.total
+        )
+    };
+    success(
+        "import approve",
+        ImportApproveData {
+            import_id: result.run.import_id,
+            message,
+            summary: ImportCreateSummary {
+                rows_read: result.run.rows_read,
+                rows_valid: result.run.rows_valid,
+                rows_invalid: result.run.rows_invalid,
+                inserted: result.inserted,
+            },
+            duplicate_summary: result.duplicate_summary,
+            staged_duplicate_summary,
+            intelligence_refreshed: true,
+        },
+    )
+}
+
+pub fn reject(import_id: &str) -> ClientResult<SuccessEnvelope> {
+    reject_with_options(
+        import_id,
+        ImportStagedOptions {
+            home_override: None,
+        },
+    )
+}
+
+#[doc(hidden)]
+pub fn reject_with_options(
+    import_id: &str,
+    options: ImportStagedOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let rows_discarded = import::staging::reject_staged(&mut connection, &db_path, import_id)?;
+    success(
+        "import reject",
+        ImportRejectData {
+            import_id: import_id.to_string(),
+            message: "Staged import rejected. No rows were written.".to_string(),
+            rows_discarded,
+        },
+    )
+}
+
+fn execute_import(
+    home_override: Option<&Path>,
+    request: import::ImportRequest,
+) -> ClientResult<ImportData> {
+    let setup = load_setup(home_override)?;
+    let path = request.path.clone();
+    let execution = import::execute(&setup, request)?;
+    let context_setup = if execution.dry_run {
         setup
     } else {
-        load_setup(options.home_override)?
+        load_setup(home_override)?
     };
 
     let query_context = QueryContext {
@@ -74,9 +320,9 @@ pub fn run_with_options(options: ImportRunOptions<'_>) -> ClientResult<SuccessEn
         public_views: context_setup.public_views,
     };
 
-    let data = ImportData {
+    Ok(ImportData {
         dry_run: execution.dry_run,
-        path: options.path,
+        path,
         import_id: execution.import_id,
         message: execution.message,
         summary: execution.summary,
@@ -91,12 +337,26 @@ pub fn run_with_options(options: ImportRunOptions<'_>) -> ClientResult<SuccessEn
         warnings: execution.warnings,
         key_inventory: execution.key_inventory,
         sign_profiles: execution.sign_profiles,
+        sign_corrections: execution.sign_corrections,
+        remaps: execution.remaps,
+        alias_rewrites: execution.alias_rewrites,
+        rule_changes: execution.rule_changes,
+        category_suggestions: execution.category_suggestions,
         drift_warnings: execution.drift_warnings,
+        reconciled_statements: execution.reconciled_statements,
+        balance_gaps: execution.balance_gaps,
+        probable_duplicates: execution.probable_duplicates,
+        dedupe_policies: execution.dedupe_policies,
+        policy_warnings: execution.policy_warnings,
         ledger_accounts: execution.ledger_accounts,
+        source_hash: execution.source_hash,
+        idempotency_key: execution.idempotency_key,
+        replayed: execution.replayed,
+        replacement: execution.replacement,
+        staged: execution.staged,
+        intelligence_impact: execution.intelligence_impact,
         query_context,
-    };
-
-    success("import", data)
+    })
 }
 
 pub fn list() -> ClientResult<SuccessEnvelope> {
@@ -124,7 +384,11 @@ pub fn list_with_options(options: ImportListOptions<'_>) -> ClientResult<Success
                 inserted,
                 deduped,
                 source_kind,
-                source_ref
+                source_ref,
+                (SELECT replaced_import_id FROM internal_import_replacements r
+                 WHERE r.import_id = internal_import_runs.import_id),
+                (SELECT import_id FROM internal_import_replacements r
+                 WHERE r.replaced_import_id = internal_import_runs.import_id)
              FROM internal_import_runs
              ORDER BY CAST(created_at AS INTEGER) DESC, import_id DESC",
         )
@@ -144,6 +408,8 @@ pub fn list_with_options(options: ImportListOptions<'_>) -> ClientResult<Success
                 deduped: row.get(9)?,
                 source_kind: row.get::<_, Option<String>>(10)?,
                 source_ref: row.get::<_, Option<String>>(11)?,
+                replaces_import_id: row.get::<_, Option<String>>(12)?,
+                replaced_by_import_id: row.get::<_, Option<String>>(13)?,
                 accounts: Vec::new(),
             })
         })
@@ -250,7 +516,9 @@ pub fn duplicates_with_options(
                 COALESCE(promoted.txn_id, direct.txn_id, fallback.txn_id) AS matched_txn_id,
                 COALESCE(promoted.import_id, direct.import_id, fallback.import_id) AS matched_import_id,
                 c.matched_txn_id AS matched_txn_id_at_dedupe,
-                c.matched_import_id AS matched_import_id_at_dedupe
+                c.matched_import_id AS matched_import_id_at_dedupe,
+                c.candidate_id,
+                promoted.txn_id AS promoted_txn_id
              FROM internal_transaction_dedupe_candidates c
              LEFT JOIN internal_transactions promoted
                ON promoted.txn_id = c.promoted_txn_id
@@ -260,7 +528,7 @@ pub fn duplicates_with_options(
                ON fallback.txn_id = (
                     SELECT t.txn_id
                     FROM internal_transactions t
-                    -- Keep this predicate aligned with import::dedupe::find_existing_match.
+                    -- Mirrors import::dedupe::find_existing_match under the default dedupe policy.
                     WHERE c.dedupe_reason = 'existing_ledger'
                       AND (
                         (c.external_id IS NOT NULL
@@ -286,6 +554,7 @@ pub fn duplicates_with_options(
     let rows_iter = statement
         .query_map(params![import_id], |row| {
             Ok(ImportDuplicateRow {
+                candidate_id: row.get(14)?,
                 source_row_index: row.get(0)?,
                 dedupe_reason: row.get(1)?,
                 statement_id: row.get::<_, Option<String>>(2)?,
@@ -300,6 +569,7 @@ pub fn duplicates_with_options(
                 matched_import_id: row.get(11)?,
                 matched_txn_id_at_dedupe: row.get(12)?,
                 matched_import_id_at_dedupe: row.get(13)?,
+                promoted_txn_id: row.get(15)?,
             })
         })
         .map_err(|error| map_sqlite_error(&db_path, &error))?;
@@ -309,6 +579,8 @@ pub fn duplicates_with_options(
         let item = row.map_err(|error| map_sqlite_error(&db_path, &error))?;
         rows.push(item);
     }
+    let probable_duplicates =
+        import::probable::query_probable_duplicates(&connection, &db_path, import_id)?;
 
     success(
         "import duplicates",
@@ -316,6 +588,122 @@ pub fn duplicates_with_options(
             import_id: import_id.to_string(),
             total: rows.len() as i64,
             rows,
+            probable_duplicates,
+        },
+    )
+}
+
+pub fn duplicates_promote(candidate_id: &str) -> ClientResult<SuccessEnvelope> {
+    duplicates_promote_with_options(
+        candidate_id,
+        ImportDuplicatesOptions {
+            home_override: None,
+        },
+    )
+}
+
+#[doc(hidden)]
+pub fn duplicates_promote_with_options(
+    candidate_id: &str,
+    options: ImportDuplicatesOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result =
+        import::manual_dedupe::promote_dedupe_candidate(&mut connection, &db_path, candidate_id)?;
+
+    success(
+        "import duplicates promote",
+        ImportDuplicatePromoteData {
+            message: format!(
+                "Dedupe candidate `{candidate_id}` was inserted as transaction `{}`.",
+                result.promoted_txn_id
+            ),
+            candidate_id: result.candidate_id,
+            import_id: result.import_id,
+            promoted_txn_id: result.promoted_txn_id,
+            intelligence_refreshed: true,
+        },
+    )
+}
+
+pub fn duplicates_accept(probable_id: &str) -> ClientResult<SuccessEnvelope> {
+    duplicates_accept_with_options(
+        probable_id,
+        ImportDuplicatesOptions {
+            home_override: None,
+        },
+    )
+}
+
+#[doc(hidden)]
+pub fn duplicates_accept_with_options(
+    probable_id: &str,
+    options: ImportDuplicatesOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    resolve_probable_duplicate(probable_id, options, true)
+}
+
+pub fn duplicates_reject(probable_id: &str) -> ClientResult<SuccessEnvelope> {
+    duplicates_reject_with_options(
+        probable_id,
+        ImportDuplicatesOptions {
+            home_override: None,
+        },
+    )
+}
+
+#[doc(hidden)]
+pub fn duplicates_reject_with_options(
+    probable_id: &str,
+    options: ImportDuplicatesOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    resolve_probable_duplicate(probable_id, options, false)
+}
+
+fn resolve_probable_duplicate(
+    probable_id: &str,
+    options: ImportDuplicatesOptions<'_>,
+    accept: bool,
+) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let resolution = import::probable::resolve_probable_duplicate(
+        &mut connection,
+        &db_path,
+        probable_id,
+        accept,
+    )?;
+
+    let (command, message) = if accept {
+        (
+            "import duplicates accept",
+            format!(
+                "Probable duplicate `{probable_id}` accepted. Transaction `{}` was removed as a duplicate of `{}`.",
+                resolution
+                    .probable_duplicate
+                    .txn_id
+                    .as_deref()
+                    .unwrap_or("unknown"),
+                resolution.probable_duplicate.matched_txn_id
+            ),
+        )
+    } else {
+        (
+            "import duplicates reject",
+            format!(
+                "Probable duplicate `{probable_id}` rejected. Both transactions stay in the ledger."
+            ),
+        )
+    };
+    success(
+        command,
+        ImportProbableResolutionData {
+            message,
+            probable_duplicate: resolution.probable_duplicate,
+            intelligence_refreshed: resolution.intelligence_refreshed,
         },
     )
 }
@@ -346,10 +734,16 @@ pub fn keys_uniq_with_options(options: ImportKeysUniqOptions<'_>) -> ClientResul
     )
 }
 
-pub fn undo(import_id: &str) -> ClientResult<SuccessEnvelope> {
+pub fn undo(
+    import_id: &str,
+    account_key: Option<String>,
+    statement_id: Option<String>,
+) -> ClientResult<SuccessEnvelope> {
     undo_with_options(
         import_id,
         ImportUndoOptions {
+            account_key,
+            statement_id,
             home_override: None,
         },
     )
@@ -362,13 +756,27 @@ pub fn undo_with_options(
 ) -> ClientResult<SuccessEnvelope> {
     let setup = load_setup(options.home_override)?;
     let db_path = std::path::PathBuf::from(&setup.db_path);
+    let scope = import::undo::UndoScope {
+        account_key: parse_undo_scope_value("--account", options.account_key)?,
+        statement_id: parse_undo_scope_value("--statement", options.statement_id)?,
+    };
     let mut connection = open_connection(&db_path)?;
-    let result = import::undo::undo_import(&mut connection, &db_path, import_id)?;
+    let result = import::undo::undo_import(&mut connection, &db_path, import_id, &scope)?;
+    let message = if result.status == "reverted" {
+        format!("Import reverted successfully. Run driggsby import redo {import_id} to restore it.")
+    } else {
+        format!(
+            "Import partially reverted. Run driggsby import undo {import_id} to revert the rest."
+        )
+    };
     success(
         "import undo",
         ImportUndoData {
             import_id: result.import_id,
-            message: "Import reverted successfully.".to_string(),
+            message,
+            status: result.status,
+            account_key: scope.account_key,
+            statement_id: scope.statement_id,
             summary: ImportUndoSummary {
                 rows_reverted: result.rows_reverted,
                 rows_promoted: result.rows_promoted,
@@ -378,6 +786,52 @@ pub fn undo_with_options(
     )
 }
 
+pub fn redo(import_id: &str) -> ClientResult<SuccessEnvelope> {
+    redo_with_options(
+        import_id,
+        ImportRedoOptions {
+            home_override: None,
+        },
+    )
+}
+
+#[doc(hidden)]
+pub fn redo_with_options(
+    import_id: &str,
+    options: ImportRedoOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result = import::redo::redo_import(&mut connection, &db_path, import_id)?;
+    success(
+        "import redo",
+        ImportRedoData {
+            import_id: result.import_id,
+            message: "Import restored successfully.".to_string(),
+            summary: ImportRedoSummary {
+                rows_restored: result.rows_restored,
+                rows_deduped: result.rows_deduped,
+            },
+            intelligence_refreshed: result.intelligence_refreshed,
+        },
+    )
+}
+
+fn parse_undo_scope_value(flag: &str, value: Option<String>) -> ClientResult<Option<String>> {
+    let Some(raw_value) = value else {
+        return Ok(None);
+    };
+    let trimmed = raw_value.trim();
+    if trimmed.is_empty() {
+        return Err(ClientError::invalid_argument_for_command(
+            &format!("{flag} requires a non-empty value."),
+            Some("import undo"),
+        ));
+    }
+    Ok(Some(trimmed.to_string()))
+}
+
 fn load_setup(home_override: Option<&Path>) -> ClientResult<crate::setup::SetupContext> {
     if let Some(path) = home_override {
         return ensure_initialized_at(path);
@@ -1,4 +1,5 @@
 pub mod accounts;
+pub mod aliases;
 pub mod anomalies;
 pub(crate) mod common;
 pub mod dash;
@@ -6,5 +7,8 @@ pub mod demo;
 pub mod import;
 pub mod intelligence;
 pub mod recurring;
+pub mod rules;
 pub mod schema;
 pub mod sql;
+pub mod tags;
+pub mod transactions;
@@ -1,6 +1,7 @@
+use std::collections::BTreeSet;
 use std::path::Path;
 
-use rusqlite::params;
+use rusqlite::{Connection, params};
 
 use crate::ClientResult;
 use crate::commands::common::data_range_hint;
@@ -8,20 +9,30 @@ use crate::contracts::envelope::{SuccessEnvelope, success};
 use crate::contracts::types::{RecurringData, RecurringRow};
 use crate::intelligence::date::{build_filter, format_iso_date};
 use crate::intelligence::policy::RECURRING_POLICY_VERSION;
+use crate::intelligence::query::load_transactions_from_connection;
+use crate::intelligence::recurring::recurring_group_key;
+use crate::intelligence::types::IntelligenceFilter;
 use crate::setup::{SetupContext, ensure_initialized, ensure_initialized_at};
 use crate::state::{map_sqlite_error, open_connection};
+use crate::tags::{normalize_tag, tagged_txn_ids};
 
 #[derive(Debug, Default)]
 pub struct RecurringRunOptions<'a> {
     pub from: Option<String>,
     pub to: Option<String>,
+    pub tag: Option<String>,
     pub home_override: Option<&'a Path>,
 }
 
-pub fn run(from: Option<&str>, to: Option<&str>) -> ClientResult<SuccessEnvelope> {
+pub fn run(
+    from: Option<&str>,
+    to: Option<&str>,
+    tag: Option<&str>,
+) -> ClientResult<SuccessEnvelope> {
     run_with_options(RecurringRunOptions {
         from: from.map(std::string::ToString::to_string),
         to: to.map(std::string::ToString::to_string),
+        tag: tag.map(std::string::ToString::to_string),
         home_override: None,
     })
 }
@@ -30,6 +41,7 @@ pub fn run(from: Option<&str>, to: Option<&str>) -> ClientResult<SuccessEnvelope
 pub fn run_with_options(options: RecurringRunOptions<'_>) -> ClientResult<SuccessEnvelope> {
     let setup = load_setup(options.home_override)?;
     let filter = build_filter(options.from.as_deref(), options.to.as_deref(), "recurring")?;
+    let tag = options.tag.as_deref().map(normalize_tag).transpose()?;
     let db_path = std::path::PathBuf::from(&setup.db_path);
     let connection = open_connection(&db_path)?;
     let from_bound = filter.from.as_ref().map(format_iso_date);
@@ -83,11 +95,16 @@ pub fn run_with_options(options: RecurringRunOptions<'_>) -> ClientResult<Succes
     for row in rows_iter {
         rows.push(row.map_err(|error| map_sqlite_error(&db_path, &error))?);
     }
+    if let Some(tag) = tag.as_deref() {
+        let group_keys = tagged_group_keys(&connection, &db_path, tag)?;
+        rows.retain(|row| group_keys.contains(&row.group_key));
+    }
 
     let data = RecurringData {
         policy_version: RECURRING_POLICY_VERSION.to_string(),
         from: filter.from.as_ref().map(format_iso_date),
         to: filter.to.as_ref().map(format_iso_date),
+        tag,
         rows,
         data_range_hint: data_range_hint(&setup.data_range),
     };
@@ -95,6 +112,28 @@ pub fn run_with_options(options: RecurringRunOptions<'_>) -> ClientResult<Succes
     success("recurring", data)
 }
 
+/// Recurring groups with at least one transaction carrying `tag`.
+fn tagged_group_keys(
+    connection: &Connection,
+    db_path: &Path,
+    tag: &str,
+) -> ClientResult<BTreeSet<String>> {
+    let tagged = tagged_txn_ids(connection, db_path, tag)?
+        .into_iter()
+        .collect::<BTreeSet<String>>();
+    let unbounded = IntelligenceFilter {
+        from: None,
+        to: None,
+    };
+    Ok(
+        load_transactions_from_connection(connection, db_path, &unbounded)?
+            .iter()
+            .filter(|transaction| tagged.contains(&transaction.txn_id))
+            .filter_map(recurring_group_key)
+            .collect(),
+    )
+}
+
 fn load_setup(home_override: Option<&Path>) -> ClientResult<SetupContext> {
     if let Some(home) = home_override {
         return ensure_initialized_at(home);
@@ -0,0 +1,314 @@
+use std::path::Path;
+
+use rusqlite::{TransactionBehavior, params};
+use ulid::Ulid;
+
+use crate::contracts::envelope::{SuccessEnvelope, success};
+use crate::contracts::types::{RuleAddData, RuleListData, RuleRemoveData, RuleRow, RulesApplyData};
+use crate::import::persist::now_timestamp;
+use crate::rules::{apply_to_ledger, compile_pattern, query_rule_rows};
+use crate::setup::{ensure_initialized, ensure_initialized_at};
+use crate::state::{map_sqlite_error, open_connection};
+use crate::tags::normalize_tag;
+use crate::{ClientError, ClientResult};
+
+/// Conditions and actions of a new rule. Every condition that is set must
+/// match; at least one condition and one action are required.
+#[derive(Debug, Clone, Default)]
+pub struct RuleDefinition {
+    pub description: Option<String>,
+    pub amount_min: Option<f64>,
+    pub amount_max: Option<f64>,
+    pub account_key: Option<String>,
+    pub mcc: Option<String>,
+    pub set_merchant: Option<String>,
+    pub set_category: Option<String>,
+    pub add_tags: Vec<String>,
+    /// 1-based place in evaluation order; the rule is appended when unset.
+    pub position: Option<i64>,
+}
+
+#[derive(Debug, Default)]
+pub struct RuleAddOptions<'a> {
+    pub rule: RuleDefinition,
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct RuleListOptions<'a> {
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct RuleRemoveOptions<'a> {
+    pub rule_id: String,
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct RulesApplyOptions<'a> {
+    pub dry_run: bool,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn add(rule: RuleDefinition) -> ClientResult<SuccessEnvelope> {
+    add_with_options(RuleAddOptions {
+        rule,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn add_with_options(options: RuleAddOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let definition = options.rule;
+    let description_pattern = non_empty(definition.description);
+    if let Some(pattern) = description_pattern.as_deref() {
+        compile_pattern(pattern)?;
+    }
+    let account_key = non_empty(definition.account_key);
+    let mcc = non_empty(definition.mcc);
+    if let Some(mcc) = mcc.as_deref()
+        && !(mcc.len() == 4 && mcc.bytes().all(|byte| byte.is_ascii_digit()))
+    {
+        return Err(ClientError::invalid_argument_for_command(
+            &format!("--mcc `{mcc}` is not a four-digit merchant category code."),
+            Some("rules add"),
+        ));
+    }
+    if let (Some(min), Some(max)) = (definition.amount_min, definition.amount_max)
+        && min > max
+    {
+        return Err(ClientError::invalid_argument_for_command(
+            &format!("--amount-min {min} is greater than --amount-max {max}."),
+            Some("rules add"),
+        ));
+    }
+    let set_merchant = non_empty(definition.set_merchant);
+    let set_category = non_empty(definition.set_category);
+    let mut add_tags = Vec::new();
+    for raw in &definition.add_tags {
+        let tag = normalize_tag(raw)?;
+        if !add_tags.contains(&tag) {
+            add_tags.push(tag);
+        }
+    }
+
+    let has_condition = description_pattern.is_some()
+        || definition.amount_min.is_some()
+        || definition.amount_max.is_some()
+        || account_key.is_some()
+        || mcc.is_some();
+    if !has_condition {
+        return Err(ClientError::invalid_argument_with_recovery(
+            "A rule needs at least one condition.",
+            vec![
+                "Add --description <regex>, --amount-min, --amount-max, --account or --mcc."
+                    .to_string(),
+            ],
+        ));
+    }
+    if set_merchant.is_none() && set_category.is_none() && add_tags.is_empty() {
+        return Err(ClientError::invalid_argument_with_recovery(
+            "A rule needs at least one action.",
+            vec!["Add --set-merchant, --set-category or --add-tag.".to_string()],
+        ));
+    }
+    if definition.position.is_some_and(|position| position < 1) {
+        return Err(ClientError::invalid_argument_for_command(
+            "--position must be 1 or greater.",
+            Some("rules add"),
+        ));
+    }
+
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+    let rule_count = transaction
+        .query_row("SELECT COUNT(*) FROM internal_rules", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+    let position = definition
+        .position
+        .map_or(rule_count + 1, |position| position.min(rule_count + 1));
+    transaction
+        .execute(
+            "UPDATE internal_rules SET position = position + 1 WHERE position >= ?1",
+            params![position],
+        )
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+
+    let rule = RuleRow {
+        rule_id: format!("rule_{}", Ulid::new()),
+        position,
+        description_pattern,
+        amount_min: definition.amount_min,
+        amount_max: definition.amount_max,
+        account_key,
+        mcc,
+        set_merchant,
+        set_category,
+        add_tags,
+        created_at: now_timestamp(),
+    };
+    transaction
+        .execute(
+            "INSERT INTO internal_rules (
+                rule_id,
+                position,
+                description_pattern,
+                amount_min,
+                amount_max,
+                account_key,
+                mcc,
+                set_merchant,
+                set_category,
+                add_tags,
+                created_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                &rule.rule_id,
+                rule.position,
+                &rule.description_pattern,
+                rule.amount_min,
+                rule.amount_max,
+                &rule.account_key,
+                &rule.mcc,
+                &rule.set_merchant,
+                &rule.set_category,
+                serde_json::to_string(&rule.add_tags)
+                    .map_err(|error| ClientError::internal_serialization(&error.to_string()))?,
+                &rule.created_at,
+            ],
+        )
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+
+    success(
+        "rules add",
+        RuleAddData {
+            message: format!(
+                "Rule `{}` was added at position {position}. Future imports apply it automatically; run driggsby rules apply --dry-run to preview it on existing rows.",
+                rule.rule_id
+            ),
+            rule,
+        },
+    )
+}
+
+pub fn list() -> ClientResult<SuccessEnvelope> {
+    list_with_options(RuleListOptions::default())
+}
+
+#[doc(hidden)]
+pub fn list_with_options(options: RuleListOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+    let rows = query_rule_rows(&connection, &db_path)?;
+
+    success("rules list", RuleListData { rows })
+}
+
+pub fn remove(rule_id: String) -> ClientResult<SuccessEnvelope> {
+    remove_with_options(RuleRemoveOptions {
+        rule_id,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn remove_with_options(options: RuleRemoveOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let rule_id = options.rule_id.trim().to_string();
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+
+    let Some(existing) = query_rule_rows(&transaction, &db_path)?
+        .into_iter()
+        .find(|row| row.rule_id == rule_id)
+    else {
+        return Err(ClientError::rule_not_found(&rule_id));
+    };
+    transaction
+        .execute(
+            "DELETE FROM internal_rules WHERE rule_id = ?1",
+            params![&rule_id],
+        )
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+    transaction
+        .execute(
+            "UPDATE internal_rules SET position = position - 1 WHERE position > ?1",
+            params![existing.position],
+        )
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(&db_path, &error))?;
+
+    success(
+        "rules remove",
+        RuleRemoveData {
+            message: format!(
+                "Rule `{rule_id}` was removed. Rows it already changed keep their values."
+            ),
+            rule: existing,
+        },
+    )
+}
+
+pub fn apply(dry_run: bool) -> ClientResult<SuccessEnvelope> {
+    apply_with_options(RulesApplyOptions {
+        dry_run,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn apply_with_options(options: RulesApplyOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let outcome = apply_to_ledger(&mut connection, &db_path, options.dry_run)?;
+
+    let message = match (options.dry_run, outcome.rows_changed) {
+        (_, 0) => "No transactions need changes from the current rules.".to_string(),
+        (true, rows) => format!(
+            "Rules would change {rows} transaction(s). No rows were written. Rerun without --dry-run to apply them."
+        ),
+        (false, rows) => {
+            format!("Rules changed {rows} transaction(s). Manually edited fields were left alone.")
+        }
+    };
+    success(
+        "rules apply",
+        RulesApplyData {
+            message,
+            dry_run: options.dry_run,
+            rows_changed: outcome.rows_changed,
+            changes: outcome.changes,
+            intelligence_refreshed: outcome.intelligence_refreshed,
+        },
+    )
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn load_setup(home_override: Option<&Path>) -> ClientResult<crate::setup::SetupContext> {
+    if let Some(path) = home_override {
+        return ensure_initialized_at(path);
+    }
+    ensure_initialized()
+}
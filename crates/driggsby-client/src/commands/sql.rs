@@ -9,7 +9,7 @@ use serde_json::Value;
 
 use crate::contracts::envelope::{SuccessEnvelope, success};
 use crate::contracts::types::{SqlColumn, SqlQueryData};
-use crate::setup::{ensure_initialized, ensure_initialized_at};
+use crate::setup::{SetupContext, ensure_initialized, ensure_initialized_at};
 use crate::state::open_readonly_connection;
 use crate::{ClientError, ClientResult};
 
@@ -79,6 +79,44 @@ pub fn run_with_options(options: SqlQueryOptions<'_>) -> ClientResult<SuccessEnv
     success("db sql", data)
 }
 
+/// Selects `txn_id`s from `v1_transactions` matching a user-supplied `WHERE`
+/// expression, under the same read-only guard as `db sql`.
+pub(crate) fn select_txn_ids_where(
+    setup: &SetupContext,
+    filter: &str,
+) -> ClientResult<Vec<String>> {
+    validate_sql_input(filter)?;
+    let db_path = PathBuf::from(&setup.db_path);
+    let connection = open_readonly_connection(&db_path)?;
+    let allowed_views = setup
+        .public_views
+        .iter()
+        .map(|view| view.name.clone())
+        .collect::<Vec<String>>();
+    install_readonly_authorizer(&connection, &allowed_views)
+        .map_err(|error| map_query_error(&db_path, &error))?;
+
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT txn_id FROM v1_transactions WHERE ({filter}) ORDER BY posted_at ASC, txn_id ASC"
+        ))
+        .map_err(|error| map_query_error(&db_path, &error))?;
+    if !statement.readonly() {
+        return Err(ClientError::invalid_argument_with_recovery(
+            "Transaction filters must be read-only.",
+            vec!["Pass a WHERE expression over v1_transactions columns.".to_string()],
+        ));
+    }
+    let rows = statement
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|error| map_query_error(&db_path, &error))?;
+    let mut txn_ids = Vec::new();
+    for row in rows {
+        txn_ids.push(row.map_err(|error| map_query_error(&db_path, &error))?);
+    }
+    Ok(txn_ids)
+}
+
 fn resolve_sql_source(
     query: Option<String>,
     file: Option<String>,
@@ -0,0 +1,153 @@
+use std::path::Path;
+
+use crate::commands::sql::select_txn_ids_where;
+use crate::contracts::envelope::{SuccessEnvelope, success};
+use crate::contracts::types::{TagChangeData, TagListData};
+use crate::identity::load_identity_key;
+use crate::setup::{SetupContext, ensure_initialized, ensure_initialized_at};
+use crate::state::open_connection;
+use crate::tags::{TagAction, apply_tag, list_tags, normalize_tag};
+use crate::{ClientError, ClientResult};
+
+#[derive(Debug, Default)]
+pub struct TagChangeOptions<'a> {
+    pub tag: String,
+    pub txn_ids: Vec<String>,
+    pub filter: Option<String>,
+    pub home_override: Option<&'a Path>,
+}
+
+#[derive(Debug, Default)]
+pub struct TagListOptions<'a> {
+    pub txn_id: Option<String>,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn add(
+    tag: String,
+    txn_ids: Vec<String>,
+    filter: Option<String>,
+) -> ClientResult<SuccessEnvelope> {
+    add_with_options(TagChangeOptions {
+        tag,
+        txn_ids,
+        filter,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn add_with_options(options: TagChangeOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    change_tag(options, TagAction::Add, "tag add")
+}
+
+pub fn remove(
+    tag: String,
+    txn_ids: Vec<String>,
+    filter: Option<String>,
+) -> ClientResult<SuccessEnvelope> {
+    remove_with_options(TagChangeOptions {
+        tag,
+        txn_ids,
+        filter,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn remove_with_options(options: TagChangeOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    change_tag(options, TagAction::Remove, "tag remove")
+}
+
+pub fn list(txn_id: Option<String>) -> ClientResult<SuccessEnvelope> {
+    list_with_options(TagListOptions {
+        txn_id,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn list_with_options(options: TagListOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options
+        .txn_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+    if let Some(txn_id) = txn_id.as_deref()
+        && load_identity_key(&connection, &db_path, txn_id)?.is_none()
+    {
+        return Err(ClientError::transaction_not_found(txn_id));
+    }
+
+    let rows = list_tags(&connection, &db_path, txn_id.as_deref())?;
+    success("tag list", TagListData { txn_id, rows })
+}
+
+fn change_tag(
+    options: TagChangeOptions<'_>,
+    action: TagAction,
+    command: &str,
+) -> ClientResult<SuccessEnvelope> {
+    let tag = normalize_tag(&options.tag)?;
+    let filter = options
+        .filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    if options.txn_ids.is_empty() && filter.is_none() {
+        return Err(ClientError::invalid_argument_for_command(
+            "Pass transaction ids, --where, or both to select transactions.",
+            Some(command),
+        ));
+    }
+
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut txn_ids = Vec::new();
+    for txn_id in options.txn_ids.iter().map(|value| value.trim()) {
+        if !txn_id.is_empty() && !txn_ids.iter().any(|existing| existing == txn_id) {
+            txn_ids.push(txn_id.to_string());
+        }
+    }
+    if let Some(filter) = filter {
+        for txn_id in select_txn_ids_where(&setup, filter)? {
+            if !txn_ids.contains(&txn_id) {
+                txn_ids.push(txn_id);
+            }
+        }
+    }
+
+    let mut connection = open_connection(&db_path)?;
+    let change = apply_tag(&mut connection, &db_path, &tag, &txn_ids, action)?;
+    let selected = change.txn_ids.len();
+    let message = match action {
+        _ if selected == 0 => "No transactions matched the selection.".to_string(),
+        TagAction::Add => format!(
+            "Tagged {} of {selected} selected transaction(s) with #{tag}.",
+            change.changed
+        ),
+        TagAction::Remove => format!(
+            "Removed #{tag} from {} of {selected} selected transaction(s).",
+            change.changed
+        ),
+    };
+
+    success(
+        command,
+        TagChangeData {
+            message,
+            tag,
+            txn_ids: change.txn_ids,
+            changed: change.changed,
+        },
+    )
+}
+
+fn load_setup(home_override: Option<&Path>) -> ClientResult<SetupContext> {
+    if let Some(path) = home_override {
+        return ensure_initialized_at(path);
+    }
+    ensure_initialized()
+}
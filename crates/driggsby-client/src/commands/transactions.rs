@@ -0,0 +1,522 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::contracts::envelope::{SuccessEnvelope, success};
+use crate::contracts::types::{
+    TransactionAddData, TransactionDeleteData, TransactionEditData, TransactionExcludeData,
+    TransactionExclusion, TransactionMarkDuplicateData, TransactionProvenance, TransactionShowData,
+    TransactionSplitData,
+};
+use crate::exclusions::{load_transaction_exclusion, normalize_reason, set_transaction_exclusion};
+use crate::import::manual::{
+    ManualEdit, ManualEntry, add_manual_transaction, delete_manual_transaction,
+    edit_manual_transaction,
+};
+use crate::import::manual_dedupe::mark_transaction_duplicate;
+use crate::import::provenance::load_provenance;
+use crate::overrides::{OverrideEdit, edit_transaction_override, override_join};
+use crate::rules::load_transaction_rule_hits;
+use crate::setup::{ensure_initialized, ensure_initialized_at};
+use crate::splits::{load_splits, parse_split_allocation, set_transaction_splits};
+use crate::state::{map_sqlite_error, open_connection};
+use crate::{ClientError, ClientResult};
+
+#[derive(Debug, Default)]
+pub struct TransactionMarkDuplicateOptions<'a> {
+    pub txn_id: String,
+    pub duplicate_of: String,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn mark_duplicate(txn_id: String, duplicate_of: String) -> ClientResult<SuccessEnvelope> {
+    mark_duplicate_with_options(TransactionMarkDuplicateOptions {
+        txn_id,
+        duplicate_of,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn mark_duplicate_with_options(
+    options: TransactionMarkDuplicateOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options.txn_id.trim();
+    let duplicate_of = options.duplicate_of.trim();
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result = mark_transaction_duplicate(&mut connection, &db_path, txn_id, duplicate_of)?;
+
+    success(
+        "transaction mark-duplicate",
+        TransactionMarkDuplicateData {
+            message: format!(
+                "Transaction `{txn_id}` was removed as a duplicate of `{duplicate_of}`. Restore it with driggsby import duplicates promote {}.",
+                result.candidate_id
+            ),
+            txn_id: result.txn_id,
+            import_id: result.import_id,
+            duplicate_of_txn_id: result.duplicate_of_txn_id,
+            duplicate_of_import_id: result.duplicate_of_import_id,
+            candidate_id: result.candidate_id,
+            intelligence_refreshed: true,
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionShowOptions<'a> {
+    pub txn_id: String,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn show(txn_id: String) -> ClientResult<SuccessEnvelope> {
+    show_with_options(TransactionShowOptions {
+        txn_id,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn show_with_options(options: TransactionShowOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options.txn_id.trim();
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let connection = open_connection(&db_path)?;
+    let Some(mut data) = load_transaction(&connection, &db_path, txn_id)? else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+    data.splits = load_splits(&connection, &db_path, txn_id)?;
+    data.rule_hits = load_transaction_rule_hits(&connection, &db_path, txn_id)?;
+    data.exclusion = load_transaction_exclusion(&connection, &db_path, txn_id)?.map(|record| {
+        TransactionExclusion {
+            reason: record.reason,
+            excluded_at: record.excluded_at,
+        }
+    });
+    data.provenance =
+        load_provenance(&connection, &db_path, txn_id)?.map(|provenance| TransactionProvenance {
+            import_id: provenance.import_id,
+            source_kind: provenance.source_kind,
+            source_ref: provenance.source_ref,
+            source_row_index: provenance.source_row_index,
+            source_record: provenance.source_record,
+            committed_at: provenance.committed_at,
+        });
+
+    success("transaction show", data)
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionAddOptions<'a> {
+    pub account_key: String,
+    pub posted_at: String,
+    pub amount: String,
+    pub currency: String,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub category: Option<String>,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn add(
+    account_key: String,
+    posted_at: String,
+    amount: String,
+    currency: String,
+    description: String,
+    merchant: Option<String>,
+    category: Option<String>,
+) -> ClientResult<SuccessEnvelope> {
+    add_with_options(TransactionAddOptions {
+        account_key,
+        posted_at,
+        amount,
+        currency,
+        description,
+        merchant,
+        category,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn add_with_options(options: TransactionAddOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let entry = ManualEntry {
+        account_key: options.account_key,
+        posted_at: options.posted_at,
+        amount: options.amount,
+        currency: options.currency,
+        description: options.description,
+        merchant: options.merchant,
+        category: options.category,
+    };
+
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result = add_manual_transaction(&mut connection, &db_path, &entry)?;
+    let Some(data) = load_transaction(&connection, &db_path, &result.txn_id)? else {
+        return Err(ClientError::transaction_not_found(&result.txn_id));
+    };
+
+    success(
+        "transaction add",
+        TransactionAddData {
+            message: format!(
+                "Manual transaction `{}` was added as import `{}`. Run driggsby transaction delete {} to remove it.",
+                result.txn_id, result.import_id, result.txn_id
+            ),
+            txn_id: data.txn_id,
+            import_id: result.import_id,
+            account_key: data.account_key,
+            posted_at: data.posted_at,
+            amount: data.amount,
+            currency: data.currency,
+            description: data.description,
+            effective_merchant: data.effective_merchant,
+            effective_category: data.effective_category,
+            intelligence_refreshed: true,
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionEditOptions<'a> {
+    pub txn_id: String,
+    pub merchant: Option<String>,
+    pub category: Option<String>,
+    pub note: Option<String>,
+    /// Only manual transactions accept the entered-value fields below.
+    pub posted_at: Option<String>,
+    pub amount: Option<String>,
+    pub description: Option<String>,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn edit(
+    txn_id: String,
+    merchant: Option<String>,
+    category: Option<String>,
+    note: Option<String>,
+    posted_at: Option<String>,
+    amount: Option<String>,
+    description: Option<String>,
+) -> ClientResult<SuccessEnvelope> {
+    edit_with_options(TransactionEditOptions {
+        txn_id,
+        merchant,
+        category,
+        note,
+        posted_at,
+        amount,
+        description,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn edit_with_options(options: TransactionEditOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options.txn_id.trim();
+    let edit = OverrideEdit {
+        merchant: options.merchant,
+        category: options.category,
+        note: options.note,
+    };
+    let manual_edit = ManualEdit {
+        posted_at: options.posted_at,
+        amount: options.amount,
+        description: options.description,
+    };
+    if edit.is_empty() && manual_edit.is_empty() {
+        return Err(ClientError::invalid_argument_for_command(
+            "Pass at least one of --merchant, --category, --note, --posted-at, --amount or --description.",
+            Some("transaction edit"),
+        ));
+    }
+
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    if !manual_edit.is_empty() {
+        edit_manual_transaction(&mut connection, &db_path, txn_id, &manual_edit)?;
+    }
+    let result = if edit.is_empty() {
+        None
+    } else {
+        Some(edit_transaction_override(
+            &mut connection,
+            &db_path,
+            txn_id,
+            &edit,
+        )?)
+    };
+    let Some(data) = load_transaction(&connection, &db_path, txn_id)? else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+
+    let message = match result.as_ref() {
+        None => format!("Manual transaction `{txn_id}` was updated."),
+        Some(result) if result.applied.is_empty() => {
+            format!("Transaction `{txn_id}` now shows its imported values.")
+        }
+        Some(_) => format!(
+            "Transaction `{txn_id}` was updated. The edit is kept separately from imported data and re-applies if the row is imported again."
+        ),
+    };
+    let intelligence_refreshed = !manual_edit.is_empty()
+        || result
+            .as_ref()
+            .is_some_and(|result| result.intelligence_refreshed);
+    success(
+        "transaction edit",
+        TransactionEditData {
+            message,
+            txn_id: data.txn_id,
+            posted_at: data.posted_at,
+            amount: data.amount,
+            description: data.description,
+            merchant: data.merchant,
+            category: data.category,
+            effective_merchant: data.effective_merchant,
+            effective_category: data.effective_category,
+            note: data.note,
+            intelligence_refreshed,
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionDeleteOptions<'a> {
+    pub txn_id: String,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn delete(txn_id: String) -> ClientResult<SuccessEnvelope> {
+    delete_with_options(TransactionDeleteOptions {
+        txn_id,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn delete_with_options(options: TransactionDeleteOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options.txn_id.trim();
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result = delete_manual_transaction(&mut connection, &db_path, txn_id)?;
+
+    success(
+        "transaction delete",
+        TransactionDeleteData {
+            message: format!(
+                "Manual transaction `{txn_id}` was deleted. Run driggsby import redo {} to restore it.",
+                result.import_id
+            ),
+            txn_id: txn_id.to_string(),
+            import_id: result.import_id,
+            intelligence_refreshed: result.intelligence_refreshed,
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionSplitOptions<'a> {
+    pub txn_id: String,
+    pub parts: Vec<String>,
+    pub clear: bool,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn split(txn_id: String, parts: Vec<String>, clear: bool) -> ClientResult<SuccessEnvelope> {
+    split_with_options(TransactionSplitOptions {
+        txn_id,
+        parts,
+        clear,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn split_with_options(options: TransactionSplitOptions<'_>) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options.txn_id.trim();
+    if options.clear != options.parts.is_empty() {
+        return Err(ClientError::invalid_argument_for_command(
+            "Pass either --part AMOUNT:CATEGORY (two or more) or --clear.",
+            Some("transaction split"),
+        ));
+    }
+    let allocations = options
+        .parts
+        .iter()
+        .map(|part| parse_split_allocation(part))
+        .collect::<ClientResult<Vec<_>>>()?;
+
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    set_transaction_splits(&mut connection, &db_path, txn_id, &allocations)?;
+    let Some(data) = load_transaction(&connection, &db_path, txn_id)? else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+    let splits = load_splits(&connection, &db_path, txn_id)?;
+
+    let message = if splits.is_empty() {
+        format!("Transaction `{txn_id}` is no longer split.")
+    } else {
+        format!(
+            "Transaction `{txn_id}` was split into {} parts. The split is kept separately from imported data and re-applies if the row is imported again.",
+            splits.len()
+        )
+    };
+    success(
+        "transaction split",
+        TransactionSplitData {
+            message,
+            txn_id: data.txn_id,
+            amount: data.amount,
+            currency: data.currency,
+            splits,
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionExcludeOptions<'a> {
+    pub txn_id: String,
+    pub reason: String,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn exclude(txn_id: String, reason: String) -> ClientResult<SuccessEnvelope> {
+    exclude_with_options(TransactionExcludeOptions {
+        txn_id,
+        reason,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn exclude_with_options(
+    options: TransactionExcludeOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options.txn_id.trim();
+    let reason = normalize_reason(&options.reason)?;
+
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result = set_transaction_exclusion(&mut connection, &db_path, txn_id, Some(&reason))?;
+
+    success(
+        "transaction exclude",
+        TransactionExcludeData {
+            message: format!(
+                "Transaction `{txn_id}` is excluded from intelligence and reporting as `{reason}`. It stays in v1_transactions with excluded = 1; restore it with driggsby transaction include {txn_id}."
+            ),
+            txn_id: txn_id.to_string(),
+            excluded: true,
+            exclusion: result.exclusion.map(|record| TransactionExclusion {
+                reason: record.reason,
+                excluded_at: record.excluded_at,
+            }),
+            intelligence_refreshed: result.intelligence_refreshed,
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionIncludeOptions<'a> {
+    pub txn_id: String,
+    pub home_override: Option<&'a Path>,
+}
+
+pub fn include(txn_id: String) -> ClientResult<SuccessEnvelope> {
+    include_with_options(TransactionIncludeOptions {
+        txn_id,
+        home_override: None,
+    })
+}
+
+#[doc(hidden)]
+pub fn include_with_options(
+    options: TransactionIncludeOptions<'_>,
+) -> ClientResult<SuccessEnvelope> {
+    let txn_id = options.txn_id.trim();
+    let setup = load_setup(options.home_override)?;
+    let db_path = std::path::PathBuf::from(&setup.db_path);
+    let mut connection = open_connection(&db_path)?;
+    let result = set_transaction_exclusion(&mut connection, &db_path, txn_id, None)?;
+
+    let message = if result.intelligence_refreshed {
+        format!("Transaction `{txn_id}` is included in intelligence and reporting again.")
+    } else {
+        format!("Transaction `{txn_id}` was not excluded.")
+    };
+    success(
+        "transaction include",
+        TransactionExcludeData {
+            message,
+            txn_id: txn_id.to_string(),
+            excluded: false,
+            exclusion: None,
+            intelligence_refreshed: result.intelligence_refreshed,
+        },
+    )
+}
+
+fn load_transaction(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Option<TransactionShowData>> {
+    connection
+        .query_row(
+            &format!(
+                "SELECT t.txn_id, t.import_id, t.account_key, t.posted_at, t.amount, t.currency,
+                        t.description, t.statement_id, t.external_id, t.merchant, t.category,
+                        COALESCE(o.merchant, t.merchant), COALESCE(o.category, t.category),
+                        o.note, t.balance, t.mcc
+                 FROM internal_transactions t
+                 {override_join}
+                 WHERE t.txn_id = ?1",
+                override_join = override_join()
+            ),
+            params![txn_id],
+            |row| {
+                Ok(TransactionShowData {
+                    txn_id: row.get(0)?,
+                    import_id: row.get(1)?,
+                    account_key: row.get(2)?,
+                    posted_at: row.get(3)?,
+                    amount: row.get(4)?,
+                    currency: row.get(5)?,
+                    description: row.get(6)?,
+                    statement_id: row.get(7)?,
+                    external_id: row.get(8)?,
+                    merchant: row.get(9)?,
+                    category: row.get(10)?,
+                    effective_merchant: row.get(11)?,
+                    effective_category: row.get(12)?,
+                    note: row.get(13)?,
+                    balance: row.get(14)?,
+                    mcc: row.get(15)?,
+                    splits: Vec::new(),
+                    rule_hits: Vec::new(),
+                    exclusion: None,
+                    provenance: None,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+fn load_setup(home_override: Option<&Path>) -> ClientResult<crate::setup::SetupContext> {
+    if let Some(path) = home_override {
+        return ensure_initialized_at(path);
+    }
+    ensure_initialized()
+}
@@ -85,9 +85,40 @@ pub struct ImportData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sign_profiles: Option<Vec<ImportSignProfile>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_corrections: Option<Vec<ImportSignCorrection>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaps: Option<Vec<ImportRemap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias_rewrites: Option<Vec<ImportRemap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_changes: Option<Vec<ImportRuleChange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_suggestions: Option<Vec<ImportCategorySuggestion>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub drift_warnings: Option<Vec<ImportDriftWarning>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconciled_statements: Option<Vec<ImportStatementReconciliation>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_gaps: Option<Vec<ImportBalanceGap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probable_duplicates: Option<Vec<ImportProbableDuplicate>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dedupe_policies: Option<Vec<ImportDedupePolicy>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_warnings: Option<Vec<ImportIssue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ledger_accounts: Option<AccountsData>,
+    pub source_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replayed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<ImportReplacement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intelligence_impact: Option<ImportIntelligenceImpact>,
     pub query_context: QueryContext,
 }
 
@@ -131,6 +162,8 @@ pub struct ImportDuplicateSummary {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ImportDuplicateRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidate_id: Option<String>,
     pub source_row_index: i64,
     pub dedupe_reason: String,
     pub statement_id: Option<String>,
@@ -145,6 +178,8 @@ pub struct ImportDuplicateRow {
     pub matched_import_id: Option<String>,
     pub matched_txn_id_at_dedupe: Option<String>,
     pub matched_import_id_at_dedupe: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promoted_txn_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -154,6 +189,46 @@ pub struct ImportDuplicatesPreview {
     pub rows: Vec<ImportDuplicateRow>,
 }
 
+/// The dedupe policy applied to one account of an import. `source` is
+/// `import_policy` when the account has an entry under `dedupe` in
+/// `import_policy.json`, otherwise `default`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportDedupePolicy {
+    pub account_key: String,
+    pub key_fields: Vec<String>,
+    pub trust_external_id: bool,
+    pub date_tolerance_days: i64,
+    pub source: String,
+}
+
+/// A near match between an incoming row and a committed ledger row that was
+/// inserted anyway and queued for review instead of being auto-deduped.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProbableDuplicate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probable_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txn_id: Option<String>,
+    pub status: String,
+    pub source_row_index: i64,
+    pub account_key: String,
+    pub posted_at: String,
+    pub amount: f64,
+    pub currency: String,
+    pub description: String,
+    pub matched_txn_id: String,
+    pub matched_import_id: String,
+    pub matched_posted_at: String,
+    pub matched_description: String,
+    pub score: f64,
+    pub date_distance_days: i64,
+    pub description_similarity: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ImportIssue {
     pub row: i64,
@@ -177,6 +252,146 @@ pub struct ImportDuplicatesData {
     pub import_id: String,
     pub total: i64,
     pub rows: Vec<ImportDuplicateRow>,
+    pub probable_duplicates: Vec<ImportProbableDuplicate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportDuplicatePromoteData {
+    pub message: String,
+    pub candidate_id: String,
+    pub import_id: String,
+    pub promoted_txn_id: String,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionMarkDuplicateData {
+    pub message: String,
+    pub txn_id: String,
+    pub import_id: String,
+    pub duplicate_of_txn_id: String,
+    pub duplicate_of_import_id: String,
+    pub candidate_id: String,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionProvenance {
+    pub import_id: String,
+    pub source_kind: Option<String>,
+    pub source_ref: Option<String>,
+    pub source_row_index: i64,
+    pub source_record: String,
+    pub committed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionShowData {
+    pub txn_id: String,
+    pub import_id: String,
+    pub account_key: String,
+    pub posted_at: String,
+    pub amount: f64,
+    pub currency: String,
+    pub description: String,
+    pub statement_id: Option<String>,
+    pub external_id: Option<String>,
+    pub merchant: Option<String>,
+    pub category: Option<String>,
+    pub effective_merchant: Option<String>,
+    pub effective_category: Option<String>,
+    pub note: Option<String>,
+    pub balance: Option<f64>,
+    pub mcc: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub splits: Vec<TransactionSplitRow>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rule_hits: Vec<TransactionRuleHit>,
+    pub exclusion: Option<TransactionExclusion>,
+    pub provenance: Option<TransactionProvenance>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionExclusion {
+    pub reason: String,
+    pub excluded_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionExcludeData {
+    pub message: String,
+    pub txn_id: String,
+    pub excluded: bool,
+    pub exclusion: Option<TransactionExclusion>,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionRuleHit {
+    pub rule_id: String,
+    pub fields: Vec<String>,
+    pub applied_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionSplitRow {
+    pub split_index: i64,
+    pub amount: f64,
+    pub category: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionSplitData {
+    pub message: String,
+    pub txn_id: String,
+    pub amount: f64,
+    pub currency: String,
+    pub splits: Vec<TransactionSplitRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionEditData {
+    pub message: String,
+    pub txn_id: String,
+    pub posted_at: String,
+    pub amount: f64,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub category: Option<String>,
+    pub effective_merchant: Option<String>,
+    pub effective_category: Option<String>,
+    pub note: Option<String>,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionAddData {
+    pub message: String,
+    pub txn_id: String,
+    pub import_id: String,
+    pub account_key: String,
+    pub posted_at: String,
+    pub amount: f64,
+    pub currency: String,
+    pub description: String,
+    pub effective_merchant: Option<String>,
+    pub effective_category: Option<String>,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionDeleteData {
+    pub message: String,
+    pub txn_id: String,
+    pub import_id: String,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProbableResolutionData {
+    pub message: String,
+    pub probable_duplicate: ImportProbableDuplicate,
+    pub intelligence_refreshed: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -193,6 +408,14 @@ pub struct ImportPropertyInventory {
     pub unique_count: i64,
     pub null_count: i64,
     pub total_rows: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Vec<ImportCanonicalAliases>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportCanonicalAliases {
+    pub canonical: String,
+    pub aliases: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -214,6 +437,70 @@ pub struct ImportSignProfile {
     pub total_count: i64,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportStatementReconciliation {
+    pub statement_id: String,
+    pub account_key: String,
+    pub currency: String,
+    pub opening_balance: f64,
+    pub closing_balance: f64,
+    pub net_amount: f64,
+    pub txn_count: i64,
+    pub first_posted_at: String,
+    pub last_posted_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportBalanceGap {
+    pub account_key: String,
+    pub row: i64,
+    pub previous_row: i64,
+    pub previous_balance: f64,
+    pub amount: f64,
+    pub balance: f64,
+    pub expected_balance: f64,
+    pub difference: f64,
+    pub likely_cause: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportSignCorrection {
+    pub account_key: String,
+    pub rows_inverted: i64,
+    pub historical_negative_ratio: f64,
+    pub incoming_negative_ratio: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRemap {
+    pub property: String,
+    pub from: String,
+    pub to: String,
+    pub rows_rewritten: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRuleChange {
+    pub rule_id: String,
+    pub rows_changed: i64,
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportCategorySuggestion {
+    pub source_row_index: i64,
+    pub account_key: String,
+    pub posted_at: String,
+    pub amount: f64,
+    pub description: String,
+    pub counterparty: String,
+    pub category: String,
+    pub confidence: f64,
+    pub support: i64,
+    pub basis: String,
+    pub applied: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ImportDriftWarning {
     pub code: String,
@@ -222,6 +509,15 @@ pub struct ImportDriftWarning {
     pub incoming_value: String,
     pub message: String,
     pub suggestions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranked_suggestions: Option<Vec<ImportDriftSuggestion>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportDriftSuggestion {
+    pub value: String,
+    pub score: f64,
+    pub existing_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -249,6 +545,10 @@ pub struct ImportListItem {
     pub source_kind: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaces_import_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_by_import_id: Option<String>,
     pub accounts: Vec<ImportListAccountStat>,
 }
 
@@ -292,6 +592,10 @@ pub struct AccountRow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_posted_at: Option<String>,
     pub net_amount: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_balance: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_balance_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -310,10 +614,269 @@ pub struct ImportUndoSummary {
 pub struct ImportUndoData {
     pub import_id: String,
     pub message: String,
+    pub status: String,
+    pub account_key: Option<String>,
+    pub statement_id: Option<String>,
     pub summary: ImportUndoSummary,
     pub intelligence_refreshed: bool,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRedoSummary {
+    pub rows_restored: i64,
+    pub rows_deduped: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRedoData {
+    pub import_id: String,
+    pub message: String,
+    pub summary: ImportRedoSummary,
+    pub intelligence_refreshed: bool,
+}
+
+/// Something committed since an undo that blocks `import redo`: a ledger row
+/// matching an archived row, or a statement id that was reconciled again.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRedoConflict {
+    pub kind: String,
+    pub source_row_index: Option<i64>,
+    pub account_key: String,
+    pub statement_id: Option<String>,
+    pub posted_at: Option<String>,
+    pub amount: Option<f64>,
+    pub description: Option<String>,
+    pub conflicting_txn_id: Option<String>,
+    pub conflicting_import_id: Option<String>,
+}
+
+/// What committing a dry-run batch would change in `v1_recurring` and
+/// `v1_anomalies`, computed in memory from the ledger plus the incoming rows.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportIntelligenceImpact {
+    pub recurring_appearing: Vec<RecurringRow>,
+    pub recurring_disappearing: Vec<RecurringRow>,
+    pub anomalies_triggered: Vec<ImportTriggeredAnomaly>,
+}
+
+/// An anomaly that would be flagged after the import. Incoming rows have a
+/// `source_row_index`; ledger rows newly flagged because of the batch keep
+/// their `txn_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportTriggeredAnomaly {
+    pub source_row_index: Option<i64>,
+    pub txn_id: Option<String>,
+    pub account_key: String,
+    pub posted_at: String,
+    pub merchant: String,
+    pub amount: f64,
+    pub currency: String,
+    pub reason_code: String,
+    pub reason: String,
+    pub score: f64,
+    pub severity: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportStagedItem {
+    pub import_id: String,
+    pub staged_at: String,
+    pub source_kind: Option<String>,
+    pub source_ref: Option<String>,
+    pub rows_read: i64,
+    pub rows_valid: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportStagedListData {
+    pub rows: Vec<ImportStagedItem>,
+}
+
+/// A staged import and the analysis `import stage` reported for it. The
+/// analysis keeps the dry-run field names (`duplicate_summary`,
+/// `drift_warnings`, ...) so it renders like a dry run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportStagedShowData {
+    pub import_id: String,
+    pub status: String,
+    pub staged_at: String,
+    pub source_kind: Option<String>,
+    pub source_ref: Option<String>,
+    pub summary: ImportCreateSummary,
+    pub analysis: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportApproveData {
+    pub import_id: String,
+    pub message: String,
+    pub summary: ImportCreateSummary,
+    pub duplicate_summary: ImportDuplicateSummary,
+    /// Duplicates found when the import was staged, for comparison with the
+    /// recheck at approval time.
+    pub staged_duplicate_summary: ImportDuplicateSummary,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRejectData {
+    pub import_id: String,
+    pub message: String,
+    pub rows_discarded: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReplaceSummary {
+    pub unchanged: i64,
+    pub added: i64,
+    pub removed: i64,
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReplaceRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txn_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_row_index: Option<i64>,
+    pub account_key: String,
+    pub posted_at: String,
+    pub amount: f64,
+    pub currency: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReplaceChange {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<ImportReplaceRow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<ImportReplaceRow>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReplacement {
+    pub replaced_import_id: String,
+    pub summary: ImportReplaceSummary,
+    pub rows_reverted: i64,
+    pub rows_promoted: i64,
+    pub changes: Vec<ImportReplaceChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AliasRow {
+    pub property: String,
+    pub alias: String,
+    pub canonical: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AliasAddData {
+    pub message: String,
+    pub alias: AliasRow,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_canonical: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AliasListData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    pub rows: Vec<AliasRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AliasRemoveData {
+    pub message: String,
+    pub alias: AliasRow,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleRow {
+    pub rule_id: String,
+    pub position: i64,
+    pub description_pattern: Option<String>,
+    pub amount_min: Option<f64>,
+    pub amount_max: Option<f64>,
+    pub account_key: Option<String>,
+    pub mcc: Option<String>,
+    pub set_merchant: Option<String>,
+    pub set_category: Option<String>,
+    pub add_tags: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleAddData {
+    pub message: String,
+    pub rule: RuleRow,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleListData {
+    pub rows: Vec<RuleRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleRemoveData {
+    pub message: String,
+    pub rule: RuleRow,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleApplyChange {
+    pub txn_id: String,
+    pub rule_id: String,
+    pub field: String,
+    pub from: Option<String>,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RulesApplyData {
+    pub message: String,
+    pub dry_run: bool,
+    pub rows_changed: i64,
+    pub changes: Vec<RuleApplyChange>,
+    pub intelligence_refreshed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagRow {
+    pub tag: String,
+    pub txn_count: i64,
+    pub last_tagged_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagChangeData {
+    pub message: String,
+    pub tag: String,
+    pub txn_ids: Vec<String>,
+    pub changed: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagListData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txn_id: Option<String>,
+    pub rows: Vec<TagRow>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct AnomalyRow {
     pub txn_id: String,
@@ -339,6 +902,8 @@ pub struct AnomaliesData {
     pub policy_version: String,
     pub from: Option<String>,
     pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
     pub rows: Vec<AnomalyRow>,
     pub data_range_hint: DataRangeHint,
 }
@@ -364,6 +929,8 @@ pub struct RecurringData {
     pub policy_version: String,
     pub from: Option<String>,
     pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
     pub rows: Vec<RecurringRow>,
     pub data_range_hint: DataRangeHint,
 }
@@ -3,7 +3,7 @@ use std::path::Path;
 use serde_json::{Value, json};
 use thiserror::Error;
 
-use crate::contracts::types::{ImportIssue, ImportSummary};
+use crate::contracts::types::{ImportIssue, ImportRedoConflict, ImportSummary};
 
 pub(crate) const IMPORT_HELP_COMMAND: &str = "driggsby import create --help";
 pub(crate) const IMPORT_HELP_SECTION_TITLE: &str = "Import Troubleshooting";
@@ -150,6 +150,21 @@ impl ClientError {
         }))
     }
 
+    pub fn import_policy_invalid(path: &Path, detail: &str) -> Self {
+        let location = path.display().to_string();
+        Self::new(
+            "import_policy_invalid",
+            &format!("Import policy file `{location}` is invalid: {detail}"),
+            vec![
+                format!("Fix or remove `{location}`."),
+                "Run `driggsby import create --help` to review policy rules.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "policy_path": location,
+        }))
+    }
+
     pub fn import_id_not_found(import_id: &str) -> Self {
         Self::new(
             "import_id_not_found",
@@ -192,6 +207,376 @@ impl ClientError {
         }))
     }
 
+    pub fn import_redo_id_not_found(import_id: &str) -> Self {
+        Self::new(
+            "import_id_not_found",
+            &format!("Import id `{import_id}` was not found."),
+            vec![
+                "Run driggsby import list to find a reverted import id.".to_string(),
+                "Retry with driggsby import redo <import_id>.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+        }))
+    }
+
+    pub fn import_not_reverted(import_id: &str, status: &str) -> Self {
+        Self::new(
+            "import_not_reverted",
+            &format!(
+                "Import id `{import_id}` is {status}; only a fully reverted import can be redone."
+            ),
+            vec![
+                "Run driggsby import list to inspect import statuses.".to_string(),
+                format!(
+                    "Run driggsby import undo {import_id} first if you want to redo all of it."
+                ),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+            "status": status,
+        }))
+    }
+
+    pub fn import_undo_scope_empty(
+        import_id: &str,
+        account_key: Option<&str>,
+        statement_id: Option<&str>,
+    ) -> Self {
+        let scope = match (account_key, statement_id) {
+            (Some(account_key), Some(statement_id)) => {
+                format!(" for account `{account_key}` and statement `{statement_id}`")
+            }
+            (Some(account_key), None) => format!(" for account `{account_key}`"),
+            (None, Some(statement_id)) => format!(" for statement `{statement_id}`"),
+            (None, None) => String::new(),
+        };
+        Self::new(
+            "import_undo_scope_empty",
+            &format!("Import id `{import_id}` has no rows left to revert{scope}."),
+            vec![
+                format!("Run driggsby import duplicates {import_id} to see the accounts and statements it touched."),
+                "Run driggsby import list to inspect import statuses.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+            "account_key": account_key,
+            "statement_id": statement_id,
+        }))
+    }
+
+    pub fn import_redo_unavailable(import_id: &str) -> Self {
+        Self::new(
+            "import_redo_unavailable",
+            &format!(
+                "Import id `{import_id}` was reverted before undo kept an archive, so it cannot be restored."
+            ),
+            vec![
+                "Import the original file again with driggsby import create <path>.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+        }))
+    }
+
+    pub fn import_redo_conflict(import_id: &str, conflicts: &[ImportRedoConflict]) -> Self {
+        let mut recovery_steps: Vec<String> = conflicts
+            .iter()
+            .take(3)
+            .map(describe_redo_conflict)
+            .collect();
+        if conflicts.len() > 3 {
+            recovery_steps.push(format!("...and {} more conflicts.", conflicts.len() - 3));
+        }
+        recovery_steps.push(
+            "Undo the conflicting imports with driggsby import undo <import_id>, then retry redo."
+                .to_string(),
+        );
+        Self::new(
+            "import_redo_conflict",
+            &format!(
+                "Import id `{import_id}` cannot be restored: {} conflict(s) with data committed since the undo. No rows were written.",
+                conflicts.len()
+            ),
+            recovery_steps,
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+            "conflicts": conflicts,
+        }))
+    }
+
+    pub fn import_staged_id_not_found(import_id: &str) -> Self {
+        Self::new(
+            "import_id_not_found",
+            &format!("Staged import id `{import_id}` was not found."),
+            vec![
+                "Run driggsby import staged list to find a staged import id.".to_string(),
+                "Stage a file first with driggsby import stage <path>.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+        }))
+    }
+
+    pub fn import_not_staged(import_id: &str, status: &str) -> Self {
+        Self::new(
+            "import_not_staged",
+            &format!(
+                "Import id `{import_id}` is {status}; only a staged import can be approved or rejected."
+            ),
+            vec![
+                "Run driggsby import staged list to see imports awaiting review.".to_string(),
+                "Run driggsby import list to inspect import statuses.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+            "status": status,
+        }))
+    }
+
+    pub fn import_staged_analysis_invalid(import_id: &str, detail: &str) -> Self {
+        Self::new(
+            "import_staged_analysis_invalid",
+            &format!("Staged import `{import_id}` has an unreadable analysis record: {detail}"),
+            vec![
+                format!("Run driggsby import reject {import_id} to discard it."),
+                "Stage the file again with driggsby import stage <path>.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+        }))
+    }
+
+    pub fn import_not_committed(import_id: &str, status: &str) -> Self {
+        Self::new(
+            "import_not_committed",
+            &format!("Import id `{import_id}` is {status} and has no rows in the ledger."),
+            vec![
+                format!("Run driggsby import staged show {import_id} to review it."),
+                "Run driggsby import list to inspect import statuses.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+            "status": status,
+        }))
+    }
+
+    pub fn import_source_already_imported(import_id: &str, source_hash: &str) -> Self {
+        Self::new(
+            "import_source_already_imported",
+            &format!(
+                "These source rows were already imported as `{import_id}`. No rows were written."
+            ),
+            vec![
+                format!("Run driggsby import duplicates {import_id} to review that import."),
+                format!("Run driggsby import undo {import_id} first to import this source again."),
+                "Pass --allow-duplicate-source to import it anyway; rows already in the ledger are still deduped.".to_string(),
+                "Pass --idempotency-key on retries to get the original import back instead of an error.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "import_id": import_id,
+            "source_hash": source_hash,
+        }))
+    }
+
+    pub fn idempotency_key_conflict(idempotency_key: &str, import_id: &str, reason: &str) -> Self {
+        Self::new(
+            "idempotency_key_conflict",
+            &format!(
+                "Idempotency key `{idempotency_key}` already belongs to import `{import_id}`, {reason}."
+            ),
+            vec![
+                "Use a new --idempotency-key for a different source or a fresh import.".to_string(),
+                "Run driggsby import list to inspect import statuses.".to_string(),
+            ],
+        )
+        .with_import_help_data(json!({
+            "idempotency_key": idempotency_key,
+            "import_id": import_id,
+        }))
+    }
+
+    pub fn dedupe_candidate_not_found(candidate_id: &str) -> Self {
+        Self::new(
+            "dedupe_candidate_not_found",
+            &format!("Dedupe candidate `{candidate_id}` was not found."),
+            vec![
+                "Run driggsby import duplicates <import_id> to list candidate ids.".to_string(),
+                "Retry with driggsby import duplicates promote <candidate_id>.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "candidate_id": candidate_id,
+        }))
+    }
+
+    pub fn dedupe_candidate_already_promoted(candidate_id: &str, promoted_txn_id: &str) -> Self {
+        Self::new(
+            "dedupe_candidate_already_promoted",
+            &format!(
+                "Dedupe candidate `{candidate_id}` is already in the ledger as `{promoted_txn_id}`."
+            ),
+            vec![
+                "Run driggsby import duplicates <import_id> to review candidate statuses."
+                    .to_string(),
+            ],
+        )
+        .with_data(json!({
+            "candidate_id": candidate_id,
+            "promoted_txn_id": promoted_txn_id,
+        }))
+    }
+
+    pub fn dedupe_candidate_invalidated(candidate_id: &str, import_id: &str) -> Self {
+        Self::new(
+            "dedupe_candidate_invalidated",
+            &format!(
+                "Dedupe candidate `{candidate_id}` was invalidated when part of import `{import_id}` was undone."
+            ),
+            vec![
+                format!("Run driggsby import duplicates {import_id} to review candidate statuses."),
+                "Import the row again if it belongs in the ledger.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "candidate_id": candidate_id,
+            "import_id": import_id,
+        }))
+    }
+
+    pub fn transaction_not_found(txn_id: &str) -> Self {
+        Self::new(
+            "transaction_not_found",
+            &format!("Transaction `{txn_id}` was not found in the ledger."),
+            vec![
+                "Run driggsby db sql \"SELECT txn_id, posted_at, description FROM v1_transactions\" to find transaction ids.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "txn_id": txn_id,
+        }))
+    }
+
+    pub fn transaction_not_manual(txn_id: &str, import_id: &str) -> Self {
+        Self::new(
+            "transaction_not_manual",
+            &format!(
+                "Transaction `{txn_id}` came from import `{import_id}`, not manual entry. Its imported values cannot be changed or deleted here."
+            ),
+            vec![
+                format!(
+                    "Run driggsby transaction edit {txn_id} --merchant/--category/--note to override what it shows."
+                ),
+                format!("Run driggsby import undo {import_id} to remove the whole import."),
+            ],
+        )
+        .with_data(json!({
+            "txn_id": txn_id,
+            "import_id": import_id,
+        }))
+    }
+
+    pub fn probable_duplicate_not_found(probable_id: &str) -> Self {
+        Self::new(
+            "probable_duplicate_not_found",
+            &format!("Probable duplicate `{probable_id}` was not found."),
+            vec![
+                "Run driggsby import duplicates <import_id> to list probable duplicates."
+                    .to_string(),
+                "Retry with a probable id from that list.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "probable_id": probable_id,
+        }))
+    }
+
+    pub fn probable_duplicate_already_resolved(probable_id: &str, status: &str) -> Self {
+        Self::new(
+            "probable_duplicate_already_resolved",
+            &format!("Probable duplicate `{probable_id}` was already {status}."),
+            vec![
+                "Run driggsby import duplicates <import_id> to review queue statuses.".to_string(),
+                "Use driggsby import undo <import_id> to revert the whole import instead."
+                    .to_string(),
+            ],
+        )
+        .with_data(json!({
+            "probable_id": probable_id,
+            "status": status,
+        }))
+    }
+
+    pub fn probable_duplicate_row_missing(probable_id: &str, txn_id: Option<&str>) -> Self {
+        Self::new(
+            "probable_duplicate_row_missing",
+            &format!(
+                "Probable duplicate `{probable_id}` no longer has an incoming transaction in the ledger."
+            ),
+            vec![
+                format!("Run driggsby import duplicates reject {probable_id} to close it."),
+                "Run driggsby import duplicates <import_id> to review queue statuses.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "probable_id": probable_id,
+            "txn_id": txn_id,
+        }))
+    }
+
+    pub fn alias_not_found(property: &str, alias: &str) -> Self {
+        Self::new(
+            "alias_not_found",
+            &format!("No {property} alias `{alias}` was found."),
+            vec![
+                format!("Run driggsby alias list --property {property} to see existing aliases."),
+                "Retry with driggsby alias remove <property> <alias>.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "property": property,
+            "alias": alias,
+        }))
+    }
+
+    pub fn rule_not_found(rule_id: &str) -> Self {
+        Self::new(
+            "rule_not_found",
+            &format!("Rule `{rule_id}` was not found."),
+            vec![
+                "Run driggsby rules list to see existing rules.".to_string(),
+                "Retry with driggsby rules remove <rule-id>.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "rule_id": rule_id,
+        }))
+    }
+
+    pub fn rule_invalid(rule_id: &str, detail: &str) -> Self {
+        Self::new(
+            "rule_invalid",
+            &format!("Rule `{rule_id}` is stored in an unreadable form: {detail}"),
+            vec![
+                format!("Run driggsby rules remove {rule_id} to drop it."),
+                "Add it again with driggsby rules add.".to_string(),
+            ],
+        )
+        .with_data(json!({
+            "rule_id": rule_id,
+        }))
+    }
+
     pub fn internal_serialization(message: &str) -> Self {
         Self::new("internal_serialization_error", message, Vec::new())
     }
@@ -268,3 +653,39 @@ fn merge_import_help_data(mut data: Value) -> Value {
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
+
+fn describe_redo_conflict(conflict: &ImportRedoConflict) -> String {
+    if conflict.kind == "statement" {
+        return format!(
+            "Statement `{}` for {} was reconciled again by import `{}`.",
+            conflict.statement_id.as_deref().unwrap_or_default(),
+            conflict.account_key,
+            conflict
+                .conflicting_import_id
+                .as_deref()
+                .unwrap_or("unknown"),
+        );
+    }
+    format!(
+        "Row {} ({} {} {} {}) now matches {} from import `{}`.",
+        conflict
+            .source_row_index
+            .map(|index| index.to_string())
+            .unwrap_or_else(|| "?".to_string()),
+        conflict.account_key,
+        conflict.posted_at.as_deref().unwrap_or_default(),
+        conflict
+            .amount
+            .map(|amount| amount.to_string())
+            .unwrap_or_default(),
+        conflict.description.as_deref().unwrap_or_default(),
+        conflict
+            .conflicting_txn_id
+            .as_deref()
+            .unwrap_or("a ledger row"),
+        conflict
+            .conflicting_import_id
+            .as_deref()
+            .unwrap_or("unknown"),
+    )
+}
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+
+use crate::identity::{identity_join, load_identity_key};
+use crate::import::persist::now_timestamp;
+use crate::intelligence::refresh::refresh_all_in_transaction;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+/// Attaches `internal_transaction_exclusions` as `x` to `internal_transactions`
+/// as `t` by row identity. Callers filter on `x.identity_key IS NULL` to drop
+/// excluded rows.
+pub(crate) fn exclusion_join() -> String {
+    identity_join("internal_transaction_exclusions", "x")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TransactionExclusionRecord {
+    pub(crate) reason: String,
+    pub(crate) excluded_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ExclusionChangeResult {
+    pub(crate) exclusion: Option<TransactionExclusionRecord>,
+    pub(crate) intelligence_refreshed: bool,
+}
+
+/// Trims and lowercases a user-supplied exclusion reason such as `transfer`.
+pub(crate) fn normalize_reason(raw: &str) -> ClientResult<String> {
+    let reason = raw.trim().to_ascii_lowercase();
+    if reason.is_empty() {
+        return Err(ClientError::invalid_argument_for_command(
+            "Exclusion reason must not be empty.",
+            Some("transaction exclude"),
+        ));
+    }
+    Ok(reason)
+}
+
+/// Excludes `txn_id` from intelligence and reporting with `reason`, or brings
+/// it back when `reason` is `None`. Intelligence is refreshed when the row's
+/// excluded state changes.
+pub(crate) fn set_transaction_exclusion(
+    connection: &mut Connection,
+    db_path: &Path,
+    txn_id: &str,
+    reason: Option<&str>,
+) -> ClientResult<ExclusionChangeResult> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let Some(key) = load_identity_key(&transaction, db_path, txn_id)? else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+
+    let was_excluded = load_exclusion_by_key(&transaction, db_path, &key)?.is_some();
+
+    let exclusion = match reason {
+        Some(reason) => {
+            let record = TransactionExclusionRecord {
+                reason: reason.to_string(),
+                excluded_at: now_timestamp(),
+            };
+            transaction
+                .execute(
+                    "INSERT INTO internal_transaction_exclusions (
+                        identity_key,
+                        reason,
+                        excluded_at
+                     ) VALUES (?1, ?2, ?3)
+                     ON CONFLICT (identity_key) DO UPDATE SET
+                        reason = excluded.reason,
+                        excluded_at = excluded.excluded_at",
+                    params![&key, &record.reason, &record.excluded_at,],
+                )
+                .map_err(|error| map_sqlite_error(db_path, &error))?;
+            Some(record)
+        }
+        None => {
+            transaction
+                .execute(
+                    "DELETE FROM internal_transaction_exclusions WHERE identity_key = ?1",
+                    params![&key],
+                )
+                .map_err(|error| map_sqlite_error(db_path, &error))?;
+            None
+        }
+    };
+
+    let state_changed = was_excluded != exclusion.is_some();
+    if state_changed {
+        refresh_all_in_transaction(&transaction, db_path)?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(ExclusionChangeResult {
+        exclusion,
+        intelligence_refreshed: state_changed,
+    })
+}
+
+/// Loads the exclusion matching the row with `txn_id`, if any.
+pub(crate) fn load_transaction_exclusion(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Option<TransactionExclusionRecord>> {
+    let Some(key) = load_identity_key(connection, db_path, txn_id)? else {
+        return Ok(None);
+    };
+    load_exclusion_by_key(connection, db_path, &key)
+}
+
+fn load_exclusion_by_key(
+    connection: &Connection,
+    db_path: &Path,
+    key: &str,
+) -> ClientResult<Option<TransactionExclusionRecord>> {
+    connection
+        .query_row(
+            "SELECT reason, excluded_at
+             FROM internal_transaction_exclusions
+             WHERE identity_key = ?1",
+            params![key],
+            |row| {
+                Ok(TransactionExclusionRecord {
+                    reason: row.get(0)?,
+                    excluded_at: row.get(1)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+
+use crate::ClientResult;
+use crate::import::CanonicalTransaction;
+use crate::import::dedupe::{DedupePolicy, dedupe_key};
+use crate::import::policy::load_dedupe_policies;
+use crate::state::map_sqlite_error;
+
+/// Tables holding user annotations keyed by `identity_key`.
+const ANNOTATION_TABLES: [&str; 5] = [
+    "internal_transaction_overrides",
+    "internal_transaction_tags",
+    "internal_transaction_splits",
+    "internal_transaction_rule_hits",
+    "internal_transaction_exclusions",
+];
+
+/// Attaches the annotation `table` as `alias` to `internal_transactions` as
+/// `t`. Every ledger row has its own `identity_key`, so an annotation belongs
+/// to exactly one current row.
+pub(crate) fn identity_join(table: &str, alias: &str) -> String {
+    format!("LEFT JOIN {table} {alias} ON {alias}.identity_key = t.identity_key")
+}
+
+/// Picks the identity key for a row about to enter the ledger. The key is
+/// built from the full row, or from its external id when the account's policy
+/// trusts it, so the same source row gets the same key when it is imported
+/// again after an undo or replace and its annotations come back. Key fields
+/// and date tolerance from the policy are ignored: they widen what counts as
+/// a duplicate, and a key without the date would hand one month's
+/// annotations to the next month's identical charge. Identical rows kept side
+/// by side, such as two same-day coffees, are told apart by an occurrence
+/// ordinal: the first keeps the bare key and later ones get `#2`, `#3` and
+/// so on.
+pub(crate) fn assign_identity_key(
+    connection: &Connection,
+    db_path: &Path,
+    row: &CanonicalTransaction,
+    policy: &DedupePolicy,
+) -> ClientResult<String> {
+    let base = dedupe_key(
+        row,
+        &DedupePolicy {
+            trust_external_id: policy.trust_external_id,
+            ..DedupePolicy::default()
+        },
+    );
+    let mut ordinal = 1;
+    loop {
+        let key = if ordinal == 1 {
+            base.clone()
+        } else {
+            format!("{base}#{ordinal}")
+        };
+        let taken = connection
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM internal_transactions WHERE identity_key = ?1)",
+                params![&key],
+                |row| row.get::<_, bool>(0),
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+        if !taken {
+            return Ok(key);
+        }
+        ordinal += 1;
+    }
+}
+
+/// The identity key of the ledger row `txn_id`, or `None` when there is no
+/// such row.
+pub(crate) fn load_identity_key(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Option<String>> {
+    connection
+        .query_row(
+            "SELECT identity_key FROM internal_transactions WHERE txn_id = ?1",
+            params![txn_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+/// Gives ledger rows written before identity keys existed their key, oldest
+/// row first. Annotations used to be keyed by the imported fields alone, so
+/// identical rows shared them; they stay with the first such row, which keeps
+/// the bare key. Accounts whose dedupe policy distrusts external ids build a
+/// different key and have their annotations moved over to it.
+pub(crate) fn backfill_identity_keys(
+    connection: &mut Connection,
+    db_path: &Path,
+) -> ClientResult<()> {
+    let pending = connection
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM internal_transactions WHERE identity_key IS NULL)",
+            [],
+            |row| row.get::<_, bool>(0),
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    if !pending {
+        return Ok(());
+    }
+
+    let policies = load_dedupe_policies(db_path)?;
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = unkeyed_rows(&transaction, db_path)?;
+    for (txn_id, row) in rows {
+        let key = assign_identity_key(
+            &transaction,
+            db_path,
+            &row,
+            policies.for_account(&row.account_key),
+        )?;
+        let legacy = legacy_key(&row);
+        let legacy_claimed = transaction
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM internal_transactions WHERE identity_key = ?1)",
+                params![&legacy],
+                |row| row.get::<_, bool>(0),
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+        if key != legacy && !legacy_claimed {
+            for table in ANNOTATION_TABLES {
+                transaction
+                    .execute(
+                        &format!(
+                            "UPDATE OR IGNORE {table} SET identity_key = ?1 WHERE identity_key = ?2"
+                        ),
+                        params![&key, &legacy],
+                    )
+                    .map_err(|error| map_sqlite_error(db_path, &error))?;
+            }
+        }
+        transaction
+            .execute(
+                "UPDATE internal_transactions SET identity_key = ?2 WHERE txn_id = ?1",
+                params![&txn_id, &key],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+fn unkeyed_rows(
+    connection: &Connection,
+    db_path: &Path,
+) -> ClientResult<Vec<(String, CanonicalTransaction)>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT txn_id, statement_id, dedupe_scope_id, account_key, posted_at, amount,
+                    currency, description, external_id, merchant, category, balance, mcc
+             FROM internal_transactions
+             WHERE identity_key IS NULL
+             ORDER BY txn_id ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                CanonicalTransaction {
+                    statement_id: row.get(1)?,
+                    dedupe_scope_id: row.get(2)?,
+                    account_key: row.get(3)?,
+                    account_type: None,
+                    posted_at: row.get(4)?,
+                    amount: row.get(5)?,
+                    currency: row.get(6)?,
+                    description: row.get(7)?,
+                    external_id: row.get(8)?,
+                    merchant: row.get(9)?,
+                    category: row.get(10)?,
+                    balance: row.get(11)?,
+                    mcc: row.get(12)?,
+                },
+            ))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut unkeyed = Vec::new();
+    for row in rows {
+        unkeyed.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(unkeyed)
+}
+
+/// The key annotations were stored under before rows had their own: the
+/// default dedupe key, ignoring any account policy.
+fn legacy_key(row: &CanonicalTransaction) -> String {
+    dedupe_key(row, &DedupePolicy::default())
+}
@@ -3,9 +3,13 @@ use std::path::Path;
 use rusqlite::Connection;
 
 use crate::ClientResult;
-use crate::contracts::types::{ImportDriftWarning, ImportKeyInventory, ImportSignProfile};
+use crate::contracts::types::{
+    ImportDriftWarning, ImportIntelligenceImpact, ImportKeyInventory, ImportSignProfile,
+};
+use crate::import::coverage::{existing_coverage_spans, incoming_coverage_spans};
 use crate::import::dedupe::BatchRow;
 use crate::import::drift_warnings::build_drift_warnings;
+use crate::import::intelligence_impact::intelligence_impact;
 use crate::import::inventory::{incoming_unique_values, query_key_inventory};
 use crate::import::sign_profiles::{
     existing_sign_count_map, incoming_sign_count_map, profiles_from_sign_counts,
@@ -16,12 +20,14 @@ pub(crate) struct DryRunAnalysis {
     pub(crate) key_inventory: ImportKeyInventory,
     pub(crate) sign_profiles: Vec<ImportSignProfile>,
     pub(crate) drift_warnings: Vec<ImportDriftWarning>,
+    pub(crate) intelligence_impact: ImportIntelligenceImpact,
 }
 
 pub(crate) fn analyze_dry_run(
     connection: &Connection,
     db_path: &Path,
     rows: &[BatchRow],
+    batch_rows: &[BatchRow],
 ) -> ClientResult<DryRunAnalysis> {
     let key_inventory = query_key_inventory(connection, db_path)?;
     let existing_sign_counts = existing_sign_count_map(connection, db_path)?;
@@ -29,16 +35,25 @@ pub(crate) fn analyze_dry_run(
 
     let incoming_values = incoming_unique_values(rows.iter().map(|row| &row.row));
     let incoming_sign_counts = incoming_sign_count_map(rows.iter().map(|row| &row.row));
+    // Coverage looks at every row in the batch, including rows the ledger
+    // already has, so re-importing a statement still reports its overlap.
+    let existing_spans = existing_coverage_spans(connection, db_path)?;
+    let incoming_spans = incoming_coverage_spans(batch_rows.iter().map(|row| &row.row));
     let drift_warnings = build_drift_warnings(
         &key_inventory,
         &incoming_values,
         &existing_sign_counts,
         &incoming_sign_counts,
+        &existing_spans,
+        &incoming_spans,
     );
 
+    let intelligence_impact = intelligence_impact(connection, db_path, rows)?;
+
     Ok(DryRunAnalysis {
         key_inventory,
         sign_profiles,
         drift_warnings,
+        intelligence_impact,
     })
 }
@@ -0,0 +1,284 @@
+use std::path::Path;
+
+use rusqlite::{Connection, params};
+
+use crate::ClientResult;
+use crate::import::CanonicalTransaction;
+use crate::import::statements::ReconciledStatement;
+use crate::import::undo::{IN_SCOPE, UndoScope};
+use crate::state::map_sqlite_error;
+
+/// A row an import contributed: a ledger row (`txn_id` set) or a dedupe
+/// candidate that never reached the ledger.
+#[derive(Debug, Clone)]
+pub(crate) struct ImportRow {
+    pub(crate) txn_id: Option<String>,
+    pub(crate) source_row_index: Option<i64>,
+    pub(crate) row: CanonicalTransaction,
+}
+
+/// Every row of a committed import inside `scope`, in source row order. Rows
+/// committed before source rows were recorded have no index and sort last.
+pub(crate) fn load_import_rows(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+    scope: &UndoScope,
+) -> ClientResult<Vec<ImportRow>> {
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT r.txn_id, r.source_row_index, r.statement_id, r.dedupe_scope_id,
+                    r.account_key, a.account_type, r.posted_at, r.amount, r.currency,
+                    r.description, r.external_id, r.merchant, r.category, r.balance, r.mcc
+             FROM (
+                SELECT t.txn_id, s.source_row_index, t.statement_id, t.dedupe_scope_id,
+                       t.account_key, t.posted_at, t.amount, t.currency, t.description,
+                       t.external_id, t.merchant, t.category, t.balance, t.mcc
+                FROM internal_transactions t
+                LEFT JOIN internal_import_source_rows s ON s.txn_id = t.txn_id
+                WHERE t.import_id = ?1 AND {IN_SCOPE}
+                UNION ALL
+                SELECT NULL, NULLIF(source_row_index, 0), statement_id, dedupe_scope_id,
+                       account_key, posted_at, amount, currency, description, external_id,
+                       merchant, category, balance, mcc
+                FROM internal_transaction_dedupe_candidates
+                WHERE import_id = ?1 AND promoted_txn_id IS NULL AND {IN_SCOPE}
+             ) r
+             LEFT JOIN internal_accounts a ON a.account_key = r.account_key
+             ORDER BY r.source_row_index IS NULL, r.source_row_index, r.posted_at, r.txn_id"
+        ))
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(
+            params![import_id, &scope.account_key, &scope.statement_id],
+            |row| {
+                Ok(ImportRow {
+                    txn_id: row.get(0)?,
+                    source_row_index: row.get(1)?,
+                    row: CanonicalTransaction {
+                        statement_id: row.get(2)?,
+                        dedupe_scope_id: row.get(3)?,
+                        account_key: row.get(4)?,
+                        account_type: row.get(5)?,
+                        posted_at: row.get(6)?,
+                        amount: row.get(7)?,
+                        currency: row.get(8)?,
+                        description: row.get(9)?,
+                        external_id: row.get(10)?,
+                        merchant: row.get(11)?,
+                        category: row.get(12)?,
+                        balance: row.get(13)?,
+                        mcc: row.get(14)?,
+                    },
+                })
+            },
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut import_rows = Vec::new();
+    for row in rows {
+        import_rows.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(import_rows)
+}
+
+/// Copies the rows and reconciled statements `import undo` is about to delete
+/// into the archive, so `import redo` can restore them later. Partial undos
+/// append to what earlier ones archived.
+pub(crate) fn archive_import(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    scope: &UndoScope,
+    timestamp: &str,
+) -> ClientResult<()> {
+    let archived_count = transaction
+        .query_row(
+            "SELECT COALESCE(MAX(archive_index), 0) FROM internal_reverted_transactions
+             WHERE import_id = ?1",
+            params![import_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = load_import_rows(transaction, db_path, import_id, scope)?;
+    for (index, import_row) in rows.iter().enumerate() {
+        let row = &import_row.row;
+        transaction
+            .execute(
+                "INSERT INTO internal_reverted_transactions (
+                    import_id,
+                    archive_index,
+                    txn_id,
+                    source_row_index,
+                    statement_id,
+                    dedupe_scope_id,
+                    account_key,
+                    account_type,
+                    posted_at,
+                    amount,
+                    currency,
+                    description,
+                    external_id,
+                    merchant,
+                    category,
+                    balance,
+                    archived_at,
+                    mcc
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+                params![
+                    import_id,
+                    archived_count + (index as i64) + 1,
+                    &import_row.txn_id,
+                    import_row.source_row_index,
+                    &row.statement_id,
+                    &row.dedupe_scope_id,
+                    &row.account_key,
+                    &row.account_type,
+                    &row.posted_at,
+                    row.amount,
+                    &row.currency,
+                    &row.description,
+                    &row.external_id,
+                    &row.merchant,
+                    &row.category,
+                    row.balance,
+                    timestamp,
+                    &row.mcc
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+
+    transaction
+        .execute(
+            &format!(
+                "INSERT INTO internal_reverted_statements (
+                import_id,
+                account_key,
+                statement_id,
+                currency,
+                opening_balance,
+                closing_balance,
+                net_amount,
+                txn_count,
+                first_posted_at,
+                last_posted_at,
+                archived_at
+             )
+             SELECT import_id, account_key, statement_id, currency, opening_balance,
+                    closing_balance, net_amount, txn_count, first_posted_at, last_posted_at, ?4
+             FROM internal_statements
+             WHERE import_id = ?1 AND {IN_SCOPE}"
+            ),
+            params![
+                import_id,
+                &scope.account_key,
+                &scope.statement_id,
+                timestamp
+            ],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+/// Archived rows of a reverted import in source row order, then archive order.
+/// `txn_id` is the id the row had while it was in the ledger; deduped rows
+/// have none.
+pub(crate) fn load_archived_rows(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<Vec<ImportRow>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT txn_id, source_row_index, statement_id, dedupe_scope_id, account_key,
+                    account_type, posted_at, amount, currency, description, external_id,
+                    merchant, category, balance, mcc
+             FROM internal_reverted_transactions
+             WHERE import_id = ?1
+             ORDER BY source_row_index IS NULL, source_row_index, archive_index",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![import_id], |row| {
+            Ok(ImportRow {
+                txn_id: row.get(0)?,
+                source_row_index: row.get(1)?,
+                row: CanonicalTransaction {
+                    statement_id: row.get(2)?,
+                    dedupe_scope_id: row.get(3)?,
+                    account_key: row.get(4)?,
+                    account_type: row.get(5)?,
+                    posted_at: row.get(6)?,
+                    amount: row.get(7)?,
+                    currency: row.get(8)?,
+                    description: row.get(9)?,
+                    external_id: row.get(10)?,
+                    merchant: row.get(11)?,
+                    category: row.get(12)?,
+                    balance: row.get(13)?,
+                    mcc: row.get(14)?,
+                },
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut archived = Vec::new();
+    for row in rows {
+        archived.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(archived)
+}
+
+pub(crate) fn load_archived_statements(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<Vec<ReconciledStatement>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT account_key, statement_id, currency, opening_balance, closing_balance,
+                    net_amount, txn_count, first_posted_at, last_posted_at
+             FROM internal_reverted_statements
+             WHERE import_id = ?1
+             ORDER BY account_key ASC, statement_id ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![import_id], |row| {
+            Ok(ReconciledStatement {
+                account_key: row.get(0)?,
+                statement_id: row.get(1)?,
+                currency: row.get(2)?,
+                opening_balance: row.get(3)?,
+                closing_balance: row.get(4)?,
+                net_amount: row.get(5)?,
+                txn_count: row.get(6)?,
+                first_posted_at: row.get(7)?,
+                last_posted_at: row.get(8)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut statements = Vec::new();
+    for row in rows {
+        statements.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(statements)
+}
+
+pub(crate) fn clear_archive(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<()> {
+    for sql in [
+        "DELETE FROM internal_reverted_transactions WHERE import_id = ?1",
+        "DELETE FROM internal_reverted_statements WHERE import_id = ?1",
+    ] {
+        transaction
+            .execute(sql, params![import_id])
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use crate::contracts::types::ImportBalanceGap;
+use crate::import::CanonicalTransaction;
+use crate::import::statements::to_cents;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BalanceGap {
+    pub(crate) account_key: String,
+    pub(crate) row: i64,
+    pub(crate) previous_row: i64,
+    pub(crate) previous_balance: f64,
+    pub(crate) amount: f64,
+    pub(crate) balance: f64,
+    pub(crate) expected_balance: f64,
+    pub(crate) difference: f64,
+    pub(crate) likely_cause: &'static str,
+}
+
+impl BalanceGap {
+    pub(crate) fn to_contract(&self) -> ImportBalanceGap {
+        ImportBalanceGap {
+            account_key: self.account_key.clone(),
+            row: self.row,
+            previous_row: self.previous_row,
+            previous_balance: self.previous_balance,
+            amount: self.amount,
+            balance: self.balance,
+            expected_balance: self.expected_balance,
+            difference: self.difference,
+            likely_cause: self.likely_cause.to_string(),
+        }
+    }
+}
+
+/// Returns whether any row in the batch carries a running balance.
+pub(crate) fn has_running_balances(rows: &[CanonicalTransaction]) -> bool {
+    rows.iter().any(|row| row.balance.is_some())
+}
+
+/// Walks each account's rows in posting order and flags every step where
+/// `previous balance + amount` does not land on the reported balance.
+///
+/// Rows are numbered from 1 in source order. Rows posted on the same day keep
+/// their file order, reversed when the file lists the account newest-first.
+/// A row without a balance breaks the chain rather than producing a gap.
+pub(crate) fn find_balance_gaps(rows: &[CanonicalTransaction]) -> Vec<BalanceGap> {
+    let mut rows_by_account: BTreeMap<&str, Vec<(i64, &CanonicalTransaction)>> = BTreeMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        rows_by_account
+            .entry(row.account_key.as_str())
+            .or_default()
+            .push(((index as i64) + 1, row));
+    }
+
+    let mut gaps = Vec::new();
+    for (account_key, mut account_rows) in rows_by_account {
+        let newest_first = match (account_rows.first(), account_rows.last()) {
+            (Some((_, first)), Some((_, last))) => first.posted_at > last.posted_at,
+            _ => false,
+        };
+        if newest_first {
+            account_rows.reverse();
+        }
+        account_rows.sort_by(|left, right| left.1.posted_at.cmp(&right.1.posted_at));
+
+        let mut previous: Option<(i64, f64)> = None;
+        for (row_number, row) in account_rows {
+            let Some(balance) = row.balance else {
+                previous = None;
+                continue;
+            };
+            if let Some((previous_row, previous_balance)) = previous {
+                let expected_cents = to_cents(previous_balance) + to_cents(row.amount);
+                let balance_cents = to_cents(balance);
+                if expected_cents != balance_cents {
+                    let likely_cause = if balance_cents == to_cents(previous_balance) {
+                        "duplicated_transaction"
+                    } else {
+                        "missing_transaction"
+                    };
+                    gaps.push(BalanceGap {
+                        account_key: account_key.to_string(),
+                        row: row_number,
+                        previous_row,
+                        previous_balance,
+                        amount: row.amount,
+                        balance,
+                        expected_balance: expected_cents as f64 / 100.0,
+                        difference: (balance_cents - expected_cents) as f64 / 100.0,
+                        likely_cause,
+                    });
+                }
+            }
+            previous = Some((row_number, balance));
+        }
+    }
+
+    gaps.sort_by_key(|gap| gap.row);
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::CanonicalTransaction;
+
+    use super::find_balance_gaps;
+
+    fn row(posted_at: &str, amount: f64, balance: Option<f64>) -> CanonicalTransaction {
+        CanonicalTransaction {
+            statement_id: None,
+            dedupe_scope_id: "scope_1".to_string(),
+            account_key: "acct_1".to_string(),
+            account_type: None,
+            posted_at: posted_at.to_string(),
+            amount,
+            currency: "USD".to_string(),
+            description: "row".to_string(),
+            external_id: None,
+            merchant: None,
+            category: None,
+            balance,
+            mcc: None,
+        }
+    }
+
+    #[test]
+    fn continuous_balances_produce_no_gaps_in_either_file_order() {
+        let oldest_first = vec![
+            row("2026-01-01", -10.0, Some(90.0)),
+            row("2026-01-02", -0.1, Some(89.9)),
+            row("2026-01-02", 0.3, Some(90.2)),
+        ];
+        assert!(find_balance_gaps(&oldest_first).is_empty());
+
+        let newest_first = oldest_first.into_iter().rev().collect::<Vec<_>>();
+        assert!(find_balance_gaps(&newest_first).is_empty());
+    }
+
+    #[test]
+    fn gaps_name_the_likely_cause() {
+        let rows = vec![
+            row("2026-01-01", -10.0, Some(90.0)),
+            row("2026-01-03", -5.0, Some(80.0)),
+            row("2026-01-04", -2.0, Some(78.0)),
+            row("2026-01-04", -2.0, Some(78.0)),
+        ];
+        let gaps = find_balance_gaps(&rows);
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0].row, 2);
+        assert_eq!(gaps[0].previous_row, 1);
+        assert_eq!(gaps[0].expected_balance, 85.0);
+        assert_eq!(gaps[0].difference, -5.0);
+        assert_eq!(gaps[0].likely_cause, "missing_transaction");
+        assert_eq!(gaps[1].row, 4);
+        assert_eq!(gaps[1].likely_cause, "duplicated_transaction");
+    }
+
+    #[test]
+    fn rows_without_balances_break_the_chain() {
+        let rows = vec![
+            row("2026-01-01", -10.0, Some(90.0)),
+            row("2026-01-02", -5.0, None),
+            row("2026-01-03", -5.0, Some(80.0)),
+        ];
+        assert!(find_balance_gaps(&rows).is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::ClientResult;
+use crate::contracts::types::ImportCategorySuggestion;
+use crate::import::CanonicalTransaction;
+use crate::intelligence::categorize::{CategoryClassifier, CategorySuggestion};
+use crate::intelligence::query::load_categorized_transactions_from_connection;
+
+#[derive(Debug, Clone)]
+pub(crate) struct SuggestedCategory {
+    /// The row's 1-based position in the validated rows.
+    pub(crate) source_row_index: i64,
+    pub(crate) account_key: String,
+    pub(crate) posted_at: String,
+    pub(crate) amount: f64,
+    pub(crate) description: String,
+    pub(crate) suggestion: CategorySuggestion,
+}
+
+impl SuggestedCategory {
+    pub(crate) fn to_contract(&self, applied: bool) -> ImportCategorySuggestion {
+        ImportCategorySuggestion {
+            source_row_index: self.source_row_index,
+            account_key: self.account_key.clone(),
+            posted_at: self.posted_at.clone(),
+            amount: self.amount,
+            description: self.description.clone(),
+            counterparty: self.suggestion.counterparty.clone(),
+            category: self.suggestion.category.clone(),
+            confidence: self.suggestion.confidence,
+            support: self.suggestion.support as i64,
+            basis: self.suggestion.basis.as_str().to_string(),
+            applied,
+        }
+    }
+}
+
+/// Suggests categories for incoming rows that arrived without one, learned
+/// from the effective categories already in the ledger.
+pub(crate) fn suggest_categories(
+    connection: &Connection,
+    db_path: &Path,
+    rows: &[CanonicalTransaction],
+) -> ClientResult<Vec<SuggestedCategory>> {
+    if rows.iter().all(|row| row.category.is_some()) {
+        return Ok(Vec::new());
+    }
+    let history = load_categorized_transactions_from_connection(connection, db_path)?;
+    let classifier = CategoryClassifier::learn(&history);
+    if classifier.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.category.is_none())
+        .filter_map(|(index, row)| {
+            let suggestion =
+                classifier.suggest(row.merchant.as_deref(), &row.description, row.amount)?;
+            Some(SuggestedCategory {
+                source_row_index: index as i64 + 1,
+                account_key: row.account_key.clone(),
+                posted_at: row.posted_at.clone(),
+                amount: row.amount,
+                description: row.description.clone(),
+                suggestion,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn apply_category_suggestions(
+    rows: &mut [CanonicalTransaction],
+    suggestions: &[SuggestedCategory],
+) {
+    for suggested in suggestions {
+        let index = (suggested.source_row_index - 1) as usize;
+        if let Some(row) = rows.get_mut(index) {
+            row.category = Some(suggested.suggestion.category.clone());
+        }
+    }
+}
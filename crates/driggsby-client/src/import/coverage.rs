@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use rusqlite::{Connection, params};
+
+use crate::ClientResult;
+use crate::import::CanonicalTransaction;
+use crate::state::map_sqlite_error;
+
+pub(crate) const RANGE_KIND_COVERED: &str = "covered";
+pub(crate) const RANGE_KIND_GAP: &str = "gap";
+
+/// Dates touched by one import (or the incoming batch when `import_id` is
+/// `None`) for one account and statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CoverageSpan {
+    pub(crate) account_key: String,
+    pub(crate) import_id: Option<String>,
+    pub(crate) statement_id: Option<String>,
+    pub(crate) start: String,
+    pub(crate) end: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CoverageRange {
+    pub(crate) account_key: String,
+    pub(crate) range_kind: &'static str,
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) day_count: i64,
+    pub(crate) import_count: i64,
+}
+
+pub(crate) fn existing_coverage_spans(
+    connection: &Connection,
+    db_path: &Path,
+) -> ClientResult<Vec<CoverageSpan>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT
+                s.account_key,
+                s.import_id,
+                t.statement_id,
+                MIN(t.posted_at) AS span_start,
+                MAX(t.posted_at) AS span_end
+             FROM internal_import_account_stats s
+             JOIN internal_import_runs r ON r.import_id = s.import_id
+             JOIN internal_transactions t
+               ON t.import_id = s.import_id
+              AND t.account_key = s.account_key
+             WHERE r.status IN ('committed', 'partially_reverted')
+             GROUP BY s.account_key, s.import_id, t.statement_id
+             ORDER BY s.account_key ASC, span_start ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok(CoverageSpan {
+                account_key: row.get(0)?,
+                import_id: row.get(1)?,
+                statement_id: row.get(2)?,
+                start: row.get(3)?,
+                end: row.get(4)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut spans = Vec::new();
+    for row in rows {
+        spans.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(spans)
+}
+
+pub(crate) fn incoming_coverage_spans<'a>(
+    rows: impl Iterator<Item = &'a CanonicalTransaction>,
+) -> Vec<CoverageSpan> {
+    let mut bounds: BTreeMap<(String, Option<String>), (String, String)> = BTreeMap::new();
+    for row in rows {
+        let key = (row.account_key.clone(), row.statement_id.clone());
+        let entry = bounds
+            .entry(key)
+            .or_insert_with(|| (row.posted_at.clone(), row.posted_at.clone()));
+        if row.posted_at < entry.0 {
+            entry.0 = row.posted_at.clone();
+        }
+        if row.posted_at > entry.1 {
+            entry.1 = row.posted_at.clone();
+        }
+    }
+
+    bounds
+        .into_iter()
+        .map(|((account_key, statement_id), (start, end))| CoverageSpan {
+            account_key,
+            import_id: None,
+            statement_id,
+            start,
+            end,
+        })
+        .collect()
+}
+
+/// Folds spans into continuous covered ranges and the gaps between them.
+///
+/// A new covered range starts only when at least one whole calendar month
+/// passes with no coverage, so the few days between consecutive monthly
+/// statements are not gaps.
+pub(crate) fn build_coverage_ranges(spans: &[CoverageSpan]) -> Vec<CoverageRange> {
+    let mut spans_by_account: BTreeMap<&str, Vec<&CoverageSpan>> = BTreeMap::new();
+    for span in spans {
+        spans_by_account
+            .entry(span.account_key.as_str())
+            .or_default()
+            .push(span);
+    }
+
+    let mut ranges = Vec::new();
+    for (account_key, mut account_spans) in spans_by_account {
+        account_spans.sort_by(|left, right| {
+            (&left.start, &left.end, &left.import_id).cmp(&(
+                &right.start,
+                &right.end,
+                &right.import_id,
+            ))
+        });
+        let mut covered: Vec<(String, String, BTreeSet<Option<&str>>)> = Vec::new();
+        for span in account_spans {
+            let import_id = span.import_id.as_deref();
+            match covered.last_mut() {
+                Some((_, covered_through, imports))
+                    if !starts_new_range(covered_through, &span.start) =>
+                {
+                    if span.end > *covered_through {
+                        *covered_through = span.end.clone();
+                    }
+                    imports.insert(import_id);
+                }
+                _ => covered.push((
+                    span.start.clone(),
+                    span.end.clone(),
+                    BTreeSet::from([import_id]),
+                )),
+            }
+        }
+
+        let mut previous_end: Option<String> = None;
+        for (range_start, range_end, imports) in covered {
+            if let Some(gap_after) = previous_end.as_deref()
+                && let (Some(gap_start), Some(gap_end)) =
+                    (shift_days(gap_after, 1), shift_days(&range_start, -1))
+            {
+                ranges.push(CoverageRange {
+                    account_key: account_key.to_string(),
+                    range_kind: RANGE_KIND_GAP,
+                    day_count: day_count(&gap_start, &gap_end),
+                    range_start: gap_start,
+                    range_end: gap_end,
+                    import_count: 0,
+                });
+            }
+            ranges.push(CoverageRange {
+                account_key: account_key.to_string(),
+                range_kind: RANGE_KIND_COVERED,
+                day_count: day_count(&range_start, &range_end),
+                range_start,
+                range_end: range_end.clone(),
+                import_count: imports.len() as i64,
+            });
+            previous_end = Some(range_end);
+        }
+    }
+
+    ranges
+}
+
+/// Rebuilds `internal_account_coverage_materialized` from committed imports.
+pub(crate) fn refresh_account_coverage(
+    connection: &Connection,
+    db_path: &Path,
+) -> ClientResult<()> {
+    let ranges = build_coverage_ranges(&existing_coverage_spans(connection, db_path)?);
+
+    connection
+        .execute("DELETE FROM internal_account_coverage_materialized", [])
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let mut statement = connection
+        .prepare(
+            "INSERT INTO internal_account_coverage_materialized (
+                account_key,
+                range_kind,
+                range_start,
+                range_end,
+                day_count,
+                import_count
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    for range in &ranges {
+        statement
+            .execute(params![
+                range.account_key,
+                range.range_kind,
+                range.range_start,
+                range.range_end,
+                range.day_count,
+                range.import_count,
+            ])
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+
+    Ok(())
+}
+
+fn starts_new_range(covered_through: &str, start: &str) -> bool {
+    let Some(after_next_full_month) = parse_date(covered_through)
+        .and_then(|date| date.with_day(1))
+        .and_then(|month_start| month_start.checked_add_months(Months::new(2)))
+    else {
+        return false;
+    };
+    start
+        >= after_next_full_month
+            .format("%Y-%m-%d")
+            .to_string()
+            .as_str()
+}
+
+fn shift_days(value: &str, days: i64) -> Option<String> {
+    let date = parse_date(value)?;
+    let shifted = if days >= 0 {
+        date.checked_add_days(Days::new(days.unsigned_abs()))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    }?;
+    Some(shifted.format("%Y-%m-%d").to_string())
+}
+
+fn day_count(start: &str, end: &str) -> i64 {
+    match (parse_date(start), parse_date(end)) {
+        (Some(start), Some(end)) => (end - start).num_days() + 1,
+        _ => 0,
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CoverageSpan, RANGE_KIND_COVERED, RANGE_KIND_GAP, build_coverage_ranges};
+
+    fn span(import_id: &str, start: &str, end: &str) -> CoverageSpan {
+        CoverageSpan {
+            account_key: "acct_1".to_string(),
+            import_id: Some(import_id.to_string()),
+            statement_id: None,
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn consecutive_monthly_imports_form_one_covered_range() {
+        let ranges = build_coverage_ranges(&[
+            span("imp_1", "2026-01-03", "2026-01-29"),
+            span("imp_2", "2026-02-02", "2026-02-27"),
+            span("imp_3", "2026-03-01", "2026-03-30"),
+        ]);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].range_kind, RANGE_KIND_COVERED);
+        assert_eq!(ranges[0].range_start, "2026-01-03");
+        assert_eq!(ranges[0].range_end, "2026-03-30");
+        assert_eq!(ranges[0].import_count, 3);
+    }
+
+    #[test]
+    fn a_missing_calendar_month_splits_ranges_with_a_gap() {
+        let ranges = build_coverage_ranges(&[
+            span("imp_1", "2026-01-03", "2026-01-29"),
+            span("imp_3", "2026-03-02", "2026-03-30"),
+        ]);
+        let kinds = ranges
+            .iter()
+            .map(|range| {
+                (
+                    range.range_kind,
+                    range.range_start.as_str(),
+                    range.range_end.as_str(),
+                    range.day_count,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (RANGE_KIND_COVERED, "2026-01-03", "2026-01-29", 27),
+                (RANGE_KIND_GAP, "2026-01-30", "2026-03-01", 31),
+                (RANGE_KIND_COVERED, "2026-03-02", "2026-03-30", 29),
+            ]
+        );
+    }
+
+    #[test]
+    fn statements_of_one_import_can_leave_a_gap() {
+        let ranges = build_coverage_ranges(&[
+            span("imp_1", "2026-01-03", "2026-01-29"),
+            span("imp_1", "2026-04-01", "2026-04-20"),
+            span("imp_2", "2026-04-10", "2026-05-02"),
+        ]);
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[1].range_kind, RANGE_KIND_GAP);
+        assert_eq!(ranges[2].range_start, "2026-04-01");
+        assert_eq!(ranges[2].range_end, "2026-05-02");
+        assert_eq!(ranges[2].import_count, 2);
+    }
+}
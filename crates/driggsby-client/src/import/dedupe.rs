@@ -1,13 +1,202 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
-use rusqlite::{Connection, OptionalExtension, params};
+use chrono::NaiveDate;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
+use serde::{Deserialize, Serialize};
 
 use crate::ClientResult;
-use crate::contracts::types::ImportIssue;
+use crate::contracts::types::{ImportDedupePolicy, ImportIssue};
 use crate::import::CanonicalTransaction;
+use crate::import::manual::NOT_MANUAL_IMPORT;
 use crate::state::map_sqlite_error;
 
+/// Row fields a fallback dedupe key can be built from. `account_key` is
+/// always part of the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DedupeKeyField {
+    PostedAt,
+    Amount,
+    Currency,
+    Description,
+    StatementId,
+    ExternalId,
+    Merchant,
+}
+
+impl DedupeKeyField {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::PostedAt => "posted_at",
+            Self::Amount => "amount",
+            Self::Currency => "currency",
+            Self::Description => "description",
+            Self::StatementId => "statement_id",
+            Self::ExternalId => "external_id",
+            Self::Merchant => "merchant",
+        }
+    }
+
+    fn key_value(&self, row: &CanonicalTransaction) -> String {
+        match self {
+            Self::PostedAt => row.posted_at.clone(),
+            Self::Amount => row.amount.to_string(),
+            Self::Currency => row.currency.clone(),
+            Self::Description => row.description.clone(),
+            Self::StatementId => row.statement_id.clone().unwrap_or_default(),
+            Self::ExternalId => row.external_id.clone().unwrap_or_default(),
+            Self::Merchant => row.merchant.clone().unwrap_or_default(),
+        }
+    }
+
+    fn sql_value(&self, row: &CanonicalTransaction) -> SqlValue {
+        let optional = |value: &Option<String>| match value {
+            Some(value) => SqlValue::Text(value.clone()),
+            None => SqlValue::Null,
+        };
+        match self {
+            Self::PostedAt => SqlValue::Text(row.posted_at.clone()),
+            Self::Amount => SqlValue::Real(row.amount),
+            Self::Currency => SqlValue::Text(row.currency.clone()),
+            Self::Description => SqlValue::Text(row.description.clone()),
+            Self::StatementId => optional(&row.statement_id),
+            Self::ExternalId => optional(&row.external_id),
+            Self::Merchant => optional(&row.merchant),
+        }
+    }
+}
+
+/// How rows of one account are matched against each other and the ledger.
+/// The default reproduces the built-in behavior: a present `external_id`
+/// identifies the row on its own, otherwise posted_at, amount, currency and
+/// description must all match exactly.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct DedupePolicy {
+    #[serde(default = "default_key_fields")]
+    pub(crate) key_fields: Vec<DedupeKeyField>,
+    #[serde(default = "default_trust_external_id")]
+    pub(crate) trust_external_id: bool,
+    #[serde(default)]
+    pub(crate) date_tolerance_days: i64,
+}
+
+impl Default for DedupePolicy {
+    fn default() -> Self {
+        Self {
+            key_fields: default_key_fields(),
+            trust_external_id: default_trust_external_id(),
+            date_tolerance_days: 0,
+        }
+    }
+}
+
+fn default_key_fields() -> Vec<DedupeKeyField> {
+    vec![
+        DedupeKeyField::PostedAt,
+        DedupeKeyField::Amount,
+        DedupeKeyField::Currency,
+        DedupeKeyField::Description,
+    ]
+}
+
+fn default_trust_external_id() -> bool {
+    true
+}
+
+impl DedupePolicy {
+    fn uses_external_id(&self, row: &CanonicalTransaction) -> bool {
+        self.trust_external_id && row.external_id.is_some()
+    }
+
+    /// With a date tolerance, `posted_at` leaves the key and is compared by
+    /// distance instead.
+    fn compares_dates_by_distance(&self) -> bool {
+        self.date_tolerance_days > 0 && self.key_fields.contains(&DedupeKeyField::PostedAt)
+    }
+
+    fn dates_within_tolerance(&self, left: &str, right: &str) -> bool {
+        if !self.compares_dates_by_distance() {
+            return true;
+        }
+        match (
+            NaiveDate::parse_from_str(left, "%Y-%m-%d"),
+            NaiveDate::parse_from_str(right, "%Y-%m-%d"),
+        ) {
+            (Ok(left), Ok(right)) => (left - right).num_days().abs() <= self.date_tolerance_days,
+            _ => left == right,
+        }
+    }
+}
+
+/// Dedupe policies keyed by `account_key`; accounts without an entry use
+/// [`DedupePolicy::default`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DedupePolicies {
+    by_account: BTreeMap<String, DedupePolicy>,
+    default_policy: DedupePolicy,
+}
+
+impl DedupePolicies {
+    pub(crate) fn new(by_account: BTreeMap<String, DedupePolicy>) -> Self {
+        Self {
+            by_account,
+            default_policy: DedupePolicy::default(),
+        }
+    }
+
+    /// The per-account policies as JSON, as stored with each import run.
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string(&self.by_account).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    pub(crate) fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json).map(Self::new)
+    }
+
+    pub(crate) fn for_account(&self, account_key: &str) -> &DedupePolicy {
+        self.by_account
+            .get(account_key)
+            .unwrap_or(&self.default_policy)
+    }
+
+    /// The policy applied to each account in `rows`, in account order.
+    pub(crate) fn report<'a>(
+        &self,
+        rows: impl IntoIterator<Item = &'a CanonicalTransaction>,
+    ) -> Vec<ImportDedupePolicy> {
+        let mut account_keys = rows
+            .into_iter()
+            .map(|row| row.account_key.as_str())
+            .collect::<Vec<_>>();
+        account_keys.sort_unstable();
+        account_keys.dedup();
+        account_keys
+            .into_iter()
+            .map(|account_key| {
+                let policy = self.for_account(account_key);
+                ImportDedupePolicy {
+                    account_key: account_key.to_string(),
+                    key_fields: policy
+                        .key_fields
+                        .iter()
+                        .map(|field| field.as_str().to_string())
+                        .collect(),
+                    trust_external_id: policy.trust_external_id,
+                    date_tolerance_days: policy.date_tolerance_days,
+                    source: if self.by_account.contains_key(account_key) {
+                        "import_policy".to_string()
+                    } else {
+                        "default".to_string()
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct BatchRow {
     pub(crate) row: CanonicalTransaction,
@@ -57,17 +246,21 @@ pub(crate) struct LedgerMatch {
     pub(crate) import_id: String,
 }
 
-pub(crate) fn dedupe_batch(rows: Vec<CanonicalTransaction>) -> BatchDedupeResult {
+pub(crate) fn dedupe_batch(
+    rows: Vec<CanonicalTransaction>,
+    policies: &DedupePolicies,
+) -> BatchDedupeResult {
     let mut ext_seen: HashMap<String, i64> = HashMap::new();
-    let mut fallback_seen: HashMap<String, Vec<(String, i64)>> = HashMap::new();
+    let mut fallback_seen: HashMap<String, Vec<(String, String, i64)>> = HashMap::new();
     let mut candidate_rows = Vec::new();
     let mut duplicate_rows = Vec::new();
 
     for (index, row) in rows.into_iter().enumerate() {
         let source_row_index = (index as i64) + 1;
-        let key = dedupe_key(&row);
+        let policy = policies.for_account(&row.account_key);
+        let key = dedupe_key(&row, policy);
 
-        if row.external_id.is_some() {
+        if policy.uses_external_id(&row) {
             if let Some(matched_batch_row_index) = ext_seen.get(&key) {
                 duplicate_rows.push(DuplicateRecord {
                     row,
@@ -91,8 +284,11 @@ pub(crate) fn dedupe_batch(rows: Vec<CanonicalTransaction>) -> BatchDedupeResult
         let seen_entries = fallback_seen.entry(key).or_default();
         let matched_batch_row_index = seen_entries
             .iter()
-            .find(|(scope_id, _)| scope_id != &row.dedupe_scope_id)
-            .map(|(_, matched_index)| *matched_index);
+            .find(|(scope_id, posted_at, _)| {
+                scope_id != &row.dedupe_scope_id
+                    && policy.dates_within_tolerance(posted_at, &row.posted_at)
+            })
+            .map(|(_, _, matched_index)| *matched_index);
 
         if let Some(matched_index) = matched_batch_row_index {
             duplicate_rows.push(DuplicateRecord {
@@ -106,7 +302,11 @@ pub(crate) fn dedupe_batch(rows: Vec<CanonicalTransaction>) -> BatchDedupeResult
             continue;
         }
 
-        seen_entries.push((row.dedupe_scope_id.clone(), source_row_index));
+        seen_entries.push((
+            row.dedupe_scope_id.clone(),
+            row.posted_at.clone(),
+            source_row_index,
+        ));
         candidate_rows.push(BatchRow {
             row,
             source_row_index,
@@ -123,12 +323,14 @@ pub(crate) fn dedupe_against_existing(
     connection: &Connection,
     rows: &[BatchRow],
     db_path: &Path,
+    policies: &DedupePolicies,
 ) -> ClientResult<ExistingDedupeResult> {
     let mut insertable_rows = Vec::new();
     let mut duplicate_rows = Vec::new();
 
     for row in rows {
-        if let Some(existing) = find_existing_match(connection, &row.row, db_path)? {
+        let policy = policies.for_account(&row.row.account_key);
+        if let Some(existing) = find_existing_match(connection, &row.row, db_path, policy)? {
             duplicate_rows.push(DuplicateRecord {
                 row: row.row.clone(),
                 source_row_index: row.source_row_index,
@@ -148,23 +350,33 @@ pub(crate) fn dedupe_against_existing(
     })
 }
 
-pub(crate) fn dedupe_key(row: &CanonicalTransaction) -> String {
-    if let Some(external_id) = row.external_id.as_ref() {
+pub(crate) fn dedupe_key(row: &CanonicalTransaction, policy: &DedupePolicy) -> String {
+    if policy.uses_external_id(row)
+        && let Some(external_id) = row.external_id.as_ref()
+    {
         return format!("ext|{}|{}", row.account_key, external_id);
     }
 
-    format!(
-        "fallback|{}|{}|{}|{}|{}",
-        row.account_key, row.posted_at, row.amount, row.currency, row.description
-    )
+    let mut key = format!("fallback|{}", row.account_key);
+    for field in &policy.key_fields {
+        if *field == DedupeKeyField::PostedAt && policy.compares_dates_by_distance() {
+            continue;
+        }
+        key.push('|');
+        key.push_str(&field.key_value(row));
+    }
+    key
 }
 
 pub(crate) fn find_existing_match(
     connection: &Connection,
     row: &CanonicalTransaction,
     db_path: &Path,
+    policy: &DedupePolicy,
 ) -> ClientResult<Option<LedgerMatch>> {
-    if let Some(external_id) = row.external_id.as_ref() {
+    if policy.uses_external_id(row)
+        && let Some(external_id) = row.external_id.as_ref()
+    {
         return connection
             .query_row(
                 "SELECT txn_id, import_id
@@ -185,33 +397,49 @@ pub(crate) fn find_existing_match(
             .map_err(|error| map_sqlite_error(db_path, &error));
     }
 
-    connection
-        .query_row(
-            "SELECT txn_id, import_id
+    // Columns come from the fixed `DedupeKeyField` set; only values are bound.
+    let mut sql = format!(
+        "SELECT txn_id, import_id
              FROM internal_transactions
              WHERE account_key = ?1
-               AND posted_at = ?2
-               AND amount = ?3
-               AND currency = ?4
-               AND description = ?5
-               AND dedupe_scope_id != ?6
-             ORDER BY txn_id ASC
-             LIMIT 1",
-            params![
-                &row.account_key,
-                &row.posted_at,
-                row.amount,
-                &row.currency,
-                &row.description,
-                &row.dedupe_scope_id
-            ],
-            |result| {
-                Ok(LedgerMatch {
-                    txn_id: result.get(0)?,
-                    import_id: result.get(1)?,
-                })
-            },
-        )
+               AND dedupe_scope_id != ?2
+               AND {NOT_MANUAL_IMPORT}"
+    );
+    let mut values = vec![
+        SqlValue::Text(row.account_key.clone()),
+        SqlValue::Text(row.dedupe_scope_id.clone()),
+    ];
+    let mut order_by = "txn_id ASC".to_string();
+    for field in &policy.key_fields {
+        values.push(field.sql_value(row));
+        let placeholder = values.len();
+        let column = field.as_str();
+        match field {
+            DedupeKeyField::PostedAt if policy.compares_dates_by_distance() => {
+                let distance = format!("ABS(julianday(posted_at) - julianday(?{placeholder}))");
+                sql.push_str(&format!(
+                    "\n               AND {distance} <= {}",
+                    policy.date_tolerance_days
+                ));
+                order_by = format!("{distance} ASC, txn_id ASC");
+            }
+            DedupeKeyField::StatementId | DedupeKeyField::ExternalId | DedupeKeyField::Merchant => {
+                sql.push_str(&format!("\n               AND {column} IS ?{placeholder}"));
+            }
+            _ => sql.push_str(&format!("\n               AND {column} = ?{placeholder}")),
+        }
+    }
+    sql.push_str(&format!(
+        "\n             ORDER BY {order_by}\n             LIMIT 1"
+    ));
+
+    connection
+        .query_row(&sql, params_from_iter(values), |result| {
+            Ok(LedgerMatch {
+                txn_id: result.get(0)?,
+                import_id: result.get(1)?,
+            })
+        })
         .optional()
         .map_err(|error| map_sqlite_error(db_path, &error))
 }
@@ -1,21 +1,31 @@
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet};
 
-use crate::contracts::types::{ImportDriftWarning, ImportKeyInventory};
+use crate::contracts::types::{
+    ImportDriftSuggestion, ImportDriftWarning, ImportKeyInventory, ImportPropertyInventory,
+    ImportValueCount,
+};
+use crate::import::coverage::{CoverageSpan, RANGE_KIND_GAP, build_coverage_ranges};
 use crate::import::inventory::IncomingUniqueValues;
 use crate::import::sign_profiles::SignCounts;
+use crate::intelligence::normalize::label_similarity;
 
 const SEVERITY_HIGH: &str = "high";
 const SEVERITY_MEDIUM: &str = "medium";
 const SIGN_DRIFT_THRESHOLD: f64 = 0.40;
+const SIGN_MIRROR_TOLERANCE: f64 = 0.10;
 const MIN_EXISTING_SIGN_SAMPLE: i64 = 20;
 const MIN_INCOMING_SIGN_SAMPLE: i64 = 5;
+const MIN_LABEL_SIMILARITY: f64 = 0.6;
+const MAX_LABEL_SUGGESTIONS: usize = 3;
 
 pub(crate) fn build_drift_warnings(
     key_inventory: &ImportKeyInventory,
     incoming_values: &IncomingUniqueValues,
     existing_sign_counts: &BTreeMap<String, SignCounts>,
     incoming_sign_counts: &BTreeMap<String, SignCounts>,
+    existing_spans: &[CoverageSpan],
+    incoming_spans: &[CoverageSpan],
 ) -> Vec<ImportDriftWarning> {
     let mut warnings = Vec::new();
 
@@ -38,23 +48,19 @@ pub(crate) fn build_drift_warnings(
     }
 
     if key_inventory.merchant.unique_count > 0 {
-        warnings.extend(unseen_value_warnings(
-            "merchant",
+        warnings.extend(unseen_label_warnings(
             "merchant_unseen",
             "Incoming merchant was not found in existing ledger history.",
-            SEVERITY_MEDIUM,
-            &key_inventory.merchant.existing_values,
+            &key_inventory.merchant,
             &incoming_values.merchant,
         ));
     }
 
     if key_inventory.category.unique_count > 0 {
-        warnings.extend(unseen_value_warnings(
-            "category",
+        warnings.extend(unseen_label_warnings(
             "category_unseen",
             "Incoming category was not found in existing ledger history.",
-            SEVERITY_MEDIUM,
-            &key_inventory.category.existing_values,
+            &key_inventory.category,
             &incoming_values.category,
         ));
     }
@@ -63,6 +69,8 @@ pub(crate) fn build_drift_warnings(
         existing_sign_counts,
         incoming_sign_counts,
     ));
+    warnings.extend(statement_overlap_warnings(existing_spans, incoming_spans));
+    warnings.extend(coverage_gap_warnings(existing_spans, incoming_spans));
 
     warnings.sort_by(compare_warnings);
     warnings
@@ -90,6 +98,7 @@ fn account_key_warnings(
             incoming_value: incoming_value.clone(),
             message: "Incoming account_key was not found in existing ledger history.".to_string(),
             suggestions: Vec::new(),
+            ranked_suggestions: None,
         });
 
         let suggestions = nearest_account_key_suggestions(incoming_value, existing_values);
@@ -103,6 +112,7 @@ fn account_key_warnings(
                     "Incoming account_key `{incoming_value}` is close to an existing account key."
                 ),
                 suggestions,
+                ranked_suggestions: None,
             });
         }
     }
@@ -133,10 +143,41 @@ fn unseen_value_warnings(
             incoming_value: incoming_value.clone(),
             message: message.to_string(),
             suggestions: Vec::new(),
+            ranked_suggestions: None,
         })
         .collect()
 }
 
+/// Unseen merchant or category warnings, each carrying the existing values
+/// whose label tokens are close enough to be the same counterparty or bucket.
+fn unseen_label_warnings(
+    code: &str,
+    message: &str,
+    inventory: &ImportPropertyInventory,
+    incoming_values: &BTreeSet<String>,
+) -> Vec<ImportDriftWarning> {
+    let mut warnings = unseen_value_warnings(
+        &inventory.property,
+        code,
+        message,
+        SEVERITY_MEDIUM,
+        &inventory.existing_values,
+        incoming_values,
+    );
+    for warning in &mut warnings {
+        let ranked = nearest_label_suggestions(&warning.incoming_value, &inventory.value_counts);
+        if ranked.is_empty() {
+            continue;
+        }
+        warning.suggestions = ranked
+            .iter()
+            .map(|suggestion| suggestion.value.clone())
+            .collect();
+        warning.ranked_suggestions = Some(ranked);
+    }
+    warnings
+}
+
 fn sign_profile_anomaly_warnings(
     existing_sign_counts: &BTreeMap<String, SignCounts>,
     incoming_sign_counts: &BTreeMap<String, SignCounts>,
@@ -148,9 +189,7 @@ fn sign_profile_anomaly_warnings(
             continue;
         };
 
-        if existing_counts.total_count() < MIN_EXISTING_SIGN_SAMPLE
-            || incoming_counts.total_count() < MIN_INCOMING_SIGN_SAMPLE
-        {
+        if !meets_sign_sample_gates(existing_counts, incoming_counts) {
             continue;
         }
 
@@ -174,12 +213,133 @@ fn sign_profile_anomaly_warnings(
                 incoming_ratio * 100.0
             ),
             suggestions: Vec::new(),
+            ranked_suggestions: None,
         });
+
+        if is_mirrored_sign_profile(existing_counts, incoming_counts) {
+            warnings.push(ImportDriftWarning {
+                code: "account_sign_profile_inverted".to_string(),
+                severity: SEVERITY_HIGH.to_string(),
+                property: "account_key".to_string(),
+                incoming_value: account_key.clone(),
+                message: format!(
+                    "Incoming amounts for `{account_key}` look like a sign-inverted mirror of history (historical {:.2}, incoming {:.2}). Rerun with `--auto-fix-signs` to invert signs for this account.",
+                    historical_ratio * 100.0,
+                    incoming_ratio * 100.0
+                ),
+                suggestions: Vec::new(),
+                ranked_suggestions: None,
+            });
+        }
     }
 
     warnings
 }
 
+fn statement_overlap_warnings(
+    existing_spans: &[CoverageSpan],
+    incoming_spans: &[CoverageSpan],
+) -> Vec<ImportDriftWarning> {
+    let mut warnings = Vec::new();
+
+    for incoming in incoming_spans {
+        let Some(incoming_statement) = incoming.statement_id.as_deref() else {
+            continue;
+        };
+        let overlapping = existing_spans
+            .iter()
+            .filter(|existing| {
+                existing.account_key == incoming.account_key
+                    && existing
+                        .statement_id
+                        .as_deref()
+                        .is_some_and(|statement_id| statement_id != incoming_statement)
+                    && incoming.start < existing.end
+                    && incoming.end > existing.start
+            })
+            .collect::<Vec<&CoverageSpan>>();
+        if overlapping.is_empty() {
+            continue;
+        }
+
+        warnings.push(ImportDriftWarning {
+            code: "statement_overlap".to_string(),
+            severity: SEVERITY_HIGH.to_string(),
+            property: "statement_id".to_string(),
+            incoming_value: incoming_statement.to_string(),
+            message: format!(
+                "Incoming statement `{incoming_statement}` for `{}` ({} to {}) overlaps dates already covered by {} existing statement(s); rows may be double counted.",
+                incoming.account_key,
+                incoming.start,
+                incoming.end,
+                overlapping.len()
+            ),
+            suggestions: overlapping
+                .iter()
+                .filter_map(|existing| existing.statement_id.clone())
+                .collect(),
+            ranked_suggestions: None,
+        });
+    }
+
+    warnings
+}
+
+fn coverage_gap_warnings(
+    existing_spans: &[CoverageSpan],
+    incoming_spans: &[CoverageSpan],
+) -> Vec<ImportDriftWarning> {
+    let incoming_accounts = incoming_spans
+        .iter()
+        .map(|span| span.account_key.as_str())
+        .collect::<BTreeSet<&str>>();
+    let combined_spans = existing_spans
+        .iter()
+        .filter(|span| incoming_accounts.contains(span.account_key.as_str()))
+        .chain(incoming_spans.iter())
+        .cloned()
+        .collect::<Vec<CoverageSpan>>();
+
+    build_coverage_ranges(&combined_spans)
+        .into_iter()
+        .filter(|range| range.range_kind == RANGE_KIND_GAP)
+        .map(|gap| ImportDriftWarning {
+            code: "coverage_gap".to_string(),
+            severity: SEVERITY_MEDIUM.to_string(),
+            property: "account_key".to_string(),
+            message: format!(
+                "`{}` has no transactions from {} to {} ({} days) once this import is applied.",
+                gap.account_key, gap.range_start, gap.range_end, gap.day_count
+            ),
+            incoming_value: gap.account_key,
+            suggestions: Vec::new(),
+            ranked_suggestions: None,
+        })
+        .collect()
+}
+
+/// Returns true when the incoming batch for an account drifts past the sign
+/// threshold and its negative ratio lands within tolerance of the historical
+/// positive ratio, which is the signature of an export with flipped signs.
+pub(crate) fn is_mirrored_sign_profile(existing: &SignCounts, incoming: &SignCounts) -> bool {
+    if !meets_sign_sample_gates(existing, incoming) {
+        return false;
+    }
+
+    let historical_ratio = existing.negative_ratio();
+    let incoming_ratio = incoming.negative_ratio();
+    if (historical_ratio - incoming_ratio).abs() < SIGN_DRIFT_THRESHOLD {
+        return false;
+    }
+
+    (incoming_ratio - (1.0 - historical_ratio)).abs() <= SIGN_MIRROR_TOLERANCE
+}
+
+fn meets_sign_sample_gates(existing: &SignCounts, incoming: &SignCounts) -> bool {
+    existing.total_count() >= MIN_EXISTING_SIGN_SAMPLE
+        && incoming.total_count() >= MIN_INCOMING_SIGN_SAMPLE
+}
+
 fn nearest_account_key_suggestions(
     incoming_value: &str,
     existing_values: &[String],
@@ -200,6 +360,37 @@ fn nearest_account_key_suggestions(
     ranked.into_iter().take(3).map(|(_, value)| value).collect()
 }
 
+/// Ranks existing values by label similarity, then by how many ledger rows
+/// already use them so the established spelling wins ties.
+fn nearest_label_suggestions(
+    incoming_value: &str,
+    existing_counts: &[ImportValueCount],
+) -> Vec<ImportDriftSuggestion> {
+    let mut ranked = existing_counts
+        .iter()
+        .map(|existing| (label_similarity(incoming_value, &existing.value), existing))
+        .filter(|(score, _)| *score >= MIN_LABEL_SIMILARITY)
+        .collect::<Vec<(f64, &ImportValueCount)>>();
+
+    ranked.sort_by(|left, right| {
+        right
+            .0
+            .total_cmp(&left.0)
+            .then_with(|| right.1.count.cmp(&left.1.count))
+            .then_with(|| left.1.value.cmp(&right.1.value))
+    });
+
+    ranked
+        .into_iter()
+        .take(MAX_LABEL_SUGGESTIONS)
+        .map(|(score, existing)| ImportDriftSuggestion {
+            value: existing.value.clone(),
+            score: (score * 100.0).round() / 100.0,
+            existing_count: existing.count,
+        })
+        .collect()
+}
+
 fn compare_warnings(left: &ImportDriftWarning, right: &ImportDriftWarning) -> Ordering {
     severity_rank(&left.severity)
         .cmp(&severity_rank(&right.severity))
@@ -252,11 +443,25 @@ fn levenshtein_distance(left: &str, right: &str) -> usize {
 mod tests {
     use std::collections::{BTreeMap, BTreeSet};
 
-    use crate::contracts::types::{ImportKeyInventory, ImportPropertyInventory};
+    use crate::contracts::types::{ImportKeyInventory, ImportPropertyInventory, ImportValueCount};
+    use crate::import::coverage::CoverageSpan;
     use crate::import::inventory::IncomingUniqueValues;
     use crate::import::sign_profiles::SignCounts;
 
-    use super::build_drift_warnings;
+    use super::{
+        build_drift_warnings, coverage_gap_warnings, is_mirrored_sign_profile,
+        statement_overlap_warnings, unseen_label_warnings,
+    };
+
+    fn span(import_id: Option<&str>, statement_id: &str, start: &str, end: &str) -> CoverageSpan {
+        CoverageSpan {
+            account_key: "chase_checking_1234".to_string(),
+            import_id: import_id.map(str::to_string),
+            statement_id: Some(statement_id.to_string()),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
 
     #[test]
     fn warning_engine_captures_unseen_values_typo_and_sign_drift() {
@@ -268,6 +473,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 25,
+                aliases: None,
             },
             account_type: ImportPropertyInventory {
                 property: "account_type".to_string(),
@@ -276,6 +482,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 25,
+                aliases: None,
             },
             currency: ImportPropertyInventory {
                 property: "currency".to_string(),
@@ -284,6 +491,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 25,
+                aliases: None,
             },
             merchant: ImportPropertyInventory {
                 property: "merchant".to_string(),
@@ -292,6 +500,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 25,
+                aliases: None,
             },
             category: ImportPropertyInventory {
                 property: "category".to_string(),
@@ -300,6 +509,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 25,
+                aliases: None,
             },
         };
 
@@ -334,6 +544,8 @@ mod tests {
             &incoming_values,
             &existing_sign_counts,
             &incoming_sign_counts,
+            &[],
+            &[],
         );
 
         let codes = warnings
@@ -371,6 +583,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 20,
+                aliases: None,
             },
             account_type: ImportPropertyInventory {
                 property: "account_type".to_string(),
@@ -379,6 +592,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 20,
+                aliases: None,
             },
             currency: ImportPropertyInventory {
                 property: "currency".to_string(),
@@ -387,6 +601,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 20,
+                aliases: None,
             },
             merchant: ImportPropertyInventory {
                 property: "merchant".to_string(),
@@ -395,6 +610,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 20,
+                aliases: None,
             },
             category: ImportPropertyInventory {
                 property: "category".to_string(),
@@ -403,6 +619,7 @@ mod tests {
                 unique_count: 1,
                 null_count: 0,
                 total_rows: 20,
+                aliases: None,
             },
         };
         let incoming_values = IncomingUniqueValues {
@@ -433,6 +650,8 @@ mod tests {
             &incoming_values,
             &existing_sign_counts,
             &below_threshold,
+            &[],
+            &[],
         );
         assert!(
             !warnings
@@ -452,6 +671,8 @@ mod tests {
             &incoming_values,
             &existing_sign_counts,
             &boundary_threshold,
+            &[],
+            &[],
         );
         assert!(
             warnings
@@ -471,6 +692,8 @@ mod tests {
             &incoming_values,
             &existing_sign_counts,
             &incoming_too_small,
+            &[],
+            &[],
         );
         assert!(
             !warnings
@@ -489,6 +712,7 @@ mod tests {
                 unique_count: 0,
                 null_count: 0,
                 total_rows: 0,
+                aliases: None,
             },
             account_type: ImportPropertyInventory {
                 property: "account_type".to_string(),
@@ -497,6 +721,7 @@ mod tests {
                 unique_count: 0,
                 null_count: 0,
                 total_rows: 0,
+                aliases: None,
             },
             currency: ImportPropertyInventory {
                 property: "currency".to_string(),
@@ -505,6 +730,7 @@ mod tests {
                 unique_count: 0,
                 null_count: 0,
                 total_rows: 0,
+                aliases: None,
             },
             merchant: ImportPropertyInventory {
                 property: "merchant".to_string(),
@@ -513,6 +739,7 @@ mod tests {
                 unique_count: 0,
                 null_count: 0,
                 total_rows: 0,
+                aliases: None,
             },
             category: ImportPropertyInventory {
                 property: "category".to_string(),
@@ -521,6 +748,7 @@ mod tests {
                 unique_count: 0,
                 null_count: 0,
                 total_rows: 0,
+                aliases: None,
             },
         };
         let incoming_values = IncomingUniqueValues {
@@ -536,8 +764,115 @@ mod tests {
             &incoming_values,
             &BTreeMap::new(),
             &BTreeMap::new(),
+            &[],
+            &[],
         );
 
         assert!(warnings.is_empty());
     }
+
+    #[test]
+    fn mirrored_sign_profile_requires_drift_close_to_inverse_ratio() {
+        let existing = SignCounts {
+            negative_count: 22,
+            positive_count: 3,
+        };
+        let mirrored = SignCounts {
+            negative_count: 1,
+            positive_count: 5,
+        };
+        let drifted_only = SignCounts {
+            negative_count: 3,
+            positive_count: 3,
+        };
+        let too_small = SignCounts {
+            negative_count: 0,
+            positive_count: 4,
+        };
+
+        assert!(is_mirrored_sign_profile(&existing, &mirrored));
+        assert!(!is_mirrored_sign_profile(&existing, &drifted_only));
+        assert!(!is_mirrored_sign_profile(&existing, &too_small));
+    }
+
+    #[test]
+    fn coverage_warnings_flag_overlaps_and_skipped_months() {
+        let existing = vec![
+            span(Some("imp_1"), "stmt_jan", "2026-01-02", "2026-01-30"),
+            span(Some("imp_2"), "stmt_feb", "2026-02-01", "2026-02-27"),
+        ];
+
+        let overlapping = vec![span(None, "stmt_feb_alt", "2026-02-10", "2026-03-09")];
+        let overlaps = statement_overlap_warnings(&existing, &overlapping);
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].code, "statement_overlap");
+        assert_eq!(overlaps[0].incoming_value, "stmt_feb_alt");
+        assert_eq!(overlaps[0].suggestions, vec!["stmt_feb".to_string()]);
+        assert!(coverage_gap_warnings(&existing, &overlapping).is_empty());
+
+        let skipped_march = vec![span(None, "stmt_apr", "2026-04-01", "2026-04-29")];
+        assert!(statement_overlap_warnings(&existing, &skipped_march).is_empty());
+        let gaps = coverage_gap_warnings(&existing, &skipped_march);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].code, "coverage_gap");
+        assert!(
+            gaps[0]
+                .message
+                .contains("from 2026-02-28 to 2026-03-31 (32 days)")
+        );
+    }
+
+    #[test]
+    fn unseen_merchants_rank_similar_existing_values() {
+        let inventory = ImportPropertyInventory {
+            property: "merchant".to_string(),
+            existing_values: vec![
+                "WHOLE FOODS MKT".to_string(),
+                "Whole Foods Market".to_string(),
+                "Wholesale Club".to_string(),
+            ],
+            value_counts: vec![
+                ImportValueCount {
+                    value: "WHOLE FOODS MKT".to_string(),
+                    count: 12,
+                },
+                ImportValueCount {
+                    value: "Whole Foods Market".to_string(),
+                    count: 3,
+                },
+                ImportValueCount {
+                    value: "Wholesale Club".to_string(),
+                    count: 7,
+                },
+            ],
+            unique_count: 3,
+            null_count: 0,
+            total_rows: 22,
+            aliases: None,
+        };
+        let incoming = BTreeSet::from(["Netflix".to_string(), "Whole Foods #1043".to_string()]);
+
+        let warnings = unseen_label_warnings(
+            "merchant_unseen",
+            "Incoming merchant was not found in existing ledger history.",
+            &inventory,
+            &incoming,
+        );
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].suggestions.is_empty());
+        assert!(warnings[0].ranked_suggestions.is_none());
+
+        assert_eq!(warnings[1].incoming_value, "Whole Foods #1043");
+        assert_eq!(
+            warnings[1].suggestions,
+            vec![
+                "WHOLE FOODS MKT".to_string(),
+                "Whole Foods Market".to_string()
+            ]
+        );
+        let ranked = warnings[1].ranked_suggestions.clone().unwrap_or_default();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].score, 0.8);
+        assert_eq!(ranked[0].existing_count, 12);
+    }
 }
@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, params};
+use sha2::{Digest, Sha256};
+
+use crate::ClientResult;
+use crate::import::parse::ParsedSource;
+use crate::state::map_sqlite_error;
+
+/// A prior import run that a new import may short-circuit to.
+#[derive(Debug, Clone)]
+pub(crate) struct PriorImport {
+    pub(crate) import_id: String,
+    pub(crate) status: String,
+    pub(crate) source_hash: Option<String>,
+    pub(crate) rows_read: i64,
+    pub(crate) rows_valid: i64,
+    pub(crate) rows_invalid: i64,
+    pub(crate) inserted: i64,
+    pub(crate) batch_duplicates: i64,
+    pub(crate) existing_ledger_duplicates: i64,
+}
+
+/// Hex SHA-256 of the parsed rows and statement headers, before any
+/// remapping. Values are trimmed and blank values count as missing, so the
+/// same rows hash the same whatever the file's line endings, trailing
+/// newlines, JSON whitespace or key order.
+pub(crate) fn source_hash(source: &ParsedSource) -> String {
+    let mut hasher = Sha256::new();
+    for row in &source.rows {
+        hash_fields(
+            &mut hasher,
+            b'r',
+            &[
+                &row.statement_id,
+                &row.account_key,
+                &row.account_type,
+                &row.posted_at,
+                &row.amount,
+                &row.currency,
+                &row.description,
+                &row.external_id,
+                &row.merchant,
+                &row.category,
+                &row.balance,
+                &row.mcc,
+                &row.statement_opening_balance,
+                &row.statement_closing_balance,
+            ],
+        );
+    }
+    for header in &source.statement_headers {
+        hash_fields(
+            &mut hasher,
+            b's',
+            &[
+                &header.statement_id,
+                &header.account_key,
+                &header.opening_balance,
+                &header.closing_balance,
+            ],
+        );
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Feeds one record to `hasher`, length-prefixing each value so adjacent
+/// fields cannot run into each other.
+fn hash_fields(hasher: &mut Sha256, kind: u8, fields: &[&Option<String>]) {
+    hasher.update([kind]);
+    for field in fields {
+        match field
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            Some(value) => {
+                hasher.update([1]);
+                hasher.update((value.len() as u64).to_le_bytes());
+                hasher.update(value.as_bytes());
+            }
+            None => hasher.update([0]),
+        }
+    }
+}
+
+/// The most recent committed import of the same source rows. Imports that
+/// were undone, fully or for some accounts or statements, are ignored so the
+/// source can be imported again to restore what was taken out.
+pub(crate) fn find_committed_by_hash(
+    connection: &Connection,
+    db_path: &Path,
+    source_hash: &str,
+) -> ClientResult<Option<PriorImport>> {
+    find_prior_import(
+        connection,
+        db_path,
+        "r.source_hash = ?1 AND r.status = 'committed'",
+        source_hash,
+    )
+}
+
+pub(crate) fn find_by_idempotency_key(
+    connection: &Connection,
+    db_path: &Path,
+    idempotency_key: &str,
+) -> ClientResult<Option<PriorImport>> {
+    find_prior_import(
+        connection,
+        db_path,
+        "r.idempotency_key = ?1",
+        idempotency_key,
+    )
+}
+
+fn find_prior_import(
+    connection: &Connection,
+    db_path: &Path,
+    predicate: &str,
+    value: &str,
+) -> ClientResult<Option<PriorImport>> {
+    connection
+        .query_row(
+            &format!(
+                "SELECT
+                    r.import_id,
+                    r.status,
+                    r.source_hash,
+                    r.rows_read,
+                    r.rows_valid,
+                    r.rows_invalid,
+                    r.inserted,
+                    (SELECT COUNT(*) FROM internal_transaction_dedupe_candidates c
+                     WHERE c.import_id = r.import_id AND c.dedupe_reason = 'batch'
+                       AND c.promoted_txn_id IS NULL),
+                    (SELECT COUNT(*) FROM internal_transaction_dedupe_candidates c
+                     WHERE c.import_id = r.import_id AND c.dedupe_reason = 'existing_ledger'
+                       AND c.promoted_txn_id IS NULL)
+                 FROM internal_import_runs r
+                 WHERE {predicate}
+                 ORDER BY r.created_at DESC, r.import_id DESC
+                 LIMIT 1"
+            ),
+            params![value],
+            |row| {
+                Ok(PriorImport {
+                    import_id: row.get(0)?,
+                    status: row.get(1)?,
+                    source_hash: row.get(2)?,
+                    rows_read: row.get(3)?,
+                    rows_valid: row.get(4)?,
+                    rows_invalid: row.get(5)?,
+                    inserted: row.get(6)?,
+                    batch_duplicates: row.get(7)?,
+                    existing_ledger_duplicates: row.get(8)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::source_hash;
+    use crate::import::parse::parse_source;
+
+    fn hash(content: &str) -> String {
+        parse_source(content)
+            .map(|parsed| source_hash(&parsed))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn source_hash_follows_rows_not_formatting() {
+        let csv = "account_key,posted_at,amount,currency,description
+acct_1,2026-01-05,-15.99,USD,NETFLIX.COM
+";
+        let hashed = hash(csv);
+        assert_eq!(hashed.len(), 64);
+        assert_eq!(hash(&format!("{csv}\n")), hashed);
+        assert_eq!(hash(&csv.replace('\n', "\r\n")), hashed);
+        assert_eq!(
+            hash(
+                r#"[{"description": "NETFLIX.COM", "account_key": "acct_1",
+                    "posted_at": "2026-01-05", "amount": "-15.99", "currency": "USD"}]"#
+            ),
+            hashed
+        );
+        assert_ne!(hash(&csv.replace("-15.99", "-16.99")), hashed);
+    }
+}
@@ -9,6 +9,8 @@ use crate::{ClientError, ClientResult};
 pub(crate) enum SourceKind {
     File,
     Stdin,
+    /// Rows entered with `transaction add` rather than read from a source.
+    Manual,
 }
 
 impl SourceKind {
@@ -16,6 +18,7 @@ impl SourceKind {
         match self {
             Self::File => "file",
             Self::Stdin => "stdin",
+            Self::Manual => "manual",
         }
     }
 }
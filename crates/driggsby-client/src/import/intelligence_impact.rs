@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::ClientResult;
+use crate::contracts::types::{ImportIntelligenceImpact, ImportTriggeredAnomaly, RecurringRow};
+use crate::import::dedupe::BatchRow;
+use crate::intelligence::anomalies::{AnomalyDetection, detect_anomalies};
+use crate::intelligence::date::parse_transaction_date;
+use crate::intelligence::query::load_transactions_from_connection;
+use crate::intelligence::recurring::{RecurringDetection, detect_recurring};
+use crate::intelligence::types::{IntelligenceFilter, NormalizedTransaction};
+
+/// Placeholder ids for incoming rows; ledger ids never use this prefix.
+const INCOMING_TXN_PREFIX: &str = "incoming:";
+
+/// Runs recurring and anomaly detection on the ledger with and without the
+/// rows an import would insert, and reports the difference.
+pub(crate) fn intelligence_impact(
+    connection: &Connection,
+    db_path: &Path,
+    rows: &[BatchRow],
+) -> ClientResult<ImportIntelligenceImpact> {
+    let filter = IntelligenceFilter {
+        from: None,
+        to: None,
+    };
+    let existing = load_transactions_from_connection(connection, db_path, &filter)?;
+    let mut combined = existing.clone();
+    combined.extend(rows.iter().filter_map(normalize_incoming));
+    combined.sort_by(|left, right| {
+        left.account_key
+            .cmp(&right.account_key)
+            .then_with(|| left.currency.cmp(&right.currency))
+            .then_with(|| left.posted_at.cmp(&right.posted_at))
+            .then_with(|| left.txn_id.cmp(&right.txn_id))
+    });
+
+    let recurring_before = detect_recurring(&existing);
+    let recurring_after = detect_recurring(&combined);
+    let keys_before = recurring_keys(&recurring_before);
+    let keys_after = recurring_keys(&recurring_after);
+    let recurring_appearing = recurring_after
+        .iter()
+        .filter(|detection| !keys_before.contains(detection.group_key.as_str()))
+        .map(recurring_row)
+        .collect();
+    let recurring_disappearing = recurring_before
+        .iter()
+        .filter(|detection| !keys_after.contains(detection.group_key.as_str()))
+        .map(recurring_row)
+        .collect();
+
+    let anomalies_before = detect_anomalies(&existing)
+        .into_iter()
+        .map(|anomaly| (anomaly.txn_id, anomaly.reason_code))
+        .collect::<BTreeSet<(String, String)>>();
+    let anomalies_triggered = detect_anomalies(&combined)
+        .into_iter()
+        .filter(|anomaly| {
+            !anomalies_before.contains(&(anomaly.txn_id.clone(), anomaly.reason_code.clone()))
+        })
+        .map(triggered_anomaly)
+        .collect();
+
+    Ok(ImportIntelligenceImpact {
+        recurring_appearing,
+        recurring_disappearing,
+        anomalies_triggered,
+    })
+}
+
+/// Mirrors the cleanup `load_transactions_from_connection` applies to ledger rows.
+fn normalize_incoming(batch_row: &BatchRow) -> Option<NormalizedTransaction> {
+    let row = &batch_row.row;
+    if row.amount == 0.0 {
+        return None;
+    }
+    Some(NormalizedTransaction {
+        txn_id: format!("{INCOMING_TXN_PREFIX}{}", batch_row.source_row_index),
+        account_key: row.account_key.clone(),
+        posted_at: parse_transaction_date(&row.posted_at)?,
+        amount: row.amount,
+        currency: row.currency.trim().to_ascii_uppercase(),
+        description: row.description.trim().to_string(),
+        merchant: row
+            .merchant
+            .as_ref()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty()),
+    })
+}
+
+fn recurring_keys(detections: &[RecurringDetection]) -> BTreeSet<&str> {
+    detections
+        .iter()
+        .map(|detection| detection.group_key.as_str())
+        .collect()
+}
+
+fn recurring_row(detection: &RecurringDetection) -> RecurringRow {
+    RecurringRow {
+        group_key: detection.group_key.clone(),
+        account_key: detection.account_key.clone(),
+        merchant: detection.counterparty.clone(),
+        cadence: detection.cadence.as_str().to_string(),
+        typical_amount: detection.typical_amount,
+        currency: detection.currency.clone(),
+        last_seen_at: detection.last_seen_at.format("%Y-%m-%d").to_string(),
+        next_expected_at: detection
+            .next_expected_at
+            .map(|value| value.format("%Y-%m-%d").to_string()),
+        occurrence_count: detection.occurrence_count,
+        score: detection.score,
+        is_active: detection.is_active,
+    }
+}
+
+fn triggered_anomaly(anomaly: AnomalyDetection) -> ImportTriggeredAnomaly {
+    let (source_row_index, txn_id) = match anomaly.txn_id.strip_prefix(INCOMING_TXN_PREFIX) {
+        Some(index) => (index.parse::<i64>().ok(), None),
+        None => (None, Some(anomaly.txn_id)),
+    };
+    ImportTriggeredAnomaly {
+        source_row_index,
+        txn_id,
+        account_key: anomaly.account_key,
+        posted_at: anomaly.posted_at,
+        merchant: anomaly.merchant,
+        amount: anomaly.amount,
+        currency: anomaly.currency,
+        reason_code: anomaly.reason_code,
+        reason: anomaly.reason,
+        score: anomaly.score,
+        severity: anomaly.severity,
+    }
+}
@@ -4,7 +4,9 @@ use std::path::Path;
 use rusqlite::Connection;
 
 use crate::ClientResult;
-use crate::contracts::types::{ImportKeyInventory, ImportPropertyInventory, ImportValueCount};
+use crate::contracts::types::{
+    ImportCanonicalAliases, ImportKeyInventory, ImportPropertyInventory, ImportValueCount,
+};
 use crate::import::CanonicalTransaction;
 use crate::state::map_sqlite_error;
 
@@ -162,9 +164,45 @@ pub(crate) fn query_property_inventory(
         value_counts,
         null_count,
         total_rows,
+        aliases: query_canonical_aliases(connection, db_path, property)?,
     })
 }
 
+/// Groups stored aliases by the canonical value they rewrite to, or `None`
+/// when the property has no aliases.
+fn query_canonical_aliases(
+    connection: &Connection,
+    db_path: &Path,
+    property: TrackedProperty,
+) -> ClientResult<Option<Vec<ImportCanonicalAliases>>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT canonical, alias
+             FROM internal_aliases
+             WHERE property = ?1
+             ORDER BY canonical ASC, alias ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([property.as_str()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut grouped: Vec<ImportCanonicalAliases> = Vec::new();
+    for row in rows {
+        let (canonical, alias) = row.map_err(|error| map_sqlite_error(db_path, &error))?;
+        match grouped.last_mut() {
+            Some(entry) if entry.canonical == canonical => entry.aliases.push(alias),
+            _ => grouped.push(ImportCanonicalAliases {
+                canonical,
+                aliases: vec![alias],
+            }),
+        }
+    }
+    Ok((!grouped.is_empty()).then_some(grouped))
+}
+
 pub(crate) fn inventory_to_vec(inventory: &ImportKeyInventory) -> Vec<ImportPropertyInventory> {
     vec![
         inventory.account_key.clone(),
@@ -260,6 +298,8 @@ mod tests {
                 external_id: None,
                 merchant: Some("Coffee".to_string()),
                 category: Some("Food".to_string()),
+                balance: None,
+                mcc: None,
             },
             CanonicalTransaction {
                 statement_id: Some("acct_1_2026-01-31".to_string()),
@@ -273,6 +313,8 @@ mod tests {
                 external_id: None,
                 merchant: Some("Coffee".to_string()),
                 category: None,
+                balance: None,
+                mcc: None,
             },
             CanonicalTransaction {
                 statement_id: Some("acct_2_2026-01-31".to_string()),
@@ -286,6 +328,8 @@ mod tests {
                 external_id: None,
                 merchant: None,
                 category: Some("Travel".to_string()),
+                balance: None,
+                mcc: None,
             },
         ];
 
@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+use serde_json::{Map, Value, json};
+use ulid::Ulid;
+
+use crate::import::CanonicalTransaction;
+use crate::import::dedupe::{BatchRow, DedupePolicies};
+use crate::import::fingerprint::source_hash;
+use crate::import::input::SourceKind;
+use crate::import::parse::{ParsedRow, ParsedSource};
+use crate::import::persist::{PersistInput, PersistMode, persist_import};
+use crate::import::undo::{UndoResult, UndoScope, undo_import};
+use crate::import::{remap, validate};
+use crate::intelligence::refresh::refresh_all_in_transaction;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+/// SQL condition on `internal_transactions.import_id` that leaves out rows
+/// entered by hand. File imports never dedupe against manual entries: a cash
+/// receipt that happens to match a card row is still its own transaction.
+pub(crate) const NOT_MANUAL_IMPORT: &str =
+    "import_id NOT IN (SELECT import_id FROM internal_import_runs WHERE source_kind = 'manual')";
+
+/// A hand-entered transaction as given on the command line. Values are
+/// validated with the same rules as imported rows.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ManualEntry {
+    pub(crate) account_key: String,
+    pub(crate) posted_at: String,
+    pub(crate) amount: String,
+    pub(crate) currency: String,
+    pub(crate) description: String,
+    pub(crate) merchant: Option<String>,
+    pub(crate) category: Option<String>,
+}
+
+/// Requested changes to the entered values of a manual transaction. `None`
+/// leaves a field alone.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ManualEdit {
+    pub(crate) posted_at: Option<String>,
+    pub(crate) amount: Option<String>,
+    pub(crate) description: Option<String>,
+}
+
+impl ManualEdit {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.posted_at.is_none() && self.amount.is_none() && self.description.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ManualAddResult {
+    pub(crate) import_id: String,
+    pub(crate) txn_id: String,
+}
+
+/// Commits `entry` as a one-row import with the `manual` source kind, so it
+/// gets a txn id, shows up in `import list` and can be undone like any other
+/// import. Aliases and categorization rules apply; dedupe does not.
+pub(crate) fn add_manual_transaction(
+    connection: &mut Connection,
+    db_path: &Path,
+    entry: &ManualEntry,
+) -> ClientResult<ManualAddResult> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    // The generated external id keeps the row's identity stable when its
+    // entered values are edited later, so tags, overrides and exclusions
+    // stay attached.
+    let external_id = format!("manual_{}", Ulid::new());
+    let mut parsed = ParsedSource {
+        rows: vec![parsed_row(entry, Some(external_id))],
+        statement_headers: Vec::new(),
+        source_records: Vec::new(),
+    };
+    let hash = source_hash(&parsed);
+    let alias_rules = crate::commands::aliases::alias_rules(&transaction, db_path)?;
+    remap::apply_remaps(&mut parsed, &alias_rules);
+    let rules = crate::rules::load_rules(&transaction, db_path)?;
+    let validated = validate::validate_rows(
+        parsed,
+        &format!("manual_{}", Ulid::new()),
+        None,
+        &HashMap::new(),
+        &rules,
+    )
+    .map_err(|error| manual_entry_error(error, "transaction add"))?;
+    let Some(row) = validated.rows.first().cloned() else {
+        return Err(ClientError::invalid_argument_for_command(
+            "The manual transaction could not be validated.",
+            Some("transaction add"),
+        ));
+    };
+
+    let record = Some(source_record(&row));
+    let import_id = format!("imp_{}", Ulid::new());
+    let persisted = persist_import(
+        &transaction,
+        db_path,
+        PersistInput {
+            import_id: &import_id,
+            candidate_rows: &[BatchRow {
+                row,
+                source_row_index: 1,
+            }],
+            duplicate_rows: &[],
+            rows_read: 1,
+            rows_valid: 1,
+            rows_invalid: 0,
+            source_kind: SourceKind::Manual.as_str(),
+            source_ref: None,
+            sign_corrections: &[],
+            remaps: &[],
+            statements: &[],
+            probable_duplicates: &[],
+            // The generated external id is unique, so it keys the row even
+            // where the account's policy distrusts bank ids.
+            dedupe_policies: &DedupePolicies::new(Default::default()),
+            source_hash: &hash,
+            idempotency_key: None,
+            source_records: std::slice::from_ref(&record),
+            rule_hits: &validated.rule_hits,
+            mode: PersistMode::New,
+        },
+    )?;
+    let txn_id = transaction
+        .query_row(
+            "SELECT txn_id FROM internal_transactions WHERE import_id = ?1",
+            params![&persisted.import_id],
+            |row| row.get::<_, String>(0),
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(ManualAddResult { import_id, txn_id })
+}
+
+/// Rewrites the entered date, amount or description of a manual transaction
+/// in place and refreshes intelligence. Imported transactions are refused.
+pub(crate) fn edit_manual_transaction(
+    connection: &mut Connection,
+    db_path: &Path,
+    txn_id: &str,
+    edit: &ManualEdit,
+) -> ClientResult<()> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let current = load_manual_row(&transaction, db_path, txn_id)?;
+
+    let entry = ManualEntry {
+        account_key: current.account_key.clone(),
+        posted_at: edit
+            .posted_at
+            .clone()
+            .unwrap_or_else(|| current.posted_at.clone()),
+        amount: edit
+            .amount
+            .clone()
+            .unwrap_or_else(|| current.amount.to_string()),
+        currency: current.currency.clone(),
+        description: edit
+            .description
+            .clone()
+            .unwrap_or_else(|| current.description.clone()),
+        merchant: current.merchant.clone(),
+        category: current.category.clone(),
+    };
+    let parsed = ParsedSource {
+        rows: vec![parsed_row(&entry, current.external_id.clone())],
+        statement_headers: Vec::new(),
+        source_records: Vec::new(),
+    };
+    let validated = validate::validate_rows(parsed, "manual_edit", None, &HashMap::new(), &[])
+        .map_err(|error| manual_entry_error(error, "transaction edit"))?;
+    let Some(updated) = validated.rows.first() else {
+        return Err(ClientError::invalid_argument_for_command(
+            "The manual transaction could not be validated.",
+            Some("transaction edit"),
+        ));
+    };
+
+    transaction
+        .execute(
+            "UPDATE internal_transactions
+             SET posted_at = ?2,
+                 amount = ?3,
+                 description = ?4
+             WHERE txn_id = ?1",
+            params![
+                txn_id,
+                &updated.posted_at,
+                updated.amount,
+                &updated.description
+            ],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let mut record_row = current.clone();
+    record_row.posted_at = updated.posted_at.clone();
+    record_row.amount = updated.amount;
+    record_row.description = updated.description.clone();
+    transaction
+        .execute(
+            "UPDATE internal_import_source_rows SET source_record = ?2 WHERE txn_id = ?1",
+            params![txn_id, source_record(&record_row)],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    refresh_all_in_transaction(&transaction, db_path)?;
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+/// Removes a manual transaction by undoing the one-row import behind it, so
+/// `import redo` can bring it back.
+pub(crate) fn delete_manual_transaction(
+    connection: &mut Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<UndoResult> {
+    let import_id = manual_import_id(connection, db_path, txn_id)?;
+    undo_import(connection, db_path, &import_id, &UndoScope::default())
+}
+
+/// The import behind `txn_id`, or an error when the row does not exist or
+/// was not entered by hand.
+pub(crate) fn manual_import_id(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<String> {
+    let found = connection
+        .query_row(
+            "SELECT t.import_id, r.source_kind
+             FROM internal_transactions t
+             LEFT JOIN internal_import_runs r ON r.import_id = t.import_id
+             WHERE t.txn_id = ?1",
+            params![txn_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let Some((import_id, source_kind)) = found else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+    if source_kind.as_deref() != Some(SourceKind::Manual.as_str()) {
+        return Err(ClientError::transaction_not_manual(txn_id, &import_id));
+    }
+    Ok(import_id)
+}
+
+fn load_manual_row(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<CanonicalTransaction> {
+    manual_import_id(connection, db_path, txn_id)?;
+    connection
+        .query_row(
+            "SELECT statement_id, dedupe_scope_id, account_key, posted_at, amount, currency,
+                    description, external_id, merchant, category, balance, mcc
+             FROM internal_transactions
+             WHERE txn_id = ?1",
+            params![txn_id],
+            |row| {
+                Ok(CanonicalTransaction {
+                    statement_id: row.get(0)?,
+                    dedupe_scope_id: row.get(1)?,
+                    account_key: row.get(2)?,
+                    account_type: None,
+                    posted_at: row.get(3)?,
+                    amount: row.get(4)?,
+                    currency: row.get(5)?,
+                    description: row.get(6)?,
+                    external_id: row.get(7)?,
+                    merchant: row.get(8)?,
+                    category: row.get(9)?,
+                    balance: row.get(10)?,
+                    mcc: row.get(11)?,
+                })
+            },
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+fn parsed_row(entry: &ManualEntry, external_id: Option<String>) -> ParsedRow {
+    ParsedRow {
+        row: 1,
+        statement_id: None,
+        account_key: Some(entry.account_key.clone()),
+        account_type: None,
+        posted_at: Some(entry.posted_at.clone()),
+        amount: Some(entry.amount.clone()),
+        currency: Some(entry.currency.clone()),
+        description: Some(entry.description.clone()),
+        external_id,
+        merchant: entry.merchant.clone(),
+        category: entry.category.clone(),
+        balance: None,
+        mcc: None,
+        statement_opening_balance: None,
+        statement_closing_balance: None,
+    }
+}
+
+/// The JSON record kept as the manual row's provenance, shaped like a row of
+/// a JSON import.
+fn source_record(row: &CanonicalTransaction) -> String {
+    let mut record = Map::new();
+    record.insert("account_key".to_string(), json!(row.account_key));
+    record.insert("posted_at".to_string(), json!(row.posted_at));
+    record.insert("amount".to_string(), json!(row.amount));
+    record.insert("currency".to_string(), json!(row.currency));
+    record.insert("description".to_string(), json!(row.description));
+    let optional = [
+        ("external_id", &row.external_id),
+        ("merchant", &row.merchant),
+        ("category", &row.category),
+    ];
+    for (field, value) in optional {
+        if let Some(value) = value {
+            record.insert(field.to_string(), json!(value));
+        }
+    }
+    Value::Object(record).to_string()
+}
+
+/// Import validation reports issues against source rows; a manual entry has
+/// one row, so its issues read better as a plain argument error.
+fn manual_entry_error(error: ClientError, command: &str) -> ClientError {
+    if error.code != "import_validation_failed" {
+        return error;
+    }
+    let descriptions = error
+        .data
+        .as_ref()
+        .and_then(|data| data.get("issues"))
+        .and_then(Value::as_array)
+        .map(|issues| {
+            issues
+                .iter()
+                .filter_map(|issue| issue.get("description").and_then(Value::as_str))
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+    if descriptions.is_empty() {
+        return error;
+    }
+    ClientError::invalid_argument_for_command(&descriptions, Some(command))
+}
@@ -0,0 +1,329 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+
+use crate::import::CanonicalTransaction;
+use crate::import::dedupe::{DedupeReason, DuplicateRecord};
+use crate::import::persist::{insert_dedupe_candidate, now_timestamp};
+use crate::import::policy::load_run_dedupe_policies;
+use crate::import::provenance::{source_row_index_for_transaction, unlink_transaction};
+use crate::import::undo::{INVALIDATED_CANDIDATE, PromotionCandidate, promote_candidate};
+use crate::intelligence::refresh::refresh_all_in_transaction;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+/// Transactions committed before source rows were recorded have no row index.
+const NO_SOURCE_ROW_INDEX: i64 = 0;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PromoteResult {
+    pub(crate) candidate_id: String,
+    pub(crate) import_id: String,
+    pub(crate) promoted_txn_id: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DemoteResult {
+    pub(crate) candidate_id: String,
+    pub(crate) txn_id: String,
+    pub(crate) import_id: String,
+    pub(crate) duplicate_of_txn_id: String,
+    pub(crate) duplicate_of_import_id: String,
+}
+
+#[derive(Debug, Clone)]
+struct LedgerRow {
+    import_id: String,
+    row: CanonicalTransaction,
+}
+
+/// Inserts a dedupe candidate as a real transaction of its original import.
+pub(crate) fn promote_dedupe_candidate(
+    connection: &mut Connection,
+    db_path: &Path,
+    candidate_id: &str,
+) -> ClientResult<PromoteResult> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let candidate = transaction
+        .query_row(
+            "SELECT
+                c.import_id,
+                c.statement_id,
+                c.dedupe_scope_id,
+                c.account_key,
+                c.posted_at,
+                c.amount,
+                c.currency,
+                c.description,
+                c.external_id,
+                c.merchant,
+                c.category,
+                c.balance,
+                c.promoted_txn_id,
+                i.status,
+                c.mcc
+             FROM internal_transaction_dedupe_candidates c
+             JOIN internal_import_runs i ON i.import_id = c.import_id
+             WHERE c.candidate_id = ?1",
+            params![candidate_id],
+            |row| {
+                Ok((
+                    PromotionCandidate {
+                        candidate_id: candidate_id.to_string(),
+                        import_id: row.get(0)?,
+                        row: CanonicalTransaction {
+                            statement_id: row.get(1)?,
+                            dedupe_scope_id: row.get(2)?,
+                            account_key: row.get(3)?,
+                            account_type: None,
+                            posted_at: row.get(4)?,
+                            amount: row.get(5)?,
+                            currency: row.get(6)?,
+                            description: row.get(7)?,
+                            external_id: row.get(8)?,
+                            merchant: row.get(9)?,
+                            category: row.get(10)?,
+                            balance: row.get(11)?,
+                            mcc: row.get(14)?,
+                        },
+                    },
+                    row.get::<_, Option<String>>(12)?,
+                    row.get::<_, String>(13)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let Some((candidate, promoted_txn_id, import_status)) = candidate else {
+        return Err(ClientError::dedupe_candidate_not_found(candidate_id));
+    };
+    match import_status.as_str() {
+        "committed" | "partially_reverted" => {}
+        "reverted" => return Err(ClientError::import_already_reverted(&candidate.import_id)),
+        status => {
+            return Err(ClientError::import_not_committed(
+                &candidate.import_id,
+                status,
+            ));
+        }
+    }
+    if promoted_txn_id.as_deref() == Some(INVALIDATED_CANDIDATE) {
+        return Err(ClientError::dedupe_candidate_invalidated(
+            candidate_id,
+            &candidate.import_id,
+        ));
+    }
+    if let Some(promoted_txn_id) = promoted_txn_id {
+        return Err(ClientError::dedupe_candidate_already_promoted(
+            candidate_id,
+            &promoted_txn_id,
+        ));
+    }
+
+    let dedupe_policies = load_run_dedupe_policies(&transaction, db_path, &candidate.import_id)?;
+    let promoted_txn_id = promote_candidate(
+        &transaction,
+        db_path,
+        &candidate,
+        dedupe_policies.for_account(&candidate.row.account_key),
+    )?;
+    adjust_import_counts(
+        &transaction,
+        db_path,
+        &candidate.import_id,
+        &candidate.row.account_key,
+        1,
+    )?;
+    refresh_all_in_transaction(&transaction, db_path)?;
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(PromoteResult {
+        candidate_id: candidate_id.to_string(),
+        import_id: candidate.import_id,
+        promoted_txn_id,
+    })
+}
+
+/// Removes `txn_id` from the ledger as a duplicate of `duplicate_of_txn_id`.
+pub(crate) fn mark_transaction_duplicate(
+    connection: &mut Connection,
+    db_path: &Path,
+    txn_id: &str,
+    duplicate_of_txn_id: &str,
+) -> ClientResult<DemoteResult> {
+    if txn_id == duplicate_of_txn_id {
+        return Err(ClientError::invalid_argument_for_command(
+            "A transaction cannot be marked as a duplicate of itself.",
+            Some("transaction mark-duplicate"),
+        ));
+    }
+
+    let timestamp = now_timestamp();
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let Some(original) = load_ledger_row(&transaction, db_path, duplicate_of_txn_id)? else {
+        return Err(ClientError::transaction_not_found(duplicate_of_txn_id));
+    };
+    let Some(duplicate) = load_ledger_row(&transaction, db_path, txn_id)? else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+    if duplicate.row.account_key != original.row.account_key {
+        return Err(ClientError::invalid_argument_for_command(
+            &format!(
+                "Transaction `{txn_id}` belongs to `{}` but `{duplicate_of_txn_id}` belongs to `{}`. Duplicates must share an account.",
+                duplicate.row.account_key, original.row.account_key
+            ),
+            Some("transaction mark-duplicate"),
+        ));
+    }
+
+    let source_row_index = source_row_index_for_transaction(&transaction, db_path, txn_id)?
+        .unwrap_or(NO_SOURCE_ROW_INDEX);
+    let candidate_id = demote_transaction(
+        &transaction,
+        db_path,
+        txn_id,
+        duplicate_of_txn_id,
+        source_row_index,
+        &timestamp,
+    )?;
+    transaction
+        .execute(
+            "DELETE FROM internal_probable_duplicates
+             WHERE status = 'pending' AND (txn_id = ?1 OR matched_txn_id = ?1)",
+            params![txn_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    refresh_all_in_transaction(&transaction, db_path)?;
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(DemoteResult {
+        candidate_id,
+        txn_id: txn_id.to_string(),
+        import_id: duplicate.import_id,
+        duplicate_of_txn_id: duplicate_of_txn_id.to_string(),
+        duplicate_of_import_id: original.import_id,
+    })
+}
+
+/// Moves a committed transaction into `internal_transaction_dedupe_candidates`
+/// as an `existing_ledger` duplicate of another row and returns the candidate
+/// id. `import undo` promotes it again if the row it points at is reverted.
+pub(crate) fn demote_transaction(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    txn_id: &str,
+    duplicate_of_txn_id: &str,
+    source_row_index: i64,
+    timestamp: &str,
+) -> ClientResult<String> {
+    let Some(duplicate) = load_ledger_row(transaction, db_path, txn_id)? else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+    let Some(original) = load_ledger_row(transaction, db_path, duplicate_of_txn_id)? else {
+        return Err(ClientError::transaction_not_found(duplicate_of_txn_id));
+    };
+
+    let account_key = duplicate.row.account_key.clone();
+    let dedupe_policies = load_run_dedupe_policies(transaction, db_path, &duplicate.import_id)?;
+    let record = DuplicateRecord {
+        row: duplicate.row,
+        source_row_index,
+        dedupe_reason: DedupeReason::ExistingLedger,
+        matched_batch_row_index: None,
+        matched_txn_id: Some(duplicate_of_txn_id.to_string()),
+        matched_import_id: Some(original.import_id),
+    };
+    let candidate_id = insert_dedupe_candidate(
+        transaction,
+        db_path,
+        &duplicate.import_id,
+        &record,
+        dedupe_policies.for_account(&account_key),
+        timestamp,
+    )?;
+    transaction
+        .execute(
+            "DELETE FROM internal_transactions WHERE txn_id = ?1",
+            params![txn_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    unlink_transaction(transaction, db_path, txn_id)?;
+    adjust_import_counts(transaction, db_path, &duplicate.import_id, &account_key, -1)?;
+
+    Ok(candidate_id)
+}
+
+/// Shifts rows between the inserted and deduped counters of an import.
+fn adjust_import_counts(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    account_key: &str,
+    inserted_delta: i64,
+) -> ClientResult<()> {
+    transaction
+        .execute(
+            "UPDATE internal_import_runs
+             SET inserted = inserted + ?2, deduped = deduped - ?2
+             WHERE import_id = ?1",
+            params![import_id, inserted_delta],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    transaction
+        .execute(
+            "UPDATE internal_import_account_stats
+             SET inserted = inserted + ?3, deduped = deduped - ?3
+             WHERE import_id = ?1 AND account_key = ?2",
+            params![import_id, account_key, inserted_delta],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+fn load_ledger_row(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Option<LedgerRow>> {
+    connection
+        .query_row(
+            "SELECT import_id, statement_id, dedupe_scope_id, account_key, posted_at, amount,
+                    currency, description, external_id, merchant, category, balance, mcc
+             FROM internal_transactions
+             WHERE txn_id = ?1",
+            params![txn_id],
+            |row| {
+                Ok(LedgerRow {
+                    import_id: row.get(0)?,
+                    row: CanonicalTransaction {
+                        statement_id: row.get(1)?,
+                        dedupe_scope_id: row.get(2)?,
+                        account_key: row.get(3)?,
+                        account_type: None,
+                        posted_at: row.get(4)?,
+                        amount: row.get(5)?,
+                        currency: row.get(6)?,
+                        description: row.get(7)?,
+                        external_id: row.get(8)?,
+                        merchant: row.get(9)?,
+                        category: row.get(10)?,
+                        balance: row.get(11)?,
+                        mcc: row.get(12)?,
+                    },
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
@@ -1,11 +1,27 @@
 pub(crate) mod analysis;
+pub(crate) mod archive;
+pub(crate) mod balances;
+pub(crate) mod category_suggestions;
+pub(crate) mod coverage;
 pub(crate) mod dedupe;
 pub(crate) mod drift_warnings;
+pub(crate) mod fingerprint;
 pub(crate) mod input;
+pub(crate) mod intelligence_impact;
 pub(crate) mod inventory;
+pub(crate) mod manual;
+pub(crate) mod manual_dedupe;
 pub(crate) mod parse;
 pub(crate) mod persist;
+pub(crate) mod policy;
+pub(crate) mod probable;
+pub(crate) mod provenance;
+pub(crate) mod redo;
+pub(crate) mod remap;
+pub(crate) mod replace;
 pub(crate) mod sign_profiles;
+pub(crate) mod staging;
+pub(crate) mod statements;
 pub(crate) mod undo;
 pub(crate) mod validate;
 
@@ -16,9 +32,12 @@ use rusqlite::{OptionalExtension, TransactionBehavior, params};
 use ulid::Ulid;
 
 use crate::contracts::types::{
-    ImportAction, ImportCreateSummary, ImportDriftWarning, ImportDuplicateRow,
-    ImportDuplicateSummary, ImportDuplicatesPreview, ImportIssue, ImportKeyInventory,
-    ImportNextStep, ImportSignProfile, ImportWarning,
+    ImportAction, ImportBalanceGap, ImportCategorySuggestion, ImportCreateSummary,
+    ImportDedupePolicy, ImportDriftWarning, ImportDuplicateRow, ImportDuplicateSummary,
+    ImportDuplicatesPreview, ImportIntelligenceImpact, ImportIssue, ImportKeyInventory,
+    ImportNextStep, ImportProbableDuplicate, ImportRemap, ImportReplacement, ImportRuleChange,
+    ImportSignCorrection, ImportSignProfile, ImportStatementReconciliation, ImportSummary,
+    ImportWarning,
 };
 use crate::setup::SetupContext;
 use crate::state::open_connection;
@@ -37,6 +56,8 @@ pub(crate) struct CanonicalTransaction {
     pub external_id: Option<String>,
     pub merchant: Option<String>,
     pub category: Option<String>,
+    pub balance: Option<f64>,
+    pub mcc: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,68 +77,263 @@ pub(crate) struct ImportExecutionResult {
     pub warnings: Vec<ImportWarning>,
     pub key_inventory: Option<ImportKeyInventory>,
     pub sign_profiles: Option<Vec<ImportSignProfile>>,
+    pub sign_corrections: Option<Vec<ImportSignCorrection>>,
+    pub remaps: Option<Vec<ImportRemap>>,
+    pub alias_rewrites: Option<Vec<ImportRemap>>,
+    pub rule_changes: Option<Vec<ImportRuleChange>>,
+    pub category_suggestions: Option<Vec<ImportCategorySuggestion>>,
     pub drift_warnings: Option<Vec<ImportDriftWarning>>,
+    pub reconciled_statements: Option<Vec<ImportStatementReconciliation>>,
+    pub balance_gaps: Option<Vec<ImportBalanceGap>>,
+    pub probable_duplicates: Option<Vec<ImportProbableDuplicate>>,
+    pub dedupe_policies: Option<Vec<ImportDedupePolicy>>,
+    pub policy_warnings: Option<Vec<ImportIssue>>,
     pub ledger_accounts: Option<crate::contracts::types::AccountsData>,
+    pub source_hash: String,
+    pub idempotency_key: Option<String>,
+    pub replayed: Option<bool>,
+    pub replacement: Option<ImportReplacement>,
+    pub staged: Option<bool>,
+    pub intelligence_impact: Option<ImportIntelligenceImpact>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImportRequest {
+    pub path: Option<String>,
+    pub dry_run: bool,
+    pub stdin_override: Option<String>,
+    pub auto_fix_signs: bool,
+    /// Fill missing categories from ledger history before dedupe and commit.
+    pub apply_category_suggestions: bool,
+    pub remaps: Vec<String>,
+    pub remap_file: Option<String>,
+    pub idempotency_key: Option<String>,
+    /// Commit rows that a committed import already brought in from the same
+    /// source, instead of refusing them.
+    pub allow_duplicate_source: bool,
+    /// Committed import that this source replaces atomically (`import replace`).
+    pub replace_import_id: Option<String>,
+    /// Analyze like a dry run, then hold the batch as a staged import run
+    /// for `import approve` or `import reject` (`import stage`).
+    pub stage: bool,
 }
 
 pub(crate) fn execute(
     setup: &SetupContext,
-    path: Option<String>,
-    dry_run: bool,
-    stdin_override: Option<String>,
+    request: ImportRequest,
 ) -> ClientResult<ImportExecutionResult> {
-    let resolved_source = input::resolve_source(path, stdin_override)?;
-    let parsed_rows = parse::parse_source(&resolved_source.content)?;
+    let dry_run = request.dry_run;
+    let stage = request.stage;
+    let replace_import_id = request.replace_import_id.as_deref();
+    let idempotency_key = normalize_idempotency_key(request.idempotency_key, dry_run)?;
+    let remap_rules = remap::build_remap_rules(&request.remaps, request.remap_file.as_deref())?;
+    let mut resolved_source = input::resolve_source(request.path, request.stdin_override)?;
+    let mut parsed_source = parse::parse_source(&resolved_source.content)?;
+    let source_hash = fingerprint::source_hash(&parsed_source);
+    let applied_remaps = remap::apply_remaps(&mut parsed_source, &remap_rules);
+    let remaps = (!applied_remaps.is_empty()).then(|| {
+        applied_remaps
+            .iter()
+            .map(remap::AppliedRemap::to_contract)
+            .collect::<Vec<ImportRemap>>()
+    });
     let statement_scope_id = format!("scope_{}", Ulid::new());
-    let validated = validate::validate_rows(parsed_rows, &statement_scope_id)?;
-
     let db_path = PathBuf::from(&setup.db_path);
     let mut connection = open_connection(&db_path)?;
+    if let Some(import_id) = replace_import_id {
+        replace::ensure_replaceable(&connection, &db_path, import_id)?;
+    }
+    if let Some(key) = idempotency_key.as_deref()
+        && let Some(prior) = fingerprint::find_by_idempotency_key(&connection, &db_path, key)?
+    {
+        if prior.status != "committed" {
+            let reason = if prior.status == "partially_reverted" {
+                "which was partially reverted"
+            } else {
+                "which was reverted"
+            };
+            return Err(ClientError::idempotency_key_conflict(
+                key,
+                &prior.import_id,
+                reason,
+            ));
+        }
+        if prior.source_hash.as_deref() != Some(source_hash.as_str()) {
+            return Err(ClientError::idempotency_key_conflict(
+                key,
+                &prior.import_id,
+                "which imported different source content",
+            ));
+        }
+        return replayed_import_result(&connection, &db_path, prior, key, resolved_source);
+    }
+    if !request.allow_duplicate_source
+        && let Some(prior) =
+            fingerprint::find_committed_by_hash(&connection, &db_path, &source_hash)?
+        && Some(prior.import_id.as_str()) != replace_import_id
+    {
+        if !dry_run {
+            return Err(ClientError::import_source_already_imported(
+                &prior.import_id,
+                &source_hash,
+            ));
+        }
+        resolved_source.warnings.push(ImportWarning {
+            code: "source_already_imported".to_string(),
+            message: format!(
+                "These source rows were already imported as `{}`. Committing them will be refused unless that import is undone or --allow-duplicate-source is passed.",
+                prior.import_id
+            ),
+        });
+    }
+    let alias_rules = crate::commands::aliases::alias_rules(&connection, &db_path)?;
+    let alias_rewrites = remap::apply_remaps(&mut parsed_source, &alias_rules)
+        .iter()
+        .filter(|applied| applied.rows_rewritten > 0)
+        .map(remap::AppliedRemap::to_contract)
+        .collect::<Vec<ImportRemap>>();
+    let alias_rewrites = (!alias_rewrites.is_empty()).then_some(alias_rewrites);
+    let import_policy = match db_path.parent() {
+        Some(ledger_home) => policy::load_policy(ledger_home)?,
+        None => None,
+    };
+    let ledger_account_types = policy::load_ledger_account_types(&connection, &db_path)?;
+    let rules = crate::rules::load_rules(&connection, &db_path)?;
+    let source_records = std::mem::take(&mut parsed_source.source_records)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<Option<String>>>();
+    let mut validated = validate::validate_rows(
+        parsed_source,
+        &statement_scope_id,
+        import_policy.as_ref(),
+        &ledger_account_types,
+        &rules,
+    )?;
+    let rule_changes = crate::rules::summarize_import_hits(&rules, &validated.rule_hits);
+    let rule_changes = (!rule_changes.is_empty()).then_some(rule_changes);
+
     let statement_id_reuse_issues = dedupe::find_statement_id_reuse_issues(
         &connection,
         &validated.statement_id_rows,
         &db_path,
     )?;
     if !statement_id_reuse_issues.is_empty() {
-        let invalid_row_count = statement_id_reuse_issues
-            .iter()
-            .map(|issue| issue.row)
-            .collect::<HashSet<i64>>()
-            .len() as i64;
-        let mut summary = validated.summary.clone();
-        summary.rows_invalid = invalid_row_count;
-        summary.rows_valid = summary.rows_read - invalid_row_count;
-        return Err(ClientError::import_validation_failed(
-            summary,
+        return Err(rejected_rows_error(
+            &validated.summary,
             statement_id_reuse_issues,
         ));
     }
     let account_type_conflict_issues =
         find_account_type_conflict_issues(&connection, &validated.account_type_rows, &db_path)?;
     if !account_type_conflict_issues.is_empty() {
-        let invalid_row_count = account_type_conflict_issues
-            .iter()
-            .map(|issue| issue.row)
-            .collect::<HashSet<i64>>()
-            .len() as i64;
-        let mut summary = validated.summary.clone();
-        summary.rows_invalid = invalid_row_count;
-        summary.rows_valid = summary.rows_read - invalid_row_count;
-        return Err(ClientError::import_validation_failed(
-            summary,
+        return Err(rejected_rows_error(
+            &validated.summary,
             account_type_conflict_issues,
         ));
     }
-    let batch_deduped = dedupe::dedupe_batch(validated.rows);
+    let applied_sign_corrections = if request.auto_fix_signs {
+        let existing_sign_counts = sign_profiles::existing_sign_count_map(&connection, &db_path)?;
+        let incoming_sign_counts = sign_profiles::incoming_sign_count_map(validated.rows.iter());
+        let corrections =
+            sign_profiles::detect_sign_corrections(&existing_sign_counts, &incoming_sign_counts);
+        sign_profiles::apply_sign_corrections(&mut validated.rows, &corrections);
+        corrections
+    } else {
+        Vec::new()
+    };
+    let sign_corrections = request.auto_fix_signs.then(|| {
+        applied_sign_corrections
+            .iter()
+            .map(sign_profiles::SignCorrection::to_contract)
+            .collect::<Vec<ImportSignCorrection>>()
+    });
+    // Suggestions run after sign corrections so amount bands see final signs.
+    let suggested_categories =
+        category_suggestions::suggest_categories(&connection, &db_path, &validated.rows)?;
+    if request.apply_category_suggestions {
+        category_suggestions::apply_category_suggestions(
+            &mut validated.rows,
+            &suggested_categories,
+        );
+    }
+    let category_suggestions = (!suggested_categories.is_empty()).then(|| {
+        suggested_categories
+            .iter()
+            .map(|suggested| suggested.to_contract(request.apply_category_suggestions))
+            .collect::<Vec<ImportCategorySuggestion>>()
+    });
+    let (statements_reconciled, statement_issues) = statements::reconcile_statements(
+        &validated.rows,
+        &validated.statement_balances,
+        &validated.statement_id_rows,
+    );
+    if !statement_issues.is_empty() {
+        return Err(rejected_rows_error(&validated.summary, statement_issues));
+    }
+    let reconciled_statements = (!statements_reconciled.is_empty()).then(|| {
+        statements_reconciled
+            .iter()
+            .map(statements::ReconciledStatement::to_contract)
+            .collect::<Vec<ImportStatementReconciliation>>()
+    });
+    let balance_gaps = balances::has_running_balances(&validated.rows).then(|| {
+        balances::find_balance_gaps(&validated.rows)
+            .iter()
+            .map(balances::BalanceGap::to_contract)
+            .collect::<Vec<ImportBalanceGap>>()
+    });
+    let dedupe_policies = import_policy
+        .as_ref()
+        .map(policy::ImportPolicy::dedupe_policies)
+        .unwrap_or_default();
+    let applied_dedupe_policies = dedupe_policies.report(validated.rows.iter());
+    let applied_dedupe_policies =
+        (!applied_dedupe_policies.is_empty()).then_some(applied_dedupe_policies);
+    let replace_diff = match replace_import_id {
+        Some(import_id) => Some(replace::diff_against_import(
+            &connection,
+            &db_path,
+            import_id,
+            &validated.rows,
+        )?),
+        None => None,
+    };
+    let staged_rows = stage.then(|| validated.rows.clone());
+    let batch_deduped = dedupe::dedupe_batch(validated.rows, &dedupe_policies);
 
-    if dry_run {
+    if dry_run || stage {
         let transaction = connection
             .transaction_with_behavior(TransactionBehavior::Immediate)
             .map_err(|error| crate::state::map_sqlite_error(&db_path, &error))?;
-        let ledger_deduped =
-            dedupe::dedupe_against_existing(&transaction, &batch_deduped.candidate_rows, &db_path)?;
-        let dry_run_analysis =
-            analysis::analyze_dry_run(&transaction, &db_path, &ledger_deduped.insertable_rows)?;
+        // Previewing a replacement reverts the old import inside this
+        // transaction, so dedupe sees the ledger exactly as the swap would.
+        let reverted = match replace_import_id {
+            Some(import_id) => undo::revert_import(
+                &transaction,
+                &db_path,
+                import_id,
+                &undo::UndoScope::default(),
+            )?,
+            None => undo::RevertedImport::default(),
+        };
+        let ledger_deduped = dedupe::dedupe_against_existing(
+            &transaction,
+            &batch_deduped.candidate_rows,
+            &db_path,
+            &dedupe_policies,
+        )?;
+        let dry_run_analysis = analysis::analyze_dry_run(
+            &transaction,
+            &db_path,
+            &ledger_deduped.insertable_rows,
+            &batch_deduped.candidate_rows,
+        )?;
+        let probable_matches = probable::find_probable_duplicates(
+            &transaction,
+            &ledger_deduped.insertable_rows,
+            &db_path,
+        )?;
         transaction
             .rollback()
             .map_err(|error| crate::state::map_sqlite_error(&db_path, &error))?;
@@ -143,14 +359,18 @@ pub(crate) fn execute(
             Some(resolved_source.source_kind.as_str()),
             resolved_source.source_ref.as_deref(),
         );
-        let message = if resolved_source.source_ignored.is_some() {
+        let message = if let Some(import_id) = replace_import_id {
+            format!(
+                "Validation passed. No rows were written. Rerun without --dry-run to replace import `{import_id}`."
+            )
+        } else if resolved_source.source_ignored.is_some() {
             "Validation passed. No rows were written. File input was used and stdin was ignored."
                 .to_string()
         } else {
             "Validation passed. No rows were written.".to_string()
         };
 
-        return Ok(ImportExecutionResult {
+        let mut result = ImportExecutionResult {
             dry_run: true,
             import_id: None,
             message,
@@ -166,21 +386,121 @@ pub(crate) fn execute(
             warnings: resolved_source.warnings,
             key_inventory: Some(dry_run_analysis.key_inventory),
             sign_profiles: Some(dry_run_analysis.sign_profiles),
+            sign_corrections,
+            remaps,
+            alias_rewrites,
+            rule_changes,
+            category_suggestions,
             drift_warnings: Some(dry_run_analysis.drift_warnings),
+            reconciled_statements,
+            balance_gaps,
+            probable_duplicates: (!probable_matches.is_empty()).then(|| {
+                probable_matches
+                    .iter()
+                    .map(probable::ProbableMatch::to_contract)
+                    .collect::<Vec<ImportProbableDuplicate>>()
+            }),
+            dedupe_policies: applied_dedupe_policies,
+            policy_warnings: validated.policy_warnings,
             ledger_accounts: None,
-        });
+            source_hash,
+            idempotency_key: None,
+            replayed: None,
+            replacement: replace_diff.map(|diff| diff.to_contract(reverted)),
+            staged: None,
+            intelligence_impact: Some(dry_run_analysis.intelligence_impact),
+        };
+        if let Some(staged_rows) = staged_rows {
+            let import_id = format!("imp_{}", Ulid::new());
+            let analysis = serde_json::json!({
+                "duplicate_summary": &result.duplicate_summary,
+                "duplicates_preview": &result.duplicates_preview,
+                "warnings": &result.warnings,
+                "key_inventory": &result.key_inventory,
+                "sign_profiles": &result.sign_profiles,
+                "sign_corrections": &result.sign_corrections,
+                "remaps": &result.remaps,
+                "alias_rewrites": &result.alias_rewrites,
+                "rule_changes": &result.rule_changes,
+                "rule_hits": &validated.rule_hits,
+                "category_suggestions": &result.category_suggestions,
+                "drift_warnings": &result.drift_warnings,
+                "reconciled_statements": &result.reconciled_statements,
+                "balance_gaps": &result.balance_gaps,
+                "probable_duplicates": &result.probable_duplicates,
+                "dedupe_policies": &result.dedupe_policies,
+                "policy_warnings": &result.policy_warnings,
+                "intelligence_impact": &result.intelligence_impact,
+            });
+            let transaction = connection
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|error| crate::state::map_sqlite_error(&db_path, &error))?;
+            staging::stage_import(
+                &transaction,
+                &db_path,
+                staging::StageInput {
+                    import_id: &import_id,
+                    rows: &staged_rows,
+                    source_records: &source_records,
+                    statements: &statements_reconciled,
+                    sign_corrections: &applied_sign_corrections,
+                    remaps: &applied_remaps,
+                    rows_read: validated.summary.rows_read,
+                    rows_valid: validated.summary.rows_valid,
+                    rows_invalid: validated.summary.rows_invalid,
+                    source_kind: resolved_source.source_kind.as_str(),
+                    source_ref: resolved_source.source_ref.as_deref(),
+                    source_hash: &result.source_hash,
+                    analysis: &analysis,
+                },
+            )?;
+            transaction
+                .commit()
+                .map_err(|error| crate::state::map_sqlite_error(&db_path, &error))?;
+            let (next_step, other_actions) = build_staged_actions(&import_id);
+            result.dry_run = false;
+            result.message = format!(
+                "Import staged for review as `{import_id}`. No rows were written. Run driggsby import approve {import_id} to commit it."
+            );
+            result.import_id = Some(import_id);
+            result.next_step = next_step;
+            result.other_actions = other_actions;
+            result.staged = Some(true);
+        }
+        return Ok(result);
     }
 
-    let existing_deduped =
-        dedupe::dedupe_against_existing(&connection, &batch_deduped.candidate_rows, &db_path)?;
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| crate::state::map_sqlite_error(&db_path, &error))?;
+    let reverted = match replace_import_id {
+        Some(import_id) => undo::revert_import(
+            &transaction,
+            &db_path,
+            import_id,
+            &undo::UndoScope::default(),
+        )?,
+        None => undo::RevertedImport::default(),
+    };
+    let existing_deduped = dedupe::dedupe_against_existing(
+        &transaction,
+        &batch_deduped.candidate_rows,
+        &db_path,
+        &dedupe_policies,
+    )?;
     let duplicate_rows = merge_duplicate_rows(
         batch_deduped.duplicate_rows.clone(),
         existing_deduped.duplicate_rows.clone(),
     );
+    let probable_matches = probable::find_probable_duplicates(
+        &transaction,
+        &existing_deduped.insertable_rows,
+        &db_path,
+    )?;
     let import_id = format!("imp_{}", Ulid::new());
 
     let persisted = persist::persist_import(
-        &mut connection,
+        &transaction,
         &db_path,
         persist::PersistInput {
             import_id: &import_id,
@@ -191,8 +511,30 @@ pub(crate) fn execute(
             duplicate_rows: &duplicate_rows,
             source_kind: resolved_source.source_kind.as_str(),
             source_ref: resolved_source.source_ref.as_deref(),
+            sign_corrections: &applied_sign_corrections,
+            remaps: &applied_remaps,
+            statements: &statements_reconciled,
+            probable_duplicates: &probable_matches,
+            dedupe_policies: &dedupe_policies,
+            source_hash: &source_hash,
+            idempotency_key: idempotency_key.as_deref(),
+            source_records: &source_records,
+            rule_hits: &validated.rule_hits,
+            mode: persist::PersistMode::New,
         },
     )?;
+    if let Some(diff) = replace_diff.as_ref() {
+        replace::record_replacement(
+            &transaction,
+            &db_path,
+            &import_id,
+            diff,
+            &persist::now_timestamp(),
+        )?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| crate::state::map_sqlite_error(&db_path, &error))?;
     let ledger_accounts = crate::commands::accounts::query_accounts_data(&connection, &db_path)?;
     let summary = ImportCreateSummary {
         rows_read: validated.summary.rows_read,
@@ -213,7 +555,11 @@ pub(crate) fn execute(
         None,
     );
 
-    let message = if resolved_source.source_ignored.is_some() {
+    let message = if let Some(import_id) = replace_import_id {
+        format!(
+            "Import completed successfully. It replaced import `{import_id}`, which was reverted."
+        )
+    } else if resolved_source.source_ignored.is_some() {
         "Import completed successfully. File input was used and stdin was ignored.".to_string()
     } else {
         "Import completed successfully.".to_string()
@@ -235,12 +581,133 @@ pub(crate) fn execute(
         warnings: resolved_source.warnings,
         key_inventory: None,
         sign_profiles: None,
+        sign_corrections,
+        remaps,
+        alias_rewrites,
+        rule_changes,
+        category_suggestions: category_suggestions.filter(|_| request.apply_category_suggestions),
         drift_warnings: None,
+        reconciled_statements,
+        balance_gaps,
+        probable_duplicates: (!persisted.probable_duplicates.is_empty())
+            .then(|| persisted.probable_duplicates.clone()),
+        dedupe_policies: applied_dedupe_policies,
+        policy_warnings: validated.policy_warnings,
         ledger_accounts: Some(ledger_accounts),
+        source_hash,
+        idempotency_key,
+        replayed: None,
+        replacement: replace_diff.map(|diff| diff.to_contract(reverted)),
+        staged: None,
+        intelligence_impact: None,
     })
 }
 
-fn find_account_type_conflict_issues(
+fn normalize_idempotency_key(raw: Option<String>, dry_run: bool) -> ClientResult<Option<String>> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    let key = raw.trim();
+    if key.is_empty() {
+        return Err(ClientError::invalid_argument_for_command(
+            "--idempotency-key must not be empty.",
+            Some("import create"),
+        ));
+    }
+    if dry_run {
+        return Err(ClientError::invalid_argument_for_command(
+            "--idempotency-key cannot be combined with --dry-run; dry runs never write.",
+            Some("import create"),
+        ));
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// The result of a retried import whose idempotency key already committed the
+/// same source. Nothing is written; the original import is reported back.
+fn replayed_import_result(
+    connection: &rusqlite::Connection,
+    db_path: &std::path::Path,
+    prior: fingerprint::PriorImport,
+    idempotency_key: &str,
+    resolved_source: input::ResolvedSource,
+) -> ClientResult<ImportExecutionResult> {
+    let duplicate_summary =
+        build_duplicate_summary(prior.batch_duplicates, prior.existing_ledger_duplicates);
+    let (next_step, other_actions) = build_next_actions(
+        false,
+        Some(&prior.import_id),
+        duplicate_summary.total,
+        Some(resolved_source.source_kind.as_str()),
+        None,
+    );
+    let ledger_accounts = crate::commands::accounts::query_accounts_data(connection, db_path)?;
+
+    Ok(ImportExecutionResult {
+        dry_run: false,
+        message: format!(
+            "Import `{}` already committed this source with idempotency key `{idempotency_key}`. No rows were written.",
+            prior.import_id
+        ),
+        import_id: Some(prior.import_id),
+        summary: ImportCreateSummary {
+            rows_read: prior.rows_read,
+            rows_valid: prior.rows_valid,
+            rows_invalid: prior.rows_invalid,
+            inserted: prior.inserted,
+        },
+        duplicates_preview: ImportDuplicatesPreview {
+            returned: 0,
+            truncated: duplicate_summary.total > 0,
+            rows: Vec::new(),
+        },
+        duplicate_summary,
+        next_step,
+        other_actions,
+        issues: Vec::new(),
+        source_used: resolved_source.source_used,
+        source_ignored: resolved_source.source_ignored,
+        source_conflict: resolved_source.source_conflict,
+        warnings: resolved_source.warnings,
+        key_inventory: None,
+        sign_profiles: None,
+        sign_corrections: None,
+        remaps: None,
+        alias_rewrites: None,
+        rule_changes: None,
+        category_suggestions: None,
+        drift_warnings: None,
+        reconciled_statements: None,
+        balance_gaps: None,
+        probable_duplicates: None,
+        dedupe_policies: None,
+        policy_warnings: None,
+        ledger_accounts: Some(ledger_accounts),
+        source_hash: prior.source_hash.unwrap_or_default(),
+        idempotency_key: Some(idempotency_key.to_string()),
+        replayed: Some(true),
+        replacement: None,
+        staged: None,
+        intelligence_impact: None,
+    })
+}
+
+pub(crate) fn rejected_rows_error(
+    validated_summary: &ImportSummary,
+    issues: Vec<ImportIssue>,
+) -> ClientError {
+    let invalid_row_count = issues
+        .iter()
+        .map(|issue| issue.row)
+        .collect::<HashSet<i64>>()
+        .len() as i64;
+    let mut summary = validated_summary.clone();
+    summary.rows_invalid = invalid_row_count;
+    summary.rows_valid = summary.rows_read - invalid_row_count;
+    ClientError::import_validation_failed(summary, issues)
+}
+
+pub(crate) fn find_account_type_conflict_issues(
     connection: &rusqlite::Connection,
     account_type_rows: &std::collections::HashMap<(String, String), Vec<i64>>,
     db_path: &std::path::Path,
@@ -287,7 +754,7 @@ fn find_account_type_conflict_issues(
     Ok(issues)
 }
 
-fn merge_duplicate_rows(
+pub(crate) fn merge_duplicate_rows(
     mut batch_rows: Vec<dedupe::DuplicateRecord>,
     mut existing_rows: Vec<dedupe::DuplicateRecord>,
 ) -> Vec<dedupe::DuplicateRecord> {
@@ -306,7 +773,7 @@ fn merge_duplicate_rows(
     all_rows
 }
 
-fn build_duplicate_summary(batch: i64, existing_ledger: i64) -> ImportDuplicateSummary {
+pub(crate) fn build_duplicate_summary(batch: i64, existing_ledger: i64) -> ImportDuplicateSummary {
     ImportDuplicateSummary {
         total: batch + existing_ledger,
         batch,
@@ -330,6 +797,7 @@ fn build_duplicates_preview(rows: &[dedupe::DuplicateRecord]) -> ImportDuplicate
 
 pub(crate) fn duplicate_record_to_contract(record: &dedupe::DuplicateRecord) -> ImportDuplicateRow {
     ImportDuplicateRow {
+        candidate_id: None,
         source_row_index: record.source_row_index,
         dedupe_reason: record.dedupe_reason.as_str().to_string(),
         statement_id: record.row.statement_id.clone(),
@@ -344,6 +812,7 @@ pub(crate) fn duplicate_record_to_contract(record: &dedupe::DuplicateRecord) ->
         matched_import_id: record.matched_import_id.clone(),
         matched_txn_id_at_dedupe: record.matched_txn_id.clone(),
         matched_import_id_at_dedupe: record.matched_import_id.clone(),
+        promoted_txn_id: None,
     }
 }
 
@@ -417,6 +886,27 @@ fn build_next_actions(
     )
 }
 
+fn build_staged_actions(import_id: &str) -> (ImportNextStep, Vec<ImportAction>) {
+    (
+        ImportNextStep {
+            label: "Approve this import".to_string(),
+            command: build_import_command_with_id("approve", import_id),
+        },
+        vec![
+            ImportAction {
+                label: "Review the staged import".to_string(),
+                command: build_import_command_with_id("staged show", import_id),
+                risk: None,
+            },
+            ImportAction {
+                label: "Reject this import (destructive)".to_string(),
+                command: build_import_command_with_id("reject", import_id),
+                risk: Some("destructive".to_string()),
+            },
+        ],
+    )
+}
+
 fn build_create_command_with_path(path: &str) -> String {
     if let Some(quoted_path) = quote_shell_arg(path) {
         format!("driggsby import create {quoted_path}")
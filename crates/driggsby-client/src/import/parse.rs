@@ -19,9 +19,30 @@ pub(crate) struct ParsedRow {
     pub(crate) external_id: Option<String>,
     pub(crate) merchant: Option<String>,
     pub(crate) category: Option<String>,
+    pub(crate) balance: Option<String>,
+    pub(crate) mcc: Option<String>,
+    pub(crate) statement_opening_balance: Option<String>,
+    pub(crate) statement_closing_balance: Option<String>,
 }
 
-pub(crate) fn parse_source(content: &str) -> ClientResult<Vec<ParsedRow>> {
+#[derive(Debug, Clone)]
+pub(crate) struct ParsedStatementHeader {
+    pub(crate) index: i64,
+    pub(crate) statement_id: Option<String>,
+    pub(crate) account_key: Option<String>,
+    pub(crate) opening_balance: Option<String>,
+    pub(crate) closing_balance: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ParsedSource {
+    pub(crate) rows: Vec<ParsedRow>,
+    pub(crate) statement_headers: Vec<ParsedStatementHeader>,
+    /// Original source record (JSON object or CSV line) for each row, in row order.
+    pub(crate) source_records: Vec<String>,
+}
+
+pub(crate) fn parse_source(content: &str) -> ClientResult<ParsedSource> {
     let trimmed = content.trim();
     if trimmed.is_empty() {
         return Err(invalid_input_error("Import source is empty."));
@@ -35,14 +56,29 @@ pub(crate) fn parse_source(content: &str) -> ClientResult<Vec<ParsedRow>> {
     }
 
     if trimmed.starts_with('[') {
-        return parse_json_array(trimmed);
+        let (rows, source_records) = parse_json_array(trimmed)?;
+        return Ok(ParsedSource {
+            rows,
+            statement_headers: Vec::new(),
+            source_records,
+        });
     }
 
     if looks_like_csv(trimmed) {
-        return parse_csv(trimmed);
+        let (rows, source_records) = parse_csv(trimmed)?;
+        return Ok(ParsedSource {
+            rows,
+            statement_headers: Vec::new(),
+            source_records,
+        });
     }
 
-    if serde_json::from_str::<Value>(trimmed).is_ok() {
+    if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+        if let Some(object) = value.as_object()
+            && object.contains_key("transactions")
+        {
+            return parse_json_envelope(object);
+        }
         return Err(ClientError::invalid_import_format(
             "JSON input must be a top-level array of transaction objects.",
             "json_non_array",
@@ -55,7 +91,7 @@ pub(crate) fn parse_source(content: &str) -> ClientResult<Vec<ParsedRow>> {
     ))
 }
 
-fn parse_json_array(content: &str) -> ClientResult<Vec<ParsedRow>> {
+fn parse_json_array(content: &str) -> ClientResult<(Vec<ParsedRow>, Vec<String>)> {
     let parsed = serde_json::from_str::<Value>(content)
         .map_err(|_| invalid_input_error("Invalid JSON input. Provide a valid JSON array."))?;
 
@@ -65,7 +101,53 @@ fn parse_json_array(content: &str) -> ClientResult<Vec<ParsedRow>> {
         ));
     };
 
+    parse_json_rows(items)
+}
+
+fn parse_json_envelope(object: &serde_json::Map<String, Value>) -> ClientResult<ParsedSource> {
+    let Some(items) = object.get("transactions").and_then(Value::as_array) else {
+        return Err(invalid_input_error(
+            "JSON `transactions` must be an array of transaction objects.",
+        ));
+    };
+    let (rows, source_records) = parse_json_rows(items)?;
+
+    let headers = match object.get("statements") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(headers)) => headers.clone(),
+        Some(_) => {
+            return Err(invalid_input_error(
+                "JSON `statements` must be an array of statement balance objects.",
+            ));
+        }
+    };
+
+    let mut statement_headers = Vec::new();
+    for (index, header) in headers.iter().enumerate() {
+        let Some(header_object) = header.as_object() else {
+            return Err(invalid_input_error(
+                "JSON `statements` entries must all be objects with statement balance fields.",
+            ));
+        };
+        statement_headers.push(ParsedStatementHeader {
+            index: (index as i64) + 1,
+            statement_id: read_optional_string(header_object.get("statement_id")),
+            account_key: read_optional_string(header_object.get("account_key")),
+            opening_balance: read_optional_string(header_object.get("opening_balance")),
+            closing_balance: read_optional_string(header_object.get("closing_balance")),
+        });
+    }
+
+    Ok(ParsedSource {
+        rows,
+        statement_headers,
+        source_records,
+    })
+}
+
+fn parse_json_rows(items: &[Value]) -> ClientResult<(Vec<ParsedRow>, Vec<String>)> {
     let mut rows = Vec::new();
+    let mut source_records = Vec::new();
     for (index, item) in items.iter().enumerate() {
         let Some(object) = item.as_object() else {
             return Err(invalid_input_error(
@@ -85,13 +167,22 @@ fn parse_json_array(content: &str) -> ClientResult<Vec<ParsedRow>> {
             external_id: read_optional_string(object.get("external_id")),
             merchant: read_optional_string(object.get("merchant")),
             category: read_optional_string(object.get("category")),
+            balance: read_optional_string(object.get("balance")),
+            mcc: read_optional_string(object.get("mcc")),
+            statement_opening_balance: read_optional_string(
+                object.get("statement_opening_balance"),
+            ),
+            statement_closing_balance: read_optional_string(
+                object.get("statement_closing_balance"),
+            ),
         });
+        source_records.push(item.to_string());
     }
 
-    Ok(rows)
+    Ok((rows, source_records))
 }
 
-fn parse_csv(content: &str) -> ClientResult<Vec<ParsedRow>> {
+fn parse_csv(content: &str) -> ClientResult<(Vec<ParsedRow>, Vec<String>)> {
     let mut reader = csv::ReaderBuilder::new()
         .has_headers(true)
         .from_reader(content.as_bytes());
@@ -126,9 +217,16 @@ fn parse_csv(content: &str) -> ClientResult<Vec<ParsedRow>> {
         .collect::<HashMap<String, usize>>();
 
     let mut rows = Vec::new();
+    let mut record_starts = Vec::new();
     for (row_index, result_row) in reader.records().enumerate() {
         let record =
             result_row.map_err(|_| invalid_input_error("CSV rows are malformed or not UTF-8."))?;
+        record_starts.push(
+            record
+                .position()
+                .map(|position| position.byte() as usize)
+                .unwrap_or(content.len()),
+        );
 
         rows.push(ParsedRow {
             row: (row_index as i64) + 1,
@@ -142,10 +240,43 @@ fn parse_csv(content: &str) -> ClientResult<Vec<ParsedRow>> {
             external_id: value_for(&record, &index_by_name, "external_id"),
             merchant: value_for(&record, &index_by_name, "merchant"),
             category: value_for(&record, &index_by_name, "category"),
+            balance: value_for(&record, &index_by_name, "balance"),
+            mcc: value_for(&record, &index_by_name, "mcc"),
+            statement_opening_balance: value_for(
+                &record,
+                &index_by_name,
+                "statement_opening_balance",
+            ),
+            statement_closing_balance: value_for(
+                &record,
+                &index_by_name,
+                "statement_closing_balance",
+            ),
         });
     }
 
-    Ok(rows)
+    let source_records = csv_source_records(content, &record_starts);
+    Ok((rows, source_records))
+}
+
+/// Slices each record's original text out of the CSV content, so quoting and
+/// embedded newlines are preserved exactly as they appeared in the file.
+fn csv_source_records(content: &str, record_starts: &[usize]) -> Vec<String> {
+    record_starts
+        .iter()
+        .enumerate()
+        .map(|(index, start)| {
+            let end = record_starts
+                .get(index + 1)
+                .copied()
+                .unwrap_or(content.len());
+            content
+                .get(*start..end)
+                .unwrap_or_default()
+                .trim_matches(['\r', '\n'])
+                .to_string()
+        })
+        .collect()
 }
 
 fn value_for(
@@ -2,13 +2,21 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use rusqlite::{Connection, TransactionBehavior, params};
+use rusqlite::params;
 use ulid::Ulid;
 
 use crate::ClientResult;
+use crate::contracts::types::ImportProbableDuplicate;
+use crate::identity::assign_identity_key;
 use crate::import::CanonicalTransaction;
-use crate::import::dedupe::{BatchRow, DuplicateRecord, dedupe_key};
+use crate::import::dedupe::{BatchRow, DedupePolicies, DedupePolicy, DuplicateRecord, dedupe_key};
+use crate::import::probable::{ProbableMatch, insert_probable_duplicates};
+use crate::import::provenance::{insert_source_rows, link_source_rows};
+use crate::import::remap::AppliedRemap;
+use crate::import::sign_profiles::SignCorrection;
+use crate::import::statements::ReconciledStatement;
 use crate::intelligence::refresh::refresh_all_in_transaction;
+use crate::rules::{RuleHit, record_hits};
 use crate::state::map_sqlite_error;
 
 #[derive(Debug, Clone)]
@@ -16,6 +24,7 @@ pub(crate) struct PersistResult {
     pub(crate) import_id: String,
     pub(crate) inserted: i64,
     pub(crate) duplicate_rows: Vec<DuplicateRecord>,
+    pub(crate) probable_duplicates: Vec<ImportProbableDuplicate>,
 }
 
 pub(crate) struct PersistInput<'a> {
@@ -27,6 +36,30 @@ pub(crate) struct PersistInput<'a> {
     pub(crate) rows_invalid: i64,
     pub(crate) source_kind: &'a str,
     pub(crate) source_ref: Option<&'a str>,
+    pub(crate) sign_corrections: &'a [SignCorrection],
+    pub(crate) remaps: &'a [AppliedRemap],
+    pub(crate) statements: &'a [ReconciledStatement],
+    pub(crate) probable_duplicates: &'a [ProbableMatch],
+    pub(crate) dedupe_policies: &'a DedupePolicies,
+    pub(crate) source_hash: &'a str,
+    pub(crate) idempotency_key: Option<&'a str>,
+    /// The original record of each source row, by position.
+    pub(crate) source_records: &'a [Option<String>],
+    /// Rule changes made while validating, keyed by source row index.
+    pub(crate) rule_hits: &'a HashMap<i64, Vec<RuleHit>>,
+    pub(crate) mode: PersistMode<'a>,
+}
+
+/// How the import run behind a persisted batch came to be.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PersistMode<'a> {
+    /// A fresh import: the run is inserted as committed.
+    New,
+    /// `import approve`: the staged run already exists and is committed now.
+    Staged,
+    /// `import redo`: the run already exists and ledger rows get back the ids
+    /// they had before the undo, keyed by source row index.
+    Restored(&'a HashMap<i64, String>),
 }
 
 #[derive(Debug, Clone, Default)]
@@ -36,23 +69,45 @@ struct AccountImportStatCounter {
     deduped: i64,
 }
 
+/// Writes an import inside the caller's transaction and refreshes
+/// intelligence. The caller commits.
 pub(crate) fn persist_import(
-    connection: &mut Connection,
+    transaction: &rusqlite::Transaction<'_>,
     db_path: &Path,
     input: PersistInput<'_>,
 ) -> ClientResult<PersistResult> {
     let timestamp = now_timestamp();
 
-    let transaction = connection
-        .transaction_with_behavior(TransactionBehavior::Immediate)
-        .map_err(|error| map_sqlite_error(db_path, &error))?;
-
     let mut inserted = 0_i64;
     let mut account_stats: HashMap<String, AccountImportStatCounter> = HashMap::new();
+    let mut txn_ids: HashMap<i64, String> = HashMap::new();
     for batch_row in input.candidate_rows {
-        insert_canonical_row(&transaction, db_path, input.import_id, &batch_row.row)?;
+        let restored_txn_id = match input.mode {
+            PersistMode::Restored(txn_ids) => txn_ids.get(&batch_row.source_row_index),
+            PersistMode::New | PersistMode::Staged => None,
+        };
+        let identity_key = assign_identity_key(
+            transaction,
+            db_path,
+            &batch_row.row,
+            input
+                .dedupe_policies
+                .for_account(&batch_row.row.account_key),
+        )?;
+        let txn_id = insert_canonical_row(
+            transaction,
+            db_path,
+            input.import_id,
+            &batch_row.row,
+            &identity_key,
+            restored_txn_id,
+        )?;
+        txn_ids.insert(batch_row.source_row_index, txn_id);
+        if let Some(hits) = input.rule_hits.get(&batch_row.source_row_index) {
+            record_hits(transaction, db_path, &identity_key, hits, &timestamp)?;
+        }
         upsert_account_metadata(
-            &transaction,
+            transaction,
             db_path,
             &batch_row.row.account_key,
             batch_row.row.account_type.as_deref(),
@@ -68,7 +123,7 @@ pub(crate) fn persist_import(
 
     for duplicate_row in input.duplicate_rows {
         upsert_account_metadata(
-            &transaction,
+            transaction,
             db_path,
             &duplicate_row.row.account_key,
             duplicate_row.row.account_type.as_deref(),
@@ -80,59 +135,256 @@ pub(crate) fn persist_import(
         stat.rows_read += 1;
         stat.deduped += 1;
         insert_dedupe_candidate(
-            &transaction,
+            transaction,
             db_path,
             input.import_id,
             duplicate_row,
+            input
+                .dedupe_policies
+                .for_account(&duplicate_row.row.account_key),
             &timestamp,
         )?;
     }
 
     let deduped_total = input.duplicate_rows.len() as i64;
 
+    if matches!(input.mode, PersistMode::New) {
+        transaction
+            .execute(
+                "INSERT INTO internal_import_runs (
+                    import_id,
+                    status,
+                    created_at,
+                    committed_at,
+                    rows_read,
+                    rows_valid,
+                    rows_invalid,
+                    inserted,
+                    deduped,
+                    source_kind,
+                    source_ref,
+                    source_hash,
+                    idempotency_key,
+                    dedupe_policies
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    input.import_id,
+                    "committed",
+                    &timestamp,
+                    &timestamp,
+                    input.rows_read,
+                    input.rows_valid,
+                    input.rows_invalid,
+                    inserted,
+                    deduped_total,
+                    input.source_kind,
+                    input.source_ref,
+                    input.source_hash,
+                    input.idempotency_key,
+                    input.dedupe_policies.to_json()
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    } else {
+        mark_run_committed(
+            transaction,
+            db_path,
+            input.import_id,
+            inserted,
+            deduped_total,
+            input.dedupe_policies,
+            &timestamp,
+        )?;
+    }
+
+    insert_import_account_stats(transaction, db_path, input.import_id, &account_stats)?;
+    insert_sign_corrections(
+        transaction,
+        db_path,
+        input.import_id,
+        input.sign_corrections,
+        &timestamp,
+    )?;
+    insert_remaps(
+        transaction,
+        db_path,
+        input.import_id,
+        input.remaps,
+        &timestamp,
+    )?;
+    insert_reconciled_statements(
+        transaction,
+        db_path,
+        input.import_id,
+        input.statements,
+        &timestamp,
+    )?;
+    let probable_duplicates = insert_probable_duplicates(
+        transaction,
+        db_path,
+        input.import_id,
+        input.probable_duplicates,
+        &txn_ids,
+        &timestamp,
+    )?;
+    if let PersistMode::Restored(_) = input.mode {
+        link_source_rows(transaction, db_path, input.import_id, &txn_ids)?;
+    } else {
+        insert_source_rows(
+            transaction,
+            db_path,
+            input.import_id,
+            input.source_records,
+            &txn_ids,
+        )?;
+    }
+    refresh_all_in_transaction(transaction, db_path)?;
+
+    Ok(PersistResult {
+        import_id: input.import_id.to_string(),
+        inserted,
+        duplicate_rows: input.duplicate_rows.to_vec(),
+        probable_duplicates,
+    })
+}
+
+/// Commits a run that already exists: a staged import being approved or a
+/// reverted one being restored.
+fn mark_run_committed(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    inserted: i64,
+    deduped: i64,
+    dedupe_policies: &DedupePolicies,
+    timestamp: &str,
+) -> ClientResult<()> {
     transaction
         .execute(
-            "INSERT INTO internal_import_runs (
+            "UPDATE internal_import_runs
+             SET status = 'committed',
+                 committed_at = ?2,
+                 reverted_at = NULL,
+                 inserted = ?3,
+                 deduped = ?4,
+                 dedupe_policies = ?5
+             WHERE import_id = ?1",
+            params![
                 import_id,
-                status,
-                created_at,
-                committed_at,
-                rows_read,
-                rows_valid,
-                rows_invalid,
+                timestamp,
                 inserted,
                 deduped,
-                source_kind,
-                source_ref
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-            params![
-                input.import_id,
-                "committed",
-                &timestamp,
-                &timestamp,
-                input.rows_read,
-                input.rows_valid,
-                input.rows_invalid,
-                inserted,
-                deduped_total,
-                input.source_kind,
-                input.source_ref
+                dedupe_policies.to_json()
             ],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
 
-    insert_import_account_stats(&transaction, db_path, input.import_id, &account_stats)?;
-    refresh_all_in_transaction(&transaction, db_path)?;
+pub(crate) fn insert_sign_corrections(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    corrections: &[SignCorrection],
+    timestamp: &str,
+) -> ClientResult<()> {
+    for correction in corrections {
+        transaction
+            .execute(
+                "INSERT INTO internal_import_sign_corrections (
+                    import_id,
+                    account_key,
+                    rows_inverted,
+                    historical_negative_ratio,
+                    incoming_negative_ratio,
+                    created_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    import_id,
+                    &correction.account_key,
+                    correction.rows_inverted,
+                    correction.historical_negative_ratio,
+                    correction.incoming_negative_ratio,
+                    timestamp
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
 
-    transaction
-        .commit()
-        .map_err(|error| map_sqlite_error(db_path, &error))?;
+pub(crate) fn insert_remaps(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    remaps: &[AppliedRemap],
+    timestamp: &str,
+) -> ClientResult<()> {
+    for remap in remaps {
+        transaction
+            .execute(
+                "INSERT INTO internal_import_remaps (
+                    import_id,
+                    property,
+                    from_value,
+                    to_value,
+                    rows_rewritten,
+                    created_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    import_id,
+                    remap.rule.property.as_str(),
+                    &remap.rule.from,
+                    &remap.rule.to,
+                    remap.rows_rewritten,
+                    timestamp
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
 
-    Ok(PersistResult {
-        import_id: input.import_id.to_string(),
-        inserted,
-        duplicate_rows: input.duplicate_rows.to_vec(),
-    })
+fn insert_reconciled_statements(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    statements: &[ReconciledStatement],
+    timestamp: &str,
+) -> ClientResult<()> {
+    for statement in statements {
+        transaction
+            .execute(
+                "INSERT INTO internal_statements (
+                    account_key,
+                    statement_id,
+                    import_id,
+                    currency,
+                    opening_balance,
+                    closing_balance,
+                    net_amount,
+                    txn_count,
+                    first_posted_at,
+                    last_posted_at,
+                    reconciled_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    &statement.account_key,
+                    &statement.statement_id,
+                    import_id,
+                    &statement.currency,
+                    statement.opening_balance,
+                    statement.closing_balance,
+                    statement.net_amount,
+                    statement.txn_count,
+                    &statement.first_posted_at,
+                    &statement.last_posted_at,
+                    timestamp
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
 }
 
 fn insert_canonical_row(
@@ -140,8 +392,12 @@ fn insert_canonical_row(
     db_path: &Path,
     import_id: &str,
     row: &CanonicalTransaction,
-) -> ClientResult<()> {
-    let txn_id = format!("txn_{}", Ulid::new());
+    identity_key: &str,
+    restored_txn_id: Option<&String>,
+) -> ClientResult<String> {
+    let txn_id = restored_txn_id
+        .cloned()
+        .unwrap_or_else(|| format!("txn_{}", Ulid::new()));
     transaction
         .execute(
             "INSERT INTO internal_transactions (
@@ -156,8 +412,11 @@ fn insert_canonical_row(
                 description,
                 external_id,
                 merchant,
-                category
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                category,
+                balance,
+                mcc,
+                identity_key
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 &txn_id,
                 import_id,
@@ -171,21 +430,25 @@ fn insert_canonical_row(
                 &row.external_id,
                 &row.merchant,
                 &row.category,
+                row.balance,
+                &row.mcc,
+                identity_key,
             ],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
-    Ok(())
+    Ok(txn_id)
 }
 
-fn insert_dedupe_candidate(
+pub(crate) fn insert_dedupe_candidate(
     transaction: &rusqlite::Transaction<'_>,
     db_path: &Path,
     import_id: &str,
     duplicate_row: &DuplicateRecord,
+    dedupe_policy: &DedupePolicy,
     timestamp: &str,
-) -> ClientResult<()> {
+) -> ClientResult<String> {
     let candidate_id = format!("cand_{}", Ulid::new());
-    let key = dedupe_key(&duplicate_row.row);
+    let key = dedupe_key(&duplicate_row.row, dedupe_policy);
     transaction
         .execute(
             "INSERT INTO internal_transaction_dedupe_candidates (
@@ -202,16 +465,18 @@ fn insert_dedupe_candidate(
                 external_id,
                 merchant,
                 category,
+                balance,
                 source_row_index,
                 dedupe_reason,
                 matched_txn_id,
                 matched_import_id,
                 matched_batch_row_index,
                 created_at,
-                promoted_txn_id
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, NULL)",
+                promoted_txn_id,
+                mcc
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, NULL, ?21)",
             params![
-                candidate_id,
+                &candidate_id,
                 import_id,
                 key,
                 &duplicate_row.row.statement_id,
@@ -224,16 +489,18 @@ fn insert_dedupe_candidate(
                 &duplicate_row.row.external_id,
                 &duplicate_row.row.merchant,
                 &duplicate_row.row.category,
+                duplicate_row.row.balance,
                 duplicate_row.source_row_index,
                 duplicate_row.dedupe_reason.as_str(),
                 &duplicate_row.matched_txn_id,
                 &duplicate_row.matched_import_id,
                 duplicate_row.matched_batch_row_index,
-                timestamp
+                timestamp,
+                &duplicate_row.row.mcc
             ],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
-    Ok(())
+    Ok(candidate_id)
 }
 
 fn upsert_account_metadata(
@@ -0,0 +1,592 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Deserialize;
+
+use crate::contracts::types::ImportIssue;
+use crate::import::CanonicalTransaction;
+use crate::import::dedupe::{DedupeKeyField, DedupePolicies, DedupePolicy};
+use crate::import::persist::now_timestamp;
+use crate::import::validate::canonical_account_type;
+use crate::state::{map_io_error, map_sqlite_error};
+use crate::{ClientError, ClientResult};
+
+pub(crate) const POLICY_FILE_NAME: &str = "import_policy.json";
+
+const MAX_DEDUPE_DATE_TOLERANCE_DAYS: i64 = 31;
+
+const REQUIRABLE_FIELDS: [&str; 6] = [
+    "statement_id",
+    "account_type",
+    "external_id",
+    "merchant",
+    "category",
+    "balance",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PolicySeverity {
+    #[default]
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum PolicyRule {
+    PostedAtNotInFuture {
+        #[serde(default)]
+        severity: PolicySeverity,
+    },
+    PostedAtNotBefore {
+        date: String,
+        #[serde(default)]
+        severity: PolicySeverity,
+    },
+    MaxAbsAmount {
+        limit: f64,
+        #[serde(default)]
+        account_type: Option<String>,
+        #[serde(default)]
+        severity: PolicySeverity,
+    },
+    RequireField {
+        field: String,
+        #[serde(default)]
+        account_type: Option<String>,
+        #[serde(default)]
+        severity: PolicySeverity,
+    },
+    CurrencyAllowlist {
+        currencies: Vec<String>,
+        #[serde(default)]
+        severity: PolicySeverity,
+    },
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ImportPolicy {
+    #[serde(default)]
+    pub(crate) rules: Vec<PolicyRule>,
+    /// Per-account dedupe policies keyed by `account_key`.
+    #[serde(default)]
+    pub(crate) dedupe: BTreeMap<String, DedupePolicy>,
+}
+
+impl ImportPolicy {
+    pub(crate) fn dedupe_policies(&self) -> DedupePolicies {
+        DedupePolicies::new(self.dedupe.clone())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PolicyFindings {
+    pub(crate) errors: Vec<ImportIssue>,
+    pub(crate) warnings: Vec<ImportIssue>,
+}
+
+impl PolicyFindings {
+    fn push(&mut self, severity: PolicySeverity, issue: ImportIssue) {
+        match severity {
+            PolicySeverity::Error => self.errors.push(issue),
+            PolicySeverity::Warning => self.warnings.push(issue),
+        }
+    }
+}
+
+pub(crate) fn policy_path(ledger_home: &Path) -> PathBuf {
+    ledger_home.join(POLICY_FILE_NAME)
+}
+
+/// Dedupe policies from the import policy next to `db_path`, for callers
+/// outside the import pipeline that re-run dedupe matching.
+pub(crate) fn load_dedupe_policies(db_path: &Path) -> ClientResult<DedupePolicies> {
+    let policy = match db_path.parent() {
+        Some(ledger_home) => load_policy(ledger_home)?,
+        None => None,
+    };
+    Ok(policy
+        .as_ref()
+        .map(ImportPolicy::dedupe_policies)
+        .unwrap_or_default())
+}
+
+/// Dedupe policies `import_id` ran under. Undo, redo and candidate promotion
+/// use them so the run's rows and candidates are keyed the way they were at
+/// import time, whatever the policy file says now. Runs recorded before
+/// policies were stored fall back to the current policy file.
+pub(crate) fn load_run_dedupe_policies(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<DedupePolicies> {
+    let stored = connection
+        .query_row(
+            "SELECT dedupe_policies FROM internal_import_runs WHERE import_id = ?1",
+            params![import_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?
+        .flatten();
+    match stored {
+        Some(json) => DedupePolicies::from_json(&json).map_err(|error| {
+            ClientError::internal_serialization(&format!(
+                "Stored dedupe policies for import `{import_id}` could not be read: {error}"
+            ))
+        }),
+        None => load_dedupe_policies(db_path),
+    }
+}
+
+/// Loads the ledger's import policy, or `None` when the ledger has no policy
+/// file. Rules are checked up front so a typo fails loudly instead of
+/// silently never matching.
+pub(crate) fn load_policy(ledger_home: &Path) -> ClientResult<Option<ImportPolicy>> {
+    let path = policy_path(ledger_home);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|error| map_io_error(&path, &error))?;
+    let mut policy = serde_json::from_str::<ImportPolicy>(&content)
+        .map_err(|error| ClientError::import_policy_invalid(&path, &error.to_string()))?;
+    normalize_rules(&mut policy)
+        .map_err(|detail| ClientError::import_policy_invalid(&path, &detail))?;
+    check_dedupe_policies(&policy.dedupe)
+        .map_err(|detail| ClientError::import_policy_invalid(&path, &detail))?;
+    Ok(Some(policy))
+}
+
+fn normalize_rules(policy: &mut ImportPolicy) -> Result<(), String> {
+    for (index, rule) in policy.rules.iter_mut().enumerate() {
+        match rule {
+            PolicyRule::PostedAtNotInFuture { .. } => {}
+            PolicyRule::PostedAtNotBefore { date, .. } => {
+                if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+                    return Err(format!(
+                        "rules[{index}].date must be a YYYY-MM-DD date; got `{date}`."
+                    ));
+                }
+            }
+            PolicyRule::MaxAbsAmount {
+                limit,
+                account_type,
+                ..
+            } => {
+                if !limit.is_finite() || *limit <= 0.0 {
+                    return Err(format!(
+                        "rules[{index}].limit must be a positive number; got `{limit}`."
+                    ));
+                }
+                normalize_account_type(index, account_type)?;
+            }
+            PolicyRule::RequireField {
+                field,
+                account_type,
+                ..
+            } => {
+                if !REQUIRABLE_FIELDS.contains(&field.as_str()) {
+                    return Err(format!(
+                        "rules[{index}].field must be one of {}; got `{field}`.",
+                        REQUIRABLE_FIELDS.join(", ")
+                    ));
+                }
+                normalize_account_type(index, account_type)?;
+            }
+            PolicyRule::CurrencyAllowlist { currencies, .. } => {
+                if currencies.is_empty() {
+                    return Err(format!("rules[{index}].currencies must not be empty."));
+                }
+                for currency in currencies.iter_mut() {
+                    *currency = currency.trim().to_uppercase();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_dedupe_policies(policies: &BTreeMap<String, DedupePolicy>) -> Result<(), String> {
+    for (account_key, policy) in policies {
+        if policy.key_fields.is_empty() {
+            return Err(format!(
+                "dedupe.{account_key}.key_fields must list at least one field."
+            ));
+        }
+        for (index, field) in policy.key_fields.iter().enumerate() {
+            if policy.key_fields[..index].contains(field) {
+                return Err(format!(
+                    "dedupe.{account_key}.key_fields lists `{}` more than once.",
+                    field.as_str()
+                ));
+            }
+        }
+        if !(0..=MAX_DEDUPE_DATE_TOLERANCE_DAYS).contains(&policy.date_tolerance_days) {
+            return Err(format!(
+                "dedupe.{account_key}.date_tolerance_days must be between 0 and {MAX_DEDUPE_DATE_TOLERANCE_DAYS}; got `{}`.",
+                policy.date_tolerance_days
+            ));
+        }
+        if policy.date_tolerance_days > 0 && !policy.key_fields.contains(&DedupeKeyField::PostedAt)
+        {
+            return Err(format!(
+                "dedupe.{account_key}.date_tolerance_days needs `posted_at` in key_fields."
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn normalize_account_type(index: usize, account_type: &mut Option<String>) -> Result<(), String> {
+    let Some(value) = account_type.as_ref() else {
+        return Ok(());
+    };
+    match canonical_account_type(value) {
+        Some(canonical) => {
+            *account_type = Some(canonical);
+            Ok(())
+        }
+        None => Err(format!(
+            "rules[{index}].account_type `{value}` is not a recognized account type."
+        )),
+    }
+}
+
+/// Today's UTC date, used as the cutoff for `posted_at_not_in_future`.
+pub(crate) fn today_utc() -> NaiveDate {
+    now_timestamp()
+        .parse::<i64>()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map(|timestamp| timestamp.date_naive())
+        .unwrap_or_default()
+}
+
+/// Account types already recorded in the ledger, keyed by `account_key`.
+pub(crate) fn load_ledger_account_types(
+    connection: &Connection,
+    db_path: &Path,
+) -> ClientResult<HashMap<String, String>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT account_key, account_type
+             FROM internal_accounts
+             WHERE account_type IS NOT NULL
+               AND TRIM(account_type) <> ''",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    rows.collect::<Result<HashMap<String, String>, _>>()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+/// Evaluates every rule against the validated rows. `row_numbers` holds the
+/// source row number of each entry in `rows`. Account-type scoped rules use
+/// the type declared for the account anywhere in the import, falling back to
+/// the type `ledger_account_types` already records for it.
+pub(crate) fn evaluate(
+    policy: &ImportPolicy,
+    rows: &[CanonicalTransaction],
+    row_numbers: &[i64],
+    ledger_account_types: &HashMap<String, String>,
+    today: NaiveDate,
+) -> PolicyFindings {
+    let mut account_types: HashMap<&str, &str> = ledger_account_types
+        .iter()
+        .map(|(account_key, account_type)| (account_key.as_str(), account_type.as_str()))
+        .collect();
+    let mut declared: HashSet<&str> = HashSet::new();
+    for row in rows {
+        if let Some(account_type) = row.account_type.as_deref()
+            && declared.insert(row.account_key.as_str())
+        {
+            account_types.insert(row.account_key.as_str(), account_type);
+        }
+    }
+
+    let mut findings = PolicyFindings::default();
+    for (row, row_number) in rows.iter().zip(row_numbers.iter().copied()) {
+        let row_account_type = account_types.get(row.account_key.as_str()).copied();
+        for rule in &policy.rules {
+            evaluate_rule(
+                rule,
+                row,
+                row_number,
+                row_account_type,
+                today,
+                &mut findings,
+            );
+        }
+    }
+    findings.errors.sort_by_key(|issue| issue.row);
+    findings.warnings.sort_by_key(|issue| issue.row);
+    findings
+}
+
+fn evaluate_rule(
+    rule: &PolicyRule,
+    row: &CanonicalTransaction,
+    row_number: i64,
+    row_account_type: Option<&str>,
+    today: NaiveDate,
+    findings: &mut PolicyFindings,
+) {
+    let applies_to = |account_type: &Option<String>| {
+        account_type
+            .as_deref()
+            .is_none_or(|wanted| row_account_type == Some(wanted))
+    };
+
+    match rule {
+        PolicyRule::PostedAtNotInFuture { severity } => {
+            let today_text = today.format("%Y-%m-%d").to_string();
+            if row.posted_at > today_text {
+                findings.push(
+                    *severity,
+                    ImportIssue {
+                        row: row_number,
+                        field: "posted_at".to_string(),
+                        code: "policy_posted_at_in_future".to_string(),
+                        description: format!(
+                            "posted_at {} is after today ({today_text}).",
+                            row.posted_at
+                        ),
+                        expected: Some(format!("<= {today_text}")),
+                        received: Some(row.posted_at.clone()),
+                    },
+                );
+            }
+        }
+        PolicyRule::PostedAtNotBefore { date, severity } => {
+            if row.posted_at < *date {
+                findings.push(
+                    *severity,
+                    ImportIssue {
+                        row: row_number,
+                        field: "posted_at".to_string(),
+                        code: "policy_posted_at_too_early".to_string(),
+                        description: format!(
+                            "posted_at {} is before the policy minimum {date}.",
+                            row.posted_at
+                        ),
+                        expected: Some(format!(">= {date}")),
+                        received: Some(row.posted_at.clone()),
+                    },
+                );
+            }
+        }
+        PolicyRule::MaxAbsAmount {
+            limit,
+            account_type,
+            severity,
+        } => {
+            if applies_to(account_type) && row.amount.abs() > *limit {
+                let scope = account_type
+                    .as_deref()
+                    .map(|value| format!(" for {value} accounts"))
+                    .unwrap_or_default();
+                findings.push(
+                    *severity,
+                    ImportIssue {
+                        row: row_number,
+                        field: "amount".to_string(),
+                        code: "policy_amount_over_limit".to_string(),
+                        description: format!(
+                            "abs(amount) {:.2} exceeds the policy limit {limit:.2}{scope}.",
+                            row.amount.abs()
+                        ),
+                        expected: Some(format!("abs(amount) <= {limit:.2}")),
+                        received: Some(format!("{:.2}", row.amount)),
+                    },
+                );
+            }
+        }
+        PolicyRule::RequireField {
+            field,
+            account_type,
+            severity,
+        } => {
+            if applies_to(account_type) && !has_field(row, field) {
+                let scope = account_type
+                    .as_deref()
+                    .map(|value| format!(" for {value} accounts"))
+                    .unwrap_or_default();
+                findings.push(
+                    *severity,
+                    ImportIssue {
+                        row: row_number,
+                        field: field.clone(),
+                        code: "policy_required_field_missing".to_string(),
+                        description: format!("{field} is required by the import policy{scope}."),
+                        expected: Some("non-empty value".to_string()),
+                        received: Some(String::new()),
+                    },
+                );
+            }
+        }
+        PolicyRule::CurrencyAllowlist {
+            currencies,
+            severity,
+        } => {
+            if !currencies.contains(&row.currency) {
+                findings.push(
+                    *severity,
+                    ImportIssue {
+                        row: row_number,
+                        field: "currency".to_string(),
+                        code: "policy_currency_not_allowed".to_string(),
+                        description: format!(
+                            "currency {} is not in the policy allowlist.",
+                            row.currency
+                        ),
+                        expected: Some(currencies.join("|")),
+                        received: Some(row.currency.clone()),
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn has_field(row: &CanonicalTransaction, field: &str) -> bool {
+    match field {
+        "statement_id" => row.statement_id.is_some(),
+        "account_type" => row.account_type.is_some(),
+        "external_id" => row.external_id.is_some(),
+        "merchant" => row.merchant.is_some(),
+        "category" => row.category.is_some(),
+        "balance" => row.balance.is_some(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveDate;
+
+    use crate::import::CanonicalTransaction;
+
+    use super::{ImportPolicy, PolicyRule, evaluate, normalize_rules};
+
+    fn row(account_type: Option<&str>, posted_at: &str, amount: f64) -> CanonicalTransaction {
+        CanonicalTransaction {
+            statement_id: None,
+            dedupe_scope_id: "scope_1".to_string(),
+            account_key: "acct_1".to_string(),
+            account_type: account_type.map(str::to_string),
+            posted_at: posted_at.to_string(),
+            amount,
+            currency: "USD".to_string(),
+            description: "row".to_string(),
+            external_id: None,
+            merchant: None,
+            category: None,
+            balance: None,
+            mcc: None,
+        }
+    }
+
+    fn parse_policy(json: &str) -> Option<ImportPolicy> {
+        let mut policy = serde_json::from_str::<ImportPolicy>(json).ok()?;
+        normalize_rules(&mut policy).ok()?;
+        Some(policy)
+    }
+
+    #[test]
+    fn rules_split_into_errors_and_warnings_by_severity() {
+        let policy = parse_policy(
+            r#"{"rules": [
+                {"rule": "posted_at_not_in_future"},
+                {"rule": "max_abs_amount", "limit": 500, "account_type": "credit-card", "severity": "warning"},
+                {"rule": "require_field", "field": "merchant", "account_type": "credit_card", "severity": "warning"}
+            ]}"#,
+        );
+        assert!(policy.is_some());
+        if let (Some(policy), Some(today)) = (policy, NaiveDate::from_ymd_opt(2026, 3, 1)) {
+            // Row 2 declares the account type for row 1 as well.
+            let rows = vec![
+                row(None, "2026-03-02", -900.0),
+                row(Some("credit_card"), "2026-02-01", -10.0),
+            ];
+            let findings = evaluate(&policy, &rows, &[1, 2], &HashMap::new(), today);
+            assert_eq!(findings.errors.len(), 1);
+            assert_eq!(findings.errors[0].code, "policy_posted_at_in_future");
+            let warning_codes = findings
+                .warnings
+                .iter()
+                .map(|issue| (issue.row, issue.code.as_str()))
+                .collect::<Vec<(i64, &str)>>();
+            assert_eq!(
+                warning_codes,
+                vec![
+                    (1, "policy_amount_over_limit"),
+                    (1, "policy_required_field_missing"),
+                    (2, "policy_required_field_missing"),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn account_type_scoped_rules_fall_back_to_the_ledger_type() {
+        let policy = parse_policy(
+            r#"{"rules": [
+                {"rule": "max_abs_amount", "limit": 500, "account_type": "credit_card"}
+            ]}"#,
+        );
+        assert!(policy.is_some());
+        if let (Some(policy), Some(today)) = (policy, NaiveDate::from_ymd_opt(2026, 3, 1)) {
+            let rows = vec![row(None, "2026-02-01", -900.0)];
+            let ledger_types = HashMap::from([("acct_1".to_string(), "credit_card".to_string())]);
+            let findings = evaluate(&policy, &rows, &[1], &ledger_types, today);
+            assert_eq!(findings.errors.len(), 1);
+            assert_eq!(findings.errors[0].code, "policy_amount_over_limit");
+
+            let declared = vec![row(Some("checking"), "2026-02-01", -900.0)];
+            let findings = evaluate(&policy, &declared, &[1], &ledger_types, today);
+            assert!(findings.errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected_at_load() {
+        assert!(
+            parse_policy(r#"{"rules": [{"rule": "posted_at_not_before", "date": "2000-13-01"}]}"#)
+                .is_none()
+        );
+        assert!(
+            parse_policy(r#"{"rules": [{"rule": "require_field", "field": "description"}]}"#)
+                .is_none()
+        );
+        assert!(parse_policy(r#"{"rules": [{"rule": "max_abs_amount", "limit": 0}]}"#).is_none());
+        assert!(parse_policy(r#"{"rules": [{"rule": "no_such_rule"}]}"#).is_none());
+        assert!(
+            parse_policy(
+                r#"{"rules": [{"rule": "currency_allowlist", "currencies": ["usd"], "severity": "fatal"}]}"#
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn currency_allowlist_is_normalized_to_uppercase_codes() {
+        let policy =
+            parse_policy(r#"{"rules": [{"rule": "currency_allowlist", "currencies": [" usd "]}]}"#);
+        let currencies = policy.and_then(|policy| match policy.rules.first() {
+            Some(PolicyRule::CurrencyAllowlist { currencies, .. }) => Some(currencies.clone()),
+            _ => None,
+        });
+        assert_eq!(currencies, Some(vec!["USD".to_string()]));
+    }
+}
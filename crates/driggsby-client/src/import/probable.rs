@@ -0,0 +1,538 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use chrono::{Duration, NaiveDate};
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+use ulid::Ulid;
+
+use crate::contracts::types::ImportProbableDuplicate;
+use crate::import::CanonicalTransaction;
+use crate::import::dedupe::BatchRow;
+use crate::import::manual::NOT_MANUAL_IMPORT;
+use crate::import::manual_dedupe::demote_transaction;
+use crate::import::persist::now_timestamp;
+use crate::intelligence::normalize::{description_fingerprint, label_similarity};
+use crate::intelligence::refresh::refresh_all_in_transaction;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+/// Ledger rows further apart than this are never considered the same charge.
+pub(crate) const MAX_DATE_DISTANCE_DAYS: i64 = 3;
+/// Minimum combined score for a pair to be queued as a probable duplicate.
+pub(crate) const PROBABLE_DUPLICATE_THRESHOLD: f64 = 0.8;
+
+const AMOUNT_WEIGHT: f64 = 0.35;
+const DATE_WEIGHT: f64 = 0.25;
+const DESCRIPTION_WEIGHT: f64 = 0.4;
+
+const PROBABLE_DUPLICATE_COLUMNS: &str = "probable_id,
+    import_id,
+    txn_id,
+    status,
+    source_row_index,
+    account_key,
+    posted_at,
+    amount,
+    currency,
+    description,
+    matched_txn_id,
+    matched_import_id,
+    matched_posted_at,
+    matched_description,
+    score,
+    date_distance_days,
+    description_similarity,
+    resolved_at";
+
+#[derive(Debug, Clone)]
+pub(crate) struct ProbableMatch {
+    pub(crate) source_row_index: i64,
+    pub(crate) row: CanonicalTransaction,
+    pub(crate) matched_txn_id: String,
+    pub(crate) matched_import_id: String,
+    pub(crate) matched_posted_at: String,
+    pub(crate) matched_description: String,
+    pub(crate) score: f64,
+    pub(crate) date_distance_days: i64,
+    pub(crate) description_similarity: f64,
+}
+
+impl ProbableMatch {
+    pub(crate) fn to_contract(&self) -> ImportProbableDuplicate {
+        ImportProbableDuplicate {
+            probable_id: None,
+            import_id: None,
+            txn_id: None,
+            status: "pending".to_string(),
+            source_row_index: self.source_row_index,
+            account_key: self.row.account_key.clone(),
+            posted_at: self.row.posted_at.clone(),
+            amount: self.row.amount,
+            currency: self.row.currency.clone(),
+            description: self.row.description.clone(),
+            matched_txn_id: self.matched_txn_id.clone(),
+            matched_import_id: self.matched_import_id.clone(),
+            matched_posted_at: self.matched_posted_at.clone(),
+            matched_description: self.matched_description.clone(),
+            score: self.score,
+            date_distance_days: self.date_distance_days,
+            description_similarity: self.description_similarity,
+            resolved_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PairScore {
+    pub(crate) score: f64,
+    pub(crate) date_distance_days: i64,
+    pub(crate) description_similarity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ProbableResolution {
+    pub(crate) probable_duplicate: ImportProbableDuplicate,
+    pub(crate) intelligence_refreshed: bool,
+}
+
+#[derive(Debug)]
+struct LedgerCandidate {
+    txn_id: String,
+    import_id: String,
+    posted_at: String,
+    description: String,
+    external_id: Option<String>,
+}
+
+/// Scores a pair of same-account, same-amount rows.
+///
+/// Amount equality is a precondition enforced by the caller, so it always
+/// contributes its full weight; date proximity decays linearly to zero just
+/// past `MAX_DATE_DISTANCE_DAYS`.
+pub(crate) fn score_pair(
+    incoming_posted_at: NaiveDate,
+    incoming_description: &str,
+    existing_posted_at: NaiveDate,
+    existing_description: &str,
+) -> Option<PairScore> {
+    let date_distance_days = (incoming_posted_at - existing_posted_at).num_days().abs();
+    if date_distance_days > MAX_DATE_DISTANCE_DAYS {
+        return None;
+    }
+
+    let date_score = 1.0 - (date_distance_days as f64 / (MAX_DATE_DISTANCE_DAYS + 1) as f64);
+    let description_similarity = label_similarity(
+        &fingerprint_or_raw(incoming_description),
+        &fingerprint_or_raw(existing_description),
+    );
+    let score =
+        AMOUNT_WEIGHT + DATE_WEIGHT * date_score + DESCRIPTION_WEIGHT * description_similarity;
+
+    Some(PairScore {
+        score: round2(score),
+        date_distance_days,
+        description_similarity: round2(description_similarity),
+    })
+}
+
+/// Finds the best committed near match for each row that survived exact
+/// dedupe. Each ledger row is claimed by at most one incoming row.
+pub(crate) fn find_probable_duplicates(
+    connection: &Connection,
+    rows: &[BatchRow],
+    db_path: &Path,
+) -> ClientResult<Vec<ProbableMatch>> {
+    let mut claimed: HashSet<String> = HashSet::new();
+    let mut matches = Vec::new();
+
+    for batch_row in rows {
+        let row = &batch_row.row;
+        let Ok(posted_at) = NaiveDate::parse_from_str(&row.posted_at, "%Y-%m-%d") else {
+            continue;
+        };
+        let window_start = (posted_at - Duration::days(MAX_DATE_DISTANCE_DAYS))
+            .format("%Y-%m-%d")
+            .to_string();
+        let window_end = (posted_at + Duration::days(MAX_DATE_DISTANCE_DAYS))
+            .format("%Y-%m-%d")
+            .to_string();
+
+        let mut best: Option<(PairScore, LedgerCandidate)> = None;
+        for candidate in ledger_candidates(connection, db_path, row, &window_start, &window_end)? {
+            if claimed.contains(&candidate.txn_id) {
+                continue;
+            }
+            if let (Some(incoming), Some(existing)) = (&row.external_id, &candidate.external_id)
+                && incoming != existing
+            {
+                continue;
+            }
+            let Ok(existing_posted_at) =
+                NaiveDate::parse_from_str(&candidate.posted_at, "%Y-%m-%d")
+            else {
+                continue;
+            };
+            let Some(scored) = score_pair(
+                posted_at,
+                &row.description,
+                existing_posted_at,
+                &candidate.description,
+            ) else {
+                continue;
+            };
+            if scored.score < PROBABLE_DUPLICATE_THRESHOLD {
+                continue;
+            }
+            let better = best.as_ref().is_none_or(|(current, _)| {
+                scored.score > current.score
+                    || (scored.score == current.score
+                        && scored.date_distance_days < current.date_distance_days)
+            });
+            if better {
+                best = Some((scored, candidate));
+            }
+        }
+
+        if let Some((scored, candidate)) = best {
+            claimed.insert(candidate.txn_id.clone());
+            matches.push(ProbableMatch {
+                source_row_index: batch_row.source_row_index,
+                row: row.clone(),
+                matched_txn_id: candidate.txn_id,
+                matched_import_id: candidate.import_id,
+                matched_posted_at: candidate.posted_at,
+                matched_description: candidate.description,
+                score: scored.score,
+                date_distance_days: scored.date_distance_days,
+                description_similarity: scored.description_similarity,
+            });
+        }
+    }
+
+    Ok(matches)
+}
+
+pub(crate) fn insert_probable_duplicates(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    matches: &[ProbableMatch],
+    txn_ids: &HashMap<i64, String>,
+    timestamp: &str,
+) -> ClientResult<Vec<ImportProbableDuplicate>> {
+    let mut queued = Vec::new();
+    for probable in matches {
+        let Some(txn_id) = txn_ids.get(&probable.source_row_index) else {
+            continue;
+        };
+        let probable_id = format!("prob_{}", Ulid::new());
+        transaction
+            .execute(
+                "INSERT INTO internal_probable_duplicates (
+                    probable_id,
+                    import_id,
+                    txn_id,
+                    source_row_index,
+                    account_key,
+                    posted_at,
+                    amount,
+                    currency,
+                    description,
+                    matched_txn_id,
+                    matched_import_id,
+                    matched_posted_at,
+                    matched_description,
+                    score,
+                    date_distance_days,
+                    description_similarity,
+                    status,
+                    candidate_id,
+                    created_at,
+                    resolved_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, 'pending', NULL, ?17, NULL)",
+                params![
+                    &probable_id,
+                    import_id,
+                    txn_id,
+                    probable.source_row_index,
+                    &probable.row.account_key,
+                    &probable.row.posted_at,
+                    probable.row.amount,
+                    &probable.row.currency,
+                    &probable.row.description,
+                    &probable.matched_txn_id,
+                    &probable.matched_import_id,
+                    &probable.matched_posted_at,
+                    &probable.matched_description,
+                    probable.score,
+                    probable.date_distance_days,
+                    probable.description_similarity,
+                    timestamp
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+        let mut contract = probable.to_contract();
+        contract.probable_id = Some(probable_id);
+        contract.import_id = Some(import_id.to_string());
+        contract.txn_id = Some(txn_id.clone());
+        queued.push(contract);
+    }
+    Ok(queued)
+}
+
+pub(crate) fn query_probable_duplicates(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<Vec<ImportProbableDuplicate>> {
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT {PROBABLE_DUPLICATE_COLUMNS}
+             FROM internal_probable_duplicates
+             WHERE import_id = ?1
+             ORDER BY source_row_index ASC, probable_id ASC"
+        ))
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![import_id], probable_from_row)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut probables = Vec::new();
+    for row in rows {
+        probables.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(probables)
+}
+
+/// Accepting removes the incoming transaction and records it as an
+/// `existing_ledger` dedupe candidate of the row it matched, so `import undo`
+/// can restore it later. Rejecting keeps both transactions.
+pub(crate) fn resolve_probable_duplicate(
+    connection: &mut Connection,
+    db_path: &Path,
+    probable_id: &str,
+    accept: bool,
+) -> ClientResult<ProbableResolution> {
+    let timestamp = now_timestamp();
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let Some(probable) = find_probable_duplicate(&transaction, db_path, probable_id)? else {
+        return Err(ClientError::probable_duplicate_not_found(probable_id));
+    };
+    if probable.status != "pending" {
+        return Err(ClientError::probable_duplicate_already_resolved(
+            probable_id,
+            &probable.status,
+        ));
+    }
+
+    let candidate_id = if accept {
+        let txn_id = match probable.txn_id.as_deref() {
+            Some(txn_id) if ledger_row_exists(&transaction, db_path, txn_id)? => txn_id,
+            txn_id => {
+                return Err(ClientError::probable_duplicate_row_missing(
+                    probable_id,
+                    txn_id,
+                ));
+            }
+        };
+        Some(demote_transaction(
+            &transaction,
+            db_path,
+            txn_id,
+            &probable.matched_txn_id,
+            probable.source_row_index,
+            &timestamp,
+        )?)
+    } else {
+        None
+    };
+
+    let status = if accept { "accepted" } else { "rejected" };
+    transaction
+        .execute(
+            "UPDATE internal_probable_duplicates
+             SET status = ?2, candidate_id = ?3, resolved_at = ?4
+             WHERE probable_id = ?1",
+            params![probable_id, status, &candidate_id, &timestamp],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    if accept {
+        refresh_all_in_transaction(&transaction, db_path)?;
+    }
+
+    let Some(resolved) = find_probable_duplicate(&transaction, db_path, probable_id)? else {
+        return Err(ClientError::ledger_corrupt(db_path));
+    };
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(ProbableResolution {
+        probable_duplicate: resolved,
+        intelligence_refreshed: accept,
+    })
+}
+
+fn ledger_row_exists(connection: &Connection, db_path: &Path, txn_id: &str) -> ClientResult<bool> {
+    connection
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM internal_transactions WHERE txn_id = ?1)",
+            params![txn_id],
+            |row| row.get::<_, bool>(0),
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+fn find_probable_duplicate(
+    connection: &Connection,
+    db_path: &Path,
+    probable_id: &str,
+) -> ClientResult<Option<ImportProbableDuplicate>> {
+    connection
+        .query_row(
+            &format!(
+                "SELECT {PROBABLE_DUPLICATE_COLUMNS}
+                 FROM internal_probable_duplicates
+                 WHERE probable_id = ?1"
+            ),
+            params![probable_id],
+            probable_from_row,
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+fn probable_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ImportProbableDuplicate> {
+    Ok(ImportProbableDuplicate {
+        probable_id: Some(row.get(0)?),
+        import_id: Some(row.get(1)?),
+        txn_id: Some(row.get(2)?),
+        status: row.get(3)?,
+        source_row_index: row.get(4)?,
+        account_key: row.get(5)?,
+        posted_at: row.get(6)?,
+        amount: row.get(7)?,
+        currency: row.get(8)?,
+        description: row.get(9)?,
+        matched_txn_id: row.get(10)?,
+        matched_import_id: row.get(11)?,
+        matched_posted_at: row.get(12)?,
+        matched_description: row.get(13)?,
+        score: row.get(14)?,
+        date_distance_days: row.get(15)?,
+        description_similarity: row.get(16)?,
+        resolved_at: row.get(17)?,
+    })
+}
+
+fn ledger_candidates(
+    connection: &Connection,
+    db_path: &Path,
+    row: &CanonicalTransaction,
+    window_start: &str,
+    window_end: &str,
+) -> ClientResult<Vec<LedgerCandidate>> {
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT txn_id, import_id, posted_at, description, external_id
+             FROM internal_transactions
+             WHERE account_key = ?1
+               AND currency = ?2
+               AND ROUND(amount * 100) = ROUND(?3 * 100)
+               AND posted_at BETWEEN ?4 AND ?5
+               AND dedupe_scope_id != ?6
+               AND {NOT_MANUAL_IMPORT}
+             ORDER BY txn_id ASC"
+        ))
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(
+            params![
+                &row.account_key,
+                &row.currency,
+                row.amount,
+                window_start,
+                window_end,
+                &row.dedupe_scope_id
+            ],
+            |result| {
+                Ok(LedgerCandidate {
+                    txn_id: result.get(0)?,
+                    import_id: result.get(1)?,
+                    posted_at: result.get(2)?,
+                    description: result.get(3)?,
+                    external_id: result.get(4)?,
+                })
+            },
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut candidates = Vec::new();
+    for candidate in rows {
+        candidates.push(candidate.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(candidates)
+}
+
+fn fingerprint_or_raw(description: &str) -> String {
+    description_fingerprint(description).unwrap_or_else(|| description.to_string())
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{PROBABLE_DUPLICATE_THRESHOLD, score_pair};
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap_or_default()
+    }
+
+    #[test]
+    fn shifted_dates_and_reworded_descriptions_still_score_as_probable() {
+        let shifted = score_pair(
+            date("2026-01-04"),
+            "NETFLIX.COM",
+            date("2026-01-03"),
+            "NETFLIX.COM",
+        );
+        assert!(matches!(shifted, Some(scored) if scored.score >= PROBABLE_DUPLICATE_THRESHOLD));
+
+        let reworded = score_pair(
+            date("2026-01-03"),
+            "STARBUCKS STORE 1234",
+            date("2026-01-03"),
+            "STARBUCKS",
+        );
+        assert!(matches!(reworded, Some(scored) if scored.score >= PROBABLE_DUPLICATE_THRESHOLD));
+    }
+
+    #[test]
+    fn unrelated_descriptions_and_distant_dates_stay_below_threshold() {
+        let unrelated = score_pair(
+            date("2026-01-03"),
+            "WHOLE FOODS",
+            date("2026-01-03"),
+            "SHELL OIL",
+        );
+        assert!(matches!(unrelated, Some(scored) if scored.score < PROBABLE_DUPLICATE_THRESHOLD));
+
+        assert!(
+            score_pair(
+                date("2026-01-10"),
+                "NETFLIX.COM",
+                date("2026-01-03"),
+                "NETFLIX.COM"
+            )
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::ClientResult;
+use crate::state::map_sqlite_error;
+
+/// Where a committed transaction came from: the import, the file (or stdin)
+/// it read, and the exact source record that produced the row.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceRowProvenance {
+    pub(crate) import_id: String,
+    pub(crate) source_kind: Option<String>,
+    pub(crate) source_ref: Option<String>,
+    pub(crate) source_row_index: i64,
+    pub(crate) source_record: String,
+    pub(crate) committed_at: Option<String>,
+}
+
+/// Stores the original record for every source row of an import. Rows that
+/// were deduped at import time keep their record with no transaction id.
+/// `source_records` is aligned with the source rows; a row without a record
+/// is skipped.
+pub(crate) fn insert_source_rows(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    source_records: &[Option<String>],
+    txn_ids: &HashMap<i64, String>,
+) -> ClientResult<()> {
+    for (index, source_record) in source_records.iter().enumerate() {
+        let Some(source_record) = source_record else {
+            continue;
+        };
+        let source_row_index = (index as i64) + 1;
+        transaction
+            .execute(
+                "INSERT INTO internal_import_source_rows (
+                    import_id,
+                    source_row_index,
+                    source_record,
+                    txn_id
+                 ) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    import_id,
+                    source_row_index,
+                    source_record,
+                    txn_ids.get(&source_row_index)
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
+
+/// Points an import's recorded source rows back at restored ledger rows.
+pub(crate) fn link_source_rows(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    txn_ids: &HashMap<i64, String>,
+) -> ClientResult<()> {
+    for (source_row_index, txn_id) in txn_ids {
+        transaction
+            .execute(
+                "UPDATE internal_import_source_rows
+                 SET txn_id = ?3
+                 WHERE import_id = ?1 AND source_row_index = ?2",
+                params![import_id, source_row_index, txn_id],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
+
+/// Points the source row behind a dedupe candidate at its promoted transaction.
+pub(crate) fn link_promoted_candidate(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    candidate_id: &str,
+    txn_id: &str,
+) -> ClientResult<()> {
+    transaction
+        .execute(
+            "UPDATE internal_import_source_rows
+             SET txn_id = ?2
+             WHERE txn_id IS NULL
+               AND (import_id, source_row_index) = (
+                   SELECT import_id, source_row_index
+                   FROM internal_transaction_dedupe_candidates
+                   WHERE candidate_id = ?1
+               )",
+            params![candidate_id, txn_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+/// Detaches a transaction that left the ledger from its source row. The
+/// record itself is kept so the row can be traced or promoted again later.
+pub(crate) fn unlink_transaction(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<()> {
+    transaction
+        .execute(
+            "UPDATE internal_import_source_rows SET txn_id = NULL WHERE txn_id = ?1",
+            params![txn_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+/// Detaches every source row of a reverted import from the ledger.
+pub(crate) fn unlink_import(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<()> {
+    transaction
+        .execute(
+            "UPDATE internal_import_source_rows SET txn_id = NULL WHERE import_id = ?1",
+            params![import_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+/// The source row index recorded for a committed transaction, if any.
+pub(crate) fn source_row_index_for_transaction(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Option<i64>> {
+    connection
+        .query_row(
+            "SELECT source_row_index FROM internal_import_source_rows WHERE txn_id = ?1",
+            params![txn_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+/// Provenance of a committed transaction. Transactions committed before
+/// source rows were recorded have none.
+pub(crate) fn load_provenance(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Option<SourceRowProvenance>> {
+    connection
+        .query_row(
+            "SELECT
+                s.import_id,
+                i.source_kind,
+                i.source_ref,
+                s.source_row_index,
+                s.source_record,
+                i.committed_at
+             FROM internal_import_source_rows s
+             LEFT JOIN internal_import_runs i ON i.import_id = s.import_id
+             WHERE s.txn_id = ?1",
+            params![txn_id],
+            |row| {
+                Ok(SourceRowProvenance {
+                    import_id: row.get(0)?,
+                    source_kind: row.get(1)?,
+                    source_ref: row.get(2)?,
+                    source_row_index: row.get(3)?,
+                    source_record: row.get(4)?,
+                    committed_at: row.get(5)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+
+use crate::contracts::types::ImportRedoConflict;
+use crate::import::archive::{
+    ImportRow, clear_archive, load_archived_rows, load_archived_statements,
+};
+use crate::import::dedupe::{dedupe_against_existing, dedupe_batch};
+use crate::import::merge_duplicate_rows;
+use crate::import::persist::{PersistInput, PersistMode, persist_import};
+use crate::import::policy::load_run_dedupe_policies;
+use crate::import::probable::find_probable_duplicates;
+use crate::import::provenance::unlink_transaction;
+use crate::import::statements::ReconciledStatement;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+#[derive(Debug, Clone)]
+pub(crate) struct RedoResult {
+    pub(crate) import_id: String,
+    pub(crate) rows_restored: i64,
+    pub(crate) rows_deduped: i64,
+    pub(crate) intelligence_refreshed: bool,
+}
+
+struct RevertedRun {
+    rows_read: i64,
+    rows_valid: i64,
+    rows_invalid: i64,
+    source_kind: String,
+    source_ref: Option<String>,
+    source_hash: String,
+    idempotency_key: Option<String>,
+}
+
+/// Restores a reverted import from its archive through the normal dedupe
+/// path. Rows that were in the ledger get their original ids back, and
+/// candidates that `import undo` promoted in their place are demoted again.
+/// Nothing is written if a row or statement now conflicts with data committed
+/// since the undo.
+pub(crate) fn redo_import(
+    connection: &mut Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<RedoResult> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let run = load_reverted_run(&transaction, db_path, import_id)?;
+    let archived = load_archived_rows(&transaction, db_path, import_id)?;
+    if archived.is_empty() {
+        return Err(ClientError::import_redo_unavailable(import_id));
+    }
+    let statements = load_archived_statements(&transaction, db_path, import_id)?;
+
+    demote_promoted_candidates(&transaction, db_path, import_id)?;
+
+    let dedupe_policies = load_run_dedupe_policies(&transaction, db_path, import_id)?;
+    let batch_deduped = dedupe_batch(
+        archived
+            .iter()
+            .map(|archived| archived.row.clone())
+            .collect(),
+        &dedupe_policies,
+    );
+    let existing_deduped = dedupe_against_existing(
+        &transaction,
+        &batch_deduped.candidate_rows,
+        db_path,
+        &dedupe_policies,
+    )?;
+
+    let mut conflicts = Vec::new();
+    for duplicate in &existing_deduped.duplicate_rows {
+        let Some(archived_row) = archived_row(&archived, duplicate.source_row_index) else {
+            continue;
+        };
+        if archived_row.txn_id.is_none() {
+            continue;
+        }
+        conflicts.push(ImportRedoConflict {
+            kind: "transaction".to_string(),
+            source_row_index: archived_row.source_row_index,
+            account_key: archived_row.row.account_key.clone(),
+            statement_id: archived_row.row.statement_id.clone(),
+            posted_at: Some(archived_row.row.posted_at.clone()),
+            amount: Some(archived_row.row.amount),
+            description: Some(archived_row.row.description.clone()),
+            conflicting_txn_id: duplicate.matched_txn_id.clone(),
+            conflicting_import_id: duplicate.matched_import_id.clone(),
+        });
+    }
+    conflicts.extend(statement_conflicts(&transaction, db_path, &statements)?);
+    if !conflicts.is_empty() {
+        return Err(ClientError::import_redo_conflict(import_id, &conflicts));
+    }
+
+    transaction
+        .execute(
+            "DELETE FROM internal_transaction_dedupe_candidates WHERE import_id = ?1",
+            params![import_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    // Archive indexes match the batch row indexes `dedupe_batch` assigns, and
+    // the source row indexes recorded at import time.
+    let restored_txn_ids: HashMap<i64, String> = archived
+        .iter()
+        .enumerate()
+        .filter_map(|(index, archived)| {
+            archived
+                .txn_id
+                .clone()
+                .map(|txn_id| ((index as i64) + 1, txn_id))
+        })
+        .collect();
+    let duplicate_rows = merge_duplicate_rows(
+        batch_deduped.duplicate_rows,
+        existing_deduped.duplicate_rows,
+    );
+    let probable_matches =
+        find_probable_duplicates(&transaction, &existing_deduped.insertable_rows, db_path)?;
+
+    let persisted = persist_import(
+        &transaction,
+        db_path,
+        PersistInput {
+            import_id,
+            candidate_rows: &existing_deduped.insertable_rows,
+            duplicate_rows: &duplicate_rows,
+            rows_read: run.rows_read,
+            rows_valid: run.rows_valid,
+            rows_invalid: run.rows_invalid,
+            source_kind: &run.source_kind,
+            source_ref: run.source_ref.as_deref(),
+            sign_corrections: &[],
+            remaps: &[],
+            statements: &statements,
+            probable_duplicates: &probable_matches,
+            dedupe_policies: &dedupe_policies,
+            source_hash: &run.source_hash,
+            idempotency_key: run.idempotency_key.as_deref(),
+            source_records: &[],
+            rule_hits: &HashMap::new(),
+            mode: PersistMode::Restored(&restored_txn_ids),
+        },
+    )?;
+    clear_archive(&transaction, db_path, import_id)?;
+
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(RedoResult {
+        import_id: import_id.to_string(),
+        rows_restored: persisted.inserted,
+        rows_deduped: persisted.duplicate_rows.len() as i64,
+        intelligence_refreshed: true,
+    })
+}
+
+fn load_reverted_run(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<RevertedRun> {
+    let run = transaction
+        .query_row(
+            "SELECT status, rows_read, rows_valid, rows_invalid, source_kind, source_ref,
+                    source_hash, idempotency_key
+             FROM internal_import_runs
+             WHERE import_id = ?1",
+            params![import_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    RevertedRun {
+                        rows_read: row.get(1)?,
+                        rows_valid: row.get(2)?,
+                        rows_invalid: row.get(3)?,
+                        source_kind: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                        source_ref: row.get(5)?,
+                        source_hash: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                        idempotency_key: row.get(7)?,
+                    },
+                ))
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    match run {
+        None => Err(ClientError::import_redo_id_not_found(import_id)),
+        Some((status, run)) if status == "reverted" => Ok(run),
+        Some((status, _)) => Err(ClientError::import_not_reverted(import_id, &status)),
+    }
+}
+
+fn archived_row(archived: &[ImportRow], batch_row_index: i64) -> Option<&ImportRow> {
+    usize::try_from(batch_row_index - 1)
+        .ok()
+        .and_then(|index| archived.get(index))
+}
+
+/// Returns candidates that `import undo` promoted in place of the reverted
+/// rows to the dedupe queue, so the restored rows take their place again.
+/// Promoted rows that later imports matched against are left alone and show
+/// up as conflicts instead.
+fn demote_promoted_candidates(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<()> {
+    let mut statement = transaction
+        .prepare(
+            "SELECT c.candidate_id, c.promoted_txn_id
+             FROM internal_transaction_dedupe_candidates c
+             JOIN internal_import_runs i ON i.import_id = c.import_id
+             JOIN internal_transactions t ON t.txn_id = c.promoted_txn_id
+             WHERE c.matched_import_id = ?1
+               AND c.import_id <> ?1
+               AND i.status IN ('committed', 'partially_reverted')
+               AND NOT EXISTS (
+                   SELECT 1 FROM internal_transaction_dedupe_candidates o
+                   WHERE o.matched_txn_id = c.promoted_txn_id
+               )
+               AND NOT EXISTS (
+                   SELECT 1 FROM internal_probable_duplicates p
+                   WHERE p.txn_id = c.promoted_txn_id OR p.matched_txn_id = c.promoted_txn_id
+               )
+             ORDER BY c.candidate_id ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![import_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let mut promoted = Vec::new();
+    for row in rows {
+        promoted.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+
+    for (candidate_id, txn_id) in promoted {
+        transaction
+            .execute(
+                "DELETE FROM internal_transactions WHERE txn_id = ?1",
+                params![&txn_id],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+        transaction
+            .execute(
+                "UPDATE internal_transaction_dedupe_candidates
+                 SET promoted_txn_id = NULL
+                 WHERE candidate_id = ?1",
+                params![&candidate_id],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+        unlink_transaction(transaction, db_path, &txn_id)?;
+    }
+    Ok(())
+}
+
+fn statement_conflicts(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    statements: &[ReconciledStatement],
+) -> ClientResult<Vec<ImportRedoConflict>> {
+    let mut conflicts = Vec::new();
+    for statement in statements {
+        let conflicting_import_id = transaction
+            .query_row(
+                "SELECT import_id FROM internal_statements
+                 WHERE account_key = ?1 AND statement_id = ?2",
+                params![&statement.account_key, &statement.statement_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+        if let Some(conflicting_import_id) = conflicting_import_id {
+            conflicts.push(ImportRedoConflict {
+                kind: "statement".to_string(),
+                source_row_index: None,
+                account_key: statement.account_key.clone(),
+                statement_id: Some(statement.statement_id.clone()),
+                posted_at: None,
+                amount: None,
+                description: None,
+                conflicting_txn_id: None,
+                conflicting_import_id: Some(conflicting_import_id),
+            });
+        }
+    }
+    Ok(conflicts)
+}
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::contracts::types::ImportRemap;
+use crate::import::inventory::TrackedProperty;
+use crate::import::parse::ParsedSource;
+use crate::{ClientError, ClientResult};
+
+const REMAPPABLE_PROPERTIES: [TrackedProperty; 4] = [
+    TrackedProperty::AccountKey,
+    TrackedProperty::AccountType,
+    TrackedProperty::Merchant,
+    TrackedProperty::Category,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RemapRule {
+    pub(crate) property: TrackedProperty,
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AppliedRemap {
+    pub(crate) rule: RemapRule,
+    pub(crate) rows_rewritten: i64,
+}
+
+impl AppliedRemap {
+    pub(crate) fn to_contract(&self) -> ImportRemap {
+        ImportRemap {
+            property: self.rule.property.as_str().to_string(),
+            from: self.rule.from.clone(),
+            to: self.rule.to.clone(),
+            rows_rewritten: self.rows_rewritten,
+        }
+    }
+}
+
+/// Collects remap rules from `--remap-file` and repeated `--remap` flags.
+///
+/// The same `property:from` may appear more than once only when every
+/// occurrence maps to the same target.
+pub(crate) fn build_remap_rules(
+    specs: &[String],
+    remap_file: Option<&str>,
+) -> ClientResult<Vec<RemapRule>> {
+    let mut rules = match remap_file {
+        Some(path) => load_remap_file(path)?,
+        None => Vec::new(),
+    };
+    for spec in specs {
+        rules.push(parse_remap_spec(spec)?);
+    }
+
+    let mut targets: BTreeMap<(TrackedProperty, String), String> = BTreeMap::new();
+    let mut unique_rules = Vec::new();
+    for rule in rules {
+        let key = (rule.property, rule.from.clone());
+        match targets.get(&key) {
+            Some(existing) if *existing == rule.to => {}
+            Some(existing) => {
+                return Err(invalid_remap_error(&format!(
+                    "Remap for {} `{}` points at both `{existing}` and `{}`.",
+                    rule.property.as_str(),
+                    rule.from,
+                    rule.to
+                )));
+            }
+            None => {
+                targets.insert(key, rule.to.clone());
+                unique_rules.push(rule);
+            }
+        }
+    }
+    Ok(unique_rules)
+}
+
+/// Parses one `property:from=to` flag value.
+pub(crate) fn parse_remap_spec(spec: &str) -> ClientResult<RemapRule> {
+    let Some((property, mapping)) = spec.split_once(':') else {
+        return Err(invalid_remap_error(&format!(
+            "Remap `{spec}` must look like `property:from=to`."
+        )));
+    };
+    let Some((from, to)) = mapping.split_once('=') else {
+        return Err(invalid_remap_error(&format!(
+            "Remap `{spec}` must look like `property:from=to`."
+        )));
+    };
+    build_rule(property.trim(), from, to)
+}
+
+/// Rewrites matching values in place and reports how many rows each rule
+/// touched. Rules run in a single pass, so targets are never remapped again.
+pub(crate) fn apply_remaps(parsed: &mut ParsedSource, rules: &[RemapRule]) -> Vec<AppliedRemap> {
+    let mut applied = rules
+        .iter()
+        .map(|rule| AppliedRemap {
+            rule: rule.clone(),
+            rows_rewritten: 0,
+        })
+        .collect::<Vec<AppliedRemap>>();
+
+    for row in &mut parsed.rows {
+        let mut rewritten: Vec<TrackedProperty> = Vec::new();
+        for remap in &mut applied {
+            if rewritten.contains(&remap.rule.property) {
+                continue;
+            }
+            let field = match remap.rule.property {
+                TrackedProperty::AccountKey => &mut row.account_key,
+                TrackedProperty::AccountType => &mut row.account_type,
+                TrackedProperty::Merchant => &mut row.merchant,
+                TrackedProperty::Category => &mut row.category,
+                TrackedProperty::Currency => continue,
+            };
+            if rewrite_value(field, &remap.rule) {
+                remap.rows_rewritten += 1;
+                rewritten.push(remap.rule.property);
+            }
+        }
+    }
+
+    for header in &mut parsed.statement_headers {
+        for remap in &applied {
+            if remap.rule.property == TrackedProperty::AccountKey
+                && rewrite_value(&mut header.account_key, &remap.rule)
+            {
+                break;
+            }
+        }
+    }
+
+    applied
+}
+
+fn rewrite_value(field: &mut Option<String>, rule: &RemapRule) -> bool {
+    let matches = field
+        .as_deref()
+        .is_some_and(|value| value.trim() == rule.from);
+    if matches {
+        *field = Some(rule.to.clone());
+    }
+    matches
+}
+
+fn load_remap_file(path: &str) -> ClientResult<Vec<RemapRule>> {
+    let body = fs::read_to_string(path).map_err(|error| {
+        ClientError::invalid_argument_with_recovery(
+            &format!("Could not read remap file `{path}`: {error}"),
+            vec![
+                "Verify the path exists and is readable.".to_string(),
+                "Rerun driggsby import create with --remap-file <path>.".to_string(),
+            ],
+        )
+    })?;
+    let parsed = serde_json::from_str::<BTreeMap<String, BTreeMap<String, String>>>(&body)
+        .map_err(|error| {
+            invalid_remap_error(&format!(
+                "Remap file `{path}` must be a JSON object of {{\"property\": {{\"from\": \"to\"}}}}: {error}"
+            ))
+        })?;
+
+    let mut rules = Vec::new();
+    for (property, mappings) in parsed {
+        for (from, to) in mappings {
+            rules.push(build_rule(&property, &from, &to)?);
+        }
+    }
+    Ok(rules)
+}
+
+fn build_rule(property: &str, from: &str, to: &str) -> ClientResult<RemapRule> {
+    let Some(property) = TrackedProperty::parse(property)
+        .filter(|property| REMAPPABLE_PROPERTIES.contains(property))
+    else {
+        return Err(invalid_remap_error(&format!(
+            "Remap property `{property}` is not supported. Use one of: account_key, account_type, merchant, category."
+        )));
+    };
+    let from = from.trim();
+    let to = to.trim();
+    if from.is_empty() || to.is_empty() {
+        return Err(invalid_remap_error(&format!(
+            "Remap for {} needs both a non-empty source value and target value.",
+            property.as_str()
+        )));
+    }
+    Ok(RemapRule {
+        property,
+        from: from.to_string(),
+        to: to.to_string(),
+    })
+}
+
+fn invalid_remap_error(message: &str) -> ClientError {
+    ClientError::invalid_argument_with_recovery(
+        message,
+        vec![
+            "Pass remaps as `--remap property:from=to`, for example `--remap account_key:chase_chk=chase_checking_1234`.".to_string(),
+            "Run `driggsby import create --help` to review remap options.".to_string(),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::inventory::TrackedProperty;
+    use crate::import::parse::parse_source;
+
+    use super::{apply_remaps, build_remap_rules, parse_remap_spec};
+
+    #[test]
+    fn remap_specs_parse_property_source_and_target() {
+        let rule = parse_remap_spec("account_key: chase_chk = chase_checking_1234");
+        assert!(rule.is_ok());
+        if let Ok(rule) = rule {
+            assert_eq!(rule.property, TrackedProperty::AccountKey);
+            assert_eq!(rule.from, "chase_chk");
+            assert_eq!(rule.to, "chase_checking_1234");
+        }
+
+        assert!(parse_remap_spec("merchant:Whole Foods").is_err());
+        assert!(parse_remap_spec("currency:usd=USD").is_err());
+        assert!(parse_remap_spec("category:=Groceries").is_err());
+    }
+
+    #[test]
+    fn conflicting_remaps_are_rejected_and_repeats_collapse() {
+        let repeated = build_remap_rules(
+            &[
+                "merchant:AMZN=Amazon".to_string(),
+                "merchant:AMZN=Amazon".to_string(),
+            ],
+            None,
+        );
+        assert!(matches!(repeated, Ok(ref rules) if rules.len() == 1));
+
+        let conflicting = build_remap_rules(
+            &[
+                "merchant:AMZN=Amazon".to_string(),
+                "merchant:AMZN=Amazon Prime".to_string(),
+            ],
+            None,
+        );
+        assert!(conflicting.is_err());
+    }
+
+    #[test]
+    fn remaps_rewrite_rows_and_statement_headers_in_one_pass() {
+        let parsed = parse_source(
+            r#"[
+                {"account_key": "chase_chk", "posted_at": "2026-01-01", "amount": -1, "currency": "USD", "description": "a", "merchant": " AMZN "},
+                {"account_key": "chase_chk", "posted_at": "2026-01-02", "amount": -2, "currency": "USD", "description": "b", "merchant": "Amazon"},
+                {"account_key": "other", "posted_at": "2026-01-03", "amount": -3, "currency": "USD", "description": "c"}
+            ]"#,
+        );
+        assert!(parsed.is_ok());
+        let rules = build_remap_rules(
+            &[
+                "account_key:chase_chk=chase_checking_1234".to_string(),
+                "merchant:AMZN=Amazon".to_string(),
+                "merchant:Amazon=Amazon.com".to_string(),
+            ],
+            None,
+        );
+        assert!(rules.is_ok());
+        if let (Ok(mut parsed), Ok(rules)) = (parsed, rules) {
+            let applied = apply_remaps(&mut parsed, &rules);
+            let counts = applied
+                .iter()
+                .map(|remap| remap.rows_rewritten)
+                .collect::<Vec<i64>>();
+            assert_eq!(counts, vec![2, 1, 1]);
+            assert_eq!(
+                parsed.rows[0].account_key.as_deref(),
+                Some("chase_checking_1234")
+            );
+            assert_eq!(parsed.rows[0].merchant.as_deref(), Some("Amazon"));
+            assert_eq!(parsed.rows[1].merchant.as_deref(), Some("Amazon.com"));
+            assert_eq!(parsed.rows[2].account_key.as_deref(), Some("other"));
+        }
+    }
+}
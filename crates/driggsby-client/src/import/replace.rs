@@ -0,0 +1,281 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::contracts::types::{
+    ImportReplaceChange, ImportReplaceRow, ImportReplaceSummary, ImportReplacement,
+};
+use crate::import::CanonicalTransaction;
+use crate::import::archive::{ImportRow, load_import_rows};
+use crate::import::undo::{RevertedImport, UndoScope};
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ReplaceDiff {
+    pub(crate) replaced_import_id: String,
+    unchanged: i64,
+    changes: Vec<ImportReplaceChange>,
+}
+
+type FieldValue = fn(&CanonicalTransaction) -> Option<String>;
+
+const COMPARED_FIELDS: [(&str, FieldValue); 11] = [
+    ("account_key", |row| Some(row.account_key.clone())),
+    ("posted_at", |row| Some(row.posted_at.clone())),
+    ("amount", |row| Some(row.amount.to_string())),
+    ("currency", |row| Some(row.currency.clone())),
+    ("description", |row| Some(row.description.clone())),
+    ("statement_id", |row| row.statement_id.clone()),
+    ("external_id", |row| row.external_id.clone()),
+    ("merchant", |row| row.merchant.clone()),
+    ("category", |row| row.category.clone()),
+    ("balance", |row| row.balance.map(|value| value.to_string())),
+    ("mcc", |row| row.mcc.clone()),
+];
+
+/// Pairings tried, in order, for rows that are not identical. A corrected row
+/// usually keeps its external id, or its date plus one of amount/description.
+const MODIFIED_PAIRINGS: [&[&str]; 4] = [
+    &["account_key", "external_id"],
+    &["account_key", "posted_at", "amount"],
+    &["account_key", "posted_at", "description"],
+    &["account_key", "amount", "description"],
+];
+
+impl ReplaceDiff {
+    fn count(&self, status: &str) -> i64 {
+        self.changes
+            .iter()
+            .filter(|change| change.status == status)
+            .count() as i64
+    }
+
+    pub(crate) fn to_contract(&self, reverted: RevertedImport) -> ImportReplacement {
+        ImportReplacement {
+            replaced_import_id: self.replaced_import_id.clone(),
+            summary: ImportReplaceSummary {
+                unchanged: self.unchanged,
+                added: self.count("added"),
+                removed: self.count("removed"),
+                modified: self.count("modified"),
+            },
+            rows_reverted: reverted.rows_reverted,
+            rows_promoted: reverted.rows_promoted,
+            changes: self.changes.clone(),
+        }
+    }
+}
+
+/// Fails unless `import_id` names a committed import.
+pub(crate) fn ensure_replaceable(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<()> {
+    let status = connection
+        .query_row(
+            "SELECT status FROM internal_import_runs WHERE import_id = ?1",
+            params![import_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    match status.as_deref() {
+        None => Err(ClientError::import_id_not_found(import_id)),
+        Some("reverted") => Err(ClientError::import_already_reverted(import_id)),
+        Some("committed" | "partially_reverted") => Ok(()),
+        Some(status) => Err(ClientError::import_not_committed(import_id, status)),
+    }
+}
+
+/// Diffs the validated rows of a corrected file against the rows of the
+/// committed import it replaces. Callers check `ensure_replaceable` first.
+pub(crate) fn diff_against_import(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+    new_rows: &[CanonicalTransaction],
+) -> ClientResult<ReplaceDiff> {
+    let old_rows = load_import_rows(connection, db_path, import_id, &UndoScope::default())?;
+    let mut old_matched = vec![false; old_rows.len()];
+    let mut new_matched = vec![false; new_rows.len()];
+    let mut unchanged = 0_i64;
+    let mut changes = Vec::new();
+
+    for (new_index, new_row) in new_rows.iter().enumerate() {
+        let found = old_rows.iter().enumerate().position(|(old_index, old)| {
+            !old_matched[old_index] && changed_fields(&old.row, new_row).is_empty()
+        });
+        if let Some(old_index) = found {
+            old_matched[old_index] = true;
+            new_matched[new_index] = true;
+            unchanged += 1;
+        }
+    }
+
+    for pairing in MODIFIED_PAIRINGS {
+        for (new_index, new_row) in new_rows.iter().enumerate() {
+            if new_matched[new_index] {
+                continue;
+            }
+            let found = old_rows.iter().enumerate().position(|(old_index, old)| {
+                !old_matched[old_index] && fields_match(&old.row, new_row, pairing)
+            });
+            if let Some(old_index) = found {
+                old_matched[old_index] = true;
+                new_matched[new_index] = true;
+                changes.push(ImportReplaceChange {
+                    status: "modified".to_string(),
+                    old: Some(old_row_contract(&old_rows[old_index])),
+                    new: Some(new_row_contract(new_index, new_row)),
+                    changed_fields: changed_fields(&old_rows[old_index].row, new_row),
+                });
+            }
+        }
+    }
+
+    for (new_index, new_row) in new_rows.iter().enumerate() {
+        if !new_matched[new_index] {
+            changes.push(ImportReplaceChange {
+                status: "added".to_string(),
+                old: None,
+                new: Some(new_row_contract(new_index, new_row)),
+                changed_fields: Vec::new(),
+            });
+        }
+    }
+    for (old_index, old) in old_rows.iter().enumerate() {
+        if !old_matched[old_index] {
+            changes.push(ImportReplaceChange {
+                status: "removed".to_string(),
+                old: Some(old_row_contract(old)),
+                new: None,
+                changed_fields: Vec::new(),
+            });
+        }
+    }
+
+    Ok(ReplaceDiff {
+        replaced_import_id: import_id.to_string(),
+        unchanged,
+        changes,
+    })
+}
+
+/// Links the new import to the one it replaced so `import list` can show the
+/// chain after the swap.
+pub(crate) fn record_replacement(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    diff: &ReplaceDiff,
+    timestamp: &str,
+) -> ClientResult<()> {
+    transaction
+        .execute(
+            "INSERT INTO internal_import_replacements (
+                import_id,
+                replaced_import_id,
+                rows_unchanged,
+                rows_added,
+                rows_removed,
+                rows_modified,
+                created_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                import_id,
+                &diff.replaced_import_id,
+                diff.unchanged,
+                diff.count("added"),
+                diff.count("removed"),
+                diff.count("modified"),
+                timestamp
+            ],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+fn changed_fields(old: &CanonicalTransaction, new: &CanonicalTransaction) -> Vec<String> {
+    COMPARED_FIELDS
+        .iter()
+        .filter(|(_, value)| value(old) != value(new))
+        .map(|(name, _)| (*name).to_string())
+        .collect()
+}
+
+fn fields_match(old: &CanonicalTransaction, new: &CanonicalTransaction, names: &[&str]) -> bool {
+    names.iter().all(|name| {
+        COMPARED_FIELDS
+            .iter()
+            .find(|(field, _)| field == name)
+            .is_some_and(|(_, value)| value(old).is_some() && value(old) == value(new))
+    })
+}
+
+fn old_row_contract(old: &ImportRow) -> ImportReplaceRow {
+    replace_row(old.txn_id.clone(), old.source_row_index, &old.row)
+}
+
+fn new_row_contract(index: usize, row: &CanonicalTransaction) -> ImportReplaceRow {
+    replace_row(None, Some((index as i64) + 1), row)
+}
+
+fn replace_row(
+    txn_id: Option<String>,
+    source_row_index: Option<i64>,
+    row: &CanonicalTransaction,
+) -> ImportReplaceRow {
+    ImportReplaceRow {
+        txn_id,
+        source_row_index,
+        account_key: row.account_key.clone(),
+        posted_at: row.posted_at.clone(),
+        amount: row.amount,
+        currency: row.currency.clone(),
+        description: row.description.clone(),
+        statement_id: row.statement_id.clone(),
+        external_id: row.external_id.clone(),
+        merchant: row.merchant.clone(),
+        category: row.category.clone(),
+        balance: row.balance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{changed_fields, fields_match};
+    use crate::import::CanonicalTransaction;
+
+    fn row(posted_at: &str, amount: f64, description: &str) -> CanonicalTransaction {
+        CanonicalTransaction {
+            statement_id: None,
+            dedupe_scope_id: "scope_1".to_string(),
+            account_key: "chase_checking_1234".to_string(),
+            account_type: None,
+            posted_at: posted_at.to_string(),
+            amount,
+            currency: "USD".to_string(),
+            description: description.to_string(),
+            external_id: None,
+            merchant: None,
+            category: None,
+            balance: None,
+            mcc: None,
+        }
+    }
+
+    #[test]
+    fn modified_pairing_ignores_missing_external_ids() {
+        let old = row("2026-01-05", -42.1, "WHOLE FOODS");
+        let new = row("2026-01-05", -42.01, "WHOLE FOODS");
+        assert_eq!(changed_fields(&old, &new), vec!["amount".to_string()]);
+        assert!(!fields_match(&old, &new, &["account_key", "external_id"]));
+        assert!(fields_match(
+            &old,
+            &new,
+            &["account_key", "posted_at", "description"]
+        ));
+    }
+}
@@ -4,8 +4,9 @@ use std::path::Path;
 use rusqlite::Connection;
 
 use crate::ClientResult;
-use crate::contracts::types::ImportSignProfile;
+use crate::contracts::types::{ImportSignCorrection, ImportSignProfile};
 use crate::import::CanonicalTransaction;
+use crate::import::drift_warnings::is_mirrored_sign_profile;
 use crate::state::map_sqlite_error;
 
 #[derive(Debug, Clone, Default)]
@@ -36,6 +37,25 @@ impl SignCounts {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct SignCorrection {
+    pub(crate) account_key: String,
+    pub(crate) rows_inverted: i64,
+    pub(crate) historical_negative_ratio: f64,
+    pub(crate) incoming_negative_ratio: f64,
+}
+
+impl SignCorrection {
+    pub(crate) fn to_contract(&self) -> ImportSignCorrection {
+        ImportSignCorrection {
+            account_key: self.account_key.clone(),
+            rows_inverted: self.rows_inverted,
+            historical_negative_ratio: self.historical_negative_ratio,
+            incoming_negative_ratio: self.incoming_negative_ratio,
+        }
+    }
+}
+
 pub(crate) fn existing_sign_count_map(
     connection: &Connection,
     db_path: &Path,
@@ -109,13 +129,61 @@ pub(crate) fn profiles_from_sign_counts(
         .collect()
 }
 
+pub(crate) fn detect_sign_corrections(
+    existing_sign_counts: &BTreeMap<String, SignCounts>,
+    incoming_sign_counts: &BTreeMap<String, SignCounts>,
+) -> Vec<SignCorrection> {
+    incoming_sign_counts
+        .iter()
+        .filter_map(|(account_key, incoming_counts)| {
+            let existing_counts = existing_sign_counts.get(account_key)?;
+            if !is_mirrored_sign_profile(existing_counts, incoming_counts) {
+                return None;
+            }
+            Some(SignCorrection {
+                account_key: account_key.clone(),
+                rows_inverted: incoming_counts.total_count(),
+                historical_negative_ratio: existing_counts.negative_ratio(),
+                incoming_negative_ratio: incoming_counts.negative_ratio(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn apply_sign_corrections(
+    rows: &mut [CanonicalTransaction],
+    corrections: &[SignCorrection],
+) {
+    if corrections.is_empty() {
+        return;
+    }
+
+    for row in rows.iter_mut() {
+        if !corrections
+            .iter()
+            .any(|correction| correction.account_key == row.account_key)
+        {
+            continue;
+        }
+        if row.amount != 0.0 {
+            row.amount = -row.amount;
+        }
+        row.balance = row
+            .balance
+            .map(|balance| if balance == 0.0 { balance } else { -balance });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
     use crate::import::CanonicalTransaction;
 
-    use super::{SignCounts, incoming_sign_count_map, profiles_from_sign_counts};
+    use super::{
+        SignCounts, apply_sign_corrections, detect_sign_corrections, incoming_sign_count_map,
+        profiles_from_sign_counts,
+    };
 
     #[test]
     fn incoming_sign_count_map_tracks_positive_and_negative_amounts() {
@@ -132,6 +200,8 @@ mod tests {
                 external_id: None,
                 merchant: None,
                 category: None,
+                balance: None,
+                mcc: None,
             },
             CanonicalTransaction {
                 statement_id: Some("acct_1_2026-01-31".to_string()),
@@ -145,6 +215,8 @@ mod tests {
                 external_id: None,
                 merchant: None,
                 category: None,
+                balance: None,
+                mcc: None,
             },
             CanonicalTransaction {
                 statement_id: Some("acct_2_2026-01-31".to_string()),
@@ -158,6 +230,8 @@ mod tests {
                 external_id: None,
                 merchant: None,
                 category: None,
+                balance: None,
+                mcc: None,
             },
         ];
 
@@ -195,4 +269,66 @@ mod tests {
         assert_eq!(profiles[1].negative_ratio, 0.0);
         assert_eq!(profiles[1].positive_ratio, 1.0);
     }
+
+    #[test]
+    fn mirrored_accounts_are_detected_and_only_their_rows_are_inverted() {
+        let existing = BTreeMap::from([
+            (
+                "acct_1".to_string(),
+                SignCounts {
+                    negative_count: 22,
+                    positive_count: 3,
+                },
+            ),
+            (
+                "acct_2".to_string(),
+                SignCounts {
+                    negative_count: 22,
+                    positive_count: 3,
+                },
+            ),
+        ]);
+        let incoming = BTreeMap::from([
+            (
+                "acct_1".to_string(),
+                SignCounts {
+                    negative_count: 1,
+                    positive_count: 5,
+                },
+            ),
+            (
+                "acct_2".to_string(),
+                SignCounts {
+                    negative_count: 5,
+                    positive_count: 1,
+                },
+            ),
+        ]);
+
+        let corrections = detect_sign_corrections(&existing, &incoming);
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].account_key, "acct_1");
+        assert_eq!(corrections[0].rows_inverted, 6);
+
+        let row = |account_key: &str, amount: f64| CanonicalTransaction {
+            statement_id: None,
+            dedupe_scope_id: format!("gen|scope|{account_key}"),
+            account_key: account_key.to_string(),
+            account_type: None,
+            posted_at: "2026-01-01".to_string(),
+            amount,
+            currency: "USD".to_string(),
+            description: "row".to_string(),
+            external_id: None,
+            merchant: None,
+            category: None,
+            balance: None,
+            mcc: None,
+        };
+        let mut rows = vec![row("acct_1", 12.5), row("acct_1", 0.0), row("acct_2", 4.0)];
+        apply_sign_corrections(&mut rows, &corrections);
+        assert_eq!(rows[0].amount, -12.5);
+        assert_eq!(rows[1].amount, 0.0);
+        assert_eq!(rows[2].amount, 4.0);
+    }
 }
@@ -0,0 +1,554 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+use serde_json::Value;
+
+use crate::contracts::types::{ImportDuplicateSummary, ImportStagedItem, ImportSummary};
+use crate::import::CanonicalTransaction;
+use crate::import::dedupe::{
+    dedupe_against_existing, dedupe_batch, find_statement_id_reuse_issues,
+};
+use crate::import::fingerprint::find_committed_by_hash;
+use crate::import::persist::{
+    PersistInput, PersistMode, insert_remaps, insert_sign_corrections, now_timestamp,
+    persist_import,
+};
+use crate::import::policy::load_dedupe_policies;
+use crate::import::probable::find_probable_duplicates;
+use crate::import::remap::AppliedRemap;
+use crate::import::sign_profiles::SignCorrection;
+use crate::import::statements::ReconciledStatement;
+use crate::import::{
+    build_duplicate_summary, find_account_type_conflict_issues, merge_duplicate_rows,
+    rejected_rows_error,
+};
+use crate::rules::RuleHit;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+pub(crate) struct StageInput<'a> {
+    pub(crate) import_id: &'a str,
+    pub(crate) rows: &'a [CanonicalTransaction],
+    pub(crate) source_records: &'a [Option<String>],
+    pub(crate) statements: &'a [ReconciledStatement],
+    pub(crate) sign_corrections: &'a [SignCorrection],
+    pub(crate) remaps: &'a [AppliedRemap],
+    pub(crate) rows_read: i64,
+    pub(crate) rows_valid: i64,
+    pub(crate) rows_invalid: i64,
+    pub(crate) source_kind: &'a str,
+    pub(crate) source_ref: Option<&'a str>,
+    pub(crate) source_hash: &'a str,
+    pub(crate) analysis: &'a Value,
+}
+
+/// A staged import run together with the analysis recorded when it was staged.
+#[derive(Debug, Clone)]
+pub(crate) struct StagedRun {
+    pub(crate) import_id: String,
+    pub(crate) status: String,
+    pub(crate) staged_at: String,
+    pub(crate) rows_read: i64,
+    pub(crate) rows_valid: i64,
+    pub(crate) rows_invalid: i64,
+    pub(crate) source_kind: Option<String>,
+    pub(crate) source_ref: Option<String>,
+    pub(crate) source_hash: String,
+    pub(crate) analysis: Value,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ApproveResult {
+    pub(crate) run: StagedRun,
+    pub(crate) inserted: i64,
+    pub(crate) duplicate_summary: ImportDuplicateSummary,
+}
+
+/// Records a validated batch as a `staged` import run. The rows wait in the
+/// staging tables until `import approve` commits them or `import reject`
+/// discards them; nothing reaches the ledger here.
+pub(crate) fn stage_import(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    input: StageInput<'_>,
+) -> ClientResult<()> {
+    let timestamp = now_timestamp();
+    transaction
+        .execute(
+            "INSERT INTO internal_import_runs (
+                import_id,
+                status,
+                created_at,
+                rows_read,
+                rows_valid,
+                rows_invalid,
+                source_kind,
+                source_ref,
+                source_hash
+             ) VALUES (?1, 'staged', ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                input.import_id,
+                &timestamp,
+                input.rows_read,
+                input.rows_valid,
+                input.rows_invalid,
+                input.source_kind,
+                input.source_ref,
+                input.source_hash
+            ],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    transaction
+        .execute(
+            "INSERT INTO internal_staged_imports (import_id, analysis, staged_at)
+             VALUES (?1, ?2, ?3)",
+            params![input.import_id, input.analysis.to_string(), &timestamp],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    for (index, row) in input.rows.iter().enumerate() {
+        transaction
+            .execute(
+                "INSERT INTO internal_staged_transactions (
+                    import_id,
+                    row_index,
+                    statement_id,
+                    dedupe_scope_id,
+                    account_key,
+                    account_type,
+                    posted_at,
+                    amount,
+                    currency,
+                    description,
+                    external_id,
+                    merchant,
+                    category,
+                    balance,
+                    source_record,
+                    mcc
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    input.import_id,
+                    (index as i64) + 1,
+                    &row.statement_id,
+                    &row.dedupe_scope_id,
+                    &row.account_key,
+                    &row.account_type,
+                    &row.posted_at,
+                    row.amount,
+                    &row.currency,
+                    &row.description,
+                    &row.external_id,
+                    &row.merchant,
+                    &row.category,
+                    row.balance,
+                    input.source_records.get(index).cloned().flatten(),
+                    &row.mcc
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+
+    for statement in input.statements {
+        transaction
+            .execute(
+                "INSERT INTO internal_staged_statements (
+                    import_id,
+                    account_key,
+                    statement_id,
+                    currency,
+                    opening_balance,
+                    closing_balance,
+                    net_amount,
+                    txn_count,
+                    first_posted_at,
+                    last_posted_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    input.import_id,
+                    &statement.account_key,
+                    &statement.statement_id,
+                    &statement.currency,
+                    statement.opening_balance,
+                    statement.closing_balance,
+                    statement.net_amount,
+                    statement.txn_count,
+                    &statement.first_posted_at,
+                    &statement.last_posted_at
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+
+    insert_sign_corrections(
+        transaction,
+        db_path,
+        input.import_id,
+        input.sign_corrections,
+        &timestamp,
+    )?;
+    insert_remaps(
+        transaction,
+        db_path,
+        input.import_id,
+        input.remaps,
+        &timestamp,
+    )?;
+    Ok(())
+}
+
+/// Commits a staged import. The statement, account type, and source checks
+/// and the dedupe pass run again against the ledger as it is now, so data
+/// committed since staging is never imported twice.
+pub(crate) fn approve_staged(
+    connection: &mut Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<ApproveResult> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let run = load_staged_run(&transaction, db_path, import_id)?;
+    if run.status != "staged" {
+        return Err(ClientError::import_not_staged(import_id, &run.status));
+    }
+    if let Some(prior) = find_committed_by_hash(&transaction, db_path, &run.source_hash)? {
+        return Err(ClientError::import_source_already_imported(
+            &prior.import_id,
+            &run.source_hash,
+        ));
+    }
+
+    let (rows, source_records) = load_staged_rows(&transaction, db_path, import_id)?;
+    // Rules already ran when the batch was staged; their changes are in the
+    // staged rows and only need recording against the rows that get inserted.
+    // Batches staged before rules existed have no hits recorded.
+    let rule_hits: HashMap<i64, Vec<RuleHit>> = match &run.analysis["rule_hits"] {
+        Value::Null => HashMap::new(),
+        hits => serde_json::from_value(hits.clone()).map_err(|error| {
+            ClientError::import_staged_analysis_invalid(import_id, &error.to_string())
+        })?,
+    };
+    let statements = load_staged_statements(&transaction, db_path, import_id)?;
+    let validated_summary = ImportSummary {
+        rows_read: run.rows_read,
+        rows_valid: run.rows_valid,
+        rows_invalid: run.rows_invalid,
+        inserted: 0,
+        deduped: 0,
+    };
+    let mut statement_id_rows: HashMap<(String, String), Vec<i64>> = HashMap::new();
+    let mut account_type_rows: HashMap<(String, String), Vec<i64>> = HashMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        let row_index = (index as i64) + 1;
+        if let Some(statement_id) = &row.statement_id {
+            statement_id_rows
+                .entry((row.account_key.clone(), statement_id.clone()))
+                .or_default()
+                .push(row_index);
+        }
+        if let Some(account_type) = &row.account_type {
+            account_type_rows
+                .entry((row.account_key.clone(), account_type.clone()))
+                .or_default()
+                .push(row_index);
+        }
+    }
+    let statement_id_reuse_issues =
+        find_statement_id_reuse_issues(&transaction, &statement_id_rows, db_path)?;
+    if !statement_id_reuse_issues.is_empty() {
+        return Err(rejected_rows_error(
+            &validated_summary,
+            statement_id_reuse_issues,
+        ));
+    }
+    let account_type_conflict_issues =
+        find_account_type_conflict_issues(&transaction, &account_type_rows, db_path)?;
+    if !account_type_conflict_issues.is_empty() {
+        return Err(rejected_rows_error(
+            &validated_summary,
+            account_type_conflict_issues,
+        ));
+    }
+
+    let dedupe_policies = load_dedupe_policies(db_path)?;
+    let batch_deduped = dedupe_batch(rows, &dedupe_policies);
+    let existing_deduped = dedupe_against_existing(
+        &transaction,
+        &batch_deduped.candidate_rows,
+        db_path,
+        &dedupe_policies,
+    )?;
+    let duplicate_summary = build_duplicate_summary(
+        batch_deduped.duplicate_rows.len() as i64,
+        existing_deduped.duplicate_rows.len() as i64,
+    );
+    let duplicate_rows = merge_duplicate_rows(
+        batch_deduped.duplicate_rows,
+        existing_deduped.duplicate_rows,
+    );
+    let probable_matches =
+        find_probable_duplicates(&transaction, &existing_deduped.insertable_rows, db_path)?;
+
+    let persisted = persist_import(
+        &transaction,
+        db_path,
+        PersistInput {
+            import_id,
+            candidate_rows: &existing_deduped.insertable_rows,
+            duplicate_rows: &duplicate_rows,
+            rows_read: run.rows_read,
+            rows_valid: run.rows_valid,
+            rows_invalid: run.rows_invalid,
+            source_kind: run.source_kind.as_deref().unwrap_or_default(),
+            source_ref: run.source_ref.as_deref(),
+            sign_corrections: &[],
+            remaps: &[],
+            statements: &statements,
+            probable_duplicates: &probable_matches,
+            dedupe_policies: &dedupe_policies,
+            source_hash: &run.source_hash,
+            idempotency_key: None,
+            source_records: &source_records,
+            rule_hits: &rule_hits,
+            mode: PersistMode::Staged,
+        },
+    )?;
+    clear_staging(&transaction, db_path, import_id)?;
+
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(ApproveResult {
+        run,
+        inserted: persisted.inserted,
+        duplicate_summary,
+    })
+}
+
+/// Discards a staged import. The run stays in the import list as `rejected`.
+/// Returns how many staged rows were dropped.
+pub(crate) fn reject_staged(
+    connection: &mut Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<i64> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let run = load_staged_run(&transaction, db_path, import_id)?;
+    if run.status != "staged" {
+        return Err(ClientError::import_not_staged(import_id, &run.status));
+    }
+    let rows_discarded = transaction
+        .query_row(
+            "SELECT COUNT(*) FROM internal_staged_transactions WHERE import_id = ?1",
+            params![import_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    for sql in [
+        "DELETE FROM internal_import_sign_corrections WHERE import_id = ?1",
+        "DELETE FROM internal_import_remaps WHERE import_id = ?1",
+        "UPDATE internal_import_runs SET status = 'rejected' WHERE import_id = ?1",
+    ] {
+        transaction
+            .execute(sql, params![import_id])
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    clear_staging(&transaction, db_path, import_id)?;
+
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(rows_discarded)
+}
+
+pub(crate) fn list_staged(
+    connection: &Connection,
+    db_path: &Path,
+) -> ClientResult<Vec<ImportStagedItem>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT r.import_id, s.staged_at, r.source_kind, r.source_ref, r.rows_read,
+                    r.rows_valid
+             FROM internal_import_runs r
+             JOIN internal_staged_imports s ON s.import_id = r.import_id
+             WHERE r.status = 'staged'
+             ORDER BY CAST(s.staged_at AS INTEGER) DESC, r.import_id DESC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok(ImportStagedItem {
+                import_id: row.get(0)?,
+                staged_at: row.get(1)?,
+                source_kind: row.get(2)?,
+                source_ref: row.get(3)?,
+                rows_read: row.get(4)?,
+                rows_valid: row.get(5)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(items)
+}
+
+/// Loads a staged run. Runs that were approved or rejected come back with
+/// their current status and an empty analysis.
+pub(crate) fn load_staged_run(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<StagedRun> {
+    let run = connection
+        .query_row(
+            "SELECT r.import_id, r.status, COALESCE(s.staged_at, r.created_at), r.rows_read,
+                    r.rows_valid, r.rows_invalid, r.source_kind, r.source_ref, r.source_hash,
+                    s.analysis
+             FROM internal_import_runs r
+             LEFT JOIN internal_staged_imports s ON s.import_id = r.import_id
+             WHERE r.import_id = ?1",
+            params![import_id],
+            |row| {
+                Ok(StagedRun {
+                    import_id: row.get(0)?,
+                    status: row.get(1)?,
+                    staged_at: row.get(2)?,
+                    rows_read: row.get(3)?,
+                    rows_valid: row.get(4)?,
+                    rows_invalid: row.get(5)?,
+                    source_kind: row.get(6)?,
+                    source_ref: row.get(7)?,
+                    source_hash: row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+                    analysis: row
+                        .get::<_, Option<String>>(9)?
+                        .and_then(|analysis| serde_json::from_str(&analysis).ok())
+                        .unwrap_or(Value::Null),
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    run.ok_or_else(|| ClientError::import_staged_id_not_found(import_id))
+}
+
+/// Duplicate counts `import stage` reported, read back from the analysis.
+pub(crate) fn staged_duplicate_summary(analysis: &Value) -> ImportDuplicateSummary {
+    let count = |field: &str| {
+        analysis
+            .get("duplicate_summary")
+            .and_then(|summary| summary.get(field))
+            .and_then(Value::as_i64)
+            .unwrap_or(0)
+    };
+    build_duplicate_summary(count("batch"), count("existing_ledger"))
+}
+
+fn load_staged_rows(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<(Vec<CanonicalTransaction>, Vec<Option<String>>)> {
+    let mut statement = connection
+        .prepare(
+            "SELECT statement_id, dedupe_scope_id, account_key, account_type, posted_at, amount,
+                    currency, description, external_id, merchant, category, balance,
+                    source_record, mcc
+             FROM internal_staged_transactions
+             WHERE import_id = ?1
+             ORDER BY row_index ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![import_id], |row| {
+            Ok((
+                CanonicalTransaction {
+                    statement_id: row.get(0)?,
+                    dedupe_scope_id: row.get(1)?,
+                    account_key: row.get(2)?,
+                    account_type: row.get(3)?,
+                    posted_at: row.get(4)?,
+                    amount: row.get(5)?,
+                    currency: row.get(6)?,
+                    description: row.get(7)?,
+                    external_id: row.get(8)?,
+                    merchant: row.get(9)?,
+                    category: row.get(10)?,
+                    balance: row.get(11)?,
+                    mcc: row.get(13)?,
+                },
+                row.get::<_, Option<String>>(12)?,
+            ))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut staged_rows = Vec::new();
+    let mut source_records = Vec::new();
+    for row in rows {
+        let (staged_row, source_record) = row.map_err(|error| map_sqlite_error(db_path, &error))?;
+        staged_rows.push(staged_row);
+        source_records.push(source_record);
+    }
+    Ok((staged_rows, source_records))
+}
+
+fn load_staged_statements(
+    connection: &Connection,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<Vec<ReconciledStatement>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT account_key, statement_id, currency, opening_balance, closing_balance,
+                    net_amount, txn_count, first_posted_at, last_posted_at
+             FROM internal_staged_statements
+             WHERE import_id = ?1
+             ORDER BY account_key ASC, statement_id ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![import_id], |row| {
+            Ok(ReconciledStatement {
+                account_key: row.get(0)?,
+                statement_id: row.get(1)?,
+                currency: row.get(2)?,
+                opening_balance: row.get(3)?,
+                closing_balance: row.get(4)?,
+                net_amount: row.get(5)?,
+                txn_count: row.get(6)?,
+                first_posted_at: row.get(7)?,
+                last_posted_at: row.get(8)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut statements = Vec::new();
+    for row in rows {
+        statements.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(statements)
+}
+
+fn clear_staging(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<()> {
+    for sql in [
+        "DELETE FROM internal_staged_transactions WHERE import_id = ?1",
+        "DELETE FROM internal_staged_statements WHERE import_id = ?1",
+        "DELETE FROM internal_staged_imports WHERE import_id = ?1",
+    ] {
+        transaction
+            .execute(sql, params![import_id])
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
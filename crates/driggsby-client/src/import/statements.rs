@@ -0,0 +1,424 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::contracts::types::{ImportIssue, ImportStatementReconciliation};
+use crate::import::CanonicalTransaction;
+use crate::import::parse::ParsedStatementHeader;
+use crate::import::validate::{normalize_optional, validate_money};
+
+pub(crate) type StatementKey = (String, String);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct StatementBalance {
+    pub(crate) opening_balance: f64,
+    pub(crate) closing_balance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ReconciledStatement {
+    pub(crate) account_key: String,
+    pub(crate) statement_id: String,
+    pub(crate) currency: String,
+    pub(crate) opening_balance: f64,
+    pub(crate) closing_balance: f64,
+    pub(crate) net_amount: f64,
+    pub(crate) txn_count: i64,
+    pub(crate) first_posted_at: String,
+    pub(crate) last_posted_at: String,
+}
+
+impl ReconciledStatement {
+    pub(crate) fn to_contract(&self) -> ImportStatementReconciliation {
+        ImportStatementReconciliation {
+            statement_id: self.statement_id.clone(),
+            account_key: self.account_key.clone(),
+            currency: self.currency.clone(),
+            opening_balance: self.opening_balance,
+            closing_balance: self.closing_balance,
+            net_amount: self.net_amount,
+            txn_count: self.txn_count,
+            first_posted_at: self.first_posted_at.clone(),
+            last_posted_at: self.last_posted_at.clone(),
+        }
+    }
+}
+
+/// Reads the per-row `statement_opening_balance`/`statement_closing_balance`
+/// pair. Both values must be present together and require a `statement_id`.
+pub(crate) fn row_statement_balance(
+    row: i64,
+    statement_id: Option<&str>,
+    opening_balance: Option<f64>,
+    closing_balance: Option<f64>,
+    issues: &mut Vec<ImportIssue>,
+) -> Option<StatementBalance> {
+    if opening_balance.is_none() && closing_balance.is_none() {
+        return None;
+    }
+
+    if statement_id.is_none() {
+        issues.push(ImportIssue {
+            row,
+            field: "statement_id".to_string(),
+            code: "statement_balance_requires_statement_id".to_string(),
+            description:
+                "statement balances require a statement_id so rows can be grouped into a statement."
+                    .to_string(),
+            expected: Some("non-empty statement_id".to_string()),
+            received: Some(String::new()),
+        });
+        return None;
+    }
+
+    match (opening_balance, closing_balance) {
+        (Some(opening_balance), Some(closing_balance)) => Some(StatementBalance {
+            opening_balance,
+            closing_balance,
+        }),
+        (Some(_), None) => {
+            issues.push(incomplete_balance_issue(row, "statement_closing_balance"));
+            None
+        }
+        _ => {
+            issues.push(incomplete_balance_issue(row, "statement_opening_balance"));
+            None
+        }
+    }
+}
+
+/// Records a row-level balance for its statement. Returns false and pushes an
+/// issue when a different balance was already recorded for the same statement.
+pub(crate) fn record_statement_balance(
+    balances: &mut BTreeMap<StatementKey, StatementBalance>,
+    key: &StatementKey,
+    balance: StatementBalance,
+    row: i64,
+    issues: &mut Vec<ImportIssue>,
+) -> bool {
+    match balances.get(key) {
+        Some(existing) if !same_balance(existing, &balance) => {
+            issues.push(conflicting_balance_issue(row, key, existing, &balance));
+            false
+        }
+        Some(_) => true,
+        None => {
+            balances.insert(key.clone(), balance);
+            true
+        }
+    }
+}
+
+/// Applies balances from the JSON `statements` sidecar to the statements
+/// present in this import.
+pub(crate) fn apply_statement_headers(
+    headers: Vec<ParsedStatementHeader>,
+    statement_id_rows: &HashMap<StatementKey, Vec<i64>>,
+    balances: &mut BTreeMap<StatementKey, StatementBalance>,
+    issues: &mut Vec<ImportIssue>,
+) {
+    for header in headers {
+        let mut header_issues = Vec::new();
+        let field_prefix = format!("statements[{}]", header.index);
+        let statement_id = normalize_optional(header.statement_id);
+        let account_key = normalize_optional(header.account_key);
+        let opening_balance = validate_money(
+            0,
+            &format!("{field_prefix}.opening_balance"),
+            normalize_optional(header.opening_balance),
+            &mut header_issues,
+        );
+        let closing_balance = validate_money(
+            0,
+            &format!("{field_prefix}.closing_balance"),
+            normalize_optional(header.closing_balance),
+            &mut header_issues,
+        );
+        let Some(statement_id) = statement_id else {
+            header_issues.push(ImportIssue {
+                row: 0,
+                field: format!("{field_prefix}.statement_id"),
+                code: "missing_required_field".to_string(),
+                description: "statement headers must include a non-empty statement_id.".to_string(),
+                expected: Some("non-empty string".to_string()),
+                received: Some(String::new()),
+            });
+            issues.extend(header_issues);
+            continue;
+        };
+        if !header_issues.is_empty() {
+            issues.extend(header_issues);
+            continue;
+        }
+        let (Some(opening_balance), Some(closing_balance)) = (opening_balance, closing_balance)
+        else {
+            continue;
+        };
+        let balance = StatementBalance {
+            opening_balance,
+            closing_balance,
+        };
+
+        let matched_keys = statement_id_rows
+            .keys()
+            .filter(|(row_account_key, row_statement_id)| {
+                *row_statement_id == statement_id
+                    && account_key
+                        .as_ref()
+                        .is_none_or(|value| value == row_account_key)
+            })
+            .cloned()
+            .collect::<BTreeSet<StatementKey>>();
+
+        if matched_keys.is_empty() {
+            issues.push(ImportIssue {
+                row: 0,
+                field: format!("{field_prefix}.statement_id"),
+                code: "statement_header_unmatched".to_string(),
+                description: format!(
+                    "statement header `{statement_id}` does not match any transaction statement_id in this import."
+                ),
+                expected: Some("statement_id used by at least one transaction".to_string()),
+                received: Some(statement_id),
+            });
+            continue;
+        }
+
+        for key in matched_keys {
+            let first_row = statement_id_rows
+                .get(&key)
+                .and_then(|rows| rows.iter().min().copied())
+                .unwrap_or(0);
+            record_statement_balance(balances, &key, balance, first_row, issues);
+        }
+    }
+}
+
+/// Verifies opening + sum(amounts) = closing for every statement that carries
+/// balances. Comparison happens in whole cents to avoid float drift.
+pub(crate) fn reconcile_statements(
+    rows: &[CanonicalTransaction],
+    balances: &BTreeMap<StatementKey, StatementBalance>,
+    statement_id_rows: &HashMap<StatementKey, Vec<i64>>,
+) -> (Vec<ReconciledStatement>, Vec<ImportIssue>) {
+    let mut reconciled = Vec::new();
+    let mut issues = Vec::new();
+
+    for (key, balance) in balances {
+        let (account_key, statement_id) = key;
+        let statement_rows = rows
+            .iter()
+            .filter(|row| {
+                row.account_key == *account_key
+                    && row.statement_id.as_deref() == Some(statement_id.as_str())
+            })
+            .collect::<Vec<&CanonicalTransaction>>();
+        let first_row = statement_id_rows
+            .get(key)
+            .and_then(|source_rows| source_rows.iter().min().copied())
+            .unwrap_or(0);
+
+        let currencies = statement_rows
+            .iter()
+            .map(|row| row.currency.as_str())
+            .collect::<BTreeSet<&str>>();
+        if currencies.len() > 1 {
+            issues.push(ImportIssue {
+                row: first_row,
+                field: "currency".to_string(),
+                code: "statement_mixed_currency".to_string(),
+                description: format!(
+                    "statement `{statement_id}` for account_key `{account_key}` mixes currencies ({}); balances can only reconcile within one currency.",
+                    currencies.iter().copied().collect::<Vec<&str>>().join(", ")
+                ),
+                expected: Some("one currency per statement".to_string()),
+                received: Some(currencies.iter().copied().collect::<Vec<&str>>().join(",")),
+            });
+            continue;
+        }
+
+        let net_cents = statement_rows
+            .iter()
+            .map(|row| to_cents(row.amount))
+            .sum::<i64>();
+        let opening_cents = to_cents(balance.opening_balance);
+        let closing_cents = to_cents(balance.closing_balance);
+        let computed_cents = opening_cents + net_cents;
+        if computed_cents != closing_cents {
+            issues.push(ImportIssue {
+                row: first_row,
+                field: "statement_closing_balance".to_string(),
+                code: "statement_balance_mismatch".to_string(),
+                description: format!(
+                    "statement `{statement_id}` for account_key `{account_key}` does not reconcile: opening {} + sum of {} amounts {} = {}, but closing balance is {} (off by {}). Check for dropped, duplicated, or sign-flipped rows.",
+                    format_cents(opening_cents),
+                    statement_rows.len(),
+                    format_cents(net_cents),
+                    format_cents(computed_cents),
+                    format_cents(closing_cents),
+                    format_cents(closing_cents - computed_cents)
+                ),
+                expected: Some(format_cents(closing_cents)),
+                received: Some(format_cents(computed_cents)),
+            });
+            continue;
+        }
+
+        let first_posted_at = statement_rows
+            .iter()
+            .map(|row| row.posted_at.clone())
+            .min()
+            .unwrap_or_default();
+        let last_posted_at = statement_rows
+            .iter()
+            .map(|row| row.posted_at.clone())
+            .max()
+            .unwrap_or_default();
+        reconciled.push(ReconciledStatement {
+            account_key: account_key.clone(),
+            statement_id: statement_id.clone(),
+            currency: currencies
+                .iter()
+                .next()
+                .map(|value| (*value).to_string())
+                .unwrap_or_default(),
+            opening_balance: balance.opening_balance,
+            closing_balance: balance.closing_balance,
+            net_amount: net_cents as f64 / 100.0,
+            txn_count: statement_rows.len() as i64,
+            first_posted_at,
+            last_posted_at,
+        });
+    }
+
+    issues.sort_by_key(|issue| issue.row);
+    (reconciled, issues)
+}
+
+fn incomplete_balance_issue(row: i64, missing_field: &str) -> ImportIssue {
+    ImportIssue {
+        row,
+        field: missing_field.to_string(),
+        code: "statement_balance_incomplete".to_string(),
+        description: format!(
+            "statement_opening_balance and statement_closing_balance must be provided together; `{missing_field}` is missing."
+        ),
+        expected: Some("number (e.g. 1250.00)".to_string()),
+        received: Some(String::new()),
+    }
+}
+
+fn conflicting_balance_issue(
+    row: i64,
+    key: &StatementKey,
+    existing: &StatementBalance,
+    incoming: &StatementBalance,
+) -> ImportIssue {
+    let (account_key, statement_id) = key;
+    ImportIssue {
+        row,
+        field: "statement_opening_balance".to_string(),
+        code: "statement_balance_conflict".to_string(),
+        description: format!(
+            "statement `{statement_id}` for account_key `{account_key}` has conflicting balances in this import: opening/closing {}/{} vs {}/{}.",
+            format_cents(to_cents(existing.opening_balance)),
+            format_cents(to_cents(existing.closing_balance)),
+            format_cents(to_cents(incoming.opening_balance)),
+            format_cents(to_cents(incoming.closing_balance))
+        ),
+        expected: Some(format!(
+            "{}/{}",
+            format_cents(to_cents(existing.opening_balance)),
+            format_cents(to_cents(existing.closing_balance))
+        )),
+        received: Some(format!(
+            "{}/{}",
+            format_cents(to_cents(incoming.opening_balance)),
+            format_cents(to_cents(incoming.closing_balance))
+        )),
+    }
+}
+
+fn same_balance(left: &StatementBalance, right: &StatementBalance) -> bool {
+    to_cents(left.opening_balance) == to_cents(right.opening_balance)
+        && to_cents(left.closing_balance) == to_cents(right.closing_balance)
+}
+
+pub(crate) fn to_cents(value: f64) -> i64 {
+    (value * 100.0).round() as i64
+}
+
+pub(crate) fn format_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let absolute = cents.unsigned_abs();
+    format!("{sign}{}.{:02}", absolute / 100, absolute % 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use crate::import::CanonicalTransaction;
+
+    use super::{StatementBalance, format_cents, reconcile_statements};
+
+    fn row(statement_id: &str, amount: f64) -> CanonicalTransaction {
+        CanonicalTransaction {
+            statement_id: Some(statement_id.to_string()),
+            dedupe_scope_id: format!("stmt|acct_1|{statement_id}"),
+            account_key: "acct_1".to_string(),
+            account_type: None,
+            posted_at: "2026-01-15".to_string(),
+            amount,
+            currency: "USD".to_string(),
+            description: "row".to_string(),
+            external_id: None,
+            merchant: None,
+            category: None,
+            balance: None,
+            mcc: None,
+        }
+    }
+
+    #[test]
+    fn reconcile_compares_in_cents_and_reports_the_gap() {
+        let key = ("acct_1".to_string(), "stmt_1".to_string());
+        let rows = vec![
+            row("stmt_1", -0.1),
+            row("stmt_1", -0.2),
+            row("stmt_1", 10.3),
+        ];
+        let statement_id_rows = HashMap::from([(key.clone(), vec![2, 3, 4])]);
+
+        let balanced = BTreeMap::from([(
+            key.clone(),
+            StatementBalance {
+                opening_balance: 100.0,
+                closing_balance: 110.0,
+            },
+        )]);
+        let (reconciled, issues) = reconcile_statements(&rows, &balanced, &statement_id_rows);
+        assert!(issues.is_empty());
+        assert_eq!(reconciled.len(), 1);
+        assert_eq!(reconciled[0].txn_count, 3);
+
+        let unbalanced = BTreeMap::from([(
+            key,
+            StatementBalance {
+                opening_balance: 100.0,
+                closing_balance: 109.8,
+            },
+        )]);
+        let (reconciled, issues) = reconcile_statements(&rows, &unbalanced, &statement_id_rows);
+        assert!(reconciled.is_empty());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].row, 2);
+        assert_eq!(issues[0].code, "statement_balance_mismatch");
+        assert_eq!(issues[0].expected.as_deref(), Some("109.80"));
+        assert_eq!(issues[0].received.as_deref(), Some("110.00"));
+    }
+
+    #[test]
+    fn format_cents_keeps_sign_for_sub_dollar_values() {
+        assert_eq!(format_cents(-5), "-0.05");
+        assert_eq!(format_cents(12345), "123.45");
+    }
+}
@@ -1,41 +1,98 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
 use ulid::Ulid;
 
+use crate::identity::assign_identity_key;
 use crate::import::CanonicalTransaction;
-use crate::import::dedupe::{dedupe_key, find_existing_match};
+use crate::import::archive::archive_import;
+use crate::import::dedupe::{DedupePolicies, DedupePolicy, dedupe_key, find_existing_match};
 use crate::import::persist::now_timestamp;
+use crate::import::policy::load_run_dedupe_policies;
+use crate::import::provenance::{link_promoted_candidate, unlink_import};
 use crate::intelligence::refresh::refresh_all_in_transaction;
 use crate::state::map_sqlite_error;
 use crate::{ClientError, ClientResult};
 
+/// The slice of an import to revert. With neither field set the whole import
+/// is reverted; otherwise only rows matching every set field are.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UndoScope {
+    pub(crate) account_key: Option<String>,
+    pub(crate) statement_id: Option<String>,
+}
+
+impl UndoScope {
+    pub(crate) fn is_partial(&self) -> bool {
+        self.account_key.is_some() || self.statement_id.is_some()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct UndoResult {
     pub(crate) import_id: String,
+    pub(crate) status: String,
     pub(crate) rows_reverted: i64,
     pub(crate) rows_promoted: i64,
     pub(crate) intelligence_refreshed: bool,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RevertedImport {
+    pub(crate) rows_reverted: i64,
+    pub(crate) rows_promoted: i64,
+    pub(crate) fully_reverted: bool,
+}
+
 #[derive(Debug, Clone)]
-struct PromotionCandidate {
-    candidate_id: String,
-    import_id: String,
-    row: CanonicalTransaction,
+pub(crate) struct PromotionCandidate {
+    pub(crate) candidate_id: String,
+    pub(crate) import_id: String,
+    pub(crate) row: CanonicalTransaction,
 }
 
 pub(crate) fn undo_import(
     connection: &mut Connection,
     db_path: &Path,
     import_id: &str,
+    scope: &UndoScope,
 ) -> ClientResult<UndoResult> {
-    let timestamp = now_timestamp();
     let transaction = connection
         .transaction_with_behavior(TransactionBehavior::Immediate)
         .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let reverted = revert_import(&transaction, db_path, import_id, scope)?;
+    refresh_all_in_transaction(&transaction, db_path)?;
+
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(UndoResult {
+        import_id: import_id.to_string(),
+        status: if reverted.fully_reverted {
+            "reverted".to_string()
+        } else {
+            "partially_reverted".to_string()
+        },
+        rows_reverted: reverted.rows_reverted,
+        rows_promoted: reverted.rows_promoted,
+        intelligence_refreshed: true,
+    })
+}
 
+/// Reverts a committed import, or the slice of it `scope` selects, inside the
+/// caller's transaction and promotes dedupe candidates that no longer conflict
+/// with the ledger. An import with rows left after a partial revert becomes
+/// `partially_reverted`. Intelligence is not refreshed here so callers that
+/// keep writing (such as `import replace`) refresh once at the end.
+pub(crate) fn revert_import(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    scope: &UndoScope,
+) -> ClientResult<RevertedImport> {
+    let timestamp = now_timestamp();
     let status = transaction
         .query_row(
             "SELECT status FROM internal_import_runs WHERE import_id = ?1 LIMIT 1",
@@ -51,83 +108,286 @@ pub(crate) fn undo_import(
     if current_status == "reverted" {
         return Err(ClientError::import_already_reverted(import_id));
     }
-    if current_status != "committed" {
-        return Err(ClientError::ledger_corrupt(db_path));
+    if current_status != "committed" && current_status != "partially_reverted" {
+        return Err(ClientError::import_not_committed(
+            import_id,
+            &current_status,
+        ));
+    }
+
+    let scope_account = scope.account_key.as_deref();
+    let scope_statement = scope.statement_id.as_deref();
+    let slice_counts = slice_counts_by_account(transaction, db_path, import_id, scope)?;
+    if slice_counts.is_empty() {
+        return Err(ClientError::import_undo_scope_empty(
+            import_id,
+            scope_account,
+            scope_statement,
+        ));
     }
 
-    let touched_account_keys = touched_account_keys_for_import(&transaction, db_path, import_id)?;
-    let touched_key_counts = touched_key_counts_for_import(&transaction, db_path, import_id)?;
+    let dedupe_policies = load_run_dedupe_policies(transaction, db_path, import_id)?;
+    let touched_account_keys =
+        touched_account_keys_for_import(transaction, db_path, import_id, scope)?;
+    let (touched_key_counts, reverted_keys) =
+        touched_keys_for_import(transaction, db_path, import_id, scope, &dedupe_policies)?;
+    archive_import(transaction, db_path, import_id, scope, &timestamp)?;
+    if scope.is_partial() {
+        unlink_slice(transaction, db_path, import_id, scope)?;
+        delete_slice_probable_duplicates(transaction, db_path, import_id, scope)?;
+    } else {
+        unlink_import(transaction, db_path, import_id)?;
+    }
     let rows_reverted = transaction
         .execute(
-            "DELETE FROM internal_transactions WHERE import_id = ?1",
-            params![import_id],
+            &format!("DELETE FROM internal_transactions WHERE import_id = ?1 AND {IN_SCOPE}"),
+            params![import_id, scope_account, scope_statement],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))? as i64;
 
     transaction
         .execute(
-            "UPDATE internal_import_runs
-             SET status = 'reverted', reverted_at = ?2
-             WHERE import_id = ?1",
-            params![import_id, &timestamp],
+            &format!("DELETE FROM internal_statements WHERE import_id = ?1 AND {IN_SCOPE}"),
+            params![import_id, scope_account, scope_statement],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     transaction
         .execute(
-            "UPDATE internal_transaction_dedupe_candidates
-             SET promoted_txn_id = COALESCE(promoted_txn_id, '__invalid__')
+            &format!(
+                "UPDATE internal_transaction_dedupe_candidates
+                 SET promoted_txn_id = COALESCE(promoted_txn_id, '{INVALIDATED_CANDIDATE}')
+                 WHERE import_id = ?1 AND {IN_SCOPE}"
+            ),
+            params![import_id, scope_account, scope_statement],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let fully_reverted = !scope.is_partial() || !import_has_rows(transaction, db_path, import_id)?;
+    if scope.is_partial() {
+        subtract_account_stats(transaction, db_path, import_id, &slice_counts)?;
+    }
+    transaction
+        .execute(
+            "UPDATE internal_import_runs
+             SET status = ?2, reverted_at = ?3
              WHERE import_id = ?1",
-            params![import_id],
+            params![
+                import_id,
+                if fully_reverted {
+                    "reverted"
+                } else {
+                    "partially_reverted"
+                },
+                &timestamp
+            ],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let mut rows_promoted = 0_i64;
     for (key, target_promotions) in touched_key_counts {
         let mut promoted_for_key = 0_i64;
-        let candidates = candidates_for_key(&transaction, db_path, &key)?;
+        let candidates = candidates_for_key(transaction, db_path, &key)?;
         for candidate in candidates {
             if promoted_for_key >= target_promotions {
                 break;
             }
 
-            if find_existing_match(&transaction, &candidate.row, db_path)?.is_some() {
+            let candidate_policies =
+                load_run_dedupe_policies(transaction, db_path, &candidate.import_id)?;
+            let policy = candidate_policies.for_account(&candidate.row.account_key);
+            if find_existing_match(transaction, &candidate.row, db_path, policy)?.is_some() {
                 // This candidate still conflicts with a committed canonical row.
                 // Keep it pending so later undo calls can promote it when safe.
                 continue;
             }
 
-            promote_candidate(&transaction, db_path, &candidate)?;
+            promote_candidate(transaction, db_path, &candidate, policy)?;
             rows_promoted += 1;
             promoted_for_key += 1;
         }
     }
 
-    reconcile_account_metadata_for_undo(&transaction, db_path, &touched_account_keys)?;
-    refresh_all_in_transaction(&transaction, db_path)?;
+    // Rows demoted by hand (accepted probable duplicates, mark-duplicate) can
+    // carry a different key than the reverted row they point at, so the
+    // key-count promotion above never reaches them.
+    for candidate in demoted_candidates(transaction, db_path, import_id, &reverted_keys)? {
+        let candidate_policies =
+            load_run_dedupe_policies(transaction, db_path, &candidate.import_id)?;
+        let policy = candidate_policies.for_account(&candidate.row.account_key);
+        if find_existing_match(transaction, &candidate.row, db_path, policy)?.is_some() {
+            continue;
+        }
+        promote_candidate(transaction, db_path, &candidate, policy)?;
+        rows_promoted += 1;
+    }
+    if !scope.is_partial() {
+        transaction
+            .execute(
+                "DELETE FROM internal_probable_duplicates
+                 WHERE import_id = ?1 OR matched_import_id = ?1",
+                params![import_id],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
 
-    transaction
-        .commit()
-        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    reconcile_account_metadata_for_undo(transaction, db_path, &touched_account_keys)?;
 
-    Ok(UndoResult {
-        import_id: import_id.to_string(),
+    Ok(RevertedImport {
         rows_reverted,
         rows_promoted,
-        intelligence_refreshed: true,
+        fully_reverted,
     })
 }
 
+/// `promoted_txn_id` of a dedupe candidate whose slice of its import was
+/// undone. Such a candidate can no longer be promoted.
+pub(crate) const INVALIDATED_CANDIDATE: &str = "__invalid__";
+
+/// Matches rows inside an [`UndoScope`] bound as `?2` (account key) and `?3`
+/// (statement id). Both unset matches every row.
+pub(crate) const IN_SCOPE: &str =
+    "(?2 IS NULL OR account_key = ?2) AND (?3 IS NULL OR statement_id = ?3)";
+
+#[derive(Debug, Clone, Default)]
+struct SliceCounts {
+    inserted: i64,
+    deduped: i64,
+}
+
+/// Ledger rows and pending candidates per account inside the scope.
+fn slice_counts_by_account(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    scope: &UndoScope,
+) -> ClientResult<BTreeMap<String, SliceCounts>> {
+    let mut statement = transaction
+        .prepare(&format!(
+            "SELECT account_key, 1 FROM internal_transactions
+             WHERE import_id = ?1 AND {IN_SCOPE}
+             UNION ALL
+             SELECT account_key, 0 FROM internal_transaction_dedupe_candidates
+             WHERE import_id = ?1 AND promoted_txn_id IS NULL AND {IN_SCOPE}"
+        ))
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(
+            params![import_id, &scope.account_key, &scope.statement_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? == 1)),
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut counts: BTreeMap<String, SliceCounts> = BTreeMap::new();
+    for row in rows {
+        let (account_key, in_ledger) = row.map_err(|error| map_sqlite_error(db_path, &error))?;
+        let entry = counts.entry(account_key).or_default();
+        if in_ledger {
+            entry.inserted += 1;
+        } else {
+            entry.deduped += 1;
+        }
+    }
+    Ok(counts)
+}
+
+fn import_has_rows(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+) -> ClientResult<bool> {
+    transaction
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM internal_transactions WHERE import_id = ?1)
+                 OR EXISTS (
+                     SELECT 1 FROM internal_transaction_dedupe_candidates
+                     WHERE import_id = ?1 AND promoted_txn_id IS NULL
+                 )",
+            params![import_id],
+            |row| row.get::<_, bool>(0),
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+fn subtract_account_stats(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    slice_counts: &BTreeMap<String, SliceCounts>,
+) -> ClientResult<()> {
+    for (account_key, counts) in slice_counts {
+        transaction
+            .execute(
+                "UPDATE internal_import_account_stats
+                 SET rows_read = MAX(rows_read - ?3 - ?4, 0),
+                     inserted = MAX(inserted - ?3, 0),
+                     deduped = MAX(deduped - ?4, 0)
+                 WHERE import_id = ?1 AND account_key = ?2",
+                params![import_id, account_key, counts.inserted, counts.deduped],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
+
+fn unlink_slice(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    scope: &UndoScope,
+) -> ClientResult<()> {
+    transaction
+        .execute(
+            &format!(
+                "UPDATE internal_import_source_rows SET txn_id = NULL
+                 WHERE txn_id IN (
+                     SELECT txn_id FROM internal_transactions
+                     WHERE import_id = ?1 AND {IN_SCOPE}
+                 )"
+            ),
+            params![import_id, &scope.account_key, &scope.statement_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+fn delete_slice_probable_duplicates(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    scope: &UndoScope,
+) -> ClientResult<()> {
+    transaction
+        .execute(
+            &format!(
+                "DELETE FROM internal_probable_duplicates
+                 WHERE txn_id IN (
+                     SELECT txn_id FROM internal_transactions
+                     WHERE import_id = ?1 AND {IN_SCOPE}
+                 )
+                 OR matched_txn_id IN (
+                     SELECT txn_id FROM internal_transactions
+                     WHERE import_id = ?1 AND {IN_SCOPE}
+                 )"
+            ),
+            params![import_id, &scope.account_key, &scope.statement_id],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
 fn touched_account_keys_for_import(
     transaction: &rusqlite::Transaction<'_>,
     db_path: &Path,
     import_id: &str,
+    scope: &UndoScope,
 ) -> ClientResult<Vec<String>> {
     let mut statement = transaction
         .prepare(
             "SELECT DISTINCT account_key
              FROM internal_import_account_stats
              WHERE import_id = ?1
+               AND (?2 IS NULL OR account_key = ?2)
                AND account_key IS NOT NULL
                AND TRIM(account_key) <> ''
              ORDER BY account_key ASC",
@@ -135,7 +395,9 @@ fn touched_account_keys_for_import(
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let rows = statement
-        .query_map(params![import_id], |row| row.get::<_, String>(0))
+        .query_map(params![import_id, &scope.account_key], |row| {
+            row.get::<_, String>(0)
+        })
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let mut account_keys = Vec::new();
@@ -173,46 +435,63 @@ fn reconcile_account_metadata_for_undo(
     Ok(())
 }
 
-fn touched_key_counts_for_import(
+/// Dedupe key counts for the rows an import is about to revert, plus each
+/// reverted row's key by `txn_id`.
+fn touched_keys_for_import(
     transaction: &rusqlite::Transaction<'_>,
     db_path: &Path,
     import_id: &str,
-) -> ClientResult<BTreeMap<String, i64>> {
+    scope: &UndoScope,
+    dedupe_policies: &DedupePolicies,
+) -> ClientResult<(BTreeMap<String, i64>, HashMap<String, String>)> {
     let mut statement = transaction
-        .prepare(
-            "SELECT statement_id, dedupe_scope_id, account_key, posted_at, amount, currency, description, external_id
+        .prepare(&format!(
+            "SELECT txn_id, statement_id, dedupe_scope_id, account_key, posted_at, amount, currency, description, external_id, merchant
              FROM internal_transactions
-             WHERE import_id = ?1",
-        )
+             WHERE import_id = ?1 AND {IN_SCOPE}"
+        ))
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let rows = statement
-        .query_map(params![import_id], |row| {
-            Ok(CanonicalTransaction {
-                statement_id: row.get::<_, Option<String>>(0)?,
-                dedupe_scope_id: row.get(1)?,
-                account_key: row.get(2)?,
-                account_type: None,
-                posted_at: row.get(3)?,
-                amount: row.get(4)?,
-                currency: row.get(5)?,
-                description: row.get(6)?,
-                external_id: row.get(7)?,
-                merchant: None,
-                category: None,
-            })
-        })
+        .query_map(
+            params![import_id, &scope.account_key, &scope.statement_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    CanonicalTransaction {
+                        statement_id: row.get::<_, Option<String>>(1)?,
+                        dedupe_scope_id: row.get(2)?,
+                        account_key: row.get(3)?,
+                        account_type: None,
+                        posted_at: row.get(4)?,
+                        amount: row.get(5)?,
+                        currency: row.get(6)?,
+                        description: row.get(7)?,
+                        external_id: row.get(8)?,
+                        merchant: row.get(9)?,
+                        category: None,
+                        balance: None,
+                        mcc: None,
+                    },
+                ))
+            },
+        )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let mut counts = BTreeMap::new();
+    let mut keys_by_txn_id = HashMap::new();
     for row in rows {
-        let canonical = row.map_err(|error| map_sqlite_error(db_path, &error))?;
-        let key = dedupe_key(&canonical);
-        let current = counts.entry(key).or_insert(0);
+        let (txn_id, canonical) = row.map_err(|error| map_sqlite_error(db_path, &error))?;
+        let key = dedupe_key(
+            &canonical,
+            dedupe_policies.for_account(&canonical.account_key),
+        );
+        let current = counts.entry(key.clone()).or_insert(0);
         *current += 1;
+        keys_by_txn_id.insert(txn_id, key);
     }
 
-    Ok(counts)
+    Ok((counts, keys_by_txn_id))
 }
 
 fn candidates_for_key(
@@ -234,12 +513,14 @@ fn candidates_for_key(
                 c.description,
                 c.external_id,
                 c.merchant,
-                c.category
+                c.category,
+                c.balance,
+                c.mcc
              FROM internal_transaction_dedupe_candidates c
              JOIN internal_import_runs i ON i.import_id = c.import_id
              WHERE c.dedupe_key = ?1
                AND c.promoted_txn_id IS NULL
-               AND i.status = 'committed'
+               AND i.status IN ('committed', 'partially_reverted')
              ORDER BY CAST(i.created_at AS INTEGER) ASC,
                       c.source_row_index ASC,
                       c.dedupe_reason ASC,
@@ -248,25 +529,7 @@ fn candidates_for_key(
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let rows = statement
-        .query_map(params![dedupe_key_value], |row| {
-            Ok(PromotionCandidate {
-                candidate_id: row.get(0)?,
-                import_id: row.get(1)?,
-                row: CanonicalTransaction {
-                    statement_id: row.get::<_, Option<String>>(2)?,
-                    dedupe_scope_id: row.get(3)?,
-                    account_key: row.get(4)?,
-                    account_type: None,
-                    posted_at: row.get(5)?,
-                    amount: row.get(6)?,
-                    currency: row.get(7)?,
-                    description: row.get(8)?,
-                    external_id: row.get(9)?,
-                    merchant: row.get(10)?,
-                    category: row.get(11)?,
-                },
-            })
-        })
+        .query_map(params![dedupe_key_value], promotion_candidate_from_row)
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let mut candidates = Vec::new();
@@ -277,12 +540,96 @@ fn candidates_for_key(
     Ok(candidates)
 }
 
-fn promote_candidate(
+fn demoted_candidates(
+    transaction: &rusqlite::Transaction<'_>,
+    db_path: &Path,
+    import_id: &str,
+    reverted_keys: &HashMap<String, String>,
+) -> ClientResult<Vec<PromotionCandidate>> {
+    let mut statement = transaction
+        .prepare(
+            "SELECT
+                c.candidate_id,
+                c.import_id,
+                c.statement_id,
+                c.dedupe_scope_id,
+                c.account_key,
+                c.posted_at,
+                c.amount,
+                c.currency,
+                c.description,
+                c.external_id,
+                c.merchant,
+                c.category,
+                c.balance,
+                c.mcc,
+                c.matched_txn_id,
+                c.dedupe_key
+             FROM internal_transaction_dedupe_candidates c
+             JOIN internal_import_runs i ON i.import_id = c.import_id
+             WHERE c.matched_import_id = ?1
+               AND c.dedupe_reason = 'existing_ledger'
+               AND c.promoted_txn_id IS NULL
+               AND i.status IN ('committed', 'partially_reverted')
+             ORDER BY c.source_row_index ASC, c.candidate_id ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let rows = statement
+        .query_map(params![import_id], |row| {
+            Ok((
+                promotion_candidate_from_row(row)?,
+                row.get::<_, Option<String>>(14)?,
+                row.get::<_, String>(15)?,
+            ))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut candidates = Vec::new();
+    for row in rows {
+        let (candidate, matched_txn_id, candidate_key) =
+            row.map_err(|error| map_sqlite_error(db_path, &error))?;
+        let demoted_by_hand = matched_txn_id
+            .and_then(|txn_id| reverted_keys.get(&txn_id))
+            .is_some_and(|reverted_key| *reverted_key != candidate_key);
+        if demoted_by_hand {
+            candidates.push(candidate);
+        }
+    }
+
+    Ok(candidates)
+}
+
+fn promotion_candidate_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<PromotionCandidate> {
+    Ok(PromotionCandidate {
+        candidate_id: row.get(0)?,
+        import_id: row.get(1)?,
+        row: CanonicalTransaction {
+            statement_id: row.get::<_, Option<String>>(2)?,
+            dedupe_scope_id: row.get(3)?,
+            account_key: row.get(4)?,
+            account_type: None,
+            posted_at: row.get(5)?,
+            amount: row.get(6)?,
+            currency: row.get(7)?,
+            description: row.get(8)?,
+            external_id: row.get(9)?,
+            merchant: row.get(10)?,
+            category: row.get(11)?,
+            balance: row.get(12)?,
+            mcc: row.get(13)?,
+        },
+    })
+}
+
+pub(crate) fn promote_candidate(
     transaction: &rusqlite::Transaction<'_>,
     db_path: &Path,
     candidate: &PromotionCandidate,
-) -> ClientResult<()> {
+    policy: &DedupePolicy,
+) -> ClientResult<String> {
     let txn_id = format!("txn_{}", Ulid::new());
+    let identity_key = assign_identity_key(transaction, db_path, &candidate.row, policy)?;
     transaction
         .execute(
             "INSERT INTO internal_transactions (
@@ -297,8 +644,11 @@ fn promote_candidate(
                 description,
                 external_id,
                 merchant,
-                category
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                category,
+                balance,
+                mcc,
+                identity_key
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 &txn_id,
                 &candidate.import_id,
@@ -311,7 +661,10 @@ fn promote_candidate(
                 &candidate.row.description,
                 &candidate.row.external_id,
                 &candidate.row.merchant,
-                &candidate.row.category
+                &candidate.row.category,
+                candidate.row.balance,
+                &candidate.row.mcc,
+                &identity_key
             ],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
@@ -324,6 +677,7 @@ fn promote_candidate(
             params![&candidate.candidate_id, &txn_id],
         )
         .map_err(|error| map_sqlite_error(db_path, &error))?;
+    link_promoted_candidate(transaction, db_path, &candidate.candidate_id, &txn_id)?;
 
-    Ok(())
+    Ok(txn_id)
 }
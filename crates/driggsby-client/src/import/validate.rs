@@ -1,10 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::NaiveDate;
 
 use crate::contracts::types::{ImportIssue, ImportSummary};
 use crate::import::CanonicalTransaction;
-use crate::import::parse::ParsedRow;
+use crate::import::parse::ParsedSource;
+use crate::import::policy::{self, ImportPolicy};
+use crate::import::statements::{self, StatementBalance, StatementKey};
+use crate::rules::{self, Rule, RuleHit};
 use crate::{ClientError, ClientResult};
 
 #[derive(Debug, Clone)]
@@ -13,18 +16,34 @@ pub(crate) struct ValidatedRows {
     pub(crate) summary: ImportSummary,
     pub(crate) statement_id_rows: HashMap<(String, String), Vec<i64>>,
     pub(crate) account_type_rows: HashMap<(String, String), Vec<i64>>,
+    pub(crate) statement_balances: BTreeMap<StatementKey, StatementBalance>,
+    pub(crate) policy_warnings: Option<Vec<ImportIssue>>,
+    /// Rule changes keyed by the row's 1-based position in `rows`, which is
+    /// the source row index dedupe assigns.
+    pub(crate) rule_hits: HashMap<i64, Vec<RuleHit>>,
 }
 
 pub(crate) fn validate_rows(
-    parsed_rows: Vec<ParsedRow>,
+    parsed_source: ParsedSource,
     statement_scope_id: &str,
+    import_policy: Option<&ImportPolicy>,
+    ledger_account_types: &HashMap<String, String>,
+    rules: &[Rule],
 ) -> ClientResult<ValidatedRows> {
+    let ParsedSource {
+        rows: parsed_rows,
+        statement_headers,
+        ..
+    } = parsed_source;
     let total_rows = parsed_rows.len();
     let mut rows = Vec::new();
+    let mut row_numbers = Vec::new();
     let mut issues = Vec::new();
     let mut statement_id_rows: HashMap<(String, String), Vec<i64>> = HashMap::new();
     let mut account_type_rows: HashMap<(String, String), Vec<i64>> = HashMap::new();
     let mut account_type_by_account: HashMap<String, String> = HashMap::new();
+    let mut statement_balances: BTreeMap<StatementKey, StatementBalance> = BTreeMap::new();
+    let mut rule_hits: HashMap<i64, Vec<RuleHit>> = HashMap::new();
 
     for raw in parsed_rows {
         let mut row_issues = Vec::new();
@@ -56,6 +75,36 @@ pub(crate) fn validate_rows(
         let external_id = normalize_optional(raw.external_id);
         let merchant = normalize_optional(raw.merchant);
         let category = normalize_optional(raw.category);
+        let mcc = validate_mcc(raw.row, raw.mcc, &mut row_issues);
+        let balance = validate_money(
+            raw.row,
+            "balance",
+            normalize_optional(raw.balance),
+            &mut row_issues,
+        );
+        let opening_balance = validate_money(
+            raw.row,
+            "statement_opening_balance",
+            normalize_optional(raw.statement_opening_balance),
+            &mut row_issues,
+        );
+        let closing_balance = validate_money(
+            raw.row,
+            "statement_closing_balance",
+            normalize_optional(raw.statement_closing_balance),
+            &mut row_issues,
+        );
+        let statement_balance = if row_issues.is_empty() {
+            statements::row_statement_balance(
+                raw.row,
+                statement_id.as_deref(),
+                opening_balance,
+                closing_balance,
+                &mut row_issues,
+            )
+        } else {
+            None
+        };
 
         if row_issues.is_empty() {
             if let (Some(account_key_value), Some(account_type_value)) =
@@ -81,6 +130,23 @@ pub(crate) fn validate_rows(
                 }
             }
 
+            if let (Some(account_key_value), Some(statement_id_value), Some(balance)) = (
+                account_key.as_ref(),
+                statement_id.as_ref(),
+                statement_balance,
+            ) {
+                let key = (account_key_value.clone(), statement_id_value.clone());
+                if !statements::record_statement_balance(
+                    &mut statement_balances,
+                    &key,
+                    balance,
+                    raw.row,
+                    &mut issues,
+                ) {
+                    continue;
+                }
+            }
+
             if let (Some(account_key_value), Some(statement_id_value)) =
                 (account_key.as_ref(), statement_id.as_ref())
             {
@@ -97,7 +163,7 @@ pub(crate) fn validate_rows(
                     .or_default()
                     .push(raw.row);
             }
-            rows.push(CanonicalTransaction {
+            let mut row = CanonicalTransaction {
                 statement_id,
                 dedupe_scope_id: dedupe_scope_id.unwrap_or_default(),
                 account_key: account_key.unwrap_or_default(),
@@ -109,12 +175,38 @@ pub(crate) fn validate_rows(
                 external_id,
                 merchant,
                 category,
-            });
+                balance,
+                mcc,
+            };
+            let hits = rules::apply_to_row(rules, &mut row);
+            if !hits.is_empty() {
+                rule_hits.insert(rows.len() as i64 + 1, hits);
+            }
+            rows.push(row);
+            row_numbers.push(raw.row);
         } else {
             issues.extend(row_issues);
         }
     }
 
+    statements::apply_statement_headers(
+        statement_headers,
+        &statement_id_rows,
+        &mut statement_balances,
+        &mut issues,
+    );
+    let policy_warnings = import_policy.map(|import_policy| {
+        let findings = policy::evaluate(
+            import_policy,
+            &rows,
+            &row_numbers,
+            ledger_account_types,
+            policy::today_utc(),
+        );
+        issues.extend(findings.errors);
+        findings.warnings
+    });
+
     let summary = ImportSummary {
         rows_read: total_rows as i64,
         rows_valid: rows.len() as i64,
@@ -136,6 +228,9 @@ pub(crate) fn validate_rows(
         summary,
         statement_id_rows,
         account_type_rows,
+        statement_balances,
+        policy_warnings,
+        rule_hits,
     })
 }
 
@@ -219,7 +314,7 @@ fn validate_posted_at(
 
 fn validate_amount(row: i64, value: Option<String>, issues: &mut Vec<ImportIssue>) -> Option<f64> {
     let normalized = normalize_optional(value);
-    let Some(candidate) = normalized else {
+    if normalized.is_none() {
         issues.push(ImportIssue {
             row,
             field: "amount".to_string(),
@@ -229,16 +324,29 @@ fn validate_amount(row: i64, value: Option<String>, issues: &mut Vec<ImportIssue
             received: Some(String::new()),
         });
         return None;
-    };
+    }
+
+    validate_money(row, "amount", normalized, issues)
+}
+
+/// Parses an optional money value with the same numeric and 2-decimal rules
+/// as `amount`. Returns `None` without an issue when the value is absent.
+pub(crate) fn validate_money(
+    row: i64,
+    field: &str,
+    value: Option<String>,
+    issues: &mut Vec<ImportIssue>,
+) -> Option<f64> {
+    let candidate = value?;
 
     let parsed = candidate.parse::<f64>();
     if let Ok(amount) = parsed {
         if !amount.is_finite() {
             issues.push(ImportIssue {
                 row,
-                field: "amount".to_string(),
+                field: field.to_string(),
                 code: "invalid_number".to_string(),
-                description: format!("amount must be numeric; got \"{candidate}\""),
+                description: format!("{field} must be numeric; got \"{candidate}\""),
                 expected: Some("number (e.g. -42.15)".to_string()),
                 received: Some(candidate),
             });
@@ -250,10 +358,10 @@ fn validate_amount(row: i64, value: Option<String>, issues: &mut Vec<ImportIssue
         {
             issues.push(ImportIssue {
                 row,
-                field: "amount".to_string(),
+                field: field.to_string(),
                 code: "invalid_amount_scale".to_string(),
                 description: format!(
-                    "amount must use at most 2 decimal places; got {scale} decimal places."
+                    "{field} must use at most 2 decimal places; got {scale} decimal places."
                 ),
                 expected: Some("number with <= 2 decimal places (e.g. -42.15)".to_string()),
                 received: Some(candidate),
@@ -266,9 +374,9 @@ fn validate_amount(row: i64, value: Option<String>, issues: &mut Vec<ImportIssue
 
     issues.push(ImportIssue {
         row,
-        field: "amount".to_string(),
+        field: field.to_string(),
         code: "invalid_number".to_string(),
-        description: format!("amount must be numeric; got \"{candidate}\""),
+        description: format!("{field} must be numeric; got \"{candidate}\""),
         expected: Some("number (e.g. -42.15)".to_string()),
         received: Some(candidate),
     });
@@ -356,6 +464,23 @@ fn validate_currency(
     Some(candidate.to_uppercase())
 }
 
+fn validate_mcc(row: i64, value: Option<String>, issues: &mut Vec<ImportIssue>) -> Option<String> {
+    let candidate = normalize_optional(value)?;
+    if candidate.len() == 4 && candidate.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Some(candidate);
+    }
+
+    issues.push(ImportIssue {
+        row,
+        field: "mcc".to_string(),
+        code: "invalid_mcc".to_string(),
+        description: format!("mcc `{candidate}` is not a four-digit merchant category code."),
+        expected: Some("four digits, for example 5411".to_string()),
+        received: Some(candidate),
+    });
+    None
+}
+
 fn validate_account_type(
     row: i64,
     value: Option<String>,
@@ -384,7 +509,7 @@ fn validate_account_type(
     None
 }
 
-fn canonical_account_type(value: &str) -> Option<String> {
+pub(crate) fn canonical_account_type(value: &str) -> Option<String> {
     let normalized = normalize_account_type_key(value);
     let canonical = match normalized.as_str() {
         "checking" => "checking",
@@ -473,7 +598,7 @@ fn has_loan_tokens(tokens: &[&str]) -> bool {
         .any(|token| matches!(*token, "loan" | "mortgage" | "studentloan" | "auto"))
 }
 
-fn normalize_optional(value: Option<String>) -> Option<String> {
+pub(crate) fn normalize_optional(value: Option<String>) -> Option<String> {
     let raw = value?;
     let trimmed = raw.trim();
     if trimmed.is_empty() {
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+
+use crate::intelligence::normalize::counterparty_from_transaction;
+use crate::intelligence::policy::{CATEGORY_SUGGESTIONS_POLICY_V1, CategorySuggestionPolicy};
+use crate::intelligence::types::CategorizedTransaction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestionBasis {
+    AmountBand,
+    Counterparty,
+}
+
+impl SuggestionBasis {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::AmountBand => "amount_band",
+            Self::Counterparty => "counterparty",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategorySuggestion {
+    pub category: String,
+    pub confidence: f64,
+    pub support: usize,
+    pub counterparty: String,
+    pub amount_band: String,
+    pub basis: SuggestionBasis,
+}
+
+/// Category frequencies learned from categorized ledger rows, keyed by
+/// counterparty and by counterparty plus amount band.
+#[derive(Debug, Clone)]
+pub struct CategoryClassifier {
+    policy: CategorySuggestionPolicy,
+    by_band: BTreeMap<(String, String), BTreeMap<String, usize>>,
+    by_counterparty: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl CategoryClassifier {
+    pub fn learn(history: &[CategorizedTransaction]) -> Self {
+        Self::learn_with_policy(history, CATEGORY_SUGGESTIONS_POLICY_V1)
+    }
+
+    fn learn_with_policy(
+        history: &[CategorizedTransaction],
+        policy: CategorySuggestionPolicy,
+    ) -> Self {
+        let mut by_band: BTreeMap<(String, String), BTreeMap<String, usize>> = BTreeMap::new();
+        let mut by_counterparty: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for row in history {
+            let category = row.category.trim();
+            if category.is_empty() {
+                continue;
+            }
+            let Some(counterparty) =
+                counterparty_from_transaction(row.merchant.as_deref(), &row.description)
+            else {
+                continue;
+            };
+            *by_band
+                .entry((counterparty.key.clone(), amount_band(row.amount)))
+                .or_default()
+                .entry(category.to_string())
+                .or_default() += 1;
+            *by_counterparty
+                .entry(counterparty.key)
+                .or_default()
+                .entry(category.to_string())
+                .or_default() += 1;
+        }
+
+        Self {
+            policy,
+            by_band,
+            by_counterparty,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_counterparty.is_empty()
+    }
+
+    /// Suggests a category for an uncategorized row, or `None` when history
+    /// is too thin or too split to clear the policy's confidence floor.
+    pub fn suggest(
+        &self,
+        merchant: Option<&str>,
+        description: &str,
+        amount: f64,
+    ) -> Option<CategorySuggestion> {
+        let counterparty = counterparty_from_transaction(merchant, description)?;
+        let band = amount_band(amount);
+
+        let band_counts = self.by_band.get(&(counterparty.key.clone(), band.clone()));
+        let (counts, basis, discount) = match band_counts {
+            Some(counts) if counts.values().sum::<usize>() >= self.policy.min_support => {
+                (counts, SuggestionBasis::AmountBand, 1.0)
+            }
+            _ => (
+                self.by_counterparty.get(&counterparty.key)?,
+                SuggestionBasis::Counterparty,
+                self.policy.counterparty_only_discount,
+            ),
+        };
+
+        let support = counts.values().sum::<usize>();
+        if support < self.policy.min_support {
+            return None;
+        }
+        // Ties resolve to the alphabetically first category so repeated runs
+        // over the same ledger always agree.
+        let (category, votes) = counts
+            .iter()
+            .max_by(|left, right| left.1.cmp(right.1).then_with(|| right.0.cmp(left.0)))?;
+
+        let share = *votes as f64 / support as f64;
+        let thinness = support as f64 / (support as f64 + 1.0);
+        let confidence = round_to(share * counterparty.quality_score * thinness * discount, 4);
+        if confidence < self.policy.min_confidence {
+            return None;
+        }
+
+        Some(CategorySuggestion {
+            category: category.clone(),
+            confidence,
+            support,
+            counterparty: counterparty.label,
+            amount_band: band,
+            basis,
+        })
+    }
+}
+
+/// Direction plus order of magnitude, so a $4 coffee and a $400 catering
+/// order from the same counterparty learn separately.
+pub fn amount_band(amount: f64) -> String {
+    let direction = if amount < 0.0 { "debit" } else { "credit" };
+    let magnitude = match amount.abs() {
+        value if value < 10.0 => "under_10",
+        value if value < 100.0 => "10_to_100",
+        value if value < 1000.0 => "100_to_1000",
+        _ => "1000_plus",
+    };
+    format!("{direction}:{magnitude}")
+}
+
+fn round_to(value: f64, decimals: u32) -> f64 {
+    let exponent = i32::try_from(decimals).unwrap_or(2);
+    let factor = 10_f64.powi(exponent);
+    (value * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intelligence::categorize::{CategoryClassifier, SuggestionBasis, amount_band};
+    use crate::intelligence::types::CategorizedTransaction;
+
+    fn row(merchant: &str, amount: f64, category: &str) -> CategorizedTransaction {
+        CategorizedTransaction {
+            merchant: Some(merchant.to_string()),
+            description: merchant.to_string(),
+            amount,
+            category: category.to_string(),
+        }
+    }
+
+    #[test]
+    fn amount_bands_split_direction_and_magnitude() {
+        assert_eq!(amount_band(-4.5), "debit:under_10");
+        assert_eq!(amount_band(-45.0), "debit:10_to_100");
+        assert_eq!(amount_band(450.0), "credit:100_to_1000");
+        assert_eq!(amount_band(-4500.0), "debit:1000_plus");
+    }
+
+    #[test]
+    fn suggests_the_majority_category_for_the_amount_band() {
+        let classifier = CategoryClassifier::learn(&[
+            row("Blue Bottle", -4.0, "Coffee"),
+            row("Blue Bottle", -5.0, "Coffee"),
+            row("Blue Bottle", -5.5, "Coffee"),
+            row("Blue Bottle", -6.0, "Coffee"),
+            row("Blue Bottle", -6.5, "Coffee"),
+            row("Blue Bottle", -350.0, "Catering"),
+        ]);
+
+        let small = classifier.suggest(Some("BLUE BOTTLE"), "SQ *BLUE BOTTLE", -5.5);
+        assert!(small.is_some());
+        if let Some(suggestion) = small {
+            assert_eq!(suggestion.category, "Coffee");
+            assert_eq!(suggestion.basis, SuggestionBasis::AmountBand);
+            assert_eq!(suggestion.support, 5);
+            assert_eq!(suggestion.confidence, 0.8333);
+        }
+
+        // One catering row is too little band history, and counterparty-wide
+        // history is mostly coffee.
+        let large = classifier.suggest(Some("Blue Bottle"), "SQ *BLUE BOTTLE", -400.0);
+        assert!(large.is_some());
+        if let Some(suggestion) = large {
+            assert_eq!(suggestion.category, "Coffee");
+            assert_eq!(suggestion.basis, SuggestionBasis::Counterparty);
+            assert_eq!(suggestion.support, 6);
+        }
+    }
+
+    #[test]
+    fn thin_or_split_history_suggests_nothing() {
+        let classifier = CategoryClassifier::learn(&[
+            row("Target", -40.0, "Groceries"),
+            row("Target", -45.0, "Household"),
+            row("Netflix", -15.49, "Streaming"),
+        ]);
+
+        assert!(
+            classifier
+                .suggest(Some("Target"), "TARGET", -42.0)
+                .is_none()
+        );
+        assert!(
+            classifier
+                .suggest(Some("Netflix"), "NETFLIX", -15.49)
+                .is_none()
+        );
+        assert!(
+            classifier
+                .suggest(Some("Spotify"), "SPOTIFY", -9.99)
+                .is_none()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod anomalies;
+pub mod categorize;
 pub mod date;
 pub mod normalize;
 pub mod policy;
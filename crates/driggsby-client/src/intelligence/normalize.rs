@@ -90,6 +90,60 @@ pub fn description_fingerprint(value: &str) -> Option<String> {
     Some(stable_tokens.join(" "))
 }
 
+/// Uppercased label tokens with store numbers and other all-digit tokens
+/// dropped, so "Whole Foods #123" and "WHOLE FOODS" share every token.
+pub fn label_tokens(value: &str) -> Vec<String> {
+    let Some(normalized) = normalize_text(value) else {
+        return Vec::new();
+    };
+    let mut tokens: Vec<String> = Vec::new();
+    for token in normalized.split_whitespace() {
+        if is_numeric_token(token) || tokens.iter().any(|seen| seen == token) {
+            continue;
+        }
+        tokens.push(token.to_string());
+    }
+    tokens
+}
+
+/// Dice similarity between the label tokens of two values, in `0.0..=1.0`.
+///
+/// Tokens of at least three characters also match when one is a prefix of
+/// the other, which covers truncations such as "MKTP" against "MKTPLACE".
+pub fn label_similarity(left: &str, right: &str) -> f64 {
+    let left_tokens = label_tokens(left);
+    let right_tokens = label_tokens(right);
+    if left_tokens.is_empty() || right_tokens.is_empty() {
+        return 0.0;
+    }
+
+    let mut unmatched = right_tokens.iter().collect::<Vec<&String>>();
+    let mut shared = 0usize;
+    for token in &left_tokens {
+        if let Some(position) = unmatched
+            .iter()
+            .position(|candidate| tokens_match(token, candidate))
+        {
+            unmatched.swap_remove(position);
+            shared += 1;
+        }
+    }
+
+    (2 * shared) as f64 / (left_tokens.len() + right_tokens.len()) as f64
+}
+
+fn tokens_match(left: &str, right: &str) -> bool {
+    if left == right {
+        return true;
+    }
+    let (shorter, longer) = if left.len() <= right.len() {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    shorter.len() >= 3 && longer.starts_with(shorter)
+}
+
 fn normalize_text(value: &str) -> Option<String> {
     let mut output = String::new();
     let mut previous_space = false;
@@ -139,7 +193,10 @@ fn is_noise_token(token: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{counterparty_from_transaction, description_fingerprint, normalize_merchant};
+    use super::{
+        counterparty_from_transaction, description_fingerprint, label_similarity, label_tokens,
+        normalize_merchant,
+    };
 
     #[test]
     fn merchant_normalization_uppercases_and_collapses_noise() {
@@ -166,4 +223,22 @@ mod tests {
             assert!(!counterparty.fallback_eligible);
         }
     }
+
+    #[test]
+    fn label_tokens_drop_store_numbers_and_repeats() {
+        assert_eq!(
+            label_tokens("Whole Foods #123 whole"),
+            vec!["WHOLE".to_string(), "FOODS".to_string()]
+        );
+        assert!(label_tokens(" #42 ").is_empty());
+    }
+
+    #[test]
+    fn label_similarity_scores_shared_and_prefixed_tokens() {
+        assert_eq!(label_similarity("Groceries", "GROCERIES"), 1.0);
+        assert_eq!(label_similarity("Whole Foods", "WHOLE FOODS MKT"), 0.8);
+        assert_eq!(label_similarity("AMZN Mktp US", "AMZN MKTPLACE"), 0.8);
+        assert_eq!(label_similarity("MK", "MKT"), 0.0);
+        assert_eq!(label_similarity("Netflix", "Spotify"), 0.0);
+    }
 }
@@ -6,6 +6,7 @@ use crate::intelligence::date::CadenceKind;
 /// auditable and easy to reason about in diffs and support/debug sessions.
 pub const RECURRING_POLICY_VERSION: &str = "recurring/v1";
 pub const ANOMALIES_POLICY_VERSION: &str = "anomalies/v1";
+pub const CATEGORY_SUGGESTIONS_POLICY_VERSION: &str = "category_suggestions/v1";
 
 /// v1 recurring classifier policy.
 ///
@@ -104,6 +105,27 @@ pub const ANOMALIES_POLICY_V1: AnomaliesPolicy = AnomaliesPolicy {
     relative_floor: 0.45,
 };
 
+/// v1 category suggestion policy.
+///
+/// Notes:
+/// - Confidence is the share of matching history that agrees on a category,
+///   scaled by counterparty quality and by `n / (n + 1)` so thin history
+///   never reaches full confidence.
+/// - Counterparty-wide history is only consulted when the row's amount band
+///   has too little support, and is discounted by `counterparty_only_discount`.
+#[derive(Debug, Clone, Copy)]
+pub struct CategorySuggestionPolicy {
+    pub min_support: usize,
+    pub min_confidence: f64,
+    pub counterparty_only_discount: f64,
+}
+
+pub const CATEGORY_SUGGESTIONS_POLICY_V1: CategorySuggestionPolicy = CategorySuggestionPolicy {
+    min_support: 2,
+    min_confidence: 0.6,
+    counterparty_only_discount: 0.9,
+};
+
 #[cfg(test)]
 mod tests {
     use crate::intelligence::policy::RECURRING_POLICY_V1;
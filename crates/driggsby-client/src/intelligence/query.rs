@@ -3,8 +3,12 @@ use std::path::Path;
 use rusqlite::{Connection, params};
 
 use crate::ClientResult;
+use crate::exclusions::exclusion_join;
 use crate::intelligence::date::{format_iso_date, parse_transaction_date};
-use crate::intelligence::types::{IntelligenceFilter, NormalizedTransaction};
+use crate::intelligence::types::{
+    CategorizedTransaction, IntelligenceFilter, NormalizedTransaction,
+};
+use crate::overrides::override_join;
 use crate::state::{map_sqlite_error, open_connection};
 
 pub fn load_transactions(
@@ -21,21 +25,26 @@ pub fn load_transactions_from_connection(
     filter: &IntelligenceFilter,
 ) -> ClientResult<Vec<NormalizedTransaction>> {
     let mut statement = connection
-        .prepare(
+        .prepare(&format!(
             "SELECT
-                txn_id,
-                account_key,
-                posted_at,
-                amount,
-                currency,
-                description,
-                merchant
-             FROM internal_transactions
-             WHERE amount <> 0
-               AND (?1 IS NULL OR posted_at >= ?1)
-               AND (?2 IS NULL OR posted_at <= ?2)
-             ORDER BY account_key ASC, currency ASC, posted_at ASC, txn_id ASC",
-        )
+                t.txn_id,
+                t.account_key,
+                t.posted_at,
+                t.amount,
+                t.currency,
+                t.description,
+                COALESCE(o.merchant, t.merchant)
+             FROM internal_transactions t
+             {override_join}
+             {exclusion_join}
+             WHERE t.amount <> 0
+               AND x.identity_key IS NULL
+               AND (?1 IS NULL OR t.posted_at >= ?1)
+               AND (?2 IS NULL OR t.posted_at <= ?2)
+             ORDER BY t.account_key ASC, t.currency ASC, t.posted_at ASC, t.txn_id ASC",
+            override_join = override_join(),
+            exclusion_join = exclusion_join()
+        ))
         .map_err(|error| map_sqlite_error(db_path, &error))?;
 
     let from_bound = filter.from.as_ref().map(format_iso_date);
@@ -88,3 +97,41 @@ pub fn load_transactions_from_connection(
 
     Ok(rows)
 }
+
+/// Ledger rows whose effective category is set, for learning category history.
+pub fn load_categorized_transactions_from_connection(
+    connection: &Connection,
+    db_path: &Path,
+) -> ClientResult<Vec<CategorizedTransaction>> {
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT
+                COALESCE(o.merchant, t.merchant),
+                t.description,
+                t.amount,
+                COALESCE(o.category, t.category)
+             FROM internal_transactions t
+             {override_join}
+             WHERE TRIM(COALESCE(o.category, t.category, '')) <> ''
+             ORDER BY t.posted_at ASC, t.txn_id ASC",
+            override_join = override_join()
+        ))
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let rows_iter = statement
+        .query_map([], |row| {
+            Ok(CategorizedTransaction {
+                merchant: row.get(0)?,
+                description: row.get(1)?,
+                amount: row.get(2)?,
+                category: row.get(3)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut rows = Vec::new();
+    for row in rows_iter {
+        rows.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(rows)
+}
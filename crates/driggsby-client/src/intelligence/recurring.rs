@@ -64,6 +64,22 @@ pub fn detect_recurring(transactions: &[NormalizedTransaction]) -> Vec<Recurring
     detect_recurring_with_policy(transactions, RECURRING_POLICY_V1)
 }
 
+/// The recurring group a transaction falls into, if it has a counterparty.
+pub(crate) fn recurring_group_key(transaction: &NormalizedTransaction) -> Option<String> {
+    let counterparty =
+        counterparty_from_transaction(transaction.merchant.as_deref(), &transaction.description)?;
+    Some(group_key_for(transaction, &counterparty.key))
+}
+
+fn group_key_for(transaction: &NormalizedTransaction, counterparty_key: &str) -> String {
+    format!(
+        "{}|{}|{}|{counterparty_key}",
+        transaction.account_key,
+        transaction.currency,
+        transaction.amount_sign_key(),
+    )
+}
+
 fn detect_recurring_with_policy(
     transactions: &[NormalizedTransaction],
     policy: RecurringPolicy,
@@ -77,13 +93,7 @@ fn detect_recurring_with_policy(
             continue;
         };
 
-        let group_key = format!(
-            "{}|{}|{}|{}",
-            transaction.account_key,
-            transaction.currency,
-            transaction.amount_sign_key(),
-            counterparty.key
-        );
+        let group_key = group_key_for(transaction, &counterparty.key);
 
         let entry = groups
             .entry(group_key.clone())
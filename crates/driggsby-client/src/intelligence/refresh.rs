@@ -3,6 +3,7 @@ use std::path::Path;
 use rusqlite::{Connection, Transaction, TransactionBehavior, params};
 
 use crate::ClientResult;
+use crate::import::coverage::refresh_account_coverage;
 use crate::intelligence::anomalies::detect_anomalies;
 use crate::intelligence::query::load_transactions_from_connection;
 use crate::intelligence::recurring::detect_recurring;
@@ -48,6 +49,7 @@ fn refresh_all_internal(connection: &Connection, db_path: &Path) -> ClientResult
     connection
         .execute("DELETE FROM internal_anomalies_materialized", [])
         .map_err(|error| map_sqlite_error(db_path, &error))?;
+    refresh_account_coverage(connection, db_path)?;
 
     let mut recurring_inserted = 0_i64;
     if !recurring_rows.is_empty() {
@@ -26,3 +26,12 @@ impl NormalizedTransaction {
         self.amount.abs()
     }
 }
+
+/// A ledger row with an effective category, used to learn category history.
+#[derive(Debug, Clone)]
+pub struct CategorizedTransaction {
+    pub merchant: Option<String>,
+    pub description: String,
+    pub amount: f64,
+    pub category: String,
+}
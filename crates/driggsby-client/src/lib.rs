@@ -1,11 +1,17 @@
 pub mod commands;
 pub mod contracts;
 pub mod error;
+mod exclusions;
+mod identity;
 mod import;
 pub mod intelligence;
 pub mod migrations;
+mod overrides;
+mod rules;
 pub mod setup;
+mod splits;
 pub mod state;
+mod tags;
 
 pub use contracts::envelope::{FailureEnvelope, SuccessEnvelope};
 pub use error::{ClientError, ClientResult};
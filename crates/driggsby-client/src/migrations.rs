@@ -14,16 +14,85 @@ const ADD_ACCOUNTS_METADATA_AND_IMPORT_ACCOUNT_STATS_SQL: &str =
     include_str!("migrations/0005_accounts_metadata_and_import_account_stats.sql");
 const MATERIALIZED_INTELLIGENCE_REFRESH_SQL: &str =
     include_str!("migrations/0006_materialized_intelligence_refresh.sql");
+const ADD_IMPORT_SIGN_CORRECTIONS_SQL: &str =
+    include_str!("migrations/0007_import_sign_corrections.sql");
+const ADD_STATEMENT_RECONCILIATION_SQL: &str =
+    include_str!("migrations/0008_statement_reconciliation.sql");
+const ADD_TRANSACTION_RUNNING_BALANCE_SQL: &str =
+    include_str!("migrations/0009_transaction_running_balance.sql");
+const ADD_ACCOUNT_COVERAGE_SQL: &str = include_str!("migrations/0010_account_coverage.sql");
+const ADD_IMPORT_REMAPS_SQL: &str = include_str!("migrations/0011_import_remaps.sql");
+const ADD_ALIASES_SQL: &str = include_str!("migrations/0012_aliases.sql");
+const ADD_PROBABLE_DUPLICATES_SQL: &str = include_str!("migrations/0013_probable_duplicates.sql");
+const ADD_IMPORT_SOURCE_FINGERPRINT_SQL: &str =
+    include_str!("migrations/0014_import_source_fingerprint.sql");
+const ADD_IMPORT_SOURCE_ROWS_SQL: &str = include_str!("migrations/0015_import_source_rows.sql");
+const ADD_IMPORT_REPLACEMENTS_SQL: &str = include_str!("migrations/0016_import_replacements.sql");
+const ADD_REVERTED_IMPORT_ARCHIVE_SQL: &str =
+    include_str!("migrations/0017_reverted_import_archive.sql");
 
-pub const REQUIRED_VIEW_NAMES: [&str; 5] = [
+const ADD_IMPORT_STAGING_SQL: &str = include_str!("migrations/0018_import_staging.sql");
+
+const ADD_TRANSACTION_OVERRIDES_SQL: &str =
+    include_str!("migrations/0019_transaction_overrides.sql");
+
+const ADD_TRANSACTION_TAGS_SQL: &str = include_str!("migrations/0020_transaction_tags.sql");
+
+const ADD_TRANSACTION_SPLITS_SQL: &str = include_str!("migrations/0021_transaction_splits.sql");
+
+const ADD_TRANSACTION_RULES_SQL: &str = include_str!("migrations/0022_transaction_rules.sql");
+
+const ADD_TRANSACTION_EXCLUSIONS_SQL: &str =
+    include_str!("migrations/0023_transaction_exclusions.sql");
+const ADD_TRANSACTION_IDENTITY_KEYS_SQL: &str =
+    include_str!("migrations/0024_transaction_identity_keys.sql");
+const ADD_IMPORT_RUN_DEDUPE_POLICIES_SQL: &str =
+    include_str!("migrations/0025_import_run_dedupe_policies.sql");
+
+/// Every migration in the order it runs.
+const MIGRATIONS: [&str; 25] = [
+    BOOTSTRAP_SQL,
+    ADD_TRANSACTION_DEDUPE_CANDIDATES_SQL,
+    ADD_STATEMENT_ID_AND_DUPLICATE_METADATA_SQL,
+    ADD_INTERNAL_DEDUPE_SCOPE_ID_SQL,
+    ADD_ACCOUNTS_METADATA_AND_IMPORT_ACCOUNT_STATS_SQL,
+    MATERIALIZED_INTELLIGENCE_REFRESH_SQL,
+    ADD_IMPORT_SIGN_CORRECTIONS_SQL,
+    ADD_STATEMENT_RECONCILIATION_SQL,
+    ADD_TRANSACTION_RUNNING_BALANCE_SQL,
+    ADD_ACCOUNT_COVERAGE_SQL,
+    ADD_IMPORT_REMAPS_SQL,
+    ADD_ALIASES_SQL,
+    ADD_PROBABLE_DUPLICATES_SQL,
+    ADD_IMPORT_SOURCE_FINGERPRINT_SQL,
+    ADD_IMPORT_SOURCE_ROWS_SQL,
+    ADD_IMPORT_REPLACEMENTS_SQL,
+    ADD_REVERTED_IMPORT_ARCHIVE_SQL,
+    ADD_IMPORT_STAGING_SQL,
+    ADD_TRANSACTION_OVERRIDES_SQL,
+    ADD_TRANSACTION_TAGS_SQL,
+    ADD_TRANSACTION_SPLITS_SQL,
+    ADD_TRANSACTION_RULES_SQL,
+    ADD_TRANSACTION_EXCLUSIONS_SQL,
+    ADD_TRANSACTION_IDENTITY_KEYS_SQL,
+    ADD_IMPORT_RUN_DEDUPE_POLICIES_SQL,
+];
+
+pub const REQUIRED_VIEW_NAMES: [&str; 11] = [
     "v1_transactions",
     "v1_accounts",
     "v1_imports",
     "v1_recurring",
     "v1_anomalies",
+    "v1_statements",
+    "v1_account_coverage",
+    "v1_transaction_tags",
+    "v1_transaction_splits",
+    "v1_category_rollup",
+    "v1_transaction_rule_hits",
 ];
 
-pub const REQUIRED_INDEX_NAMES: [&str; 11] = [
+pub const REQUIRED_INDEX_NAMES: [&str; 13] = [
     "idx_internal_transactions_import_id",
     "idx_internal_transactions_account_posted_at",
     "idx_internal_transactions_account_external_id",
@@ -35,6 +104,8 @@ pub const REQUIRED_INDEX_NAMES: [&str; 11] = [
     "idx_internal_import_account_stats_account_key",
     "idx_internal_recurring_materialized_last_seen_at",
     "idx_internal_anomalies_materialized_posted_at",
+    "idx_internal_statements_import_id",
+    "idx_internal_transactions_identity_key",
 ];
 
 pub const REQUIRED_META_KEYS: [(&str, &str); 3] = [
@@ -44,14 +115,7 @@ pub const REQUIRED_META_KEYS: [(&str, &str); 3] = [
 ];
 
 pub fn run_pending(conn: &mut Connection) -> rusqlite_migration::Result<()> {
-    let migrations = Migrations::new(vec![
-        M::up(BOOTSTRAP_SQL),
-        M::up(ADD_TRANSACTION_DEDUPE_CANDIDATES_SQL),
-        M::up(ADD_STATEMENT_ID_AND_DUPLICATE_METADATA_SQL),
-        M::up(ADD_INTERNAL_DEDUPE_SCOPE_ID_SQL),
-        M::up(ADD_ACCOUNTS_METADATA_AND_IMPORT_ACCOUNT_STATS_SQL),
-        M::up(MATERIALIZED_INTELLIGENCE_REFRESH_SQL),
-    ]);
+    let migrations = Migrations::new(MIGRATIONS.iter().map(|sql| M::up(sql)).collect());
     migrations.to_latest(conn)
 }
 
@@ -59,12 +123,14 @@ pub fn safe_repair_statement(statement_name: &str) -> Option<String> {
     parse_safe_repair_statements().remove(statement_name)
 }
 
+/// Repair blocks from every migration; when a later migration redefines an
+/// object, its block wins.
 fn parse_safe_repair_statements() -> HashMap<String, String> {
     let mut blocks: HashMap<String, String> = HashMap::new();
     let mut active_name: Option<String> = None;
     let mut active_sql = String::new();
 
-    for line in BOOTSTRAP_SQL.lines() {
+    for line in MIGRATIONS.iter().flat_map(|sql| sql.lines()) {
         let trimmed = line.trim();
 
         if let Some(name) = trimmed.strip_prefix("-- driggsby:safe_repair:start:") {
@@ -105,6 +171,12 @@ mod tests {
             "v1_imports",
             "v1_recurring",
             "v1_anomalies",
+            "v1_statements",
+            "v1_account_coverage",
+            "v1_transaction_tags",
+            "v1_transaction_splits",
+            "v1_category_rollup",
+            "v1_transaction_rule_hits",
             "idx_internal_transactions_import_id",
             "idx_internal_transactions_account_posted_at",
             "idx_internal_transactions_account_external_id",
@@ -116,6 +188,8 @@ mod tests {
             "idx_internal_import_account_stats_account_key",
             "idx_internal_recurring_materialized_last_seen_at",
             "idx_internal_anomalies_materialized_posted_at",
+            "idx_internal_statements_import_id",
+            "idx_internal_transactions_identity_key",
         ] {
             let sql = safe_repair_statement(name);
             assert!(sql.is_some());
@@ -15,7 +15,9 @@ CREATE TABLE IF NOT EXISTS internal_import_runs (
     inserted INTEGER NOT NULL DEFAULT 0,
     deduped INTEGER NOT NULL DEFAULT 0,
     source_kind TEXT,
-    source_ref TEXT
+    source_ref TEXT,
+    source_hash TEXT,
+    idempotency_key TEXT UNIQUE
 );
 
 CREATE TABLE IF NOT EXISTS internal_transactions (
@@ -30,7 +32,10 @@ CREATE TABLE IF NOT EXISTS internal_transactions (
     description TEXT NOT NULL,
     external_id TEXT,
     merchant TEXT,
-    category TEXT
+    category TEXT,
+    balance REAL,
+    mcc TEXT,
+    identity_key TEXT
 );
 
 CREATE TABLE IF NOT EXISTS internal_accounts (
@@ -63,13 +68,15 @@ CREATE TABLE IF NOT EXISTS internal_transaction_dedupe_candidates (
     external_id TEXT,
     merchant TEXT,
     category TEXT,
+    balance REAL,
     source_row_index INTEGER NOT NULL,
     dedupe_reason TEXT NOT NULL CHECK (dedupe_reason IN ('batch', 'existing_ledger')),
     matched_txn_id TEXT,
     matched_import_id TEXT,
     matched_batch_row_index INTEGER,
     created_at TEXT NOT NULL,
-    promoted_txn_id TEXT
+    promoted_txn_id TEXT,
+    mcc TEXT
 );
 
 CREATE TABLE IF NOT EXISTS internal_recurring_materialized (
@@ -99,6 +106,189 @@ CREATE TABLE IF NOT EXISTS internal_anomalies_materialized (
     severity TEXT NOT NULL
 );
 
+CREATE TABLE IF NOT EXISTS internal_import_sign_corrections (
+    import_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    rows_inverted INTEGER NOT NULL DEFAULT 0,
+    historical_negative_ratio REAL NOT NULL,
+    incoming_negative_ratio REAL NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, account_key)
+);
+
+CREATE TABLE IF NOT EXISTS internal_statements (
+    account_key TEXT NOT NULL,
+    statement_id TEXT NOT NULL,
+    import_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    opening_balance REAL NOT NULL,
+    closing_balance REAL NOT NULL,
+    net_amount REAL NOT NULL,
+    txn_count INTEGER NOT NULL,
+    first_posted_at TEXT NOT NULL,
+    last_posted_at TEXT NOT NULL,
+    reconciled_at TEXT NOT NULL,
+    PRIMARY KEY (account_key, statement_id)
+);
+
+CREATE TABLE IF NOT EXISTS internal_account_coverage_materialized (
+    account_key TEXT NOT NULL,
+    range_kind TEXT NOT NULL CHECK (range_kind IN ('covered', 'gap')),
+    range_start TEXT NOT NULL,
+    range_end TEXT NOT NULL,
+    day_count INTEGER NOT NULL,
+    import_count INTEGER NOT NULL,
+    PRIMARY KEY (account_key, range_start)
+);
+
+CREATE TABLE IF NOT EXISTS internal_import_remaps (
+    import_id TEXT NOT NULL,
+    property TEXT NOT NULL,
+    from_value TEXT NOT NULL,
+    to_value TEXT NOT NULL,
+    rows_rewritten INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, property, from_value)
+);
+
+CREATE TABLE IF NOT EXISTS internal_aliases (
+    property TEXT NOT NULL CHECK (property IN ('account_key', 'merchant', 'category')),
+    alias TEXT NOT NULL,
+    canonical TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (property, alias)
+);
+
+CREATE TABLE IF NOT EXISTS internal_probable_duplicates (
+    probable_id TEXT PRIMARY KEY,
+    import_id TEXT NOT NULL,
+    txn_id TEXT NOT NULL,
+    source_row_index INTEGER NOT NULL,
+    account_key TEXT NOT NULL,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    matched_txn_id TEXT NOT NULL,
+    matched_import_id TEXT NOT NULL,
+    matched_posted_at TEXT NOT NULL,
+    matched_description TEXT NOT NULL,
+    score REAL NOT NULL,
+    date_distance_days INTEGER NOT NULL,
+    description_similarity REAL NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('pending', 'accepted', 'rejected')),
+    candidate_id TEXT,
+    created_at TEXT NOT NULL,
+    resolved_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS internal_import_source_rows (
+    import_id TEXT NOT NULL,
+    source_row_index INTEGER NOT NULL,
+    source_record TEXT NOT NULL,
+    txn_id TEXT UNIQUE,
+    PRIMARY KEY (import_id, source_row_index)
+);
+
+CREATE TABLE IF NOT EXISTS internal_import_replacements (
+    import_id TEXT PRIMARY KEY,
+    replaced_import_id TEXT NOT NULL UNIQUE,
+    rows_unchanged INTEGER NOT NULL,
+    rows_added INTEGER NOT NULL,
+    rows_removed INTEGER NOT NULL,
+    rows_modified INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS internal_reverted_transactions (
+    import_id TEXT NOT NULL,
+    archive_index INTEGER NOT NULL,
+    txn_id TEXT,
+    source_row_index INTEGER,
+    statement_id TEXT,
+    dedupe_scope_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    account_type TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    external_id TEXT,
+    merchant TEXT,
+    category TEXT,
+    balance REAL,
+    archived_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, archive_index)
+);
+
+CREATE TABLE IF NOT EXISTS internal_reverted_statements (
+    import_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    statement_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    opening_balance REAL NOT NULL,
+    closing_balance REAL NOT NULL,
+    net_amount REAL NOT NULL,
+    txn_count INTEGER NOT NULL,
+    first_posted_at TEXT NOT NULL,
+    last_posted_at TEXT NOT NULL,
+    archived_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, account_key, statement_id)
+);
+
+CREATE TABLE IF NOT EXISTS internal_staged_imports (
+    import_id TEXT PRIMARY KEY,
+    analysis TEXT NOT NULL,
+    staged_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS internal_staged_transactions (
+    import_id TEXT NOT NULL,
+    row_index INTEGER NOT NULL,
+    statement_id TEXT,
+    dedupe_scope_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    account_type TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    external_id TEXT,
+    merchant TEXT,
+    category TEXT,
+    balance REAL,
+    source_record TEXT,
+    PRIMARY KEY (import_id, row_index)
+);
+
+CREATE TABLE IF NOT EXISTS internal_staged_statements (
+    import_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    statement_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    opening_balance REAL NOT NULL,
+    closing_balance REAL NOT NULL,
+    net_amount REAL NOT NULL,
+    txn_count INTEGER NOT NULL,
+    first_posted_at TEXT NOT NULL,
+    last_posted_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, account_key, statement_id)
+);
+
+CREATE TABLE IF NOT EXISTS internal_rules (
+    rule_id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    description_pattern TEXT,
+    amount_min REAL,
+    amount_max REAL,
+    account_key TEXT,
+    mcc TEXT,
+    set_merchant TEXT,
+    set_category TEXT,
+    add_tags TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
 INSERT OR IGNORE INTO internal_meta (key, value) VALUES ('schema_version', 'v1');
 INSERT OR IGNORE INTO internal_meta (key, value) VALUES ('public_views_version', 'v1');
 INSERT OR IGNORE INTO internal_meta (key, value) VALUES ('import_contract_version', 'v1');
@@ -117,9 +307,18 @@ SELECT
     t.description,
     t.external_id,
     t.merchant,
-    t.category
+    t.category,
+    COALESCE(o.merchant, t.merchant) AS effective_merchant,
+    COALESCE(o.category, t.category) AS effective_category,
+    o.note,
+    t.balance,
+    t.mcc,
+    x.identity_key IS NOT NULL AS excluded,
+    x.reason AS exclusion_reason
 FROM internal_transactions t
-LEFT JOIN internal_accounts a ON a.account_key = t.account_key;
+LEFT JOIN internal_accounts a ON a.account_key = t.account_key
+LEFT JOIN internal_transaction_overrides o ON o.identity_key = t.identity_key
+LEFT JOIN internal_transaction_exclusions x ON x.identity_key = t.identity_key;
 -- driggsby:safe_repair:end:v1_transactions
 
 -- driggsby:safe_repair:start:v1_accounts
@@ -188,6 +387,37 @@ SELECT
 FROM internal_anomalies_materialized;
 -- driggsby:safe_repair:end:v1_anomalies
 
+-- driggsby:safe_repair:start:v1_statements
+CREATE VIEW v1_statements AS
+SELECT
+    s.statement_id,
+    s.account_key,
+    a.account_type,
+    s.import_id,
+    s.currency,
+    s.opening_balance,
+    s.closing_balance,
+    s.net_amount,
+    s.txn_count,
+    s.first_posted_at,
+    s.last_posted_at,
+    s.reconciled_at
+FROM internal_statements s
+LEFT JOIN internal_accounts a ON a.account_key = s.account_key;
+-- driggsby:safe_repair:end:v1_statements
+
+-- driggsby:safe_repair:start:v1_account_coverage
+CREATE VIEW v1_account_coverage AS
+SELECT
+    account_key,
+    range_kind,
+    range_start,
+    range_end,
+    day_count,
+    import_count
+FROM internal_account_coverage_materialized;
+-- driggsby:safe_repair:end:v1_account_coverage
+
 -- driggsby:safe_repair:start:idx_internal_transactions_import_id
 CREATE INDEX idx_internal_transactions_import_id
 ON internal_transactions(import_id);
@@ -242,3 +472,8 @@ ON internal_recurring_materialized(last_seen_at);
 CREATE INDEX idx_internal_anomalies_materialized_posted_at
 ON internal_anomalies_materialized(posted_at);
 -- driggsby:safe_repair:end:idx_internal_anomalies_materialized_posted_at
+
+-- driggsby:safe_repair:start:idx_internal_statements_import_id
+CREATE INDEX idx_internal_statements_import_id
+ON internal_statements(import_id);
+-- driggsby:safe_repair:end:idx_internal_statements_import_id
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS internal_import_sign_corrections (
+    import_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    rows_inverted INTEGER NOT NULL DEFAULT 0,
+    historical_negative_ratio REAL NOT NULL,
+    incoming_negative_ratio REAL NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, account_key)
+);
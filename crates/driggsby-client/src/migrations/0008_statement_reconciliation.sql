@@ -0,0 +1,35 @@
+CREATE TABLE IF NOT EXISTS internal_statements (
+    account_key TEXT NOT NULL,
+    statement_id TEXT NOT NULL,
+    import_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    opening_balance REAL NOT NULL,
+    closing_balance REAL NOT NULL,
+    net_amount REAL NOT NULL,
+    txn_count INTEGER NOT NULL,
+    first_posted_at TEXT NOT NULL,
+    last_posted_at TEXT NOT NULL,
+    reconciled_at TEXT NOT NULL,
+    PRIMARY KEY (account_key, statement_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_internal_statements_import_id
+ON internal_statements(import_id);
+
+DROP VIEW IF EXISTS v1_statements;
+CREATE VIEW v1_statements AS
+SELECT
+    s.statement_id,
+    s.account_key,
+    a.account_type,
+    s.import_id,
+    s.currency,
+    s.opening_balance,
+    s.closing_balance,
+    s.net_amount,
+    s.txn_count,
+    s.first_posted_at,
+    s.last_posted_at,
+    s.reconciled_at
+FROM internal_statements s
+LEFT JOIN internal_accounts a ON a.account_key = s.account_key;
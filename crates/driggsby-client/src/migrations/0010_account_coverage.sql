@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS internal_account_coverage_materialized (
+    account_key TEXT NOT NULL,
+    range_kind TEXT NOT NULL CHECK (range_kind IN ('covered', 'gap')),
+    range_start TEXT NOT NULL,
+    range_end TEXT NOT NULL,
+    day_count INTEGER NOT NULL,
+    import_count INTEGER NOT NULL,
+    PRIMARY KEY (account_key, range_start)
+);
+
+DROP VIEW IF EXISTS v1_account_coverage;
+CREATE VIEW v1_account_coverage AS
+SELECT
+    account_key,
+    range_kind,
+    range_start,
+    range_end,
+    day_count,
+    import_count
+FROM internal_account_coverage_materialized;
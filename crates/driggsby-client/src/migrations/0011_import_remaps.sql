@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS internal_import_remaps (
+    import_id TEXT NOT NULL,
+    property TEXT NOT NULL,
+    from_value TEXT NOT NULL,
+    to_value TEXT NOT NULL,
+    rows_rewritten INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, property, from_value)
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS internal_aliases (
+    property TEXT NOT NULL CHECK (property IN ('account_key', 'merchant', 'category')),
+    alias TEXT NOT NULL,
+    canonical TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (property, alias)
+);
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS internal_probable_duplicates (
+    probable_id TEXT PRIMARY KEY,
+    import_id TEXT NOT NULL,
+    txn_id TEXT NOT NULL,
+    source_row_index INTEGER NOT NULL,
+    account_key TEXT NOT NULL,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    matched_txn_id TEXT NOT NULL,
+    matched_import_id TEXT NOT NULL,
+    matched_posted_at TEXT NOT NULL,
+    matched_description TEXT NOT NULL,
+    score REAL NOT NULL,
+    date_distance_days INTEGER NOT NULL,
+    description_similarity REAL NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('pending', 'accepted', 'rejected')),
+    candidate_id TEXT,
+    created_at TEXT NOT NULL,
+    resolved_at TEXT
+);
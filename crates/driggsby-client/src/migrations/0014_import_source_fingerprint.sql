@@ -0,0 +1,82 @@
+PRAGMA foreign_keys = OFF;
+
+DROP VIEW IF EXISTS v1_imports;
+
+ALTER TABLE internal_import_runs RENAME TO internal_import_runs_old;
+
+CREATE TABLE internal_import_runs (
+    import_id TEXT PRIMARY KEY,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    committed_at TEXT,
+    reverted_at TEXT,
+    rows_read INTEGER NOT NULL DEFAULT 0,
+    rows_valid INTEGER NOT NULL DEFAULT 0,
+    rows_invalid INTEGER NOT NULL DEFAULT 0,
+    inserted INTEGER NOT NULL DEFAULT 0,
+    deduped INTEGER NOT NULL DEFAULT 0,
+    source_kind TEXT,
+    source_ref TEXT,
+    source_hash TEXT,
+    idempotency_key TEXT UNIQUE
+);
+
+INSERT INTO internal_import_runs (
+    import_id,
+    status,
+    created_at,
+    committed_at,
+    reverted_at,
+    rows_read,
+    rows_valid,
+    rows_invalid,
+    inserted,
+    deduped,
+    source_kind,
+    source_ref,
+    source_hash,
+    idempotency_key
+)
+SELECT
+    import_id,
+    status,
+    created_at,
+    committed_at,
+    reverted_at,
+    rows_read,
+    rows_valid,
+    rows_invalid,
+    inserted,
+    deduped,
+    source_kind,
+    source_ref,
+    NULL AS source_hash,
+    NULL AS idempotency_key
+FROM internal_import_runs_old;
+
+DROP TABLE internal_import_runs_old;
+
+DROP INDEX IF EXISTS idx_internal_import_runs_created_at_desc;
+
+CREATE VIEW v1_imports AS
+SELECT
+    import_id,
+    status,
+    created_at,
+    committed_at,
+    reverted_at,
+    rows_read,
+    rows_valid,
+    rows_invalid,
+    inserted,
+    deduped,
+    source_kind,
+    source_ref
+FROM internal_import_runs;
+
+CREATE INDEX IF NOT EXISTS idx_internal_import_runs_created_at_desc
+ON internal_import_runs(created_at DESC);
+
+PRAGMA foreign_keys = ON;
+
+SELECT 1;
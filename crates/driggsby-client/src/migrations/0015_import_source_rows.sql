@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS internal_import_source_rows (
+    import_id TEXT NOT NULL,
+    source_row_index INTEGER NOT NULL,
+    source_record TEXT NOT NULL,
+    txn_id TEXT UNIQUE,
+    PRIMARY KEY (import_id, source_row_index)
+);
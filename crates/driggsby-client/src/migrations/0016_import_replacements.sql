@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS internal_import_replacements (
+    import_id TEXT PRIMARY KEY,
+    replaced_import_id TEXT NOT NULL UNIQUE,
+    rows_unchanged INTEGER NOT NULL,
+    rows_added INTEGER NOT NULL,
+    rows_removed INTEGER NOT NULL,
+    rows_modified INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
@@ -0,0 +1,35 @@
+CREATE TABLE IF NOT EXISTS internal_reverted_transactions (
+    import_id TEXT NOT NULL,
+    archive_index INTEGER NOT NULL,
+    txn_id TEXT,
+    source_row_index INTEGER,
+    statement_id TEXT,
+    dedupe_scope_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    account_type TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    external_id TEXT,
+    merchant TEXT,
+    category TEXT,
+    balance REAL,
+    archived_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, archive_index)
+);
+
+CREATE TABLE IF NOT EXISTS internal_reverted_statements (
+    import_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    statement_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    opening_balance REAL NOT NULL,
+    closing_balance REAL NOT NULL,
+    net_amount REAL NOT NULL,
+    txn_count INTEGER NOT NULL,
+    first_posted_at TEXT NOT NULL,
+    last_posted_at TEXT NOT NULL,
+    archived_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, account_key, statement_id)
+);
@@ -0,0 +1,38 @@
+CREATE TABLE IF NOT EXISTS internal_staged_imports (
+    import_id TEXT PRIMARY KEY,
+    analysis TEXT NOT NULL,
+    staged_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS internal_staged_transactions (
+    import_id TEXT NOT NULL,
+    row_index INTEGER NOT NULL,
+    statement_id TEXT,
+    dedupe_scope_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    account_type TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    external_id TEXT,
+    merchant TEXT,
+    category TEXT,
+    balance REAL,
+    source_record TEXT,
+    PRIMARY KEY (import_id, row_index)
+);
+
+CREATE TABLE IF NOT EXISTS internal_staged_statements (
+    import_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    statement_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    opening_balance REAL NOT NULL,
+    closing_balance REAL NOT NULL,
+    net_amount REAL NOT NULL,
+    txn_count INTEGER NOT NULL,
+    first_posted_at TEXT NOT NULL,
+    last_posted_at TEXT NOT NULL,
+    PRIMARY KEY (import_id, account_key, statement_id)
+);
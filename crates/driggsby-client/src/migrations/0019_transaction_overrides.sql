@@ -0,0 +1,48 @@
+CREATE TABLE IF NOT EXISTS internal_transaction_overrides (
+    override_key TEXT PRIMARY KEY,
+    account_key TEXT NOT NULL,
+    external_id TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    merchant TEXT,
+    category TEXT,
+    note TEXT,
+    updated_at TEXT NOT NULL
+);
+
+DROP VIEW IF EXISTS v1_transactions;
+CREATE VIEW v1_transactions AS
+SELECT
+    t.txn_id,
+    t.import_id,
+    t.statement_id,
+    t.account_key,
+    a.account_type,
+    t.posted_at,
+    t.amount,
+    t.currency,
+    t.description,
+    t.external_id,
+    t.merchant,
+    t.category,
+    COALESCE(o.merchant, t.merchant) AS effective_merchant,
+    COALESCE(o.category, t.category) AS effective_category,
+    o.note,
+    t.balance
+FROM internal_transactions t
+LEFT JOIN internal_accounts a ON a.account_key = t.account_key
+LEFT JOIN internal_transaction_overrides o
+    ON o.account_key = t.account_key
+   AND (
+        (o.external_id IS NOT NULL AND o.external_id = t.external_id)
+        OR (
+            o.external_id IS NULL
+            AND t.external_id IS NULL
+            AND o.posted_at = t.posted_at
+            AND o.amount = t.amount
+            AND o.currency = t.currency
+            AND o.description = t.description
+        )
+   );
@@ -0,0 +1,33 @@
+CREATE TABLE IF NOT EXISTS internal_transaction_tags (
+    identity_key TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    external_id TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (identity_key, tag)
+);
+
+DROP VIEW IF EXISTS v1_transaction_tags;
+CREATE VIEW v1_transaction_tags AS
+SELECT
+    t.txn_id,
+    g.tag,
+    g.created_at AS tagged_at
+FROM internal_transactions t
+JOIN internal_transaction_tags g
+    ON g.account_key = t.account_key
+   AND (
+        (g.external_id IS NOT NULL AND g.external_id = t.external_id)
+        OR (
+            g.external_id IS NULL
+            AND t.external_id IS NULL
+            AND g.posted_at = t.posted_at
+            AND g.amount = t.amount
+            AND g.currency = t.currency
+            AND g.description = t.description
+        )
+   );
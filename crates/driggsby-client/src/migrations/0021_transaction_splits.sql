@@ -0,0 +1,100 @@
+CREATE TABLE IF NOT EXISTS internal_transaction_splits (
+    identity_key TEXT NOT NULL,
+    split_index INTEGER NOT NULL,
+    account_key TEXT NOT NULL,
+    external_id TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    split_amount REAL NOT NULL,
+    category TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (identity_key, split_index)
+);
+
+DROP VIEW IF EXISTS v1_transaction_splits;
+CREATE VIEW v1_transaction_splits AS
+SELECT
+    t.txn_id,
+    s.split_index,
+    s.split_amount AS amount,
+    t.currency,
+    s.category,
+    s.created_at AS split_at
+FROM internal_transactions t
+JOIN internal_transaction_splits s
+    ON s.account_key = t.account_key
+   AND (
+        (s.external_id IS NOT NULL AND s.external_id = t.external_id)
+        OR (
+            s.external_id IS NULL
+            AND t.external_id IS NULL
+            AND s.posted_at = t.posted_at
+            AND s.amount = t.amount
+            AND s.currency = t.currency
+            AND s.description = t.description
+        )
+   )
+   AND s.amount = t.amount;
+
+DROP VIEW IF EXISTS v1_category_rollup;
+CREATE VIEW v1_category_rollup AS
+SELECT
+    substr(a.posted_at, 1, 7) AS month,
+    a.currency,
+    a.category,
+    COUNT(DISTINCT a.txn_id) AS txn_count,
+    ROUND(SUM(a.amount), 2) AS amount
+FROM (
+    SELECT t.txn_id, t.posted_at, t.currency, s.split_amount AS amount, s.category
+    FROM internal_transactions t
+    JOIN internal_transaction_splits s
+        ON s.account_key = t.account_key
+       AND (
+            (s.external_id IS NOT NULL AND s.external_id = t.external_id)
+            OR (
+                s.external_id IS NULL
+                AND t.external_id IS NULL
+                AND s.posted_at = t.posted_at
+                AND s.amount = t.amount
+                AND s.currency = t.currency
+                AND s.description = t.description
+            )
+       )
+       AND s.amount = t.amount
+    UNION ALL
+    SELECT t.txn_id, t.posted_at, t.currency, t.amount, COALESCE(o.category, t.category)
+    FROM internal_transactions t
+    LEFT JOIN internal_transaction_overrides o
+        ON o.account_key = t.account_key
+       AND (
+            (o.external_id IS NOT NULL AND o.external_id = t.external_id)
+            OR (
+                o.external_id IS NULL
+                AND t.external_id IS NULL
+                AND o.posted_at = t.posted_at
+                AND o.amount = t.amount
+                AND o.currency = t.currency
+                AND o.description = t.description
+            )
+       )
+    WHERE NOT EXISTS (
+        SELECT 1
+        FROM internal_transaction_splits s
+        WHERE s.account_key = t.account_key
+           AND (
+                (s.external_id IS NOT NULL AND s.external_id = t.external_id)
+                OR (
+                    s.external_id IS NULL
+                    AND t.external_id IS NULL
+                    AND s.posted_at = t.posted_at
+                    AND s.amount = t.amount
+                    AND s.currency = t.currency
+                    AND s.description = t.description
+                )
+           )
+           AND s.amount = t.amount
+    )
+) a
+GROUP BY substr(a.posted_at, 1, 7), a.currency, a.category;
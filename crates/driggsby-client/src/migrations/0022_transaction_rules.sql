@@ -0,0 +1,93 @@
+-- Bootstrap declares mcc on internal_transactions and the dedupe candidates,
+-- but 0004 and 0009 rebuild both tables without it, so every path adds it here.
+-- The archive and staging tables never had it in bootstrap.
+ALTER TABLE internal_transactions ADD COLUMN mcc TEXT;
+ALTER TABLE internal_transaction_dedupe_candidates ADD COLUMN mcc TEXT;
+ALTER TABLE internal_reverted_transactions ADD COLUMN mcc TEXT;
+ALTER TABLE internal_staged_transactions ADD COLUMN mcc TEXT;
+
+CREATE TABLE IF NOT EXISTS internal_rules (
+    rule_id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    description_pattern TEXT,
+    amount_min REAL,
+    amount_max REAL,
+    account_key TEXT,
+    mcc TEXT,
+    set_merchant TEXT,
+    set_category TEXT,
+    add_tags TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS internal_transaction_rule_hits (
+    identity_key TEXT NOT NULL,
+    rule_id TEXT NOT NULL,
+    account_key TEXT NOT NULL,
+    external_id TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    fields TEXT NOT NULL,
+    applied_at TEXT NOT NULL,
+    PRIMARY KEY (identity_key, rule_id)
+);
+
+DROP VIEW IF EXISTS v1_transactions;
+CREATE VIEW v1_transactions AS
+SELECT
+    t.txn_id,
+    t.import_id,
+    t.statement_id,
+    t.account_key,
+    a.account_type,
+    t.posted_at,
+    t.amount,
+    t.currency,
+    t.description,
+    t.external_id,
+    t.merchant,
+    t.category,
+    COALESCE(o.merchant, t.merchant) AS effective_merchant,
+    COALESCE(o.category, t.category) AS effective_category,
+    o.note,
+    t.balance,
+    t.mcc
+FROM internal_transactions t
+LEFT JOIN internal_accounts a ON a.account_key = t.account_key
+LEFT JOIN internal_transaction_overrides o
+    ON o.account_key = t.account_key
+   AND (
+        (o.external_id IS NOT NULL AND o.external_id = t.external_id)
+        OR (
+            o.external_id IS NULL
+            AND t.external_id IS NULL
+            AND o.posted_at = t.posted_at
+            AND o.amount = t.amount
+            AND o.currency = t.currency
+            AND o.description = t.description
+        )
+   );
+
+DROP VIEW IF EXISTS v1_transaction_rule_hits;
+CREATE VIEW v1_transaction_rule_hits AS
+SELECT
+    t.txn_id,
+    h.rule_id,
+    h.fields,
+    h.applied_at
+FROM internal_transactions t
+JOIN internal_transaction_rule_hits h
+    ON h.account_key = t.account_key
+   AND (
+        (h.external_id IS NOT NULL AND h.external_id = t.external_id)
+        OR (
+            h.external_id IS NULL
+            AND t.external_id IS NULL
+            AND h.posted_at = t.posted_at
+            AND h.amount = t.amount
+            AND h.currency = t.currency
+            AND h.description = t.description
+        )
+   );
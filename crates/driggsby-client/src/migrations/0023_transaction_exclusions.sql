@@ -0,0 +1,141 @@
+CREATE TABLE IF NOT EXISTS internal_transaction_exclusions (
+    identity_key TEXT PRIMARY KEY,
+    account_key TEXT NOT NULL,
+    external_id TEXT,
+    posted_at TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    excluded_at TEXT NOT NULL
+);
+
+DROP VIEW IF EXISTS v1_transactions;
+CREATE VIEW v1_transactions AS
+SELECT
+    t.txn_id,
+    t.import_id,
+    t.statement_id,
+    t.account_key,
+    a.account_type,
+    t.posted_at,
+    t.amount,
+    t.currency,
+    t.description,
+    t.external_id,
+    t.merchant,
+    t.category,
+    COALESCE(o.merchant, t.merchant) AS effective_merchant,
+    COALESCE(o.category, t.category) AS effective_category,
+    o.note,
+    t.balance,
+    t.mcc,
+    x.identity_key IS NOT NULL AS excluded,
+    x.reason AS exclusion_reason
+FROM internal_transactions t
+LEFT JOIN internal_accounts a ON a.account_key = t.account_key
+LEFT JOIN internal_transaction_overrides o
+    ON o.account_key = t.account_key
+   AND (
+        (o.external_id IS NOT NULL AND o.external_id = t.external_id)
+        OR (
+            o.external_id IS NULL
+            AND t.external_id IS NULL
+            AND o.posted_at = t.posted_at
+            AND o.amount = t.amount
+            AND o.currency = t.currency
+            AND o.description = t.description
+        )
+   )
+LEFT JOIN internal_transaction_exclusions x
+    ON x.account_key = t.account_key
+   AND (
+        (x.external_id IS NOT NULL AND x.external_id = t.external_id)
+        OR (
+            x.external_id IS NULL
+            AND t.external_id IS NULL
+            AND x.posted_at = t.posted_at
+            AND x.amount = t.amount
+            AND x.currency = t.currency
+            AND x.description = t.description
+        )
+   );
+
+DROP VIEW IF EXISTS v1_category_rollup;
+CREATE VIEW v1_category_rollup AS
+SELECT
+    substr(a.posted_at, 1, 7) AS month,
+    a.currency,
+    a.category,
+    COUNT(DISTINCT a.txn_id) AS txn_count,
+    ROUND(SUM(a.amount), 2) AS amount
+FROM (
+    SELECT t.txn_id, t.account_key, t.external_id, t.posted_at, t.amount AS txn_amount,
+           t.currency, t.description, s.split_amount AS amount, s.category
+    FROM internal_transactions t
+    JOIN internal_transaction_splits s
+        ON s.account_key = t.account_key
+       AND (
+            (s.external_id IS NOT NULL AND s.external_id = t.external_id)
+            OR (
+                s.external_id IS NULL
+                AND t.external_id IS NULL
+                AND s.posted_at = t.posted_at
+                AND s.amount = t.amount
+                AND s.currency = t.currency
+                AND s.description = t.description
+            )
+       )
+       AND s.amount = t.amount
+    UNION ALL
+    SELECT t.txn_id, t.account_key, t.external_id, t.posted_at, t.amount AS txn_amount,
+           t.currency, t.description, t.amount, COALESCE(o.category, t.category)
+    FROM internal_transactions t
+    LEFT JOIN internal_transaction_overrides o
+        ON o.account_key = t.account_key
+       AND (
+            (o.external_id IS NOT NULL AND o.external_id = t.external_id)
+            OR (
+                o.external_id IS NULL
+                AND t.external_id IS NULL
+                AND o.posted_at = t.posted_at
+                AND o.amount = t.amount
+                AND o.currency = t.currency
+                AND o.description = t.description
+            )
+       )
+    WHERE NOT EXISTS (
+        SELECT 1
+        FROM internal_transaction_splits s
+        WHERE s.account_key = t.account_key
+           AND (
+                (s.external_id IS NOT NULL AND s.external_id = t.external_id)
+                OR (
+                    s.external_id IS NULL
+                    AND t.external_id IS NULL
+                    AND s.posted_at = t.posted_at
+                    AND s.amount = t.amount
+                    AND s.currency = t.currency
+                    AND s.description = t.description
+                )
+           )
+           AND s.amount = t.amount
+    )
+) a
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM internal_transaction_exclusions x
+    WHERE x.account_key = a.account_key
+       AND (
+            (x.external_id IS NOT NULL AND x.external_id = a.external_id)
+            OR (
+                x.external_id IS NULL
+                AND a.external_id IS NULL
+                AND x.posted_at = a.posted_at
+                AND x.amount = a.txn_amount
+                AND x.currency = a.currency
+                AND x.description = a.description
+            )
+       )
+)
+GROUP BY substr(a.posted_at, 1, 7), a.currency, a.category;
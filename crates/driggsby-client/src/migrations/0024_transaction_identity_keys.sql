@@ -0,0 +1,194 @@
+-- Annotations used to be keyed by a row's imported fields, so identical rows
+-- kept side by side shared one override, tag set, split or exclusion. Each
+-- ledger row now carries its own identity key, filled in by setup for rows
+-- that predate it, and annotations join on that key alone.
+DROP VIEW IF EXISTS v1_transactions;
+DROP VIEW IF EXISTS v1_transaction_tags;
+DROP VIEW IF EXISTS v1_transaction_splits;
+DROP VIEW IF EXISTS v1_category_rollup;
+DROP VIEW IF EXISTS v1_transaction_rule_hits;
+
+ALTER TABLE internal_transactions ADD COLUMN identity_key TEXT;
+
+-- driggsby:safe_repair:start:idx_internal_transactions_identity_key
+CREATE UNIQUE INDEX idx_internal_transactions_identity_key
+ON internal_transactions(identity_key);
+-- driggsby:safe_repair:end:idx_internal_transactions_identity_key
+
+CREATE TABLE internal_transaction_overrides_new (
+    identity_key TEXT PRIMARY KEY,
+    merchant TEXT,
+    category TEXT,
+    note TEXT,
+    updated_at TEXT NOT NULL
+);
+INSERT INTO internal_transaction_overrides_new (
+    identity_key,
+    merchant,
+    category,
+    note,
+    updated_at
+)
+SELECT override_key, merchant, category, note, updated_at
+FROM internal_transaction_overrides;
+DROP TABLE internal_transaction_overrides;
+ALTER TABLE internal_transaction_overrides_new RENAME TO internal_transaction_overrides;
+
+CREATE TABLE internal_transaction_tags_new (
+    identity_key TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (identity_key, tag)
+);
+INSERT INTO internal_transaction_tags_new (identity_key, tag, created_at)
+SELECT identity_key, tag, created_at
+FROM internal_transaction_tags;
+DROP TABLE internal_transaction_tags;
+ALTER TABLE internal_transaction_tags_new RENAME TO internal_transaction_tags;
+
+-- Splits keep the parent amount they were made for, so they fall away when
+-- the parent amount changes.
+CREATE TABLE internal_transaction_splits_new (
+    identity_key TEXT NOT NULL,
+    split_index INTEGER NOT NULL,
+    amount REAL NOT NULL,
+    split_amount REAL NOT NULL,
+    category TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (identity_key, split_index)
+);
+INSERT INTO internal_transaction_splits_new (
+    identity_key,
+    split_index,
+    amount,
+    split_amount,
+    category,
+    created_at
+)
+SELECT identity_key, split_index, amount, split_amount, category, created_at
+FROM internal_transaction_splits;
+DROP TABLE internal_transaction_splits;
+ALTER TABLE internal_transaction_splits_new RENAME TO internal_transaction_splits;
+
+CREATE TABLE internal_transaction_rule_hits_new (
+    identity_key TEXT NOT NULL,
+    rule_id TEXT NOT NULL,
+    fields TEXT NOT NULL,
+    applied_at TEXT NOT NULL,
+    PRIMARY KEY (identity_key, rule_id)
+);
+INSERT INTO internal_transaction_rule_hits_new (identity_key, rule_id, fields, applied_at)
+SELECT identity_key, rule_id, fields, applied_at
+FROM internal_transaction_rule_hits;
+DROP TABLE internal_transaction_rule_hits;
+ALTER TABLE internal_transaction_rule_hits_new RENAME TO internal_transaction_rule_hits;
+
+CREATE TABLE internal_transaction_exclusions_new (
+    identity_key TEXT PRIMARY KEY,
+    reason TEXT NOT NULL,
+    excluded_at TEXT NOT NULL
+);
+INSERT INTO internal_transaction_exclusions_new (identity_key, reason, excluded_at)
+SELECT identity_key, reason, excluded_at
+FROM internal_transaction_exclusions;
+DROP TABLE internal_transaction_exclusions;
+ALTER TABLE internal_transaction_exclusions_new RENAME TO internal_transaction_exclusions;
+
+-- driggsby:safe_repair:start:v1_transactions
+CREATE VIEW v1_transactions AS
+SELECT
+    t.txn_id,
+    t.import_id,
+    t.statement_id,
+    t.account_key,
+    a.account_type,
+    t.posted_at,
+    t.amount,
+    t.currency,
+    t.description,
+    t.external_id,
+    t.merchant,
+    t.category,
+    COALESCE(o.merchant, t.merchant) AS effective_merchant,
+    COALESCE(o.category, t.category) AS effective_category,
+    o.note,
+    t.balance,
+    t.mcc,
+    x.identity_key IS NOT NULL AS excluded,
+    x.reason AS exclusion_reason
+FROM internal_transactions t
+LEFT JOIN internal_accounts a ON a.account_key = t.account_key
+LEFT JOIN internal_transaction_overrides o ON o.identity_key = t.identity_key
+LEFT JOIN internal_transaction_exclusions x ON x.identity_key = t.identity_key;
+-- driggsby:safe_repair:end:v1_transactions
+
+-- driggsby:safe_repair:start:v1_transaction_tags
+CREATE VIEW v1_transaction_tags AS
+SELECT
+    t.txn_id,
+    g.tag,
+    g.created_at AS tagged_at
+FROM internal_transactions t
+JOIN internal_transaction_tags g ON g.identity_key = t.identity_key;
+-- driggsby:safe_repair:end:v1_transaction_tags
+
+-- driggsby:safe_repair:start:v1_transaction_splits
+CREATE VIEW v1_transaction_splits AS
+SELECT
+    t.txn_id,
+    s.split_index,
+    s.split_amount AS amount,
+    t.currency,
+    s.category,
+    s.created_at AS split_at
+FROM internal_transactions t
+JOIN internal_transaction_splits s
+    ON s.identity_key = t.identity_key
+   AND s.amount = t.amount;
+-- driggsby:safe_repair:end:v1_transaction_splits
+
+-- driggsby:safe_repair:start:v1_category_rollup
+CREATE VIEW v1_category_rollup AS
+SELECT
+    substr(a.posted_at, 1, 7) AS month,
+    a.currency,
+    a.category,
+    COUNT(DISTINCT a.txn_id) AS txn_count,
+    ROUND(SUM(a.amount), 2) AS amount
+FROM (
+    SELECT t.txn_id, t.identity_key, t.posted_at, t.currency, s.split_amount AS amount,
+           s.category
+    FROM internal_transactions t
+    JOIN internal_transaction_splits s
+        ON s.identity_key = t.identity_key
+       AND s.amount = t.amount
+    UNION ALL
+    SELECT t.txn_id, t.identity_key, t.posted_at, t.currency, t.amount,
+           COALESCE(o.category, t.category)
+    FROM internal_transactions t
+    LEFT JOIN internal_transaction_overrides o ON o.identity_key = t.identity_key
+    WHERE NOT EXISTS (
+        SELECT 1
+        FROM internal_transaction_splits s
+        WHERE s.identity_key = t.identity_key
+          AND s.amount = t.amount
+    )
+) a
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM internal_transaction_exclusions x
+    WHERE x.identity_key = a.identity_key
+)
+GROUP BY substr(a.posted_at, 1, 7), a.currency, a.category;
+-- driggsby:safe_repair:end:v1_category_rollup
+
+-- driggsby:safe_repair:start:v1_transaction_rule_hits
+CREATE VIEW v1_transaction_rule_hits AS
+SELECT
+    t.txn_id,
+    h.rule_id,
+    h.fields,
+    h.applied_at
+FROM internal_transactions t
+JOIN internal_transaction_rule_hits h ON h.identity_key = t.identity_key;
+-- driggsby:safe_repair:end:v1_transaction_rule_hits
@@ -0,0 +1,4 @@
+-- The dedupe policies each import ran under, so undo, redo and candidate
+-- promotion key that import's rows the same way after the policy file
+-- changes. Runs from before this column fall back to the current file.
+ALTER TABLE internal_import_runs ADD COLUMN dedupe_policies TEXT;
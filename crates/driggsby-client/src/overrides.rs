@@ -0,0 +1,173 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+
+use crate::identity::{identity_join, load_identity_key};
+use crate::import::persist::now_timestamp;
+use crate::intelligence::refresh::refresh_all_in_transaction;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+/// Attaches `internal_transaction_overrides` as `o` to `internal_transactions`
+/// as `t` by row identity.
+pub(crate) fn override_join() -> String {
+    identity_join("internal_transaction_overrides", "o")
+}
+
+/// Requested override changes. `None` leaves a field alone; an empty string
+/// clears it back to the imported value.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OverrideEdit {
+    pub(crate) merchant: Option<String>,
+    pub(crate) category: Option<String>,
+    pub(crate) note: Option<String>,
+}
+
+impl OverrideEdit {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.merchant.is_none() && self.category.is_none() && self.note.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TransactionOverride {
+    pub(crate) merchant: Option<String>,
+    pub(crate) category: Option<String>,
+    pub(crate) note: Option<String>,
+}
+
+impl TransactionOverride {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.merchant.is_none() && self.category.is_none() && self.note.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OverrideEditResult {
+    pub(crate) applied: TransactionOverride,
+    pub(crate) intelligence_refreshed: bool,
+}
+
+/// Applies `edit` to the override of `txn_id`. Intelligence is refreshed when
+/// the effective merchant changes.
+pub(crate) fn edit_transaction_override(
+    connection: &mut Connection,
+    db_path: &Path,
+    txn_id: &str,
+    edit: &OverrideEdit,
+) -> ClientResult<OverrideEditResult> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let Some(key) = load_identity_key(&transaction, db_path, txn_id)? else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+
+    let current = transaction
+        .query_row(
+            "SELECT merchant, category, note
+             FROM internal_transaction_overrides
+             WHERE identity_key = ?1",
+            params![&key],
+            |row| {
+                Ok(TransactionOverride {
+                    merchant: row.get(0)?,
+                    category: row.get(1)?,
+                    note: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?
+        .unwrap_or_default();
+
+    let updated = TransactionOverride {
+        merchant: apply_field(current.merchant.clone(), edit.merchant.as_deref()),
+        category: apply_field(current.category.clone(), edit.category.as_deref()),
+        note: apply_field(current.note.clone(), edit.note.as_deref()),
+    };
+
+    if updated.is_empty() {
+        transaction
+            .execute(
+                "DELETE FROM internal_transaction_overrides WHERE identity_key = ?1",
+                params![&key],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    } else {
+        transaction
+            .execute(
+                "INSERT INTO internal_transaction_overrides (
+                    identity_key,
+                    merchant,
+                    category,
+                    note,
+                    updated_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (identity_key) DO UPDATE SET
+                    merchant = excluded.merchant,
+                    category = excluded.category,
+                    note = excluded.note,
+                    updated_at = excluded.updated_at",
+                params![
+                    &key,
+                    &updated.merchant,
+                    &updated.category,
+                    &updated.note,
+                    now_timestamp(),
+                ],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+
+    let merchant_changed = updated.merchant != current.merchant;
+    if merchant_changed {
+        refresh_all_in_transaction(&transaction, db_path)?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(OverrideEditResult {
+        applied: updated,
+        intelligence_refreshed: merchant_changed,
+    })
+}
+
+/// Writes rule-applied merchant and category values into the override of the
+/// row with `identity_key`, keeping any field that is passed as `None`.
+pub(crate) fn upsert_rule_override(
+    connection: &Connection,
+    db_path: &Path,
+    identity_key: &str,
+    merchant: Option<&str>,
+    category: Option<&str>,
+    updated_at: &str,
+) -> ClientResult<()> {
+    connection
+        .execute(
+            "INSERT INTO internal_transaction_overrides (
+                identity_key,
+                merchant,
+                category,
+                note,
+                updated_at
+             ) VALUES (?1, ?2, ?3, NULL, ?4)
+             ON CONFLICT (identity_key) DO UPDATE SET
+                merchant = COALESCE(excluded.merchant, merchant),
+                category = COALESCE(excluded.category, category),
+                updated_at = excluded.updated_at",
+            params![identity_key, merchant, category, updated_at,],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(())
+}
+
+fn apply_field(current: Option<String>, requested: Option<&str>) -> Option<String> {
+    match requested.map(str::trim) {
+        None => current,
+        Some("") => None,
+        Some(value) => Some(value.to_string()),
+    }
+}
@@ -0,0 +1,657 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use regex::Regex;
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+use serde::{Deserialize, Serialize};
+
+use crate::contracts::types::{ImportRuleChange, RuleApplyChange, RuleRow, TransactionRuleHit};
+use crate::import::CanonicalTransaction;
+use crate::import::persist::now_timestamp;
+use crate::import::statements::to_cents;
+use crate::intelligence::refresh::refresh_all_in_transaction;
+use crate::overrides::{override_join, upsert_rule_override};
+use crate::state::map_sqlite_error;
+use crate::tags::insert_tag;
+use crate::{ClientError, ClientResult};
+
+const FIELD_ORDER: [&str; 3] = ["merchant", "category", "tags"];
+
+/// A stored rule with its description pattern compiled.
+#[derive(Debug, Clone)]
+pub(crate) struct Rule {
+    pub(crate) row: RuleRow,
+    pattern: Option<Regex>,
+}
+
+/// The row a rule is evaluated against. Locked fields carry a manual edit and
+/// are never overwritten by a rule.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RuleTarget<'a> {
+    pub(crate) description: &'a str,
+    pub(crate) amount: f64,
+    pub(crate) account_key: &'a str,
+    pub(crate) mcc: Option<&'a str>,
+    pub(crate) merchant: Option<&'a str>,
+    pub(crate) category: Option<&'a str>,
+    pub(crate) tags: &'a [String],
+    pub(crate) merchant_locked: bool,
+    pub(crate) category_locked: bool,
+}
+
+/// What one rule changed on one row.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RuleHit {
+    pub(crate) rule_id: String,
+    pub(crate) merchant: Option<String>,
+    pub(crate) category: Option<String>,
+    pub(crate) tags: Vec<String>,
+}
+
+impl RuleHit {
+    pub(crate) fn fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.merchant.is_some() {
+            fields.push("merchant");
+        }
+        if self.category.is_some() {
+            fields.push("category");
+        }
+        if !self.tags.is_empty() {
+            fields.push("tags");
+        }
+        fields
+    }
+}
+
+impl Rule {
+    pub(crate) fn new(row: RuleRow) -> ClientResult<Self> {
+        let pattern = row
+            .description_pattern
+            .as_deref()
+            .map(compile_pattern)
+            .transpose()?;
+        Ok(Self { row, pattern })
+    }
+
+    fn matches(&self, target: &RuleTarget<'_>) -> bool {
+        let amount = to_cents(target.amount);
+        self.pattern
+            .as_ref()
+            .is_none_or(|pattern| pattern.is_match(target.description))
+            && self
+                .row
+                .amount_min
+                .is_none_or(|min| amount >= to_cents(min))
+            && self
+                .row
+                .amount_max
+                .is_none_or(|max| amount <= to_cents(max))
+            && self
+                .row
+                .account_key
+                .as_deref()
+                .is_none_or(|account_key| account_key == target.account_key)
+            && self
+                .row
+                .mcc
+                .as_deref()
+                .is_none_or(|mcc| Some(mcc) == target.mcc)
+    }
+}
+
+pub(crate) fn compile_pattern(pattern: &str) -> ClientResult<Regex> {
+    Regex::new(pattern).map_err(|error| {
+        ClientError::invalid_argument_with_recovery(
+            &format!("`{pattern}` is not a valid description pattern: {error}"),
+            vec![
+                "Patterns use Rust regex syntax, for example ^SQ \\*BLUE BOTTLE or (?i)netflix."
+                    .to_string(),
+            ],
+        )
+    })
+}
+
+/// Rules in evaluation order.
+pub(crate) fn load_rules(connection: &Connection, db_path: &Path) -> ClientResult<Vec<Rule>> {
+    query_rule_rows(connection, db_path)?
+        .into_iter()
+        .map(Rule::new)
+        .collect()
+}
+
+pub(crate) fn query_rule_rows(
+    connection: &Connection,
+    db_path: &Path,
+) -> ClientResult<Vec<RuleRow>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT
+                rule_id,
+                position,
+                description_pattern,
+                amount_min,
+                amount_max,
+                account_key,
+                mcc,
+                set_merchant,
+                set_category,
+                add_tags,
+                created_at
+             FROM internal_rules
+             ORDER BY position ASC, created_at ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((
+                RuleRow {
+                    rule_id: row.get(0)?,
+                    position: row.get(1)?,
+                    description_pattern: row.get(2)?,
+                    amount_min: row.get(3)?,
+                    amount_max: row.get(4)?,
+                    account_key: row.get(5)?,
+                    mcc: row.get(6)?,
+                    set_merchant: row.get(7)?,
+                    set_category: row.get(8)?,
+                    add_tags: Vec::new(),
+                    created_at: row.get(10)?,
+                },
+                row.get::<_, String>(9)?,
+            ))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut rules = Vec::new();
+    for row in rows {
+        let (mut rule, add_tags) = row.map_err(|error| map_sqlite_error(db_path, &error))?;
+        rule.add_tags = serde_json::from_str(&add_tags).map_err(|error| {
+            ClientError::rule_invalid(
+                &rule.rule_id,
+                &format!("add_tags is not a list of tags: {error}"),
+            )
+        })?;
+        rules.push(rule);
+    }
+    Ok(rules)
+}
+
+/// Runs `rules` in order against `target`. The first matching rule that sets
+/// merchant or category wins that field; tags from every matching rule are
+/// added. A hit is only reported for a rule that changes something.
+pub(crate) fn evaluate(rules: &[Rule], target: &RuleTarget<'_>) -> Vec<RuleHit> {
+    let mut merchant_claimed = target.merchant_locked;
+    let mut category_claimed = target.category_locked;
+    let mut tags = target.tags.to_vec();
+    let mut hits = Vec::new();
+    for rule in rules.iter().filter(|rule| rule.matches(target)) {
+        let mut hit = RuleHit {
+            rule_id: rule.row.rule_id.clone(),
+            ..RuleHit::default()
+        };
+        if let Some(merchant) = rule.row.set_merchant.as_ref()
+            && !merchant_claimed
+        {
+            merchant_claimed = true;
+            if target.merchant != Some(merchant.as_str()) {
+                hit.merchant = Some(merchant.clone());
+            }
+        }
+        if let Some(category) = rule.row.set_category.as_ref()
+            && !category_claimed
+        {
+            category_claimed = true;
+            if target.category != Some(category.as_str()) {
+                hit.category = Some(category.clone());
+            }
+        }
+        for tag in &rule.row.add_tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+                hit.tags.push(tag.clone());
+            }
+        }
+        if !hit.fields().is_empty() {
+            hits.push(hit);
+        }
+    }
+    hits
+}
+
+/// Applies `rules` to an incoming row before it is written, returning what
+/// changed.
+pub(crate) fn apply_to_row(rules: &[Rule], row: &mut CanonicalTransaction) -> Vec<RuleHit> {
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let hits = evaluate(
+        rules,
+        &RuleTarget {
+            description: &row.description,
+            amount: row.amount,
+            account_key: &row.account_key,
+            mcc: row.mcc.as_deref(),
+            merchant: row.merchant.as_deref(),
+            category: row.category.as_deref(),
+            tags: &[],
+            merchant_locked: false,
+            category_locked: false,
+        },
+    );
+    for hit in &hits {
+        if let Some(merchant) = hit.merchant.as_ref() {
+            row.merchant = Some(merchant.clone());
+        }
+        if let Some(category) = hit.category.as_ref() {
+            row.category = Some(category.clone());
+        }
+    }
+    hits
+}
+
+/// Stores the hits of one inserted row: its rule tags and which rule changed
+/// which field. Both are keyed by row identity, like other annotations.
+pub(crate) fn record_hits(
+    connection: &Connection,
+    db_path: &Path,
+    identity_key: &str,
+    hits: &[RuleHit],
+    applied_at: &str,
+) -> ClientResult<()> {
+    for hit in hits {
+        for tag in &hit.tags {
+            insert_tag(connection, identity_key, tag, applied_at)
+                .map_err(|error| map_sqlite_error(db_path, &error))?;
+        }
+        let recorded = connection
+            .query_row(
+                "SELECT fields FROM internal_transaction_rule_hits
+                 WHERE identity_key = ?1 AND rule_id = ?2",
+                params![identity_key, &hit.rule_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|error| map_sqlite_error(db_path, &error))?
+            .unwrap_or_default();
+        let fields = FIELD_ORDER
+            .iter()
+            .filter(|field| {
+                hit.fields().contains(field) || recorded.split(',').any(|seen| seen == **field)
+            })
+            .copied()
+            .collect::<Vec<&str>>()
+            .join(",");
+        connection
+            .execute(
+                "INSERT INTO internal_transaction_rule_hits (
+                    identity_key,
+                    rule_id,
+                    fields,
+                    applied_at
+                 ) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (identity_key, rule_id) DO UPDATE SET
+                    fields = excluded.fields,
+                    applied_at = excluded.applied_at",
+                params![identity_key, &hit.rule_id, &fields, applied_at,],
+            )
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+    }
+    Ok(())
+}
+
+/// Rules that changed rows of one import, in rule order.
+pub(crate) fn summarize_import_hits(
+    rules: &[Rule],
+    hits: &HashMap<i64, Vec<RuleHit>>,
+) -> Vec<ImportRuleChange> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let mut rows_changed = 0;
+            let mut fields = Vec::new();
+            for hit in hits
+                .values()
+                .flatten()
+                .filter(|hit| hit.rule_id == rule.row.rule_id)
+            {
+                rows_changed += 1;
+                fields.extend(hit.fields());
+            }
+            (rows_changed > 0).then(|| ImportRuleChange {
+                rule_id: rule.row.rule_id.clone(),
+                rows_changed,
+                fields: FIELD_ORDER
+                    .iter()
+                    .filter(|field| fields.contains(field))
+                    .map(|field| field.to_string())
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Rules that changed `txn_id`, oldest first.
+pub(crate) fn load_transaction_rule_hits(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Vec<TransactionRuleHit>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT rule_id, fields, applied_at
+             FROM v1_transaction_rule_hits
+             WHERE txn_id = ?1
+             ORDER BY applied_at ASC, rule_id ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![txn_id], |row| {
+            let fields: String = row.get(1)?;
+            Ok(TransactionRuleHit {
+                rule_id: row.get(0)?,
+                fields: fields.split(',').map(str::to_string).collect(),
+                applied_at: row.get(2)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut hits = Vec::new();
+    for row in rows {
+        hits.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(hits)
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LedgerApplyOutcome {
+    pub(crate) rows_changed: i64,
+    pub(crate) changes: Vec<RuleApplyChange>,
+    pub(crate) intelligence_refreshed: bool,
+}
+
+struct LedgerRow {
+    txn_id: String,
+    identity_key: String,
+    account_key: String,
+    amount: f64,
+    description: String,
+    mcc: Option<String>,
+    merchant: Option<String>,
+    category: Option<String>,
+    merchant_overridden: bool,
+    category_overridden: bool,
+}
+
+/// Runs every rule over the current ledger. Rule values land in the row
+/// override, so they survive re-import like manual edits do; a field that was
+/// edited by hand is left alone. With `dry_run` nothing is written.
+pub(crate) fn apply_to_ledger(
+    connection: &mut Connection,
+    db_path: &Path,
+    dry_run: bool,
+) -> ClientResult<LedgerApplyOutcome> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rules = load_rules(&transaction, db_path)?;
+    if rules.is_empty() {
+        return Ok(LedgerApplyOutcome::default());
+    }
+
+    let rows = load_ledger_rows(&transaction, db_path)?;
+    let tags = txn_strings(
+        &transaction,
+        db_path,
+        "SELECT txn_id, tag FROM v1_transaction_tags",
+    )?;
+    let rule_fields = txn_strings(
+        &transaction,
+        db_path,
+        "SELECT txn_id, fields FROM v1_transaction_rule_hits",
+    )?;
+
+    let applied_at = now_timestamp();
+    let mut outcome = LedgerApplyOutcome::default();
+    let mut merchant_changed = false;
+    for row in rows {
+        let row_tags = tags.get(&row.txn_id).cloned().unwrap_or_default();
+        let set_by_rule = |field: &str| {
+            rule_fields.get(&row.txn_id).is_some_and(|recorded| {
+                recorded
+                    .iter()
+                    .any(|fields| fields.split(',').any(|seen| seen == field))
+            })
+        };
+        let hits = evaluate(
+            &rules,
+            &RuleTarget {
+                description: &row.description,
+                amount: row.amount,
+                account_key: &row.account_key,
+                mcc: row.mcc.as_deref(),
+                merchant: row.merchant.as_deref(),
+                category: row.category.as_deref(),
+                tags: &row_tags,
+                merchant_locked: row.merchant_overridden && !set_by_rule("merchant"),
+                category_locked: row.category_overridden && !set_by_rule("category"),
+            },
+        );
+        if hits.is_empty() {
+            continue;
+        }
+
+        outcome.rows_changed += 1;
+        let mut merchant = None;
+        let mut category = None;
+        for hit in &hits {
+            if let Some(value) = hit.merchant.as_ref() {
+                outcome.changes.push(change(
+                    &row.txn_id,
+                    hit,
+                    "merchant",
+                    row.merchant.clone(),
+                    value,
+                ));
+                merchant = Some(value.as_str());
+            }
+            if let Some(value) = hit.category.as_ref() {
+                outcome.changes.push(change(
+                    &row.txn_id,
+                    hit,
+                    "category",
+                    row.category.clone(),
+                    value,
+                ));
+                category = Some(value.as_str());
+            }
+            for tag in &hit.tags {
+                outcome
+                    .changes
+                    .push(change(&row.txn_id, hit, "tags", None, tag));
+            }
+        }
+        merchant_changed |= merchant.is_some();
+        if !dry_run {
+            if merchant.is_some() || category.is_some() {
+                upsert_rule_override(
+                    &transaction,
+                    db_path,
+                    &row.identity_key,
+                    merchant,
+                    category,
+                    &applied_at,
+                )?;
+            }
+            record_hits(&transaction, db_path, &row.identity_key, &hits, &applied_at)?;
+        }
+    }
+
+    if dry_run {
+        transaction
+            .rollback()
+            .map_err(|error| map_sqlite_error(db_path, &error))?;
+        return Ok(outcome);
+    }
+    if merchant_changed {
+        refresh_all_in_transaction(&transaction, db_path)?;
+        outcome.intelligence_refreshed = true;
+    }
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    Ok(outcome)
+}
+
+fn change(
+    txn_id: &str,
+    hit: &RuleHit,
+    field: &str,
+    from: Option<String>,
+    to: &str,
+) -> RuleApplyChange {
+    RuleApplyChange {
+        txn_id: txn_id.to_string(),
+        rule_id: hit.rule_id.clone(),
+        field: field.to_string(),
+        from,
+        to: to.to_string(),
+    }
+}
+
+fn load_ledger_rows(connection: &Connection, db_path: &Path) -> ClientResult<Vec<LedgerRow>> {
+    let query = format!(
+        "SELECT
+            t.txn_id,
+            t.identity_key,
+            t.account_key,
+            t.amount,
+            t.description,
+            t.mcc,
+            COALESCE(o.merchant, t.merchant),
+            COALESCE(o.category, t.category),
+            o.merchant IS NOT NULL,
+            o.category IS NOT NULL
+         FROM internal_transactions t
+         {}
+         ORDER BY t.posted_at ASC, t.txn_id ASC",
+        override_join()
+    );
+    let mut statement = connection
+        .prepare(&query)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok(LedgerRow {
+                txn_id: row.get(0)?,
+                identity_key: row.get(1)?,
+                account_key: row.get(2)?,
+                amount: row.get(3)?,
+                description: row.get(4)?,
+                mcc: row.get(5)?,
+                merchant: row.get(6)?,
+                category: row.get(7)?,
+                merchant_overridden: row.get(8)?,
+                category_overridden: row.get(9)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut ledger = Vec::new();
+    for row in rows {
+        ledger.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(ledger)
+}
+
+/// Groups the second column of a two-column `txn_id` query by transaction.
+fn txn_strings(
+    connection: &Connection,
+    db_path: &Path,
+    query: &str,
+) -> ClientResult<BTreeMap<String, Vec<String>>> {
+    let mut statement = connection
+        .prepare(query)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for row in rows {
+        let (txn_id, value) = row.map_err(|error| map_sqlite_error(db_path, &error))?;
+        grouped.entry(txn_id).or_default().push(value);
+    }
+    Ok(grouped)
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use crate::contracts::types::RuleRow;
+
+    use super::{Rule, RuleTarget, evaluate};
+
+    fn rule(rule_id: &str, pattern: &str, merchant: Option<&str>, category: Option<&str>) -> Rule {
+        Rule {
+            row: RuleRow {
+                rule_id: rule_id.to_string(),
+                position: 1,
+                description_pattern: Some(pattern.to_string()),
+                amount_min: None,
+                amount_max: Some(0.0),
+                account_key: None,
+                mcc: None,
+                set_merchant: merchant.map(str::to_string),
+                set_category: category.map(str::to_string),
+                add_tags: vec!["coffee".to_string()],
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+            pattern: Regex::new(pattern).ok(),
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins_each_field() {
+        let rules = vec![
+            rule(
+                "rule_a",
+                r"^SQ \*BLUE BOTTLE",
+                Some("Blue Bottle Coffee"),
+                None,
+            ),
+            rule("rule_b", "BLUE", Some("Blue Something"), Some("Coffee")),
+        ];
+        let target = RuleTarget {
+            description: "SQ *BLUE BOTTLE OAKLAND",
+            amount: -6.5,
+            account_key: "acct_1",
+            mcc: None,
+            merchant: None,
+            category: Some("Coffee"),
+            tags: &[],
+            merchant_locked: false,
+            category_locked: false,
+        };
+        let hits = evaluate(&rules, &target);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].rule_id, "rule_a");
+        assert_eq!(hits[0].merchant.as_deref(), Some("Blue Bottle Coffee"));
+        assert_eq!(hits[0].tags, vec!["coffee".to_string()]);
+
+        let refund = RuleTarget {
+            amount: 6.5,
+            ..target
+        };
+        assert!(evaluate(&rules, &refund).is_empty());
+        let locked = RuleTarget {
+            merchant_locked: true,
+            tags: &["coffee".to_string()],
+            ..target
+        };
+        assert!(evaluate(&rules, &locked).is_empty());
+    }
+}
@@ -4,6 +4,7 @@ use rusqlite::{Connection, OptionalExtension, params};
 
 use crate::commands::common::public_view_contracts;
 use crate::contracts::types::{DataRange, PublicView};
+use crate::identity::backfill_identity_keys;
 use crate::migrations::{
     REQUIRED_INDEX_NAMES, REQUIRED_META_KEYS, REQUIRED_VIEW_NAMES, run_pending,
     safe_repair_statement,
@@ -14,7 +15,7 @@ use crate::state::{
 use crate::{ClientError, ClientResult};
 
 const INTERNAL_META_COLUMNS: [&str; 2] = ["key", "value"];
-const INTERNAL_IMPORT_RUNS_COLUMNS: [&str; 12] = [
+const INTERNAL_IMPORT_RUNS_COLUMNS: [&str; 14] = [
     "import_id",
     "status",
     "created_at",
@@ -27,8 +28,10 @@ const INTERNAL_IMPORT_RUNS_COLUMNS: [&str; 12] = [
     "deduped",
     "source_kind",
     "source_ref",
+    "source_hash",
+    "idempotency_key",
 ];
-const INTERNAL_TRANSACTIONS_COLUMNS: [&str; 12] = [
+const INTERNAL_TRANSACTIONS_COLUMNS: [&str; 15] = [
     "txn_id",
     "import_id",
     "statement_id",
@@ -41,6 +44,9 @@ const INTERNAL_TRANSACTIONS_COLUMNS: [&str; 12] = [
     "external_id",
     "merchant",
     "category",
+    "balance",
+    "mcc",
+    "identity_key",
 ];
 const INTERNAL_ACCOUNTS_COLUMNS: [&str; 4] =
     ["account_key", "account_type", "created_at", "updated_at"];
@@ -51,7 +57,7 @@ const INTERNAL_IMPORT_ACCOUNT_STATS_COLUMNS: [&str; 5] = [
     "inserted",
     "deduped",
 ];
-const INTERNAL_TRANSACTION_DEDUPE_CANDIDATES_COLUMNS: [&str; 20] = [
+const INTERNAL_TRANSACTION_DEDUPE_CANDIDATES_COLUMNS: [&str; 22] = [
     "candidate_id",
     "import_id",
     "dedupe_key",
@@ -65,6 +71,7 @@ const INTERNAL_TRANSACTION_DEDUPE_CANDIDATES_COLUMNS: [&str; 20] = [
     "external_id",
     "merchant",
     "category",
+    "balance",
     "source_row_index",
     "dedupe_reason",
     "matched_txn_id",
@@ -72,6 +79,7 @@ const INTERNAL_TRANSACTION_DEDUPE_CANDIDATES_COLUMNS: [&str; 20] = [
     "matched_batch_row_index",
     "created_at",
     "promoted_txn_id",
+    "mcc",
 ];
 const INTERNAL_RECURRING_COLUMNS: [&str; 11] = [
     "group_key",
@@ -98,9 +106,172 @@ const INTERNAL_ANOMALIES_COLUMNS: [&str; 10] = [
     "score",
     "severity",
 ];
-const EXPECTED_USER_VERSION: i64 = 6;
+const INTERNAL_IMPORT_SIGN_CORRECTIONS_COLUMNS: [&str; 6] = [
+    "import_id",
+    "account_key",
+    "rows_inverted",
+    "historical_negative_ratio",
+    "incoming_negative_ratio",
+    "created_at",
+];
+const INTERNAL_STATEMENTS_COLUMNS: [&str; 11] = [
+    "account_key",
+    "statement_id",
+    "import_id",
+    "currency",
+    "opening_balance",
+    "closing_balance",
+    "net_amount",
+    "txn_count",
+    "first_posted_at",
+    "last_posted_at",
+    "reconciled_at",
+];
+const INTERNAL_ACCOUNT_COVERAGE_COLUMNS: [&str; 6] = [
+    "account_key",
+    "range_kind",
+    "range_start",
+    "range_end",
+    "day_count",
+    "import_count",
+];
+const INTERNAL_IMPORT_REMAPS_COLUMNS: [&str; 6] = [
+    "import_id",
+    "property",
+    "from_value",
+    "to_value",
+    "rows_rewritten",
+    "created_at",
+];
+const INTERNAL_ALIASES_COLUMNS: [&str; 4] = ["property", "alias", "canonical", "created_at"];
+const INTERNAL_PROBABLE_DUPLICATES_COLUMNS: [&str; 20] = [
+    "probable_id",
+    "import_id",
+    "txn_id",
+    "source_row_index",
+    "account_key",
+    "posted_at",
+    "amount",
+    "currency",
+    "description",
+    "matched_txn_id",
+    "matched_import_id",
+    "matched_posted_at",
+    "matched_description",
+    "score",
+    "date_distance_days",
+    "description_similarity",
+    "status",
+    "candidate_id",
+    "created_at",
+    "resolved_at",
+];
+const INTERNAL_IMPORT_SOURCE_ROWS_COLUMNS: [&str; 4] =
+    ["import_id", "source_row_index", "source_record", "txn_id"];
+const INTERNAL_IMPORT_REPLACEMENTS_COLUMNS: [&str; 7] = [
+    "import_id",
+    "replaced_import_id",
+    "rows_unchanged",
+    "rows_added",
+    "rows_removed",
+    "rows_modified",
+    "created_at",
+];
+const INTERNAL_REVERTED_TRANSACTIONS_COLUMNS: [&str; 18] = [
+    "import_id",
+    "archive_index",
+    "txn_id",
+    "source_row_index",
+    "statement_id",
+    "dedupe_scope_id",
+    "account_key",
+    "account_type",
+    "posted_at",
+    "amount",
+    "currency",
+    "description",
+    "external_id",
+    "merchant",
+    "category",
+    "balance",
+    "archived_at",
+    "mcc",
+];
+const INTERNAL_REVERTED_STATEMENTS_COLUMNS: [&str; 11] = [
+    "import_id",
+    "account_key",
+    "statement_id",
+    "currency",
+    "opening_balance",
+    "closing_balance",
+    "net_amount",
+    "txn_count",
+    "first_posted_at",
+    "last_posted_at",
+    "archived_at",
+];
+const INTERNAL_STAGED_IMPORTS_COLUMNS: [&str; 3] = ["import_id", "analysis", "staged_at"];
+const INTERNAL_STAGED_TRANSACTIONS_COLUMNS: [&str; 16] = [
+    "import_id",
+    "row_index",
+    "statement_id",
+    "dedupe_scope_id",
+    "account_key",
+    "account_type",
+    "posted_at",
+    "amount",
+    "currency",
+    "description",
+    "external_id",
+    "merchant",
+    "category",
+    "balance",
+    "source_record",
+    "mcc",
+];
+const INTERNAL_STAGED_STATEMENTS_COLUMNS: [&str; 10] = [
+    "import_id",
+    "account_key",
+    "statement_id",
+    "currency",
+    "opening_balance",
+    "closing_balance",
+    "net_amount",
+    "txn_count",
+    "first_posted_at",
+    "last_posted_at",
+];
+const INTERNAL_TRANSACTION_OVERRIDES_COLUMNS: [&str; 5] =
+    ["identity_key", "merchant", "category", "note", "updated_at"];
+const INTERNAL_TRANSACTION_TAGS_COLUMNS: [&str; 3] = ["identity_key", "tag", "created_at"];
+const INTERNAL_TRANSACTION_SPLITS_COLUMNS: [&str; 6] = [
+    "identity_key",
+    "split_index",
+    "amount",
+    "split_amount",
+    "category",
+    "created_at",
+];
+const INTERNAL_RULES_COLUMNS: [&str; 11] = [
+    "rule_id",
+    "position",
+    "description_pattern",
+    "amount_min",
+    "amount_max",
+    "account_key",
+    "mcc",
+    "set_merchant",
+    "set_category",
+    "add_tags",
+    "created_at",
+];
+const INTERNAL_TRANSACTION_RULE_HITS_COLUMNS: [&str; 4] =
+    ["identity_key", "rule_id", "fields", "applied_at"];
+const INTERNAL_TRANSACTION_EXCLUSIONS_COLUMNS: [&str; 3] =
+    ["identity_key", "reason", "excluded_at"];
+const EXPECTED_USER_VERSION: i64 = 25;
 
-const REQUIRED_CORE_TABLES: [(&str, &[&str]); 8] = [
+const REQUIRED_CORE_TABLES: [(&str, &[&str]); 27] = [
     ("internal_meta", &INTERNAL_META_COLUMNS),
     ("internal_import_runs", &INTERNAL_IMPORT_RUNS_COLUMNS),
     ("internal_transactions", &INTERNAL_TRANSACTIONS_COLUMNS),
@@ -121,6 +292,67 @@ const REQUIRED_CORE_TABLES: [(&str, &[&str]); 8] = [
         "internal_anomalies_materialized",
         &INTERNAL_ANOMALIES_COLUMNS,
     ),
+    (
+        "internal_import_sign_corrections",
+        &INTERNAL_IMPORT_SIGN_CORRECTIONS_COLUMNS,
+    ),
+    ("internal_statements", &INTERNAL_STATEMENTS_COLUMNS),
+    (
+        "internal_account_coverage_materialized",
+        &INTERNAL_ACCOUNT_COVERAGE_COLUMNS,
+    ),
+    ("internal_import_remaps", &INTERNAL_IMPORT_REMAPS_COLUMNS),
+    ("internal_aliases", &INTERNAL_ALIASES_COLUMNS),
+    (
+        "internal_probable_duplicates",
+        &INTERNAL_PROBABLE_DUPLICATES_COLUMNS,
+    ),
+    (
+        "internal_import_source_rows",
+        &INTERNAL_IMPORT_SOURCE_ROWS_COLUMNS,
+    ),
+    (
+        "internal_import_replacements",
+        &INTERNAL_IMPORT_REPLACEMENTS_COLUMNS,
+    ),
+    (
+        "internal_reverted_transactions",
+        &INTERNAL_REVERTED_TRANSACTIONS_COLUMNS,
+    ),
+    (
+        "internal_reverted_statements",
+        &INTERNAL_REVERTED_STATEMENTS_COLUMNS,
+    ),
+    ("internal_staged_imports", &INTERNAL_STAGED_IMPORTS_COLUMNS),
+    (
+        "internal_staged_transactions",
+        &INTERNAL_STAGED_TRANSACTIONS_COLUMNS,
+    ),
+    (
+        "internal_staged_statements",
+        &INTERNAL_STAGED_STATEMENTS_COLUMNS,
+    ),
+    (
+        "internal_transaction_overrides",
+        &INTERNAL_TRANSACTION_OVERRIDES_COLUMNS,
+    ),
+    (
+        "internal_transaction_tags",
+        &INTERNAL_TRANSACTION_TAGS_COLUMNS,
+    ),
+    (
+        "internal_transaction_splits",
+        &INTERNAL_TRANSACTION_SPLITS_COLUMNS,
+    ),
+    ("internal_rules", &INTERNAL_RULES_COLUMNS),
+    (
+        "internal_transaction_rule_hits",
+        &INTERNAL_TRANSACTION_RULE_HITS_COLUMNS,
+    ),
+    (
+        "internal_transaction_exclusions",
+        &INTERNAL_TRANSACTION_EXCLUSIONS_COLUMNS,
+    ),
 ];
 
 #[derive(Debug, Clone)]
@@ -154,6 +386,7 @@ fn ensure_initialized_with_home_override(
     verify_core_tables(&connection, &db_path)?;
     repair_safe_objects(&connection, &db_path)?;
     verify_post_repair_objects(&connection, &db_path)?;
+    backfill_identity_keys(&mut connection, &db_path)?;
 
     let schema_version = read_schema_version(&connection, &db_path)?;
     let data_range = read_data_range(&connection, &db_path)?;
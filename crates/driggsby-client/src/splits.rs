@@ -0,0 +1,218 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+
+use crate::contracts::types::TransactionSplitRow;
+use crate::import::persist::now_timestamp;
+use crate::import::statements::to_cents;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+/// One requested allocation of a parent transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SplitAllocation {
+    pub(crate) amount: f64,
+    pub(crate) category: String,
+}
+
+/// Parses `AMOUNT:CATEGORY`, e.g. `42.10:Groceries`.
+pub(crate) fn parse_split_allocation(raw: &str) -> ClientResult<SplitAllocation> {
+    let invalid = || {
+        ClientError::invalid_argument_with_recovery(
+            &format!("`{raw}` is not a valid split part."),
+            vec!["Write each part as AMOUNT:CATEGORY, for example 42.10:Groceries.".to_string()],
+        )
+    };
+    let (amount, category) = raw.split_once(':').ok_or_else(invalid)?;
+    let amount = amount
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && to_cents(*value) != 0)
+        .ok_or_else(invalid)?;
+    let category = category.trim();
+    if category.is_empty() {
+        return Err(invalid());
+    }
+    Ok(SplitAllocation {
+        amount,
+        category: category.to_string(),
+    })
+}
+
+/// Replaces the splits of `txn_id` with `allocations`; an empty list removes
+/// them. Allocations must sum to the parent amount to the cent. When the
+/// parent is negative and every part is given as a positive amount, the parts
+/// take the parent's sign, so a -120.00 charge can be split as 80:A 40:B.
+pub(crate) fn set_transaction_splits(
+    connection: &mut Connection,
+    db_path: &Path,
+    txn_id: &str,
+    allocations: &[SplitAllocation],
+) -> ClientResult<()> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let Some((key, parent_amount)) = transaction
+        .query_row(
+            "SELECT identity_key, amount FROM internal_transactions WHERE txn_id = ?1",
+            params![txn_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)),
+        )
+        .optional()
+        .map_err(|error| map_sqlite_error(db_path, &error))?
+    else {
+        return Err(ClientError::transaction_not_found(txn_id));
+    };
+
+    transaction
+        .execute(
+            "DELETE FROM internal_transaction_splits WHERE identity_key = ?1",
+            params![&key],
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    if !allocations.is_empty() {
+        let allocations = signed_allocations(parent_amount, allocations);
+        validate_allocations(txn_id, parent_amount, &allocations)?;
+
+        let created_at = now_timestamp();
+        for (index, allocation) in allocations.iter().enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO internal_transaction_splits (
+                        identity_key,
+                        split_index,
+                        amount,
+                        split_amount,
+                        category,
+                        created_at
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        &key,
+                        index as i64 + 1,
+                        parent_amount,
+                        to_cents(allocation.amount) as f64 / 100.0,
+                        &allocation.category,
+                        &created_at,
+                    ],
+                )
+                .map_err(|error| map_sqlite_error(db_path, &error))?;
+        }
+    }
+
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))
+}
+
+/// Current splits of `txn_id`, in the order they were given.
+pub(crate) fn load_splits(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: &str,
+) -> ClientResult<Vec<TransactionSplitRow>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT split_index, amount, category
+             FROM v1_transaction_splits
+             WHERE txn_id = ?1
+             ORDER BY split_index ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![txn_id], |row| {
+            Ok(TransactionSplitRow {
+                split_index: row.get(0)?,
+                amount: row.get(1)?,
+                category: row.get(2)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut splits = Vec::new();
+    for row in rows {
+        splits.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(splits)
+}
+
+fn signed_allocations(parent_amount: f64, allocations: &[SplitAllocation]) -> Vec<SplitAllocation> {
+    let flip = parent_amount < 0.0 && allocations.iter().all(|part| part.amount > 0.0);
+    allocations
+        .iter()
+        .map(|part| SplitAllocation {
+            amount: if flip { -part.amount } else { part.amount },
+            category: part.category.clone(),
+        })
+        .collect()
+}
+
+fn validate_allocations(
+    txn_id: &str,
+    parent_amount: f64,
+    allocations: &[SplitAllocation],
+) -> ClientResult<()> {
+    if allocations.len() < 2 {
+        return Err(ClientError::invalid_argument_with_recovery(
+            "A split needs at least two parts.",
+            vec![format!(
+                "To recategorize the whole transaction, run driggsby transaction edit {txn_id} --category <name>."
+            )],
+        ));
+    }
+
+    let total_cents = allocations
+        .iter()
+        .map(|part| to_cents(part.amount))
+        .sum::<i64>();
+    let parent_cents = to_cents(parent_amount);
+    if total_cents != parent_cents {
+        return Err(ClientError::invalid_argument_with_recovery(
+            &format!(
+                "Split parts add up to {:.2}, but transaction `{txn_id}` is {:.2}.",
+                total_cents as f64 / 100.0,
+                parent_cents as f64 / 100.0
+            ),
+            vec![format!(
+                "Adjust the parts by {:.2} so they sum to the transaction amount.",
+                (parent_cents - total_cents) as f64 / 100.0
+            )],
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SplitAllocation, parse_split_allocation, signed_allocations};
+
+    #[test]
+    fn parses_parts_and_takes_parent_sign() {
+        let part = parse_split_allocation("42.10: Home & Garden");
+        assert!(part.is_ok());
+        if let Ok(part) = part {
+            assert_eq!(part.amount, 42.10);
+            assert_eq!(part.category, "Home & Garden");
+        }
+        assert!(parse_split_allocation("Groceries").is_err());
+        assert!(parse_split_allocation("0:Groceries").is_err());
+        assert!(parse_split_allocation("12.00:").is_err());
+
+        let parts = vec![
+            SplitAllocation {
+                amount: 80.0,
+                category: "Groceries".to_string(),
+            },
+            SplitAllocation {
+                amount: 40.0,
+                category: "Gifts".to_string(),
+            },
+        ];
+        let signed = signed_allocations(-120.0, &parts);
+        assert_eq!(signed[0].amount, -80.0);
+        assert_eq!(signed[1].amount, -40.0);
+        assert_eq!(signed_allocations(120.0, &parts), parts);
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use rusqlite::{Connection, TransactionBehavior, params};
+
+use crate::contracts::types::TagRow;
+use crate::identity::load_identity_key;
+use crate::import::persist::now_timestamp;
+use crate::state::map_sqlite_error;
+use crate::{ClientError, ClientResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TagAction {
+    Add,
+    Remove,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TagChange {
+    pub(crate) txn_ids: Vec<String>,
+    pub(crate) changed: i64,
+}
+
+/// Lowercases a tag and drops a leading `#`, so `#Vacation-2026` and
+/// `vacation-2026` are the same tag.
+pub(crate) fn normalize_tag(raw: &str) -> ClientResult<String> {
+    let tag = raw.trim().trim_start_matches('#').to_ascii_lowercase();
+    let valid = !tag.is_empty()
+        && tag
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.' | ':'));
+    if !valid {
+        return Err(ClientError::invalid_argument_with_recovery(
+            &format!("`{raw}` is not a valid tag."),
+            vec![
+                "Use letters, digits, `-`, `_`, `.` or `:`, for example #vacation-2026."
+                    .to_string(),
+            ],
+        ));
+    }
+    Ok(tag)
+}
+
+/// Adds or removes `tag` on every listed transaction, all or nothing.
+pub(crate) fn apply_tag(
+    connection: &mut Connection,
+    db_path: &Path,
+    tag: &str,
+    txn_ids: &[String],
+    action: TagAction,
+) -> ClientResult<TagChange> {
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let created_at = now_timestamp();
+    let mut changed = 0;
+    for txn_id in txn_ids {
+        let Some(key) = load_identity_key(&transaction, db_path, txn_id)? else {
+            return Err(ClientError::transaction_not_found(txn_id));
+        };
+        let affected = match action {
+            TagAction::Add => insert_tag(&transaction, &key, tag, &created_at),
+            TagAction::Remove => transaction.execute(
+                "DELETE FROM internal_transaction_tags WHERE identity_key = ?1 AND tag = ?2",
+                params![&key, tag],
+            ),
+        }
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+        changed += affected as i64;
+    }
+
+    transaction
+        .commit()
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    Ok(TagChange {
+        txn_ids: txn_ids.to_vec(),
+        changed,
+    })
+}
+
+/// Attaches `tag` to the row with `identity_key`; a tag it already has is
+/// left alone. Returns how many tags were added.
+pub(crate) fn insert_tag(
+    connection: &Connection,
+    identity_key: &str,
+    tag: &str,
+    created_at: &str,
+) -> rusqlite::Result<usize> {
+    connection.execute(
+        "INSERT OR IGNORE INTO internal_transaction_tags (identity_key, tag, created_at)
+         VALUES (?1, ?2, ?3)",
+        params![identity_key, tag, created_at],
+    )
+}
+
+/// Tags in use on current ledger rows, optionally limited to one transaction.
+pub(crate) fn list_tags(
+    connection: &Connection,
+    db_path: &Path,
+    txn_id: Option<&str>,
+) -> ClientResult<Vec<TagRow>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT tag, COUNT(*), MAX(tagged_at)
+             FROM v1_transaction_tags
+             WHERE (?1 IS NULL OR txn_id = ?1)
+             GROUP BY tag
+             ORDER BY tag ASC",
+        )
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![txn_id], |row| {
+            Ok(TagRow {
+                tag: row.get(0)?,
+                txn_count: row.get(1)?,
+                last_tagged_at: row.get(2)?,
+            })
+        })
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut tags = Vec::new();
+    for row in rows {
+        tags.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(tags)
+}
+
+/// Ids of current ledger rows carrying `tag`.
+pub(crate) fn tagged_txn_ids(
+    connection: &Connection,
+    db_path: &Path,
+    tag: &str,
+) -> ClientResult<Vec<String>> {
+    let mut statement = connection
+        .prepare("SELECT txn_id FROM v1_transaction_tags WHERE tag = ?1")
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+    let rows = statement
+        .query_map(params![tag], |row| row.get::<_, String>(0))
+        .map_err(|error| map_sqlite_error(db_path, &error))?;
+
+    let mut txn_ids = Vec::new();
+    for row in rows {
+        txn_ids.push(row.map_err(|error| map_sqlite_error(db_path, &error))?);
+    }
+    Ok(txn_ids)
+}
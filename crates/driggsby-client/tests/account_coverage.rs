@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import;
+use driggsby_client::commands::import::{ImportRunOptions, ImportUndoOptions};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn write_file(path: &Path, body: &str) {
+    let result = fs::write(path, body);
+    assert!(result.is_ok());
+}
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn run_import(home: &Path, path: &Path, dry_run: bool) -> Value {
+    let result = import::run_with_options(ImportRunOptions {
+        path: Some(path.display().to_string()),
+        dry_run,
+        home_override: Some(home),
+        ..Default::default()
+    });
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn coverage_rows(db_path: &Path) -> Vec<(String, String, String, i64)> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(
+            "SELECT range_kind, range_start, range_end, import_count
+             FROM v1_account_coverage
+             WHERE account_key = 'acct_cov_1'
+             ORDER BY range_start",
+        )
+        && let Ok(mapped) = statement.query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+fn warning_codes(data: &Value) -> Vec<String> {
+    data["drift_warnings"]
+        .as_array()
+        .map(|warnings| {
+            warnings
+                .iter()
+                .filter_map(|warning| warning["code"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[test]
+fn coverage_view_tracks_gaps_and_dry_run_warns_about_overlaps() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let january = home.join("january.csv");
+        write_file(
+            &january,
+            "statement_id,account_key,posted_at,amount,currency,description
+acct_cov_1_2026-01-31,acct_cov_1,2026-01-03,-10.00,USD,JAN A
+acct_cov_1_2026-01-31,acct_cov_1,2026-01-28,-11.00,USD,JAN B
+",
+        );
+        let march = home.join("march.csv");
+        write_file(
+            &march,
+            "statement_id,account_key,posted_at,amount,currency,description
+acct_cov_1_2026-03-31,acct_cov_1,2026-03-02,-12.00,USD,MAR A
+acct_cov_1_2026-03-31,acct_cov_1,2026-03-27,-13.00,USD,MAR B
+",
+        );
+
+        let first = run_import(&home, &january, false);
+        assert!(first["import_id"].is_string());
+
+        let preview = run_import(&home, &march, true);
+        assert!(warning_codes(&preview).contains(&"coverage_gap".to_string()));
+        assert!(!warning_codes(&preview).contains(&"statement_overlap".to_string()));
+
+        let second = run_import(&home, &march, false);
+        assert_eq!(
+            coverage_rows(&db_path),
+            vec![
+                (
+                    "covered".to_string(),
+                    "2026-01-03".to_string(),
+                    "2026-01-28".to_string(),
+                    1
+                ),
+                (
+                    "gap".to_string(),
+                    "2026-01-29".to_string(),
+                    "2026-03-01".to_string(),
+                    0
+                ),
+                (
+                    "covered".to_string(),
+                    "2026-03-02".to_string(),
+                    "2026-03-27".to_string(),
+                    1
+                ),
+            ]
+        );
+
+        let overlapping = home.join("overlap.csv");
+        write_file(
+            &overlapping,
+            "statement_id,account_key,posted_at,amount,currency,description
+acct_cov_1_2026-03-15,acct_cov_1,2026-03-10,-14.00,USD,MID MARCH
+acct_cov_1_2026-03-15,acct_cov_1,2026-03-15,-15.00,USD,MID MARCH B
+",
+        );
+        let overlap_preview = run_import(&home, &overlapping, true);
+        assert!(warning_codes(&overlap_preview).contains(&"statement_overlap".to_string()));
+
+        if let Some(import_id) = second["import_id"].as_str() {
+            let undo = import::undo_with_options(
+                import_id,
+                ImportUndoOptions {
+                    home_override: Some(&home),
+                    ..Default::default()
+                },
+            );
+            assert!(undo.is_ok());
+            assert_eq!(coverage_rows(&db_path).len(), 1);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::aliases::{
+    self, AliasAddOptions, AliasListOptions, AliasRemoveOptions,
+};
+use driggsby_client::commands::import::{self, ImportKeysUniqOptions, ImportRunOptions};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn add_alias(home: &Path, property: &str, alias: &str, canonical: &str) -> Value {
+    to_data(aliases::add_with_options(AliasAddOptions {
+        property: property.to_string(),
+        alias: alias.to_string(),
+        canonical: canonical.to_string(),
+        home_override: Some(home),
+    }))
+}
+
+#[test]
+fn aliases_rewrite_imports_and_show_up_in_keys_uniq() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        add_alias(&home, "account_key", "chase-chk", "chase_checking_1234");
+        add_alias(&home, "merchant", "AMZN Mktp US", "Amazon");
+        let replaced = add_alias(&home, "merchant", "Amazon.com", "Amazon Inc");
+        assert!(replaced.get("replaced_canonical").is_none());
+        let replaced = add_alias(&home, "merchant", "Amazon.com", "Amazon");
+        assert_eq!(replaced["replaced_canonical"], "Amazon Inc");
+
+        let chained = aliases::add_with_options(AliasAddOptions {
+            property: "merchant".to_string(),
+            alias: "AMZN".to_string(),
+            canonical: "AMZN Mktp US".to_string(),
+            home_override: Some(&home),
+        });
+        assert!(chained.is_err());
+
+        let listed = to_data(aliases::list_with_options(AliasListOptions {
+            property: Some("merchant".to_string()),
+            home_override: Some(&home),
+        }));
+        assert_eq!(listed["rows"].as_array().map(Vec::len), Some(2));
+
+        let source = home.join("rows.csv");
+        let write = fs::write(
+            &source,
+            "account_key,posted_at,amount,currency,description,merchant
+chase-chk,2026-01-03,-10.00,USD,AMZN MKTP,AMZN Mktp US
+chase-chk,2026-01-04,-20.00,USD,AMAZON,Amazon.com
+",
+        );
+        assert!(write.is_ok());
+        let imported = to_data(import::run_with_options(ImportRunOptions {
+            path: Some(source.display().to_string()),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        assert_eq!(
+            imported["alias_rewrites"],
+            json!([
+                {"property": "account_key", "from": "chase-chk", "to": "chase_checking_1234", "rows_rewritten": 2},
+                {"property": "merchant", "from": "AMZN Mktp US", "to": "Amazon", "rows_rewritten": 1},
+                {"property": "merchant", "from": "Amazon.com", "to": "Amazon", "rows_rewritten": 1}
+            ])
+        );
+
+        let keys = to_data(import::keys_uniq_with_options(ImportKeysUniqOptions {
+            property: Some("merchant".to_string()),
+            home_override: Some(&home),
+        }));
+        assert_eq!(keys["inventories"][0]["existing_values"], json!(["Amazon"]));
+        assert_eq!(
+            keys["inventories"][0]["aliases"],
+            json!([{"canonical": "Amazon", "aliases": ["AMZN Mktp US", "Amazon.com"]}])
+        );
+
+        let removed = to_data(aliases::remove_with_options(AliasRemoveOptions {
+            property: "merchant".to_string(),
+            alias: "Amazon.com".to_string(),
+            home_override: Some(&home),
+        }));
+        assert_eq!(removed["alias"]["canonical"], "Amazon");
+
+        let missing = aliases::remove_with_options(AliasRemoveOptions {
+            property: "merchant".to_string(),
+            alias: "Amazon.com".to_string(),
+            home_override: Some(&home),
+        });
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "alias_not_found");
+        }
+    }
+}
+
+#[test]
+fn alias_properties_are_limited_to_keys_merchants_and_categories() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let result = aliases::add_with_options(AliasAddOptions {
+            property: "currency".to_string(),
+            alias: "usd".to_string(),
+            canonical: "USD".to_string(),
+            home_override: Some(&home),
+        });
+        assert!(result.is_err());
+        if let Err(error) = result {
+            assert_eq!(error.code, "invalid_argument");
+        }
+    }
+}
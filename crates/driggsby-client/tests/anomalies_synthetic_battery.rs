@@ -55,6 +55,7 @@ fn import_rows(home: &Path, rows: &[Value]) {
                 dry_run: false,
                 home_override: Some(home),
                 stdin_override: None,
+                ..Default::default()
             });
             assert!(imported.is_ok());
         }
@@ -65,6 +66,7 @@ fn anomalies_txn_ids(home: &Path) -> Vec<String> {
     let response = anomalies::run_with_options(AnomaliesRunOptions {
         from: None,
         to: None,
+        tag: None,
         home_override: Some(home),
     });
     assert!(response.is_ok());
@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{self, ImportRunOptions};
+use driggsby_client::commands::sql::{self, SqlQueryOptions};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str, dry_run: bool, apply: bool) -> Value {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        dry_run,
+        home_override: Some(home),
+        apply_category_suggestions: apply,
+        ..Default::default()
+    }))
+}
+
+fn categories(home: &Path) -> Value {
+    let data = to_data(sql::run_with_options(SqlQueryOptions {
+        query: Some(
+            "SELECT description, category FROM v1_transactions
+             WHERE posted_at >= '2026-03-01'
+             ORDER BY posted_at"
+                .to_string(),
+        ),
+        file: None,
+        home_override: Some(home),
+        stdin_override: None,
+        max_rows: None,
+    }));
+    data["rows"].clone()
+}
+
+const LEDGER: &str = "account_key,posted_at,amount,currency,description,merchant,category
+chase_card_1234,2026-01-03,-15.49,USD,NETFLIX.COM,Netflix,Streaming
+chase_card_1234,2026-02-03,-15.49,USD,NETFLIX.COM 2,Netflix,Streaming
+chase_card_1234,2026-02-04,-15.99,USD,NETFLIX.COM 3,Netflix,Streaming
+chase_card_1234,2026-01-09,-41.20,USD,TARGET 0042,Target,Groceries
+chase_card_1234,2026-02-09,-45.10,USD,TARGET 0042,Target,Household
+";
+
+const INCOMING: &str = "account_key,posted_at,amount,currency,description,merchant,category
+chase_card_1234,2026-03-03,-15.49,USD,NETFLIX.COM,Netflix,
+chase_card_1234,2026-03-09,-43.00,USD,TARGET 0042,Target,
+chase_card_1234,2026-03-10,-15.49,USD,NETFLIX.COM GIFT,Netflix,Gifts
+";
+
+#[test]
+fn dry_run_lists_suggestions_without_setting_categories() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", LEDGER, false, false);
+
+        let preview = import_file(&home, "march.csv", INCOMING, true, false);
+        let suggestions = preview["category_suggestions"].as_array().cloned();
+        assert_eq!(suggestions.as_ref().map(Vec::len), Some(1));
+        if let Some(suggestions) = suggestions {
+            // Target history is split between two categories, so only the
+            // uncategorized Netflix row gets a suggestion.
+            assert_eq!(suggestions[0]["source_row_index"], json!(1));
+            assert_eq!(suggestions[0]["category"], json!("Streaming"));
+            assert_eq!(suggestions[0]["basis"], json!("amount_band"));
+            assert_eq!(suggestions[0]["support"], json!(3));
+            assert_eq!(suggestions[0]["confidence"], json!(0.75));
+            assert_eq!(suggestions[0]["applied"], json!(false));
+        }
+
+        let committed = import_file(&home, "march.csv", INCOMING, false, false);
+        assert!(committed.get("category_suggestions").is_none());
+        assert_eq!(
+            categories(&home),
+            json!([
+                ["NETFLIX.COM", null],
+                ["TARGET 0042", null],
+                ["NETFLIX.COM GIFT", "Gifts"]
+            ])
+        );
+    }
+}
+
+#[test]
+fn apply_flag_sets_suggested_categories_on_commit() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", LEDGER, false, false);
+
+        let committed = import_file(&home, "march.csv", INCOMING, false, true);
+        assert_eq!(committed["category_suggestions"][0]["applied"], json!(true));
+        assert_eq!(
+            categories(&home),
+            json!([
+                ["NETFLIX.COM", "Streaming"],
+                ["TARGET 0042", null],
+                ["NETFLIX.COM GIFT", "Gifts"]
+            ])
+        );
+    }
+}
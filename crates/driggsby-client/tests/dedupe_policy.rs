@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{self, ImportRunOptions, ImportUndoOptions};
+use driggsby_client::contracts::envelope::failure_from_error;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn write_file(path: &Path, body: &str) {
+    let result = fs::write(path, body);
+    assert!(result.is_ok());
+}
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn run_import(
+    home: &Path,
+    name: &str,
+    body: &str,
+    dry_run: bool,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    let path = home.join(name);
+    write_file(&path, body);
+    import::run_with_options(ImportRunOptions {
+        path: Some(path.display().to_string()),
+        dry_run,
+        home_override: Some(home),
+        ..Default::default()
+    })
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+const POLICY: &str = r#"{
+  "dedupe": {
+    "chase_checking_1234": {
+      "key_fields": ["external_id", "posted_at", "amount"],
+      "trust_external_id": false,
+      "date_tolerance_days": 2
+    }
+  }
+}"#;
+
+const FIRST_IMPORT: &str = "account_key,posted_at,amount,currency,description,external_id
+chase_checking_1234,2025-01-05,-10.00,USD,COFFEE,T1
+chase_checking_1234,2025-01-05,-20.00,USD,LUNCH,T2
+amex_gold_1001,2025-01-05,-30.00,USD,BOOKS,
+";
+
+const SECOND_IMPORT: &str = "account_key,posted_at,amount,currency,description,external_id
+chase_checking_1234,2026-01-05,-10.00,USD,COFFEE,T1
+chase_checking_1234,2025-01-06,-20.00,USD,LUNCH POSTED,T2
+amex_gold_1001,2025-01-05,-30.00,USD,BOOKS,
+";
+
+#[test]
+fn account_policy_replaces_external_id_trust_and_tolerates_date_shifts() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        write_file(&home.join("import_policy.json"), POLICY);
+        let first = to_data(run_import(&home, "first.csv", FIRST_IMPORT, false));
+        let first_id = first["import_id"].as_str().unwrap_or_default().to_string();
+
+        let second = to_data(run_import(&home, "second.csv", SECOND_IMPORT, false));
+        // The recycled T1 a year later is new; the shifted T2 and the
+        // identical default-policy amex row are duplicates.
+        assert_eq!(second["summary"]["inserted"], 1);
+        assert_eq!(second["duplicate_summary"]["existing_ledger"], 2);
+
+        let policies = second["dedupe_policies"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(policies.len(), 2);
+        assert_eq!(policies[0]["account_key"], "amex_gold_1001");
+        assert_eq!(policies[0]["source"], "default");
+        assert_eq!(policies[0]["trust_external_id"], true);
+        assert_eq!(policies[1]["account_key"], "chase_checking_1234");
+        assert_eq!(policies[1]["source"], "import_policy");
+        assert_eq!(policies[1]["date_tolerance_days"], 2);
+        assert_eq!(
+            policies[1]["key_fields"],
+            serde_json::json!(["external_id", "posted_at", "amount"])
+        );
+
+        let undone = to_data(import::undo_with_options(
+            &first_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(undone["summary"]["rows_reverted"], 3);
+        assert_eq!(undone["summary"]["rows_promoted"], 2);
+    }
+}
+
+#[test]
+fn undo_promotes_candidates_under_the_policy_the_imports_ran_with() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let policy_path = home.join("import_policy.json");
+        write_file(
+            &policy_path,
+            r#"{"dedupe": {"acct_1": {"date_tolerance_days": 3}}}"#,
+        );
+        let header = "account_key,posted_at,amount,currency,description\n";
+        let first = to_data(run_import(
+            &home,
+            "first.csv",
+            &format!("{header}acct_1,2026-01-10,-15.49,USD,NETFLIX.COM\n"),
+            false,
+        ));
+        let first_id = first["import_id"].as_str().unwrap_or_default().to_string();
+        for (name, posted_at) in [("second.csv", "2026-01-11"), ("third.csv", "2026-01-12")] {
+            let later = to_data(run_import(
+                &home,
+                name,
+                &format!("{header}acct_1,{posted_at},-15.49,USD,NETFLIX.COM\n"),
+                false,
+            ));
+            assert_eq!(later["duplicate_summary"]["existing_ledger"], 1);
+        }
+
+        // Dropping the policy must not change how the committed imports and
+        // their candidates are keyed: one charge comes back, not two.
+        assert!(fs::remove_file(&policy_path).is_ok());
+        let undone = to_data(import::undo_with_options(
+            &first_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(undone["summary"]["rows_reverted"], 1);
+        assert_eq!(undone["summary"]["rows_promoted"], 1);
+    }
+}
+
+#[test]
+fn date_tolerance_dedupes_overlapping_statements_within_one_import() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        write_file(&home.join("import_policy.json"), POLICY);
+        let preview = to_data(run_import(
+            &home,
+            "overlap.csv",
+            "account_key,posted_at,amount,currency,description,statement_id,external_id
+chase_checking_1234,2025-01-30,-20.00,USD,LUNCH,chase_checking_1234_2025-01-31,T9
+chase_checking_1234,2025-02-01,-20.00,USD,LUNCH,chase_checking_1234_2025-02-28,T9
+chase_checking_1234,2025-02-10,-20.00,USD,LUNCH,chase_checking_1234_2025-02-28,T9
+",
+            true,
+        ));
+        assert_eq!(preview["duplicate_summary"]["batch"], 1);
+        assert_eq!(
+            preview["duplicates_preview"]["rows"][0]["source_row_index"],
+            2
+        );
+    }
+}
+
+#[test]
+fn invalid_dedupe_policy_fails_the_import() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        write_file(
+            &home.join("import_policy.json"),
+            r#"{"dedupe": {"chase_checking_1234": {"key_fields": ["amount"], "date_tolerance_days": 3}}}"#,
+        );
+        let result = run_import(&home, "first.csv", FIRST_IMPORT, true);
+        assert!(result.is_err());
+        if let Err(error) = result
+            && let Ok(value) = serde_json::to_value(failure_from_error(&error))
+        {
+            assert_eq!(value["error"]["code"], "import_policy_invalid");
+            assert!(
+                value["error"]["message"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .contains("date_tolerance_days")
+            );
+        }
+    }
+}
@@ -25,12 +25,23 @@ fn run_import(
     home: &Path,
     path: &Path,
     dry_run: bool,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    run_import_with_sign_fix(home, path, dry_run, false)
+}
+
+fn run_import_with_sign_fix(
+    home: &Path,
+    path: &Path,
+    dry_run: bool,
+    auto_fix_signs: bool,
 ) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
     import::run_with_options(ImportRunOptions {
         path: Some(path.display().to_string()),
         dry_run,
         home_override: Some(home),
         stdin_override: None,
+        auto_fix_signs,
+        ..Default::default()
     })
 }
 
@@ -175,6 +186,88 @@ fn dry_run_includes_inventory_sign_profiles_and_drift_warnings() {
     }
 }
 
+#[test]
+fn dry_run_suggests_existing_merchants_and_categories_for_drifted_labels() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+
+        let baseline_path = home.join("baseline.json");
+        write_json(
+            &baseline_path,
+            &json!([
+                {
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-01-05",
+                    "amount": -82.14,
+                    "currency": "USD",
+                    "description": "WHOLE FOODS MKT 10432",
+                    "merchant": "WHOLE FOODS MKT",
+                    "category": "Groceries & Food"
+                },
+                {
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-01-09",
+                    "amount": -15.49,
+                    "currency": "USD",
+                    "description": "NETFLIX.COM",
+                    "merchant": "Netflix",
+                    "category": "Streaming"
+                }
+            ]),
+        );
+        assert!(run_import(&home, &baseline_path, false).is_ok());
+
+        let dry_run_path = home.join("dry-run.json");
+        write_json(
+            &dry_run_path,
+            &json!([
+                {
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-02-04",
+                    "amount": -64.20,
+                    "currency": "USD",
+                    "description": "Whole Foods #10432",
+                    "merchant": "Whole Foods",
+                    "category": "groceries"
+                }
+            ]),
+        );
+        let dry_run_result = run_import(&home, &dry_run_path, true);
+        assert!(dry_run_result.is_ok());
+        if let Ok(success) = dry_run_result {
+            let payload = serde_json::to_value(success);
+            assert!(payload.is_ok());
+            if let Ok(value) = payload {
+                let warnings = value["data"]["drift_warnings"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default();
+                let merchant = warnings
+                    .iter()
+                    .find(|warning| warning["code"] == "merchant_unseen");
+                assert!(merchant.is_some());
+                if let Some(warning) = merchant {
+                    assert_eq!(warning["suggestions"], json!(["WHOLE FOODS MKT"]));
+                    assert_eq!(
+                        warning["ranked_suggestions"],
+                        json!([{ "value": "WHOLE FOODS MKT", "score": 0.8, "existing_count": 1 }])
+                    );
+                }
+                let category = warnings
+                    .iter()
+                    .find(|warning| warning["code"] == "category_unseen");
+                assert!(category.is_some());
+                if let Some(warning) = category {
+                    assert_eq!(warning["suggestions"], json!(["Groceries & Food"]));
+                }
+            }
+        }
+    }
+}
+
 #[test]
 fn dry_run_deduped_duplicates_do_not_trigger_sign_profile_anomaly() {
     let temp = temp_home();
@@ -266,6 +359,109 @@ fn dry_run_deduped_duplicates_do_not_trigger_sign_profile_anomaly() {
     }
 }
 
+#[test]
+fn auto_fix_signs_inverts_mirrored_account_batches() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+
+        let baseline_path = home.join("baseline-mirror.json");
+        let mut baseline_rows = Vec::new();
+        for index in 0..25 {
+            let amount = if index < 3 { 25.0 } else { -12.0 };
+            baseline_rows.push(json!({
+                "account_key": "acct_mirror_1",
+                "posted_at": format!("2026-01-{:02}", index + 1),
+                "amount": amount,
+                "currency": "USD",
+                "description": format!("BASELINE-MIRROR-{index}")
+            }));
+        }
+        write_json(&baseline_path, &Value::Array(baseline_rows));
+        let baseline_result = run_import(&home, &baseline_path, false);
+        assert!(baseline_result.is_ok());
+
+        let mirrored_path = home.join("mirrored.json");
+        let mut mirrored_rows = Vec::new();
+        for index in 0..6 {
+            let amount = if index == 0 { -25.0 } else { 12.0 };
+            mirrored_rows.push(json!({
+                "account_key": "acct_mirror_1",
+                "posted_at": format!("2026-02-{:02}", index + 1),
+                "amount": amount,
+                "currency": "USD",
+                "description": format!("MIRRORED-{index}")
+            }));
+        }
+        write_json(&mirrored_path, &Value::Array(mirrored_rows));
+
+        let plain_dry_run = run_import(&home, &mirrored_path, true);
+        assert!(plain_dry_run.is_ok());
+        if let Ok(success) = plain_dry_run {
+            let payload = serde_json::to_value(success);
+            assert!(payload.is_ok());
+            if let Ok(value) = payload {
+                let codes = warning_codes(&value);
+                assert!(
+                    codes
+                        .iter()
+                        .any(|code| code == "account_sign_profile_inverted")
+                );
+                assert!(value["data"].get("sign_corrections").is_none());
+            }
+        }
+
+        let fixed_dry_run = run_import_with_sign_fix(&home, &mirrored_path, true, true);
+        assert!(fixed_dry_run.is_ok());
+        if let Ok(success) = fixed_dry_run {
+            let payload = serde_json::to_value(success);
+            assert!(payload.is_ok());
+            if let Ok(value) = payload {
+                let codes = warning_codes(&value);
+                assert!(
+                    !codes
+                        .iter()
+                        .any(|code| code.starts_with("account_sign_profile"))
+                );
+                let corrections = &value["data"]["sign_corrections"];
+                assert_eq!(corrections.as_array().map(Vec::len), Some(1));
+                assert_eq!(corrections[0]["account_key"], "acct_mirror_1");
+                assert_eq!(corrections[0]["rows_inverted"], 6);
+            }
+        }
+
+        let committed = run_import_with_sign_fix(&home, &mirrored_path, false, true);
+        assert!(committed.is_ok());
+        if let Ok(success) = committed {
+            let payload = serde_json::to_value(success);
+            assert!(payload.is_ok());
+            if let Ok(value) = payload {
+                let import_id = value["data"]["import_id"].as_str().unwrap_or_default();
+                let connection = rusqlite::Connection::open(home.join("ledger.db"));
+                assert!(connection.is_ok());
+                if let Ok(connection) = connection {
+                    let negative_rows = connection.query_row(
+                        "SELECT COUNT(*) FROM internal_transactions
+                         WHERE import_id = ?1 AND amount < 0",
+                        [import_id],
+                        |row| row.get::<_, i64>(0),
+                    );
+                    assert_eq!(negative_rows.ok(), Some(5));
+                    let recorded = connection.query_row(
+                        "SELECT rows_inverted FROM internal_import_sign_corrections
+                         WHERE import_id = ?1 AND account_key = 'acct_mirror_1'",
+                        [import_id],
+                        |row| row.get::<_, i64>(0),
+                    );
+                    assert_eq!(recorded.ok(), Some(6));
+                }
+            }
+        }
+    }
+}
+
 #[test]
 fn keys_uniq_returns_sorted_values_for_property_and_all_properties() {
     let temp = temp_home();
@@ -381,3 +577,117 @@ fn keys_uniq_returns_sorted_values_for_property_and_all_properties() {
         }
     }
 }
+
+#[test]
+fn dry_run_previews_recurring_and_anomaly_changes() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+
+        let mut baseline = vec![
+            json!({
+                "account_key": "chase_checking_1234",
+                "posted_at": "2026-01-05",
+                "amount": -15.99,
+                "currency": "USD",
+                "description": "NETFLIX.COM",
+                "merchant": "Netflix"
+            }),
+            json!({
+                "account_key": "chase_checking_1234",
+                "posted_at": "2026-02-05",
+                "amount": -15.99,
+                "currency": "USD",
+                "description": "NETFLIX.COM",
+                "merchant": "Netflix"
+            }),
+        ];
+        for (day, amount) in [
+            ("2026-01-03", -41.20),
+            ("2026-01-10", -38.75),
+            ("2026-01-17", -42.10),
+            ("2026-01-24", -39.60),
+            ("2026-01-31", -40.45),
+            ("2026-02-07", -41.05),
+            ("2026-02-14", -39.90),
+        ] {
+            baseline.push(json!({
+                "account_key": "chase_checking_1234",
+                "posted_at": day,
+                "amount": amount,
+                "currency": "USD",
+                "description": "WHOLE FOODS MKT",
+                "merchant": "WHOLE FOODS MKT"
+            }));
+        }
+        let baseline_path = home.join("baseline.json");
+        write_json(&baseline_path, &Value::Array(baseline));
+        assert!(run_import(&home, &baseline_path, false).is_ok());
+
+        let incoming_path = home.join("incoming.json");
+        write_json(
+            &incoming_path,
+            &json!([
+                {
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-03-05",
+                    "amount": -15.99,
+                    "currency": "USD",
+                    "description": "NETFLIX.COM",
+                    "merchant": "Netflix"
+                },
+                {
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-04-05",
+                    "amount": -15.99,
+                    "currency": "USD",
+                    "description": "NETFLIX.COM",
+                    "merchant": "Netflix"
+                },
+                {
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-02-21",
+                    "amount": -400.00,
+                    "currency": "USD",
+                    "description": "WHOLE FOODS MKT",
+                    "merchant": "WHOLE FOODS MKT"
+                }
+            ]),
+        );
+        let dry_run = run_import(&home, &incoming_path, true);
+        assert!(dry_run.is_ok());
+        if let Ok(success) = dry_run
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            let impact = &value["data"]["intelligence_impact"];
+            let appearing = impact["recurring_appearing"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            assert!(
+                appearing
+                    .iter()
+                    .any(|row| row["merchant"] == "NETFLIX" && row["cadence"] == "monthly")
+            );
+            assert_eq!(impact["recurring_disappearing"], json!([]));
+            let anomalies = impact["anomalies_triggered"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            assert_eq!(anomalies.len(), 1);
+            assert_eq!(anomalies[0]["source_row_index"], 3);
+            assert_eq!(anomalies[0]["amount"], -400.0);
+            assert!(anomalies[0]["txn_id"].is_null());
+        }
+
+        let committed = run_import(&home, &incoming_path, false);
+        assert!(committed.is_ok());
+        if let Ok(success) = committed
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            assert!(value["data"].get("intelligence_impact").is_none());
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{self, ImportRunOptions, ImportUndoOptions};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn run_import(
+    home: &Path,
+    name: &str,
+    body: &str,
+    dry_run: bool,
+    idempotency_key: Option<&str>,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    run_import_with(home, name, body, dry_run, idempotency_key, false)
+}
+
+fn run_import_with(
+    home: &Path,
+    name: &str,
+    body: &str,
+    dry_run: bool,
+    idempotency_key: Option<&str>,
+    allow_duplicate_source: bool,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    let path = home.join(name);
+    let write = fs::write(&path, body);
+    assert!(write.is_ok());
+    import::run_with_options(ImportRunOptions {
+        path: Some(path.display().to_string()),
+        dry_run,
+        home_override: Some(home),
+        idempotency_key: idempotency_key.map(str::to_string),
+        allow_duplicate_source,
+        ..Default::default()
+    })
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn error_code(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> String {
+    assert!(result.is_err());
+    match result {
+        Err(error) => error.code,
+        Ok(_) => String::new(),
+    }
+}
+
+fn import_run_count(db_path: &Path) -> Option<i64> {
+    let connection = Connection::open(db_path).ok()?;
+    connection
+        .query_row("SELECT COUNT(*) FROM internal_import_runs", [], |row| {
+            row.get(0)
+        })
+        .ok()
+}
+
+const ROWS: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-15.49,USD,NETFLIX.COM
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+";
+
+#[test]
+fn reimporting_the_same_source_is_refused_until_undone() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let first = to_data(run_import(&home, "rows.csv", ROWS, false, None));
+        let first_id = first["import_id"].as_str().unwrap_or_default().to_string();
+        let source_hash = first["source_hash"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        assert_eq!(source_hash.len(), 64);
+
+        let retry = run_import(&home, "retry.csv", ROWS, false, None);
+        assert!(retry.is_err());
+        if let Err(error) = retry {
+            assert_eq!(error.code, "import_source_already_imported");
+            assert_eq!(
+                error.data.as_ref().map(|data| data["import_id"].clone()),
+                Some(Value::String(first_id.clone()))
+            );
+        }
+        assert_eq!(import_run_count(&home.join("ledger.db")), Some(1));
+
+        let preview = to_data(run_import(&home, "rows.csv", ROWS, true, None));
+        assert_eq!(preview["source_hash"], source_hash.as_str());
+        assert_eq!(preview["warnings"][0]["code"], "source_already_imported");
+
+        let undone = import::undo_with_options(
+            &first_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        );
+        assert!(undone.is_ok());
+        let again = to_data(run_import(&home, "rows.csv", ROWS, false, None));
+        assert_eq!(again["summary"]["inserted"], 2);
+    }
+}
+
+#[test]
+fn reformatted_copy_of_a_source_is_still_refused() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let first = to_data(run_import(&home, "rows.csv", ROWS, false, None));
+
+        let reformatted = format!("{}\n", ROWS.replace('\n', "\r\n"));
+        let preview = to_data(run_import(&home, "copy.csv", &reformatted, true, None));
+        assert_eq!(preview["source_hash"], first["source_hash"]);
+        assert_eq!(
+            error_code(run_import(&home, "copy.csv", &reformatted, false, None)),
+            "import_source_already_imported"
+        );
+
+        let forced = to_data(run_import_with(
+            &home,
+            "copy.csv",
+            &reformatted,
+            false,
+            None,
+            true,
+        ));
+        assert_eq!(forced["summary"]["inserted"], 0);
+        assert_eq!(import_run_count(&home.join("ledger.db")), Some(2));
+    }
+}
+
+#[test]
+fn partially_reverted_source_can_be_imported_again() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let body = format!("{ROWS}amex_gold_9876,2026-01-07,-12.00,USD,LYFT\n");
+        let first = to_data(run_import(&home, "rows.csv", &body, false, None));
+        let first_id = first["import_id"].as_str().unwrap_or_default().to_string();
+
+        let undone = import::undo_with_options(
+            &first_id,
+            ImportUndoOptions {
+                account_key: Some("amex_gold_9876".to_string()),
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        );
+        assert!(undone.is_ok());
+
+        let again = to_data(run_import(&home, "rows.csv", &body, false, None));
+        assert_eq!(again["source_hash"], first["source_hash"]);
+        assert_eq!(again["summary"]["inserted"], 1);
+    }
+}
+
+#[test]
+fn idempotency_key_replays_the_original_import() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first = to_data(run_import(&home, "rows.csv", ROWS, false, Some("agent-42")));
+        let first_id = first["import_id"].as_str().unwrap_or_default().to_string();
+        assert_eq!(first["idempotency_key"], "agent-42");
+        assert!(first.get("replayed").is_none());
+
+        let replay = to_data(run_import(&home, "rows.csv", ROWS, false, Some("agent-42")));
+        assert_eq!(replay["import_id"], first_id.as_str());
+        assert_eq!(replay["replayed"], true);
+        assert_eq!(replay["summary"]["inserted"], 2);
+        assert_eq!(replay["source_hash"], first["source_hash"]);
+        assert_eq!(import_run_count(&db_path), Some(1));
+
+        let changed = format!("{ROWS}chase_checking_1234,2026-01-06,-3.00,USD,COFFEE\n");
+        assert_eq!(
+            error_code(run_import(
+                &home,
+                "rows.csv",
+                &changed,
+                false,
+                Some("agent-42")
+            )),
+            "idempotency_key_conflict"
+        );
+        assert_eq!(
+            error_code(run_import(
+                &home,
+                "rows.csv",
+                &changed,
+                true,
+                Some("agent-43")
+            )),
+            "invalid_argument"
+        );
+        assert_eq!(
+            error_code(run_import(&home, "rows.csv", &changed, false, Some("  "))),
+            "invalid_argument"
+        );
+
+        let undone = import::undo_with_options(
+            &first_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        );
+        assert!(undone.is_ok());
+        assert_eq!(
+            error_code(run_import(&home, "rows.csv", ROWS, false, Some("agent-42"))),
+            "idempotency_key_conflict"
+        );
+    }
+}
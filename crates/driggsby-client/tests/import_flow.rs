@@ -46,6 +46,21 @@ fn run_import_with_raw_path(
         dry_run,
         home_override: Some(home),
         stdin_override: stdin_override.map(std::string::ToString::to_string),
+        ..Default::default()
+    })
+}
+
+/// Imports `path` again even though a committed import already used the
+/// same rows, so dedupe decides row by row.
+fn run_reimport(
+    home: &Path,
+    path: &Path,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    import::run_with_options(ImportRunOptions {
+        path: Some(path.display().to_string()),
+        home_override: Some(home),
+        allow_duplicate_source: true,
+        ..Default::default()
     })
 }
 
@@ -63,6 +78,7 @@ fn run_import_undo(
         import_id,
         ImportUndoOptions {
             home_override: Some(home),
+            ..Default::default()
         },
     )
 }
@@ -650,7 +666,7 @@ fn json_import_missing_statement_id_is_null_and_dedupes_across_imports() {
         );
         assert!(stored_statement_id.is_none());
 
-        let second_result = run_import(&home, Some(&source_path), false, None);
+        let second_result = run_reimport(&home, &source_path);
         assert!(second_result.is_ok());
         if let Ok(success) = second_result {
             let payload = serde_json::to_value(success);
@@ -707,7 +723,7 @@ fn csv_import_missing_statement_id_is_null_and_dedupes_across_imports() {
         );
         assert!(stored_statement_id.is_none());
 
-        let second_result = run_import(&home, Some(&source_path), false, None);
+        let second_result = run_reimport(&home, &source_path);
         assert!(second_result.is_ok());
         if let Ok(success) = second_result {
             let payload = serde_json::to_value(success);
@@ -2028,7 +2044,7 @@ fn duplicates_fallback_resolves_null_statement_id_using_dedupe_scope() {
 
         let first_result = run_import(&home, Some(&winner_path), false, None);
         assert!(first_result.is_ok());
-        let second_result = run_import(&home, Some(&candidate_path), false, None);
+        let second_result = run_reimport(&home, &candidate_path);
         assert!(second_result.is_ok());
 
         let mut first_import_id = None;
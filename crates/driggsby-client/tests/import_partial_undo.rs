@@ -0,0 +1,198 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportListOptions, ImportRedoOptions, ImportRunOptions, ImportUndoOptions,
+};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    let data = to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["import_id"].as_str().unwrap_or_default().to_string()
+}
+
+fn undo(
+    home: &Path,
+    import_id: &str,
+    account_key: Option<&str>,
+    statement_id: Option<&str>,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    import::undo_with_options(
+        import_id,
+        ImportUndoOptions {
+            account_key: account_key.map(str::to_string),
+            statement_id: statement_id.map(str::to_string),
+            home_override: Some(home),
+        },
+    )
+}
+
+fn list_row(home: &Path, import_id: &str) -> Value {
+    let listed = to_data(import::list_with_options(ImportListOptions {
+        home_override: Some(home),
+    }));
+    listed["rows"]
+        .as_array()
+        .and_then(|rows| rows.iter().find(|row| row["import_id"] == import_id))
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn ledger_descriptions(db_path: &Path, import_id: &str) -> Vec<String> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(
+            "SELECT description FROM internal_transactions
+             WHERE import_id = ?1 ORDER BY description",
+        )
+        && let Ok(mapped) = statement.query_map([import_id], |row| row.get(0))
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+const TWO_ACCOUNTS: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+ally_savings_9876,2026-01-06,25.00,USD,INTEREST
+";
+
+const OVERLAPPING: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+chase_checking_1234,2026-01-09,-1200.00,USD,RENT
+";
+
+const TWO_STATEMENTS: &str = "statement_id,account_key,posted_at,amount,currency,description
+stmt_jan,chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+stmt_jan,chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+stmt_feb,chase_checking_1234,2026-02-03,-5.25,USD,BLUE BOTTLE COFFEE
+";
+
+#[test]
+fn undo_by_account_reverts_one_slice_at_a_time() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first_id = import_file(&home, "first.csv", TWO_ACCOUNTS);
+        let second_id = import_file(&home, "second.csv", OVERLAPPING);
+        assert_eq!(ledger_descriptions(&db_path, &second_id), vec!["RENT"]);
+
+        let partial = to_data(undo(&home, &first_id, Some("chase_checking_1234"), None));
+        assert_eq!(partial["status"], "partially_reverted");
+        assert_eq!(partial["account_key"], "chase_checking_1234");
+        assert_eq!(partial["summary"]["rows_reverted"], 2);
+        assert_eq!(partial["summary"]["rows_promoted"], 1);
+        assert_eq!(ledger_descriptions(&db_path, &first_id), vec!["INTEREST"]);
+        assert_eq!(
+            ledger_descriptions(&db_path, &second_id),
+            vec!["RENT", "WHOLE FOODS MKT"]
+        );
+
+        let row = list_row(&home, &first_id);
+        assert_eq!(row["status"], "partially_reverted");
+        let accounts = row["accounts"].as_array().cloned().unwrap_or_default();
+        let checking = accounts
+            .iter()
+            .find(|account| account["account_key"] == "chase_checking_1234")
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(checking["inserted"], 0);
+        assert_eq!(checking["rows_read"], 0);
+        let savings = accounts
+            .iter()
+            .find(|account| account["account_key"] == "ally_savings_9876")
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(savings["inserted"], 1);
+
+        let again = undo(&home, &first_id, Some("chase_checking_1234"), None);
+        assert!(again.is_err());
+        if let Err(error) = again {
+            assert_eq!(error.code, "import_undo_scope_empty");
+        }
+        let redo_partial = import::redo_with_options(
+            &first_id,
+            ImportRedoOptions {
+                home_override: Some(&home),
+            },
+        );
+        assert!(redo_partial.is_err());
+        if let Err(error) = redo_partial {
+            assert_eq!(error.code, "import_not_reverted");
+        }
+
+        let rest = to_data(undo(&home, &first_id, None, None));
+        assert_eq!(rest["status"], "reverted");
+        assert_eq!(rest["summary"]["rows_reverted"], 1);
+        assert!(ledger_descriptions(&db_path, &first_id).is_empty());
+
+        let redone = to_data(import::redo_with_options(
+            &first_id,
+            ImportRedoOptions {
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(redone["summary"]["rows_restored"], 3);
+        assert_eq!(ledger_descriptions(&db_path, &second_id), vec!["RENT"]);
+        assert_eq!(list_row(&home, &first_id)["status"], "committed");
+    }
+}
+
+#[test]
+fn undo_by_statement_keeps_other_statements() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let import_id = import_file(&home, "statements.csv", TWO_STATEMENTS);
+
+        let partial = to_data(undo(&home, &import_id, None, Some("stmt_jan")));
+        assert_eq!(partial["status"], "partially_reverted");
+        assert_eq!(partial["summary"]["rows_reverted"], 2);
+        assert_eq!(
+            ledger_descriptions(&db_path, &import_id),
+            vec!["BLUE BOTTLE COFFEE"]
+        );
+        assert_eq!(list_row(&home, &import_id)["accounts"][0]["inserted"], 1);
+
+        let rest = to_data(undo(&home, &import_id, None, Some("stmt_feb")));
+        assert_eq!(rest["status"], "reverted");
+        assert_eq!(list_row(&home, &import_id)["status"], "reverted");
+
+        let blank = undo(&home, &import_id, Some("  "), None);
+        assert!(blank.is_err());
+        if let Err(error) = blank {
+            assert_eq!(error.code, "invalid_argument");
+        }
+    }
+}
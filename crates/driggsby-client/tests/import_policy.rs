@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import;
+use driggsby_client::commands::import::ImportRunOptions;
+use driggsby_client::contracts::envelope::failure_from_error;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn write_file(path: &Path, body: &str) {
+    let result = fs::write(path, body);
+    assert!(result.is_ok());
+}
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn run_import(
+    home: &Path,
+    path: &Path,
+    dry_run: bool,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    import::run_with_options(ImportRunOptions {
+        path: Some(path.display().to_string()),
+        dry_run,
+        home_override: Some(home),
+        ..Default::default()
+    })
+}
+
+fn error_payload(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_err());
+    if let Err(error) = result
+        && let Ok(value) = serde_json::to_value(failure_from_error(&error))
+    {
+        return value["error"].clone();
+    }
+    Value::Null
+}
+
+const POLICY: &str = r#"{
+  "rules": [
+    {"rule": "posted_at_not_in_future"},
+    {"rule": "posted_at_not_before", "date": "2000-01-01"},
+    {"rule": "max_abs_amount", "limit": 5000, "account_type": "credit_card", "severity": "warning"},
+    {"rule": "require_field", "field": "merchant", "account_type": "credit_card", "severity": "warning"},
+    {"rule": "currency_allowlist", "currencies": ["USD", "EUR"]}
+  ]
+}"#;
+
+#[test]
+fn error_rules_reject_rows_and_warning_rules_are_reported() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        write_file(&home.join("import_policy.json"), POLICY);
+
+        let rejected_path = home.join("rejected.csv");
+        write_file(
+            &rejected_path,
+            "account_key,account_type,posted_at,amount,currency,description,merchant
+acct_policy_1,checking,2999-01-01,-10.00,USD,FUTURE,Shop
+acct_policy_1,checking,1999-12-31,-10.00,USD,ANCIENT,Shop
+acct_policy_1,checking,2026-01-05,-10.00,JPY,YEN,Shop
+acct_policy_1,checking,2026-01-06,-10.00,USD,FINE,Shop
+",
+        );
+        let error = error_payload(run_import(&home, &rejected_path, true));
+        assert_eq!(error["code"], "import_validation_failed");
+        let issues = &error["data"]["issues"];
+        assert_eq!(issues.as_array().map(Vec::len), Some(3));
+        assert_eq!(issues[0]["code"], "policy_posted_at_in_future");
+        assert_eq!(issues[1]["code"], "policy_posted_at_too_early");
+        assert_eq!(issues[2]["code"], "policy_currency_not_allowed");
+        assert_eq!(issues[2]["row"], 3);
+
+        let warned_path = home.join("warned.csv");
+        write_file(
+            &warned_path,
+            "account_key,account_type,posted_at,amount,currency,description,merchant
+acct_policy_card,credit_card,2026-01-05,-7500.00,USD,TV,
+acct_policy_card,,2026-01-06,-12.00,USD,LUNCH,Cafe
+",
+        );
+        let committed = run_import(&home, &warned_path, false);
+        assert!(committed.is_ok());
+        if let Ok(success) = committed
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            assert_eq!(value["data"]["summary"]["inserted"], 2);
+            let warnings = &value["data"]["policy_warnings"];
+            assert_eq!(warnings.as_array().map(Vec::len), Some(2));
+            assert_eq!(warnings[0]["code"], "policy_amount_over_limit");
+            assert_eq!(warnings[1]["code"], "policy_required_field_missing");
+            assert_eq!(warnings[1]["field"], "merchant");
+        }
+    }
+}
+
+#[test]
+fn account_type_rules_use_the_ledger_type_when_an_import_omits_it() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        write_file(&home.join("import_policy.json"), POLICY);
+
+        let declared_path = home.join("declared.csv");
+        write_file(
+            &declared_path,
+            "account_key,account_type,posted_at,amount,currency,description,merchant
+acct_policy_card,credit_card,2026-01-05,-12.00,USD,LUNCH,Cafe
+",
+        );
+        assert!(run_import(&home, &declared_path, false).is_ok());
+
+        let later_path = home.join("later.csv");
+        write_file(
+            &later_path,
+            "account_key,posted_at,amount,currency,description,merchant
+acct_policy_card,2026-02-05,-7500.00,USD,TV,Store
+",
+        );
+        let preview = run_import(&home, &later_path, true);
+        assert!(preview.is_ok());
+        if let Ok(success) = preview
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            let warnings = &value["data"]["policy_warnings"];
+            assert_eq!(warnings.as_array().map(Vec::len), Some(1));
+            assert_eq!(warnings[0]["code"], "policy_amount_over_limit");
+            assert_eq!(warnings[0]["row"], 1);
+        }
+    }
+}
+
+#[test]
+fn imports_without_a_policy_file_omit_policy_warnings() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let source_path = home.join("plain.csv");
+        write_file(
+            &source_path,
+            "account_key,posted_at,amount,currency,description
+acct_policy_2,2999-01-01,-10.00,JPY,FUTURE
+",
+        );
+        let dry_run = run_import(&home, &source_path, true);
+        assert!(dry_run.is_ok());
+        if let Ok(success) = dry_run
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            assert!(value["data"].get("policy_warnings").is_none());
+        }
+    }
+}
+
+#[test]
+fn malformed_policy_file_fails_before_validation() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        write_file(
+            &home.join("import_policy.json"),
+            r#"{"rules": [{"rule": "require_field", "field": "description"}]}"#,
+        );
+        let source_path = home.join("any.csv");
+        write_file(
+            &source_path,
+            "account_key,posted_at,amount,currency,description
+acct_policy_3,2026-01-01,-10.00,USD,ROW
+",
+        );
+        let error = error_payload(run_import(&home, &source_path, true));
+        assert_eq!(error["code"], "import_policy_invalid");
+        assert!(
+            error["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("rules[0].field"))
+        );
+    }
+}
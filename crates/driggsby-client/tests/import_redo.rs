@@ -0,0 +1,195 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportRedoOptions, ImportRunOptions, ImportUndoOptions,
+};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    let data = to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["import_id"].as_str().unwrap_or_default().to_string()
+}
+
+fn undo(home: &Path, import_id: &str) -> Value {
+    to_data(import::undo_with_options(
+        import_id,
+        ImportUndoOptions {
+            home_override: Some(home),
+            ..Default::default()
+        },
+    ))
+}
+
+fn redo(
+    home: &Path,
+    import_id: &str,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    import::redo_with_options(
+        import_id,
+        ImportRedoOptions {
+            home_override: Some(home),
+        },
+    )
+}
+
+fn query_strings(db_path: &Path, sql: &str, import_id: &str) -> Vec<String> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(sql)
+        && let Ok(mapped) = statement.query_map([import_id], |row| row.get(0))
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+fn ledger_txn_ids(db_path: &Path, import_id: &str) -> Vec<String> {
+    query_strings(
+        db_path,
+        "SELECT txn_id FROM internal_transactions WHERE import_id = ?1 ORDER BY txn_id",
+        import_id,
+    )
+}
+
+fn pending_candidates(db_path: &Path, import_id: &str) -> Vec<String> {
+    query_strings(
+        db_path,
+        "SELECT description FROM internal_transaction_dedupe_candidates
+         WHERE import_id = ?1 AND promoted_txn_id IS NULL
+         ORDER BY description",
+        import_id,
+    )
+}
+
+const FIRST: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+chase_checking_1234,2026-01-07,-30.00,USD,SHELL OIL
+";
+
+const OVERLAPPING: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-07,-30.00,USD,SHELL OIL
+chase_checking_1234,2026-01-09,-1200.00,USD,RENT
+";
+
+#[test]
+fn redo_restores_rows_ids_and_candidates() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first_id = import_file(&home, "first.csv", FIRST);
+        let second_id = import_file(&home, "second.csv", OVERLAPPING);
+        let original_txn_ids = ledger_txn_ids(&db_path, &first_id);
+        assert_eq!(original_txn_ids.len(), 3);
+        assert_eq!(pending_candidates(&db_path, &second_id), vec!["SHELL OIL"]);
+
+        let undone = undo(&home, &first_id);
+        assert_eq!(undone["summary"]["rows_promoted"], 1);
+        assert!(
+            undone["message"]
+                .as_str()
+                .unwrap_or_default()
+                .contains(&format!("driggsby import redo {first_id}"))
+        );
+        assert!(pending_candidates(&db_path, &second_id).is_empty());
+
+        let redone = to_data(redo(&home, &first_id));
+        assert_eq!(redone["import_id"], first_id.as_str());
+        assert_eq!(redone["summary"]["rows_restored"], 3);
+        assert_eq!(redone["summary"]["rows_deduped"], 0);
+        assert_eq!(ledger_txn_ids(&db_path, &first_id), original_txn_ids);
+        assert_eq!(ledger_txn_ids(&db_path, &second_id).len(), 1);
+        assert_eq!(pending_candidates(&db_path, &second_id), vec!["SHELL OIL"]);
+
+        let linked = query_strings(
+            &db_path,
+            "SELECT txn_id FROM internal_import_source_rows
+             WHERE import_id = ?1 AND txn_id IS NOT NULL
+             ORDER BY txn_id",
+            &first_id,
+        );
+        assert_eq!(linked, original_txn_ids);
+        let status = query_strings(
+            &db_path,
+            "SELECT status FROM internal_import_runs WHERE import_id = ?1",
+            &first_id,
+        );
+        assert_eq!(status, vec!["committed"]);
+
+        let again = redo(&home, &first_id);
+        assert!(again.is_err());
+        if let Err(error) = again {
+            assert_eq!(error.code, "import_not_reverted");
+        }
+    }
+}
+
+#[test]
+fn redo_reports_rows_imported_again_since_the_undo() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first_id = import_file(&home, "first.csv", FIRST);
+        undo(&home, &first_id);
+        let reimported_id = import_file(&home, "again.csv", &format!("{FIRST}\n"));
+        let before = ledger_txn_ids(&db_path, &reimported_id);
+
+        let result = redo(&home, &first_id);
+        assert!(result.is_err());
+        if let Err(error) = result {
+            assert_eq!(error.code, "import_redo_conflict");
+            assert!(error.recovery_steps[0].contains(&reimported_id));
+            let conflicts = error
+                .data
+                .as_ref()
+                .and_then(|data| data["conflicts"].as_array().cloned())
+                .unwrap_or_default();
+            assert_eq!(conflicts.len(), 3);
+            assert_eq!(conflicts[0]["kind"], "transaction");
+            assert_eq!(conflicts[0]["source_row_index"], 1);
+            assert_eq!(
+                conflicts[0]["conflicting_import_id"],
+                reimported_id.as_str()
+            );
+        }
+        assert!(ledger_txn_ids(&db_path, &first_id).is_empty());
+        assert_eq!(ledger_txn_ids(&db_path, &reimported_id), before);
+
+        let missing = redo(&home, "imp_missing");
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "import_id_not_found");
+        }
+    }
+}
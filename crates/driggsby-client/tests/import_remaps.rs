@@ -0,0 +1,140 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import;
+use driggsby_client::commands::import::ImportRunOptions;
+use rusqlite::Connection;
+use serde_json::json;
+use tempfile::tempdir;
+
+fn write_file(path: &Path, body: &str) {
+    let result = fs::write(path, body);
+    assert!(result.is_ok());
+}
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn account_keys(db_path: &Path) -> Vec<(String, String)> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) =
+            conn.prepare("SELECT account_key, merchant FROM v1_transactions ORDER BY posted_at ASC")
+        && let Ok(mapped) = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+fn persisted_remaps(db_path: &Path) -> Vec<(String, String, String, i64)> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(
+            "SELECT property, from_value, to_value, rows_rewritten
+             FROM internal_import_remaps
+             ORDER BY property ASC, from_value ASC",
+        )
+        && let Ok(mapped) = statement.query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+#[test]
+fn remap_flags_and_file_rewrite_values_and_are_stored_with_the_import() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let source = home.join("rows.csv");
+        write_file(
+            &source,
+            "account_key,posted_at,amount,currency,description,merchant
+chase_chk,2026-01-03,-10.00,USD,AMZN MKTP,AMZN Mktp US
+chase_chk,2026-01-04,-20.00,USD,NETFLIX,Netflix
+chase_checking_1234,2026-01-05,-30.00,USD,WHOLE FOODS,Whole Foods
+",
+        );
+        let remap_file = home.join("remaps.json");
+        write_file(&remap_file, r#"{"merchant": {"AMZN Mktp US": "Amazon"}}"#);
+
+        let result = import::run_with_options(ImportRunOptions {
+            path: Some(source.display().to_string()),
+            home_override: Some(&home),
+            remaps: vec!["account_key:chase_chk=chase_checking_1234".to_string()],
+            remap_file: Some(remap_file.display().to_string()),
+            ..Default::default()
+        });
+        assert!(result.is_ok());
+        if let Ok(success) = result
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            assert_eq!(
+                value["data"]["remaps"],
+                json!([
+                    {"property": "merchant", "from": "AMZN Mktp US", "to": "Amazon", "rows_rewritten": 1},
+                    {"property": "account_key", "from": "chase_chk", "to": "chase_checking_1234", "rows_rewritten": 2}
+                ])
+            );
+        }
+
+        assert_eq!(
+            account_keys(&db_path),
+            vec![
+                ("chase_checking_1234".to_string(), "Amazon".to_string()),
+                ("chase_checking_1234".to_string(), "Netflix".to_string()),
+                ("chase_checking_1234".to_string(), "Whole Foods".to_string()),
+            ]
+        );
+        assert_eq!(
+            persisted_remaps(&db_path),
+            vec![
+                (
+                    "account_key".to_string(),
+                    "chase_chk".to_string(),
+                    "chase_checking_1234".to_string(),
+                    2
+                ),
+                (
+                    "merchant".to_string(),
+                    "AMZN Mktp US".to_string(),
+                    "Amazon".to_string(),
+                    1
+                ),
+            ]
+        );
+    }
+}
+
+#[test]
+fn invalid_remaps_fail_before_reading_the_import() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let result = import::run_with_options(ImportRunOptions {
+            path: Some(home.join("missing.csv").display().to_string()),
+            dry_run: true,
+            home_override: Some(&home),
+            remaps: vec!["currency:usd=USD".to_string()],
+            ..Default::default()
+        });
+        assert!(result.is_err());
+        if let Err(error) = result {
+            assert_eq!(error.code, "invalid_argument");
+            assert!(error.message.contains("currency"));
+        }
+        assert!(persisted_remaps(&home.join("ledger.db")).is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportListOptions, ImportReplaceOptions, ImportRunOptions,
+};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn write_source(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    source.display().to_string()
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> String {
+    let data = to_data(import::run_with_options(ImportRunOptions {
+        path: Some(write_source(home, name, body)),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["import_id"].as_str().unwrap_or_default().to_string()
+}
+
+fn replace(
+    home: &Path,
+    import_id: &str,
+    path: String,
+    dry_run: bool,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    import::replace_with_options(ImportReplaceOptions {
+        import_id: import_id.to_string(),
+        path,
+        dry_run,
+        home_override: Some(home),
+    })
+}
+
+fn ledger_labels(db_path: &Path) -> Vec<String> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(
+            "SELECT posted_at || ' ' || amount || ' ' || description FROM v1_transactions
+             ORDER BY posted_at ASC, description ASC",
+        )
+        && let Ok(mapped) = statement.query_map([], |row| row.get(0))
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+const ORIGINAL: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+chase_checking_1234,2026-01-07,-30.00,USD,SHELL OIL
+";
+
+const CORRECTED: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-01-05,-42.01,USD,WHOLE FOODS MKT
+chase_checking_1234,2026-01-09,-1200.00,USD,RENT
+";
+
+#[test]
+fn replace_previews_the_diff_then_swaps_the_import() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let original_id = import_file(&home, "original.csv", ORIGINAL);
+        let corrected = write_source(&home, "corrected.csv", CORRECTED);
+        let before = ledger_labels(&db_path);
+
+        let preview = to_data(replace(&home, &original_id, corrected.clone(), true));
+        let replacement = &preview["replacement"];
+        assert_eq!(replacement["replaced_import_id"], original_id.as_str());
+        assert_eq!(replacement["summary"]["unchanged"], 1);
+        assert_eq!(replacement["summary"]["added"], 1);
+        assert_eq!(replacement["summary"]["removed"], 1);
+        assert_eq!(replacement["summary"]["modified"], 1);
+        assert_eq!(replacement["rows_reverted"], 3);
+        let modified = replacement["changes"]
+            .as_array()
+            .and_then(|changes| changes.iter().find(|change| change["status"] == "modified"))
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(modified["changed_fields"], serde_json::json!(["amount"]));
+        assert_eq!(modified["new"]["source_row_index"], 2);
+        assert!(modified["old"]["txn_id"].is_string());
+        assert_eq!(ledger_labels(&db_path), before);
+
+        let applied = to_data(replace(&home, &original_id, corrected, false));
+        let new_id = applied["import_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        assert!(new_id.starts_with("imp_"));
+        assert_eq!(applied["summary"]["inserted"], 3);
+        assert_eq!(applied["replacement"]["summary"]["modified"], 1);
+        assert_eq!(
+            ledger_labels(&db_path),
+            vec![
+                "2026-01-03 -4.5 BLUE BOTTLE COFFEE".to_string(),
+                "2026-01-05 -42.01 WHOLE FOODS MKT".to_string(),
+                "2026-01-09 -1200.0 RENT".to_string(),
+            ]
+        );
+
+        let listed = to_data(import::list_with_options(ImportListOptions {
+            home_override: Some(&home),
+        }));
+        let rows = listed["rows"].as_array().cloned().unwrap_or_default();
+        let row_for = |import_id: &str| {
+            rows.iter()
+                .find(|row| row["import_id"] == import_id)
+                .cloned()
+                .unwrap_or_default()
+        };
+        assert_eq!(row_for(&original_id)["status"], "reverted");
+        assert_eq!(
+            row_for(&original_id)["replaced_by_import_id"],
+            new_id.as_str()
+        );
+        assert_eq!(row_for(&new_id)["replaces_import_id"], original_id.as_str());
+
+        let again = replace(
+            &home,
+            &original_id,
+            write_source(&home, "again.csv", CORRECTED),
+            false,
+        );
+        assert!(again.is_err());
+        if let Err(error) = again {
+            assert_eq!(error.code, "import_already_reverted");
+        }
+        let missing = replace(
+            &home,
+            "imp_missing",
+            write_source(&home, "missing.csv", CORRECTED),
+            true,
+        );
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "import_id_not_found");
+        }
+    }
+}
+
+#[test]
+fn replace_promotes_rows_that_were_deduped_against_the_old_import() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let original_id = import_file(&home, "original.csv", ORIGINAL);
+        import_file(
+            &home,
+            "overlap.csv",
+            "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-07,-30.00,USD,SHELL OIL
+",
+        );
+
+        let applied = to_data(replace(
+            &home,
+            &original_id,
+            write_source(&home, "corrected.csv", CORRECTED),
+            false,
+        ));
+        assert_eq!(applied["replacement"]["rows_promoted"], 1);
+        assert!(
+            ledger_labels(&db_path)
+                .iter()
+                .any(|label| label == "2026-01-07 -30.0 SHELL OIL")
+        );
+
+        // Replacing with byte-identical content is not refused as a re-import.
+        let new_id = applied["import_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let same = to_data(replace(
+            &home,
+            &new_id,
+            write_source(&home, "same.csv", CORRECTED),
+            false,
+        ));
+        assert_eq!(same["replacement"]["summary"]["unchanged"], 3);
+        assert_eq!(
+            same["replacement"]["changes"].as_array().map(Vec::len),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportListOptions, ImportRunOptions, ImportStageOptions, ImportStagedOptions,
+    ImportUndoOptions,
+};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn write_source(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    source.display().to_string()
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> String {
+    let data = to_data(import::run_with_options(ImportRunOptions {
+        path: Some(write_source(home, name, body)),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["import_id"].as_str().unwrap_or_default().to_string()
+}
+
+fn stage_file(home: &Path, name: &str, body: &str) -> Value {
+    to_data(import::stage_with_options(ImportStageOptions {
+        path: write_source(home, name, body),
+        home_override: Some(home),
+        ..Default::default()
+    }))
+}
+
+fn staged_options(home: &Path) -> ImportStagedOptions<'_> {
+    ImportStagedOptions {
+        home_override: Some(home),
+    }
+}
+
+fn import_status(home: &Path, import_id: &str) -> Value {
+    let listed = to_data(import::list_with_options(ImportListOptions {
+        home_override: Some(home),
+    }));
+    listed["rows"]
+        .as_array()
+        .and_then(|rows| rows.iter().find(|row| row["import_id"] == import_id))
+        .map(|row| row["status"].clone())
+        .unwrap_or_default()
+}
+
+fn count_rows(db_path: &Path, table: &str, import_id: &str) -> i64 {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut count = -1;
+    if let Ok(conn) = connection
+        && let Ok(value) = conn.query_row(
+            &format!("SELECT COUNT(*) FROM {table} WHERE import_id = ?1"),
+            [import_id],
+            |row| row.get(0),
+        )
+    {
+        count = value;
+    }
+    count
+}
+
+fn execute_sql(db_path: &Path, sql: &str, import_id: &str) {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection {
+        assert!(conn.execute(sql, [import_id]).is_ok());
+    }
+}
+
+const FIRST: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+chase_checking_1234,2026-01-07,-30.00,USD,SHELL OIL
+";
+
+const OVERLAPPING: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-07,-30.00,USD,SHELL OIL
+chase_checking_1234,2026-01-09,-1200.00,USD,RENT
+";
+
+const LATE: &str = "account_key,posted_at,amount,currency,description
+ally_savings_9876,2026-01-06,25.00,USD,INTEREST
+";
+
+#[test]
+fn staged_import_reaches_the_ledger_only_when_approved() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let staged = stage_file(&home, "first.csv", FIRST);
+        assert_eq!(staged["staged"], true);
+        assert_eq!(staged["summary"]["inserted"], 0);
+        assert!(staged["drift_warnings"].is_array());
+        let import_id = staged["import_id"].as_str().unwrap_or_default().to_string();
+        assert!(import_id.starts_with("imp_"));
+        assert_eq!(
+            staged["next_step"]["command"],
+            format!("driggsby import approve {import_id}")
+        );
+        assert_eq!(count_rows(&db_path, "internal_transactions", &import_id), 0);
+        assert_eq!(import_status(&home, &import_id), "staged");
+
+        let listed = to_data(import::staged_list_with_options(staged_options(&home)));
+        assert_eq!(listed["rows"][0]["import_id"], import_id.as_str());
+        assert_eq!(listed["rows"][0]["rows_read"], 3);
+
+        let shown = to_data(import::staged_show_with_options(
+            &import_id,
+            staged_options(&home),
+        ));
+        assert_eq!(shown["status"], "staged");
+        assert_eq!(shown["analysis"]["duplicate_summary"]["total"], 0);
+        assert!(shown["analysis"]["key_inventory"].is_object());
+
+        let undo = import::undo_with_options(
+            &import_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        );
+        assert!(undo.is_err());
+        if let Err(error) = undo {
+            assert_eq!(error.code, "import_not_committed");
+        }
+
+        let approved = to_data(import::approve_with_options(
+            &import_id,
+            staged_options(&home),
+        ));
+        assert_eq!(approved["summary"]["inserted"], 3);
+        assert_eq!(approved["duplicate_summary"]["total"], 0);
+        assert_eq!(count_rows(&db_path, "internal_transactions", &import_id), 3);
+        assert_eq!(
+            count_rows(&db_path, "internal_import_source_rows", &import_id),
+            3
+        );
+        assert_eq!(
+            count_rows(&db_path, "internal_staged_transactions", &import_id),
+            0
+        );
+        assert_eq!(import_status(&home, &import_id), "committed");
+
+        let listed = to_data(import::staged_list_with_options(staged_options(&home)));
+        assert_eq!(listed["rows"].as_array().map(Vec::len), Some(0));
+        let again = import::approve_with_options(&import_id, staged_options(&home));
+        assert!(again.is_err());
+        if let Err(error) = again {
+            assert_eq!(error.code, "import_not_staged");
+        }
+    }
+}
+
+#[test]
+fn approval_rechecks_the_ledger_and_reject_discards() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let staged = stage_file(&home, "first.csv", FIRST);
+        let staged_id = staged["import_id"].as_str().unwrap_or_default().to_string();
+        let overlapping_id = import_file(&home, "overlap.csv", OVERLAPPING);
+
+        let approved = to_data(import::approve_with_options(
+            &staged_id,
+            staged_options(&home),
+        ));
+        assert_eq!(approved["summary"]["inserted"], 2);
+        assert_eq!(approved["duplicate_summary"]["existing_ledger"], 1);
+        assert_eq!(approved["staged_duplicate_summary"]["total"], 0);
+        assert!(
+            approved["message"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("changed since staging")
+        );
+        assert_eq!(
+            count_rows(&db_path, "internal_transactions", &overlapping_id),
+            2
+        );
+
+        let restaged = stage_file(&home, "late.csv", LATE);
+        let restaged_id = restaged["import_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let rejected = to_data(import::reject_with_options(
+            &restaged_id,
+            staged_options(&home),
+        ));
+        assert_eq!(rejected["rows_discarded"], 1);
+        assert_eq!(import_status(&home, &restaged_id), "rejected");
+        assert_eq!(
+            count_rows(&db_path, "internal_staged_transactions", &restaged_id),
+            0
+        );
+
+        let late = stage_file(&home, "late-again.csv", LATE);
+        let late_id = late["import_id"].as_str().unwrap_or_default().to_string();
+        import_file(&home, "late-committed.csv", LATE);
+        let blocked = import::approve_with_options(&late_id, staged_options(&home));
+        assert!(blocked.is_err());
+        if let Err(error) = blocked {
+            assert_eq!(error.code, "import_source_already_imported");
+        }
+        assert_eq!(import_status(&home, &late_id), "staged");
+
+        let missing = import::reject_with_options("imp_missing", staged_options(&home));
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "import_id_not_found");
+        }
+    }
+}
+
+#[test]
+fn approval_keeps_source_records_on_their_own_rows() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let staged = stage_file(&home, "first.csv", FIRST);
+        let import_id = staged["import_id"].as_str().unwrap_or_default().to_string();
+        execute_sql(
+            &db_path,
+            "UPDATE internal_staged_transactions SET source_record = NULL
+             WHERE import_id = ?1 AND row_index = 2",
+            &import_id,
+        );
+
+        let approved = to_data(import::approve_with_options(
+            &import_id,
+            staged_options(&home),
+        ));
+        assert_eq!(approved["summary"]["inserted"], 3);
+        assert_eq!(
+            count_rows(&db_path, "internal_import_source_rows", &import_id),
+            2
+        );
+
+        let connection = Connection::open(&db_path);
+        assert!(connection.is_ok());
+        if let Ok(conn) = connection {
+            let record = conn.query_row(
+                "SELECT s.source_record
+                 FROM internal_import_source_rows s
+                 JOIN internal_transactions t ON t.txn_id = s.txn_id
+                 WHERE s.import_id = ?1 AND s.source_row_index = 3",
+                [&import_id],
+                |row| row.get::<_, String>(0),
+            );
+            assert!(record.is_ok());
+            if let Ok(record) = record {
+                assert!(record.contains("SHELL OIL"));
+            }
+        }
+    }
+}
+
+#[test]
+fn approval_refuses_unreadable_rule_hits() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let staged = stage_file(&home, "first.csv", FIRST);
+        let import_id = staged["import_id"].as_str().unwrap_or_default().to_string();
+        execute_sql(
+            &db_path,
+            "UPDATE internal_staged_imports
+             SET analysis = json_set(analysis, '$.rule_hits', 'not a map')
+             WHERE import_id = ?1",
+            &import_id,
+        );
+
+        let approved = import::approve_with_options(&import_id, staged_options(&home));
+        assert!(approved.is_err());
+        if let Err(error) = approved {
+            assert_eq!(error.code, "import_staged_analysis_invalid");
+        }
+        assert_eq!(import_status(&home, &import_id), "staged");
+        assert_eq!(count_rows(&db_path, "internal_transactions", &import_id), 0);
+    }
+}
@@ -23,6 +23,7 @@ fn run_import(home: &Path, path: &Path) {
         dry_run: false,
         home_override: Some(home),
         stdin_override: None,
+        ..Default::default()
     });
     assert!(result.is_ok());
 }
@@ -0,0 +1,286 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportDuplicatesOptions, ImportRunOptions, ImportUndoOptions,
+};
+use driggsby_client::commands::transactions::{self, TransactionMarkDuplicateOptions};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    let data = to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["import_id"].as_str().unwrap_or_default().to_string()
+}
+
+fn ledger_rows(db_path: &Path) -> Vec<(String, String)> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(
+            "SELECT txn_id, posted_at || ' ' || description FROM v1_transactions
+             ORDER BY posted_at ASC, description ASC",
+        )
+        && let Ok(mapped) = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+fn import_counts(db_path: &Path, import_id: &str) -> Option<(i64, i64)> {
+    let connection = Connection::open(db_path).ok()?;
+    connection
+        .query_row(
+            "SELECT inserted, deduped FROM internal_import_runs WHERE import_id = ?1",
+            [import_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .ok()
+}
+
+fn duplicates_options(home: &Path) -> ImportDuplicatesOptions<'_> {
+    ImportDuplicatesOptions {
+        home_override: Some(home),
+    }
+}
+
+const FIRST_IMPORT: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+";
+
+const SECOND_IMPORT: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-01-09,-42.10,USD,WHOLE FOODS
+";
+
+#[test]
+fn promoting_a_dedupe_candidate_inserts_it_once() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        import_file(&home, "first.csv", FIRST_IMPORT);
+        let second_id = import_file(&home, "second.csv", SECOND_IMPORT);
+        assert_eq!(import_counts(&db_path, &second_id), Some((1, 1)));
+
+        let listed = to_data(import::duplicates_with_options(
+            &second_id,
+            duplicates_options(&home),
+        ));
+        let candidate_id = listed["rows"][0]["candidate_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        assert!(candidate_id.starts_with("cand_"));
+        assert!(listed["rows"][0].get("promoted_txn_id").is_none());
+
+        let promoted = to_data(import::duplicates_promote_with_options(
+            &candidate_id,
+            duplicates_options(&home),
+        ));
+        assert_eq!(promoted["import_id"], second_id.as_str());
+        assert_eq!(import_counts(&db_path, &second_id), Some((2, 0)));
+        let coffee_rows = ledger_rows(&db_path)
+            .into_iter()
+            .filter(|(_, label)| label == "2026-01-03 BLUE BOTTLE COFFEE")
+            .count();
+        assert_eq!(coffee_rows, 2);
+
+        let relisted = to_data(import::duplicates_with_options(
+            &second_id,
+            duplicates_options(&home),
+        ));
+        assert_eq!(
+            relisted["rows"][0]["promoted_txn_id"],
+            promoted["promoted_txn_id"]
+        );
+
+        let again =
+            import::duplicates_promote_with_options(&candidate_id, duplicates_options(&home));
+        assert!(again.is_err());
+        if let Err(error) = again {
+            assert_eq!(error.code, "dedupe_candidate_already_promoted");
+        }
+
+        let missing =
+            import::duplicates_promote_with_options("cand_missing", duplicates_options(&home));
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "dedupe_candidate_not_found");
+        }
+    }
+}
+
+#[test]
+fn promoting_a_candidate_from_an_undone_slice_is_refused() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "first.csv", FIRST_IMPORT);
+        let body = format!("{SECOND_IMPORT}amex_gold_9876,2026-01-07,-12.00,USD,LYFT\n");
+        let second_id = import_file(&home, "second.csv", &body);
+
+        let listed = to_data(import::duplicates_with_options(
+            &second_id,
+            duplicates_options(&home),
+        ));
+        let candidate_id = listed["rows"][0]["candidate_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        assert!(candidate_id.starts_with("cand_"));
+
+        let undone = import::undo_with_options(
+            &second_id,
+            ImportUndoOptions {
+                account_key: Some("chase_checking_1234".to_string()),
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        );
+        assert!(undone.is_ok());
+
+        let promoted =
+            import::duplicates_promote_with_options(&candidate_id, duplicates_options(&home));
+        assert!(promoted.is_err());
+        if let Err(error) = promoted {
+            assert_eq!(error.code, "dedupe_candidate_invalidated");
+            let data = error.data.unwrap_or(Value::Null);
+            assert_eq!(data["candidate_id"], candidate_id.as_str());
+            assert_eq!(data["import_id"], second_id.as_str());
+        }
+    }
+}
+
+#[test]
+fn marked_duplicates_leave_the_ledger_and_return_when_the_kept_row_is_undone() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first_id = import_file(&home, "first.csv", FIRST_IMPORT);
+        let second_id = import_file(&home, "second.csv", SECOND_IMPORT);
+
+        let rows = ledger_rows(&db_path);
+        let txn_for = |label: &str| {
+            rows.iter()
+                .find(|(_, row_label)| row_label == label)
+                .map(|(txn_id, _)| txn_id.clone())
+                .unwrap_or_default()
+        };
+        let kept = txn_for("2026-01-05 WHOLE FOODS MKT");
+        let duplicate = txn_for("2026-01-09 WHOLE FOODS");
+
+        let marked = to_data(transactions::mark_duplicate_with_options(
+            TransactionMarkDuplicateOptions {
+                txn_id: duplicate.clone(),
+                duplicate_of: kept.clone(),
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(marked["import_id"], second_id.as_str());
+        assert_eq!(marked["duplicate_of_import_id"], first_id.as_str());
+        assert_eq!(import_counts(&db_path, &second_id), Some((0, 2)));
+        assert!(
+            ledger_rows(&db_path)
+                .iter()
+                .all(|(txn_id, _)| txn_id != &duplicate)
+        );
+
+        let undone = to_data(import::undo_with_options(
+            &first_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(undone["summary"]["rows_promoted"], 2);
+        let labels = ledger_rows(&db_path)
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![
+                "2026-01-03 BLUE BOTTLE COFFEE".to_string(),
+                "2026-01-09 WHOLE FOODS".to_string(),
+            ]
+        );
+    }
+}
+
+#[test]
+fn mark_duplicate_rejects_self_and_cross_account_pairs() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        import_file(
+            &home,
+            "accounts.csv",
+            "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+amex_gold_1001,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE
+",
+        );
+        let rows = ledger_rows(&db_path);
+        assert_eq!(rows.len(), 2);
+        let first = rows.first().map(|(id, _)| id.clone()).unwrap_or_default();
+        let second = rows.get(1).map(|(id, _)| id.clone()).unwrap_or_default();
+
+        let mark = |txn_id: &str, duplicate_of: &str| {
+            transactions::mark_duplicate_with_options(TransactionMarkDuplicateOptions {
+                txn_id: txn_id.to_string(),
+                duplicate_of: duplicate_of.to_string(),
+                home_override: Some(&home),
+            })
+        };
+
+        let self_mark = mark(&first, &first);
+        assert!(self_mark.is_err());
+        if let Err(error) = self_mark {
+            assert_eq!(error.code, "invalid_argument");
+        }
+
+        let cross_account = mark(&first, &second);
+        assert!(cross_account.is_err());
+        if let Err(error) = cross_account {
+            assert_eq!(error.code, "invalid_argument");
+        }
+
+        let missing = mark("txn_missing", &first);
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "transaction_not_found");
+        }
+        assert_eq!(ledger_rows(&db_path).len(), 2);
+    }
+}
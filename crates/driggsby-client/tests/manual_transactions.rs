@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{self, ImportRedoOptions, ImportRunOptions};
+use driggsby_client::commands::sql::{self, SqlQueryOptions};
+use driggsby_client::commands::transactions::{
+    self, TransactionAddOptions, TransactionDeleteOptions, TransactionEditOptions,
+    TransactionShowOptions,
+};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> Value {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }))
+}
+
+fn add(
+    home: &Path,
+    posted_at: &str,
+    amount: &str,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    transactions::add_with_options(TransactionAddOptions {
+        account_key: "chase_checking_1234".to_string(),
+        posted_at: posted_at.to_string(),
+        amount: amount.to_string(),
+        currency: "USD".to_string(),
+        description: "FARMERS MARKET".to_string(),
+        merchant: None,
+        category: Some("Groceries".to_string()),
+        home_override: Some(home),
+    })
+}
+
+fn edit_amount(
+    home: &Path,
+    txn_id: &str,
+    amount: &str,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    transactions::edit_with_options(TransactionEditOptions {
+        txn_id: txn_id.to_string(),
+        amount: Some(amount.to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    })
+}
+
+fn query_rows(home: &Path, query: &str) -> Value {
+    let data = to_data(sql::run_with_options(SqlQueryOptions {
+        query: Some(query.to_string()),
+        file: None,
+        home_override: Some(home),
+        stdin_override: None,
+        max_rows: None,
+    }));
+    data["rows"].clone()
+}
+
+const LEDGER: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-03-14,-12.50,USD,FARMERS MARKET
+";
+
+#[test]
+fn manual_entries_are_imports_that_file_imports_do_not_dedupe_against() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let invalid = add(&home, "2026-13-40", "-12.50");
+        assert!(invalid.is_err());
+        if let Err(error) = invalid {
+            assert_eq!(error.code, "invalid_argument");
+        }
+
+        let added = to_data(add(&home, "2026-03-14", "-12.50"));
+        let txn_id = added["txn_id"].as_str().unwrap_or_default().to_string();
+        assert!(txn_id.starts_with("txn_"));
+        assert_eq!(added["amount"], json!(-12.5));
+        assert_eq!(added["effective_category"], json!("Groceries"));
+
+        let shown = to_data(transactions::show_with_options(TransactionShowOptions {
+            txn_id: txn_id.clone(),
+            home_override: Some(&home),
+        }));
+        assert_eq!(shown["provenance"]["source_kind"], json!("manual"));
+        assert_eq!(shown["provenance"]["import_id"], added["import_id"]);
+
+        let imported = import_file(&home, "ledger.csv", LEDGER);
+        assert_eq!(imported["summary"]["inserted"], json!(1));
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT COUNT(*) FROM v1_transactions WHERE description = 'FARMERS MARKET'"
+            ),
+            json!([[2]])
+        );
+    }
+}
+
+#[test]
+fn only_manual_entries_accept_value_edits_and_deletes_can_be_redone() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", LEDGER);
+        let imported_id = query_rows(&home, "SELECT txn_id FROM v1_transactions")[0][0]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let refused = edit_amount(&home, &imported_id, "-13.00");
+        assert!(refused.is_err());
+        if let Err(error) = refused {
+            assert_eq!(error.code, "transaction_not_manual");
+        }
+
+        let added = to_data(add(&home, "2026-03-15", "-40.00"));
+        let txn_id = added["txn_id"].as_str().unwrap_or_default().to_string();
+        let edited = to_data(edit_amount(&home, &txn_id, "-42.25"));
+        assert_eq!(edited["amount"], json!(-42.25));
+        assert_eq!(edited["intelligence_refreshed"], json!(true));
+
+        let deleted = to_data(transactions::delete_with_options(
+            TransactionDeleteOptions {
+                txn_id: txn_id.clone(),
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(deleted["import_id"], added["import_id"]);
+        let count_query = "SELECT COUNT(*) FROM v1_transactions";
+        assert_eq!(query_rows(&home, count_query), json!([[1]]));
+
+        to_data(import::redo_with_options(
+            added["import_id"].as_str().unwrap_or_default(),
+            ImportRedoOptions {
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT posted_at, amount FROM v1_transactions WHERE posted_at = '2026-03-15'"
+            ),
+            json!([["2026-03-15", -42.25]])
+        );
+    }
+}
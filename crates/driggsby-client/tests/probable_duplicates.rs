@@ -0,0 +1,268 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportDuplicatesOptions, ImportRunOptions, ImportUndoOptions,
+};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str, dry_run: bool) -> Value {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        dry_run,
+        home_override: Some(home),
+        ..Default::default()
+    }))
+}
+
+fn ledger_descriptions(db_path: &Path) -> Vec<String> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(
+            "SELECT posted_at || ' ' || description FROM v1_transactions
+             ORDER BY posted_at ASC, description ASC",
+        )
+        && let Ok(mapped) = statement.query_map([], |row| row.get(0))
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+fn import_counts(db_path: &Path, import_id: &str) -> Option<(i64, i64)> {
+    let connection = Connection::open(db_path).ok()?;
+    connection
+        .query_row(
+            "SELECT inserted, deduped FROM internal_import_runs WHERE import_id = ?1",
+            [import_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .ok()
+}
+
+const FIRST_IMPORT: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-03,-15.49,USD,NETFLIX.COM
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MKT
+";
+
+const SECOND_IMPORT: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-01-04,-15.49,USD,NETFLIX.COM
+chase_checking_1234,2026-01-05,-42.10,USD,WHOLE FOODS MARKET #123
+chase_checking_1234,2026-01-05,-15.49,USD,SHELL OIL
+";
+
+#[test]
+fn near_matches_are_queued_and_can_be_accepted_rejected_and_undone() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first = import_file(&home, "first.csv", FIRST_IMPORT, false);
+        let first_id = first["import_id"].as_str().unwrap_or_default().to_string();
+        assert!(first.get("probable_duplicates").is_none());
+
+        let preview = import_file(&home, "second.csv", SECOND_IMPORT, true);
+        let previewed = preview["probable_duplicates"].as_array().cloned();
+        assert_eq!(previewed.as_ref().map(Vec::len), Some(2));
+        if let Some(previewed) = previewed {
+            assert!(previewed[0].get("probable_id").is_none());
+            assert_eq!(previewed[0]["source_row_index"], 1);
+            assert_eq!(previewed[0]["date_distance_days"], 1);
+            assert_eq!(previewed[1]["source_row_index"], 2);
+            assert_eq!(previewed[1]["matched_description"], "WHOLE FOODS MKT");
+        }
+
+        let second = import_file(&home, "second.csv", SECOND_IMPORT, false);
+        let second_id = second["import_id"].as_str().unwrap_or_default().to_string();
+        assert_eq!(second["summary"]["inserted"], 3);
+        assert_eq!(ledger_descriptions(&db_path).len(), 5);
+
+        let listed = to_data(import::duplicates_with_options(
+            &second_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        let probables = listed["probable_duplicates"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(probables.len(), 2);
+        let netflix_id = probables[0]["probable_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let whole_foods_id = probables[1]["probable_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        assert_eq!(probables[0]["status"], "pending");
+
+        let accepted = to_data(import::duplicates_accept_with_options(
+            &netflix_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(accepted["probable_duplicate"]["status"], "accepted");
+        assert_eq!(import_counts(&db_path, &second_id), Some((2, 1)));
+        assert_eq!(
+            ledger_descriptions(&db_path),
+            vec![
+                "2026-01-03 NETFLIX.COM".to_string(),
+                "2026-01-05 SHELL OIL".to_string(),
+                "2026-01-05 WHOLE FOODS MARKET #123".to_string(),
+                "2026-01-05 WHOLE FOODS MKT".to_string(),
+            ]
+        );
+
+        let again = import::duplicates_accept_with_options(
+            &netflix_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        );
+        assert!(again.is_err());
+        if let Err(error) = again {
+            assert_eq!(error.code, "probable_duplicate_already_resolved");
+        }
+
+        let rejected = to_data(import::duplicates_reject_with_options(
+            &whole_foods_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(rejected["probable_duplicate"]["status"], "rejected");
+        assert_eq!(ledger_descriptions(&db_path).len(), 4);
+
+        let undone = to_data(import::undo_with_options(
+            &first_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(undone["summary"]["rows_reverted"], 2);
+        assert_eq!(undone["summary"]["rows_promoted"], 1);
+        assert_eq!(
+            ledger_descriptions(&db_path),
+            vec![
+                "2026-01-04 NETFLIX.COM".to_string(),
+                "2026-01-05 SHELL OIL".to_string(),
+                "2026-01-05 WHOLE FOODS MARKET #123".to_string(),
+            ]
+        );
+
+        let after_undo = to_data(import::duplicates_with_options(
+            &second_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(
+            after_undo["probable_duplicates"].as_array().map(Vec::len),
+            Some(0)
+        );
+    }
+}
+
+#[test]
+fn accepting_a_probable_duplicate_without_its_ledger_row_fails() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        import_file(&home, "first.csv", FIRST_IMPORT, false);
+        let second = import_file(&home, "second.csv", SECOND_IMPORT, false);
+        let second_id = second["import_id"].as_str().unwrap_or_default().to_string();
+
+        let listed = to_data(import::duplicates_with_options(
+            &second_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        let probable_id = listed["probable_duplicates"][0]["probable_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let txn_id = listed["probable_duplicates"][0]["txn_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let connection = Connection::open(&db_path);
+        assert!(connection.is_ok());
+        if let Ok(connection) = connection {
+            let deleted = connection.execute(
+                "DELETE FROM internal_transactions WHERE txn_id = ?1",
+                [&txn_id],
+            );
+            assert_eq!(deleted.ok(), Some(1));
+        }
+
+        let accepted = import::duplicates_accept_with_options(
+            &probable_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        );
+        assert!(accepted.is_err());
+        if let Err(error) = accepted {
+            assert_eq!(error.code, "probable_duplicate_row_missing");
+            let data = error.data.unwrap_or(Value::Null);
+            assert_eq!(data["probable_id"], probable_id.as_str());
+            assert_eq!(data["txn_id"], txn_id.as_str());
+        }
+
+        let rejected = to_data(import::duplicates_reject_with_options(
+            &probable_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(rejected["probable_duplicate"]["status"], "rejected");
+    }
+}
+
+#[test]
+fn resolving_an_unknown_probable_duplicate_fails() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let result = import::duplicates_reject_with_options(
+            "prob_missing",
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        );
+        assert!(result.is_err());
+        if let Err(error) = result {
+            assert_eq!(error.code, "probable_duplicate_not_found");
+        }
+    }
+}
@@ -19,6 +19,7 @@ fn recurring_rejects_invalid_date_ranges_with_invalid_argument() {
         let result = recurring::run_with_options(RecurringRunOptions {
             from: Some("2026-03-01".to_string()),
             to: Some("2026-02-01".to_string()),
+            tag: None,
             home_override: Some(&home),
         });
         assert!(result.is_err());
@@ -37,6 +38,7 @@ fn anomalies_reject_invalid_calendar_dates_using_shared_validation() {
         let result = anomalies::run_with_options(AnomaliesRunOptions {
             from: Some("2026-02-31".to_string()),
             to: None,
+            tag: None,
             home_override: Some(&home),
         });
         assert!(result.is_err());
@@ -368,6 +370,7 @@ fn anomalies_reads_materialized_rows_and_filters_on_posted_at() {
         let anomalies_result = anomalies::run_with_options(AnomaliesRunOptions {
             from: None,
             to: None,
+            tag: None,
             home_override: Some(&home),
         });
         assert!(anomalies_result.is_ok());
@@ -391,6 +394,7 @@ fn anomalies_reads_materialized_rows_and_filters_on_posted_at() {
         let filtered_result = anomalies::run_with_options(AnomaliesRunOptions {
             from: Some("2026-03-11".to_string()),
             to: None,
+            tag: None,
             home_override: Some(&home),
         });
         assert!(filtered_result.is_ok());
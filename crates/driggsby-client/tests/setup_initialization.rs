@@ -218,7 +218,7 @@ fn pending_migration_applies_exactly_once() {
             assert!(connection.is_ok());
             if let Ok(conn) = connection {
                 let first_version = user_version(&conn);
-                assert_eq!(first_version, Some(6));
+                assert_eq!(first_version, Some(25));
             }
         }
 
@@ -229,7 +229,7 @@ fn pending_migration_applies_exactly_once() {
             assert!(connection.is_ok());
             if let Ok(conn) = connection {
                 let second_version = user_version(&conn);
-                assert_eq!(second_version, Some(6));
+                assert_eq!(second_version, Some(25));
             }
         }
     }
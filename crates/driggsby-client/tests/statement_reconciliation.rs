@@ -0,0 +1,354 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import;
+use driggsby_client::commands::import::{ImportRunOptions, ImportUndoOptions};
+use driggsby_client::contracts::envelope::failure_from_error;
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn write_file(path: &Path, body: &str) {
+    let result = fs::write(path, body);
+    assert!(result.is_ok());
+}
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    Ok((dir, home))
+}
+
+fn run_import(
+    home: &Path,
+    path: &Path,
+    dry_run: bool,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    import::run_with_options(ImportRunOptions {
+        path: Some(path.display().to_string()),
+        dry_run,
+        home_override: Some(home),
+        ..Default::default()
+    })
+}
+
+fn query_count(db_path: &Path, sql: &str) -> i64 {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection {
+        let value = conn.query_row(sql, [], |row| row.get::<_, i64>(0));
+        assert!(value.is_ok());
+        if let Ok(count) = value {
+            return count;
+        }
+    }
+    0
+}
+
+fn validation_issues(
+    result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>,
+) -> Value {
+    assert!(result.is_err());
+    if let Err(error) = result {
+        assert_eq!(error.code, "import_validation_failed");
+        let envelope = failure_from_error(&error);
+        if let Ok(value) = serde_json::to_value(envelope) {
+            return value["error"]["data"]["issues"].clone();
+        }
+    }
+    Value::Null
+}
+
+#[test]
+fn per_row_balances_reconcile_and_populate_statements_view() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+        let source_path = home.join("balanced.csv");
+        write_file(
+            &source_path,
+            "statement_id,account_key,posted_at,amount,currency,description,statement_opening_balance,statement_closing_balance
+acct_stmt_1_2026-01-31,acct_stmt_1,2026-01-03,-42.15,USD,GROCERY,1000.00,1207.75
+acct_stmt_1_2026-01-31,acct_stmt_1,2026-01-15,250.00,USD,PAYROLL,1000.00,1207.75
+acct_stmt_1_2026-01-31,acct_stmt_1,2026-01-20,-0.10,USD,FEE,,
+",
+        );
+
+        let dry_run = run_import(&home, &source_path, true);
+        assert!(dry_run.is_ok());
+        if let Ok(success) = dry_run {
+            let payload = serde_json::to_value(success);
+            assert!(payload.is_ok());
+            if let Ok(value) = payload {
+                let statements = &value["data"]["reconciled_statements"];
+                assert_eq!(statements.as_array().map(Vec::len), Some(1));
+                assert_eq!(statements[0]["statement_id"], "acct_stmt_1_2026-01-31");
+                assert_eq!(statements[0]["txn_count"], 3);
+                assert_eq!(statements[0]["net_amount"], 207.75);
+            }
+        }
+
+        let db_path = home.join("ledger.db");
+        assert_eq!(
+            query_count(&db_path, "SELECT COUNT(*) FROM v1_statements"),
+            0
+        );
+
+        let committed = run_import(&home, &source_path, false);
+        assert!(committed.is_ok());
+        assert_eq!(
+            query_count(
+                &db_path,
+                "SELECT COUNT(*) FROM v1_statements
+                 WHERE account_key = 'acct_stmt_1'
+                   AND opening_balance = 1000.0
+                   AND closing_balance = 1207.75
+                   AND first_posted_at = '2026-01-03'
+                   AND last_posted_at = '2026-01-20'"
+            ),
+            1
+        );
+
+        if let Ok(success) = committed
+            && let Ok(value) = serde_json::to_value(success)
+            && let Some(import_id) = value["data"]["import_id"].as_str()
+        {
+            let undo = import::undo_with_options(
+                import_id,
+                ImportUndoOptions {
+                    home_override: Some(&home),
+                    ..Default::default()
+                },
+            );
+            assert!(undo.is_ok());
+            assert_eq!(
+                query_count(&db_path, "SELECT COUNT(*) FROM v1_statements"),
+                0
+            );
+        }
+    }
+}
+
+#[test]
+fn dropped_row_fails_validation_with_precise_mismatch_issue() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+        let source_path = home.join("dropped-row.json");
+        write_file(
+            &source_path,
+            r#"{
+  "statements": [
+    {"statement_id":"acct_stmt_2_2026-02-28","opening_balance":500.00,"closing_balance":380.00}
+  ],
+  "transactions": [
+    {"statement_id":"acct_stmt_2_2026-02-28","account_key":"acct_stmt_2","posted_at":"2026-02-02","amount":-100.00,"currency":"USD","description":"RENT SHARE"},
+    {"statement_id":"acct_stmt_2_2026-02-28","account_key":"acct_stmt_2","posted_at":"2026-02-09","amount":-15.00,"currency":"USD","description":"LUNCH"}
+  ]
+}"#,
+        );
+
+        let issues = validation_issues(run_import(&home, &source_path, true));
+        assert_eq!(issues.as_array().map(Vec::len), Some(1));
+        assert_eq!(issues[0]["row"], 1);
+        assert_eq!(issues[0]["field"], "statement_closing_balance");
+        assert_eq!(issues[0]["code"], "statement_balance_mismatch");
+        assert_eq!(issues[0]["expected"], "380.00");
+        assert_eq!(issues[0]["received"], "385.00");
+        assert!(
+            issues[0]["description"]
+                .as_str()
+                .is_some_and(|text| text.contains("off by -5.00"))
+        );
+
+        let db_path = home.join("ledger.db");
+        assert_eq!(
+            query_count(&db_path, "SELECT COUNT(*) FROM internal_transactions"),
+            0
+        );
+    }
+}
+
+#[test]
+fn sidecar_header_balances_reconcile_json_envelope() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+        let source_path = home.join("sidecar.json");
+        write_file(
+            &source_path,
+            r#"{
+  "statements": [
+    {"statement_id":"acct_stmt_3_2026-03-31","account_key":"acct_stmt_3","opening_balance":-250.50,"closing_balance":-300.75}
+  ],
+  "transactions": [
+    {"statement_id":"acct_stmt_3_2026-03-31","account_key":"acct_stmt_3","posted_at":"2026-03-04","amount":-75.25,"currency":"USD","description":"FUEL"},
+    {"statement_id":"acct_stmt_3_2026-03-31","account_key":"acct_stmt_3","posted_at":"2026-03-18","amount":25.00,"currency":"USD","description":"CASHBACK"}
+  ]
+}"#,
+        );
+
+        let committed = run_import(&home, &source_path, false);
+        assert!(committed.is_ok());
+        let db_path = home.join("ledger.db");
+        assert_eq!(
+            query_count(
+                &db_path,
+                "SELECT COUNT(*) FROM v1_statements WHERE statement_id = 'acct_stmt_3_2026-03-31' AND net_amount = -50.25"
+            ),
+            1
+        );
+    }
+}
+
+#[test]
+fn inconsistent_and_orphaned_balances_are_rejected() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+        let source_path = home.join("inconsistent.json");
+        write_file(
+            &source_path,
+            r#"{
+  "statements": [
+    {"statement_id":"missing_statement","opening_balance":0,"closing_balance":0}
+  ],
+  "transactions": [
+    {"statement_id":"acct_stmt_4_2026-04-30","account_key":"acct_stmt_4","posted_at":"2026-04-01","amount":-1.00,"currency":"USD","description":"A","statement_opening_balance":10,"statement_closing_balance":8},
+    {"statement_id":"acct_stmt_4_2026-04-30","account_key":"acct_stmt_4","posted_at":"2026-04-02","amount":-1.00,"currency":"USD","description":"B","statement_opening_balance":10,"statement_closing_balance":9},
+    {"account_key":"acct_stmt_4","posted_at":"2026-04-03","amount":-1.00,"currency":"USD","description":"C","statement_opening_balance":10},
+    {"statement_id":"acct_stmt_4_2026-04-30","account_key":"acct_stmt_4","posted_at":"2026-04-04","amount":-1.00,"currency":"USD","description":"D","statement_opening_balance":10}
+  ]
+}"#,
+        );
+
+        let issues = validation_issues(run_import(&home, &source_path, true));
+        let codes = issues
+            .as_array()
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|issue| issue["code"].as_str())
+                    .collect::<Vec<&str>>()
+            })
+            .unwrap_or_default();
+        assert_eq!(
+            codes,
+            vec![
+                "statement_balance_conflict",
+                "statement_balance_requires_statement_id",
+                "statement_balance_incomplete",
+                "statement_header_unmatched",
+            ]
+        );
+    }
+}
+
+#[test]
+fn running_balances_are_stored_and_gaps_reported_without_rejecting() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+        let source_path = home.join("running-balance.csv");
+        write_file(
+            &source_path,
+            "account_key,posted_at,amount,currency,description,balance
+acct_bal_1,2026-05-09,-20.00,USD,DINNER,415.00
+acct_bal_1,2026-05-09,-20.00,USD,DINNER,415.00
+acct_bal_1,2026-05-04,-50.00,USD,GAS,435.00
+acct_bal_1,2026-05-01,500.00,USD,PAYROLL,500.00
+",
+        );
+
+        let dry_run = run_import(&home, &source_path, true);
+        assert!(dry_run.is_ok());
+        if let Ok(success) = dry_run
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            let gaps = &value["data"]["balance_gaps"];
+            assert_eq!(gaps.as_array().map(Vec::len), Some(2));
+            assert_eq!(gaps[0]["row"], 1);
+            assert_eq!(gaps[0]["previous_row"], 2);
+            assert_eq!(gaps[0]["likely_cause"], "duplicated_transaction");
+            assert_eq!(gaps[1]["row"], 3);
+            assert_eq!(gaps[1]["previous_row"], 4);
+            assert_eq!(gaps[1]["expected_balance"], 450.0);
+            assert_eq!(gaps[1]["difference"], -15.0);
+            assert_eq!(gaps[1]["likely_cause"], "missing_transaction");
+        }
+
+        let committed = run_import(&home, &source_path, false);
+        assert!(committed.is_ok());
+        let db_path = home.join("ledger.db");
+        assert_eq!(
+            query_count(
+                &db_path,
+                "SELECT COUNT(*) FROM v1_transactions WHERE account_key = 'acct_bal_1' AND balance IS NOT NULL"
+            ),
+            4
+        );
+        if let Ok(success) = committed
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            let accounts = &value["data"]["ledger_accounts"]["rows"];
+            assert_eq!(accounts[0]["latest_balance"], 415.0);
+            assert_eq!(accounts[0]["latest_balance_at"], "2026-05-09");
+        }
+    }
+}
+
+#[test]
+fn latest_balance_follows_file_order_within_the_last_day() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let create_home = fs::create_dir_all(&home);
+        assert!(create_home.is_ok());
+        let newest_first = home.join("newest-first.csv");
+        write_file(
+            &newest_first,
+            "account_key,posted_at,amount,currency,description,balance
+acct_bal_new,2026-06-10,-5.00,USD,COFFEE,480.00
+acct_bal_new,2026-06-10,-15.00,USD,LUNCH,485.00
+acct_bal_new,2026-06-10,-30.00,USD,GAS,500.00
+acct_bal_new,2026-06-01,530.00,USD,PAYROLL,530.00
+",
+        );
+        assert!(run_import(&home, &newest_first, false).is_ok());
+
+        let oldest_first = home.join("oldest-first.csv");
+        write_file(
+            &oldest_first,
+            "account_key,posted_at,amount,currency,description,balance
+acct_bal_old,2026-06-01,530.00,USD,PAYROLL,530.00
+acct_bal_old,2026-06-10,-30.00,USD,GAS,500.00
+acct_bal_old,2026-06-10,-15.00,USD,LUNCH,485.00
+acct_bal_old,2026-06-10,-5.00,USD,COFFEE,480.00
+",
+        );
+        assert!(run_import(&home, &oldest_first, false).is_ok());
+
+        let accounts = driggsby_client::commands::accounts::run_with_home_override(Some(&home));
+        assert!(accounts.is_ok());
+        if let Ok(success) = accounts
+            && let Ok(value) = serde_json::to_value(success)
+        {
+            let rows = &value["data"]["rows"];
+            assert_eq!(rows.as_array().map(Vec::len), Some(2));
+            for row in rows.as_array().into_iter().flatten() {
+                assert_eq!(row["latest_balance"], 480.0);
+                assert_eq!(row["latest_balance_at"], "2026-06-10");
+            }
+        }
+    }
+}
@@ -27,6 +27,7 @@ pub fn import_rows(home: &Path, rows: &[Value]) {
                 dry_run: false,
                 home_override: Some(home),
                 stdin_override: None,
+                ..Default::default()
             });
             assert!(result.is_ok());
         }
@@ -37,6 +38,7 @@ pub fn recurring_payload(home: &Path, from: Option<&str>, to: Option<&str>) -> V
     let result = recurring::run_with_options(RecurringRunOptions {
         from: from.map(std::string::ToString::to_string),
         to: to.map(std::string::ToString::to_string),
+        tag: None,
         home_override: Some(home),
     });
     assert!(result.is_ok());
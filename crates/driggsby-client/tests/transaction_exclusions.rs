@@ -0,0 +1,245 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{self, ImportRunOptions, ImportUndoOptions};
+use driggsby_client::commands::recurring::{self, RecurringRunOptions};
+use driggsby_client::commands::sql::{self, SqlQueryOptions};
+use driggsby_client::commands::transactions::{
+    self, TransactionExcludeOptions, TransactionIncludeOptions, TransactionShowOptions,
+};
+use rusqlite::Connection;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> Value {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }))
+}
+
+fn txn_id_for(db_path: &Path, posted_at: &str, description: &str) -> String {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection
+        && let Ok(txn_id) = conn.query_row(
+            "SELECT txn_id FROM v1_transactions WHERE posted_at = ?1 AND description = ?2",
+            [posted_at, description],
+            |row| row.get::<_, String>(0),
+        )
+    {
+        return txn_id;
+    }
+    String::new()
+}
+
+fn exclude(
+    home: &Path,
+    txn_id: &str,
+    reason: &str,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    transactions::exclude_with_options(TransactionExcludeOptions {
+        txn_id: txn_id.to_string(),
+        reason: reason.to_string(),
+        home_override: Some(home),
+    })
+}
+
+fn include(home: &Path, txn_id: &str) -> Value {
+    to_data(transactions::include_with_options(
+        TransactionIncludeOptions {
+            txn_id: txn_id.to_string(),
+            home_override: Some(home),
+        },
+    ))
+}
+
+fn query_rows(home: &Path, query: &str) -> Value {
+    let data = to_data(sql::run_with_options(SqlQueryOptions {
+        query: Some(query.to_string()),
+        file: None,
+        home_override: Some(home),
+        stdin_override: None,
+        max_rows: None,
+    }));
+    data["rows"].clone()
+}
+
+fn recurring_merchants(home: &Path) -> Vec<String> {
+    let data = to_data(recurring::run_with_options(RecurringRunOptions {
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["rows"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|row| row["merchant"].as_str().map(str::to_string))
+        .collect()
+}
+
+const ROLLUP_QUERY: &str = "SELECT month, category, txn_count, amount
+     FROM v1_category_rollup
+     ORDER BY month, category";
+
+const LEDGER: &str = "account_key,posted_at,amount,currency,description,merchant,category
+chase_checking_1234,2026-01-05,-15.99,USD,NETFLIX.COM,Netflix,Entertainment
+chase_checking_1234,2026-02-05,-15.99,USD,NETFLIX.COM,Netflix,Entertainment
+chase_checking_1234,2026-03-05,-15.99,USD,NETFLIX.COM,Netflix,Entertainment
+chase_checking_1234,2026-01-20,-500.00,USD,TRANSFER TO SAVINGS,,Transfers
+";
+
+#[test]
+fn excluded_rows_stay_visible_but_leave_intelligence_and_rollups() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        import_file(&home, "ledger.csv", LEDGER);
+        let transfer_id = txn_id_for(&db_path, "2026-01-20", "TRANSFER TO SAVINGS");
+        let netflix_id = txn_id_for(&db_path, "2026-02-05", "NETFLIX.COM");
+        assert_eq!(recurring_merchants(&home), vec!["NETFLIX".to_string()]);
+
+        assert!(exclude(&home, &transfer_id, "  ").is_err());
+        let missing = exclude(&home, "txn_missing", "transfer");
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "transaction_not_found");
+        }
+
+        let excluded = to_data(exclude(&home, &transfer_id, "Transfer"));
+        assert_eq!(excluded["excluded"], json!(true));
+        assert_eq!(excluded["exclusion"]["reason"], json!("transfer"));
+        assert_eq!(excluded["intelligence_refreshed"], json!(true));
+        to_data(exclude(&home, &netflix_id, "reimbursed"));
+
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT description, excluded, exclusion_reason FROM v1_transactions
+                 ORDER BY posted_at"
+            ),
+            json!([
+                ["NETFLIX.COM", 0, null],
+                ["TRANSFER TO SAVINGS", 1, "transfer"],
+                ["NETFLIX.COM", 1, "reimbursed"],
+                ["NETFLIX.COM", 0, null]
+            ])
+        );
+        assert_eq!(
+            query_rows(&home, ROLLUP_QUERY),
+            json!([
+                ["2026-01", "Entertainment", 1, -15.99],
+                ["2026-03", "Entertainment", 1, -15.99]
+            ])
+        );
+        assert!(recurring_merchants(&home).is_empty());
+
+        let shown = to_data(transactions::show_with_options(TransactionShowOptions {
+            txn_id: transfer_id.clone(),
+            home_override: Some(&home),
+        }));
+        assert_eq!(shown["exclusion"]["reason"], json!("transfer"));
+
+        let restored = include(&home, &netflix_id);
+        assert_eq!(restored["excluded"], json!(false));
+        assert_eq!(restored["intelligence_refreshed"], json!(true));
+        assert_eq!(
+            include(&home, &netflix_id)["intelligence_refreshed"],
+            json!(false)
+        );
+        assert_eq!(recurring_merchants(&home), vec!["NETFLIX".to_string()]);
+    }
+}
+
+#[test]
+fn exclusions_come_back_when_the_row_is_imported_again() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first = import_file(&home, "ledger.csv", LEDGER);
+        let transfer_id = txn_id_for(&db_path, "2026-01-20", "TRANSFER TO SAVINGS");
+        to_data(exclude(&home, &transfer_id, "transfer"));
+
+        to_data(import::undo_with_options(
+            first["import_id"].as_str().unwrap_or_default(),
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        import_file(&home, "ledger-again.csv", LEDGER);
+
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT description, exclusion_reason FROM v1_transactions WHERE excluded = 1"
+            ),
+            json!([["TRANSFER TO SAVINGS", "transfer"]])
+        );
+    }
+}
+
+const DOUBLE_CHARGE: &str = "account_key,posted_at,amount,currency,description,merchant,category
+chase_checking_1234,2026-01-05,-15.99,USD,NETFLIX.COM,Netflix,Entertainment
+chase_checking_1234,2026-02-05,-15.99,USD,NETFLIX.COM,Netflix,Entertainment
+chase_checking_1234,2026-03-05,-15.99,USD,NETFLIX.COM,Netflix,Entertainment
+chase_checking_1234,2026-03-05,-15.99,USD,NETFLIX.COM,Netflix,Entertainment
+";
+
+#[test]
+fn excluding_one_of_two_identical_rows_keeps_the_other() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", DOUBLE_CHARGE);
+        let twins = query_rows(
+            &home,
+            "SELECT txn_id FROM v1_transactions WHERE posted_at = '2026-03-05' ORDER BY txn_id",
+        );
+        let duplicate_id = twins[1][0].as_str().unwrap_or_default().to_string();
+        to_data(exclude(&home, &duplicate_id, "double charge"));
+
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT excluded, COUNT(*) FROM v1_transactions
+                 WHERE posted_at = '2026-03-05'
+                 GROUP BY excluded
+                 ORDER BY excluded"
+            ),
+            json!([[0, 1], [1, 1]])
+        );
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT txn_count, amount FROM v1_category_rollup WHERE month = '2026-03'"
+            ),
+            json!([[1, -15.99]])
+        );
+        assert_eq!(recurring_merchants(&home), vec!["NETFLIX".to_string()]);
+    }
+}
@@ -0,0 +1,360 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportReplaceOptions, ImportRunOptions, ImportUndoOptions,
+};
+use driggsby_client::commands::recurring::{self, RecurringRunOptions};
+use driggsby_client::commands::transactions::{self, TransactionEditOptions};
+use rusqlite::Connection;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn write_source(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    source.display().to_string()
+}
+
+fn txn_id_for(db_path: &Path, posted_at: &str) -> String {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection
+        && let Ok(txn_id) = conn.query_row(
+            "SELECT txn_id FROM v1_transactions WHERE posted_at = ?1",
+            [posted_at],
+            |row| row.get::<_, String>(0),
+        )
+    {
+        return txn_id;
+    }
+    String::new()
+}
+
+fn view_row(db_path: &Path, posted_at: &str) -> Vec<Option<String>> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection
+        && let Ok(row) = conn.query_row(
+            "SELECT merchant, category, effective_merchant, effective_category, note
+             FROM v1_transactions
+             WHERE posted_at = ?1",
+            [posted_at],
+            |row| {
+                (0..5)
+                    .map(|index| row.get::<_, Option<String>>(index))
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            },
+        )
+    {
+        return row;
+    }
+    Vec::new()
+}
+
+fn edit(home: &Path, txn_id: &str, merchant: Option<&str>, category: Option<&str>) -> Value {
+    to_data(transactions::edit_with_options(TransactionEditOptions {
+        txn_id: txn_id.to_string(),
+        merchant: merchant.map(str::to_string),
+        category: category.map(str::to_string),
+        home_override: Some(home),
+        ..Default::default()
+    }))
+}
+
+fn recurring_merchants(home: &Path) -> Vec<String> {
+    let data = to_data(recurring::run_with_options(RecurringRunOptions {
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["rows"]
+        .as_array()
+        .map(|rows| {
+            rows.iter()
+                .filter_map(|row| row["merchant"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+const STATEMENT: &str = "account_key,posted_at,amount,currency,description,merchant,category
+chase_checking_1234,2026-01-05,-15.99,USD,NETFLIX.COM 1,NFLX WEB,
+chase_checking_1234,2026-02-05,-15.99,USD,NETFLIX.COM 2,NFLX MOBILE,
+chase_checking_1234,2026-03-05,-15.99,USD,NETFLIX.COM 3,NFLX TV,
+chase_checking_1234,2026-03-09,-4.50,USD,SQ *BLUE BOTTLE,SQ BLUE BOTTLE,Shopping
+";
+
+const CORRECTED: &str = "account_key,posted_at,amount,currency,description,merchant,category
+chase_checking_1234,2026-01-05,-15.99,USD,NETFLIX.COM 1,NFLX WEB,
+chase_checking_1234,2026-02-05,-15.99,USD,NETFLIX.COM 2,NFLX MOBILE,
+chase_checking_1234,2026-03-05,-15.99,USD,NETFLIX.COM 3,NFLX TV,
+chase_checking_1234,2026-03-09,-4.50,USD,SQ *BLUE BOTTLE,SQ BLUE BOTTLE,Shopping
+chase_checking_1234,2026-03-11,-60.00,USD,SHELL OIL,,
+";
+
+#[test]
+fn overrides_change_effective_values_and_feed_intelligence() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        to_data(import::run_with_options(ImportRunOptions {
+            path: Some(write_source(&home, "statement.csv", STATEMENT)),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        assert!(recurring_merchants(&home).is_empty());
+
+        let coffee_id = txn_id_for(&db_path, "2026-03-09");
+        let edited = to_data(transactions::edit_with_options(TransactionEditOptions {
+            txn_id: coffee_id.clone(),
+            category: Some("Coffee".to_string()),
+            note: Some("Client meeting".to_string()),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        assert_eq!(edited["category"], "Shopping");
+        assert_eq!(edited["effective_category"], "Coffee");
+        assert_eq!(edited["effective_merchant"], "SQ BLUE BOTTLE");
+        assert_eq!(edited["intelligence_refreshed"], false);
+        assert_eq!(
+            view_row(&db_path, "2026-03-09"),
+            vec![
+                Some("SQ BLUE BOTTLE".to_string()),
+                Some("Shopping".to_string()),
+                Some("SQ BLUE BOTTLE".to_string()),
+                Some("Coffee".to_string()),
+                Some("Client meeting".to_string()),
+            ]
+        );
+
+        for posted_at in ["2026-01-05", "2026-02-05", "2026-03-05"] {
+            let txn_id = txn_id_for(&db_path, posted_at);
+            let edited = edit(&home, &txn_id, Some("Netflix"), None);
+            assert_eq!(edited["intelligence_refreshed"], true);
+        }
+        assert_eq!(recurring_merchants(&home), vec!["NETFLIX".to_string()]);
+
+        let cleared = edit(&home, &coffee_id, None, Some(""));
+        assert_eq!(cleared["effective_category"], "Shopping");
+        assert_eq!(cleared["note"], "Client meeting");
+
+        let missing = transactions::edit_with_options(TransactionEditOptions {
+            txn_id: "txn_missing".to_string(),
+            note: Some("x".to_string()),
+            home_override: Some(&home),
+            ..Default::default()
+        });
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "transaction_not_found");
+        }
+        let empty = transactions::edit_with_options(TransactionEditOptions {
+            txn_id: coffee_id,
+            home_override: Some(&home),
+            ..Default::default()
+        });
+        assert!(empty.is_err());
+        if let Err(error) = empty {
+            assert_eq!(error.code, "invalid_argument");
+        }
+    }
+}
+
+#[test]
+fn overrides_reattach_after_replace_and_undo() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let imported = to_data(import::run_with_options(ImportRunOptions {
+            path: Some(write_source(&home, "statement.csv", STATEMENT)),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        let import_id = imported["import_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let coffee_id = txn_id_for(&db_path, "2026-03-09");
+        edit(&home, &coffee_id, Some("Blue Bottle"), Some("Coffee"));
+
+        let replaced = to_data(import::replace_with_options(ImportReplaceOptions {
+            import_id: import_id.clone(),
+            path: write_source(&home, "corrected.csv", CORRECTED),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        let replacement_id = replaced["import_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        assert_ne!(txn_id_for(&db_path, "2026-03-09"), coffee_id);
+        let reattached = view_row(&db_path, "2026-03-09");
+        assert_eq!(reattached[2].as_deref(), Some("Blue Bottle"));
+        assert_eq!(reattached[3].as_deref(), Some("Coffee"));
+
+        to_data(import::undo_with_options(
+            &replacement_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert!(txn_id_for(&db_path, "2026-03-09").is_empty());
+        to_data(import::run_with_options(ImportRunOptions {
+            path: Some(write_source(&home, "again.csv", CORRECTED)),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        let restored = view_row(&db_path, "2026-03-09");
+        assert_eq!(restored[0].as_deref(), Some("SQ BLUE BOTTLE"));
+        assert_eq!(restored[2].as_deref(), Some("Blue Bottle"));
+        assert_eq!(restored[3].as_deref(), Some("Coffee"));
+    }
+}
+
+fn effective_categories(db_path: &Path, description: &str) -> Vec<(String, Option<String>)> {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    let mut rows = Vec::new();
+    if let Ok(conn) = connection
+        && let Ok(mut statement) = conn.prepare(
+            "SELECT txn_id, effective_category
+             FROM v1_transactions
+             WHERE description = ?1
+             ORDER BY posted_at ASC, txn_id ASC",
+        )
+        && let Ok(mapped) = statement.query_map([description], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })
+    {
+        rows = mapped.filter_map(Result::ok).collect();
+    }
+    rows
+}
+
+const TWINS: &str = "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-03-09,-4.50,USD,SQ *BLUE BOTTLE
+chase_checking_1234,2026-03-09,-4.50,USD,SQ *BLUE BOTTLE
+";
+
+const RECYCLED_POLICY: &str = r#"{
+  "dedupe": {
+    "chase_checking_1234": {
+      "key_fields": ["external_id", "posted_at", "amount"],
+      "trust_external_id": false
+    }
+  }
+}"#;
+
+const RECYCLED_FIRST: &str = "account_key,posted_at,amount,currency,description,external_id
+chase_checking_1234,2025-01-05,-10.00,USD,COFFEE,T1
+";
+
+const RECYCLED_SECOND: &str = "account_key,posted_at,amount,currency,description,external_id
+chase_checking_1234,2026-01-05,-10.00,USD,COFFEE,T1
+";
+
+#[test]
+fn overrides_stay_on_one_of_two_identical_rows() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        to_data(import::run_with_options(ImportRunOptions {
+            path: Some(write_source(&home, "twins.csv", TWINS)),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        let twins = effective_categories(&db_path, "SQ *BLUE BOTTLE");
+        assert_eq!(twins.len(), 2);
+
+        edit(&home, &twins[1].0, None, Some("Coffee"));
+        let categories = effective_categories(&db_path, "SQ *BLUE BOTTLE")
+            .into_iter()
+            .map(|(_, category)| category)
+            .collect::<Vec<_>>();
+        assert_eq!(categories, vec![None, Some("Coffee".to_string())]);
+    }
+}
+
+#[test]
+fn overrides_follow_the_account_policy_for_recycled_external_ids() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        write_source(&home, "import_policy.json", RECYCLED_POLICY);
+        for (name, body) in [
+            ("first.csv", RECYCLED_FIRST),
+            ("second.csv", RECYCLED_SECOND),
+        ] {
+            to_data(import::run_with_options(ImportRunOptions {
+                path: Some(write_source(&home, name, body)),
+                home_override: Some(&home),
+                ..Default::default()
+            }));
+        }
+        let rows = effective_categories(&db_path, "COFFEE");
+        assert_eq!(rows.len(), 2);
+
+        edit(&home, &rows[0].0, None, Some("Coffee"));
+        let categories = effective_categories(&db_path, "COFFEE")
+            .into_iter()
+            .map(|(_, category)| category)
+            .collect::<Vec<_>>();
+        assert_eq!(categories, vec![Some("Coffee".to_string()), None]);
+    }
+}
+
+#[test]
+fn setup_keys_rows_written_before_identity_keys() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        to_data(import::run_with_options(ImportRunOptions {
+            path: Some(write_source(&home, "statement.csv", STATEMENT)),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        let coffee_id = txn_id_for(&db_path, "2026-03-09");
+        edit(&home, &coffee_id, None, Some("Coffee"));
+
+        let connection = Connection::open(&db_path);
+        assert!(connection.is_ok());
+        if let Ok(conn) = connection {
+            let cleared = conn.execute("UPDATE internal_transactions SET identity_key = NULL", []);
+            assert!(cleared.is_ok());
+        }
+        assert_eq!(
+            view_row(&db_path, "2026-03-09")[3].as_deref(),
+            Some("Shopping")
+        );
+
+        recurring_merchants(&home);
+        assert_eq!(
+            view_row(&db_path, "2026-03-09")[3].as_deref(),
+            Some("Coffee")
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{self, ImportDuplicatesOptions, ImportRunOptions};
+use driggsby_client::commands::transactions::{self, TransactionShowOptions};
+use rusqlite::Connection;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> (String, String) {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    let data = to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    (
+        data["import_id"].as_str().unwrap_or_default().to_string(),
+        source.display().to_string(),
+    )
+}
+
+fn txn_id_for(db_path: &Path, description: &str) -> String {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection
+        && let Ok(txn_id) = conn.query_row(
+            "SELECT txn_id FROM v1_transactions WHERE description = ?1",
+            [description],
+            |row| row.get::<_, String>(0),
+        )
+    {
+        return txn_id;
+    }
+    String::new()
+}
+
+fn show(
+    home: &Path,
+    txn_id: &str,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    transactions::show_with_options(TransactionShowOptions {
+        txn_id: txn_id.to_string(),
+        home_override: Some(home),
+    })
+}
+
+#[test]
+fn transaction_show_traces_csv_rows_back_to_their_original_line() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let (import_id, source_ref) = import_file(
+            &home,
+            "statement.csv",
+            "account_key,posted_at,amount,currency,description\r\n\
+             chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE\r\n\
+             chase_checking_1234,2026-01-05,-42.10,USD,\"WHOLE FOODS, \"\"MKT\"\"\nAUSTIN\"\r\n",
+        );
+
+        let txn_id = txn_id_for(&db_path, "WHOLE FOODS, \"MKT\"\nAUSTIN");
+        let shown = to_data(show(&home, &txn_id));
+        assert_eq!(shown["txn_id"], txn_id.as_str());
+        assert_eq!(shown["amount"], -42.1);
+        assert_eq!(shown["provenance"]["import_id"], import_id.as_str());
+        assert_eq!(shown["provenance"]["source_kind"], "file");
+        assert_eq!(shown["provenance"]["source_ref"], source_ref.as_str());
+        assert_eq!(shown["provenance"]["source_row_index"], 2);
+        assert_eq!(
+            shown["provenance"]["source_record"],
+            "chase_checking_1234,2026-01-05,-42.10,USD,\"WHOLE FOODS, \"\"MKT\"\"\nAUSTIN\""
+        );
+
+        let first = to_data(show(&home, &txn_id_for(&db_path, "BLUE BOTTLE COFFEE")));
+        assert_eq!(first["provenance"]["source_row_index"], 1);
+        assert_eq!(
+            first["provenance"]["source_record"],
+            "chase_checking_1234,2026-01-03,-4.50,USD,BLUE BOTTLE COFFEE"
+        );
+
+        let missing = show(&home, "txn_missing");
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "transaction_not_found");
+        }
+    }
+}
+
+#[test]
+fn transaction_show_keeps_json_records_and_follows_promoted_candidates() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let record = json!({
+            "account_key": "chase_checking_1234",
+            "posted_at": "2026-01-03",
+            "amount": -4.5,
+            "currency": "USD",
+            "description": "BLUE BOTTLE COFFEE"
+        });
+        import_file(
+            &home,
+            "first.json",
+            &serde_json::to_string(&json!([record])).unwrap_or_default(),
+        );
+        let (second_id, _) = import_file(
+            &home,
+            "second.json",
+            &serde_json::to_string_pretty(&json!([
+                {
+                    "account_key": "chase_checking_1234",
+                    "posted_at": "2026-01-09",
+                    "amount": -42.1,
+                    "currency": "USD",
+                    "description": "WHOLE FOODS"
+                },
+                record
+            ]))
+            .unwrap_or_default(),
+        );
+
+        let listed = to_data(import::duplicates_with_options(
+            &second_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        let candidate_id = listed["rows"][0]["candidate_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let promoted = to_data(import::duplicates_promote_with_options(
+            &candidate_id,
+            ImportDuplicatesOptions {
+                home_override: Some(&home),
+            },
+        ));
+        let promoted_txn_id = promoted["promoted_txn_id"].as_str().unwrap_or_default();
+
+        let shown = to_data(show(&home, promoted_txn_id));
+        assert_eq!(shown["provenance"]["import_id"], second_id.as_str());
+        assert_eq!(shown["provenance"]["source_row_index"], 2);
+        let source_record = shown["provenance"]["source_record"]
+            .as_str()
+            .map(serde_json::from_str::<Value>);
+        assert!(matches!(source_record, Some(Ok(ref value)) if value == &record));
+
+        let other = to_data(show(&home, &txn_id_for(&db_path, "WHOLE FOODS")));
+        assert_eq!(other["provenance"]["source_row_index"], 1);
+    }
+}
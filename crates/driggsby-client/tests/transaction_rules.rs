@@ -0,0 +1,641 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{
+    self, ImportRunOptions, ImportStageOptions, ImportStagedOptions,
+};
+use driggsby_client::commands::rules::{
+    self, RuleAddOptions, RuleDefinition, RuleListOptions, RuleRemoveOptions, RulesApplyOptions,
+};
+use driggsby_client::commands::sql::{self, SqlQueryOptions};
+use driggsby_client::commands::transactions::{
+    self, TransactionEditOptions, TransactionShowOptions,
+};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn write_source(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    source.display().to_string()
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> Value {
+    to_data(import::run_with_options(ImportRunOptions {
+        path: Some(write_source(home, name, body)),
+        home_override: Some(home),
+        ..Default::default()
+    }))
+}
+
+fn add_rule(
+    home: &Path,
+    rule: RuleDefinition,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    rules::add_with_options(RuleAddOptions {
+        rule,
+        home_override: Some(home),
+    })
+}
+
+fn rule_id(home: &Path, rule: RuleDefinition) -> String {
+    let data = to_data(add_rule(home, rule));
+    data["rule"]["rule_id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn apply(home: &Path, dry_run: bool) -> Value {
+    to_data(rules::apply_with_options(RulesApplyOptions {
+        dry_run,
+        home_override: Some(home),
+    }))
+}
+
+fn query_rows(home: &Path, query: &str) -> Value {
+    let data = to_data(sql::run_with_options(SqlQueryOptions {
+        query: Some(query.to_string()),
+        file: None,
+        home_override: Some(home),
+        stdin_override: None,
+        max_rows: None,
+    }));
+    data["rows"].clone()
+}
+
+fn blue_bottle() -> RuleDefinition {
+    RuleDefinition {
+        description: Some(r"^SQ \*BLUE BOTTLE".to_string()),
+        set_merchant: Some("Blue Bottle Coffee".to_string()),
+        set_category: Some("Coffee".to_string()),
+        ..Default::default()
+    }
+}
+
+const LEDGER: &str = "account_key,posted_at,amount,currency,description,category,mcc
+chase_card_1234,2026-01-03,-6.50,USD,SQ *BLUE BOTTLE OAKLAND,Restaurants,5814
+chase_card_1234,2026-01-05,-4.75,USD,SQ *BLUE BOTTLE SF,,5814
+chase_card_1234,2026-01-06,6.50,USD,SQ *BLUE BOTTLE REFUND,,5814
+chase_card_1234,2026-01-09,-41.20,USD,WHOLE FOODS MKT,Groceries,5411
+";
+
+const EFFECTIVE_QUERY: &str = "SELECT description, effective_merchant, effective_category, mcc
+     FROM v1_transactions
+     ORDER BY posted_at";
+
+#[test]
+fn rules_are_validated_ordered_and_removable() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        for invalid in [
+            RuleDefinition {
+                set_category: Some("Coffee".to_string()),
+                ..Default::default()
+            },
+            RuleDefinition {
+                mcc: Some("5814".to_string()),
+                ..Default::default()
+            },
+            RuleDefinition {
+                description: Some("(unclosed".to_string()),
+                ..blue_bottle()
+            },
+            RuleDefinition {
+                mcc: Some("58x4".to_string()),
+                ..blue_bottle()
+            },
+            RuleDefinition {
+                amount_min: Some(10.0),
+                amount_max: Some(-10.0),
+                ..blue_bottle()
+            },
+        ] {
+            let rejected = add_rule(&home, invalid);
+            assert!(rejected.is_err());
+            if let Err(error) = rejected {
+                assert_eq!(error.code, "invalid_argument");
+            }
+        }
+
+        let first = rule_id(&home, blue_bottle());
+        let second = rule_id(
+            &home,
+            RuleDefinition {
+                mcc: Some("5814".to_string()),
+                add_tags: vec!["#Coffee".to_string()],
+                ..Default::default()
+            },
+        );
+        let front = rule_id(
+            &home,
+            RuleDefinition {
+                amount_max: Some(0.0),
+                add_tags: vec!["spend".to_string()],
+                position: Some(1),
+                ..Default::default()
+            },
+        );
+
+        let listed = to_data(rules::list_with_options(RuleListOptions {
+            home_override: Some(&home),
+        }));
+        let order = |listed: &Value| {
+            listed["rows"]
+                .as_array()
+                .map(|rows| {
+                    rows.iter()
+                        .map(|row| (row["rule_id"].clone(), row["position"].clone()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        assert_eq!(
+            order(&listed),
+            vec![
+                (json!(front), json!(1)),
+                (json!(first), json!(2)),
+                (json!(second), json!(3)),
+            ]
+        );
+        assert_eq!(listed["rows"][2]["add_tags"], json!(["coffee"]));
+
+        to_data(rules::remove_with_options(RuleRemoveOptions {
+            rule_id: first.clone(),
+            home_override: Some(&home),
+        }));
+        let listed = to_data(rules::list_with_options(RuleListOptions {
+            home_override: Some(&home),
+        }));
+        assert_eq!(
+            order(&listed),
+            vec![(json!(front), json!(1)), (json!(second), json!(2))]
+        );
+        let missing = rules::remove_with_options(RuleRemoveOptions {
+            rule_id: first,
+            home_override: Some(&home),
+        });
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "rule_not_found");
+        }
+    }
+}
+
+#[test]
+fn rules_run_during_import_and_record_which_rule_changed_each_row() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let merchant_rule = rule_id(
+            &home,
+            RuleDefinition {
+                amount_max: Some(0.0),
+                ..blue_bottle()
+            },
+        );
+        let tag_rule = rule_id(
+            &home,
+            RuleDefinition {
+                mcc: Some("5814".to_string()),
+                set_category: Some("Eating Out".to_string()),
+                add_tags: vec!["coffee".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let imported = import_file(&home, "ledger.csv", LEDGER);
+        assert_eq!(
+            imported["rule_changes"],
+            json!([
+                { "rule_id": merchant_rule, "rows_changed": 2, "fields": ["merchant", "category"] },
+                { "rule_id": tag_rule, "rows_changed": 3, "fields": ["category", "tags"] }
+            ])
+        );
+        assert_eq!(
+            query_rows(&home, EFFECTIVE_QUERY),
+            json!([
+                [
+                    "SQ *BLUE BOTTLE OAKLAND",
+                    "Blue Bottle Coffee",
+                    "Coffee",
+                    "5814"
+                ],
+                ["SQ *BLUE BOTTLE SF", "Blue Bottle Coffee", "Coffee", "5814"],
+                ["SQ *BLUE BOTTLE REFUND", null, "Eating Out", "5814"],
+                ["WHOLE FOODS MKT", null, "Groceries", "5411"]
+            ])
+        );
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT t.description, h.fields
+                 FROM v1_transaction_rule_hits h
+                 JOIN v1_transactions t ON t.txn_id = h.txn_id
+                 WHERE h.rule_id = (SELECT rule_id FROM v1_transaction_rule_hits WHERE fields = 'tags' LIMIT 1)
+                 ORDER BY t.posted_at"
+            ),
+            json!([
+                ["SQ *BLUE BOTTLE OAKLAND", "tags"],
+                ["SQ *BLUE BOTTLE SF", "tags"],
+                ["SQ *BLUE BOTTLE REFUND", "category,tags"]
+            ])
+        );
+
+        let oakland_id = query_rows(
+            &home,
+            "SELECT txn_id FROM v1_transactions WHERE description = 'SQ *BLUE BOTTLE OAKLAND'",
+        )[0][0]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let shown = to_data(transactions::show_with_options(TransactionShowOptions {
+            txn_id: oakland_id,
+            home_override: Some(&home),
+        }));
+        assert_eq!(shown["mcc"], "5814");
+        assert_eq!(
+            shown["rule_hits"].as_array().map(|hits| hits
+                .iter()
+                .map(|hit| hit["fields"].clone())
+                .collect::<Vec<_>>()),
+            Some(vec![json!(["merchant", "category"]), json!(["tags"])])
+        );
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT COUNT(*) FROM v1_transaction_tags WHERE tag = 'coffee'"
+            ),
+            json!([[3]])
+        );
+
+        let staged = to_data(import::stage_with_options(ImportStageOptions {
+            path: write_source(
+                &home,
+                "staged.csv",
+                "account_key,posted_at,amount,currency,description\nchase_card_1234,2026-02-02,-5.25,USD,SQ *BLUE BOTTLE BERKELEY\n",
+            ),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        assert_eq!(staged["rule_changes"][0]["rule_id"], json!(merchant_rule));
+        to_data(import::approve_with_options(
+            staged["import_id"].as_str().unwrap_or_default(),
+            ImportStagedOptions {
+                home_override: Some(&home),
+            },
+        ));
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT t.effective_merchant, h.fields
+                 FROM v1_transactions t
+                 JOIN v1_transaction_rule_hits h ON h.txn_id = t.txn_id
+                 WHERE t.posted_at = '2026-02-02'"
+            ),
+            json!([["Blue Bottle Coffee", "merchant,category"]])
+        );
+
+        let bad_mcc = import::run_with_options(ImportRunOptions {
+            path: Some(write_source(
+                &home,
+                "bad-mcc.csv",
+                "account_key,posted_at,amount,currency,description,mcc\nchase_card_1234,2026-03-01,-1.00,USD,GUM,58\n",
+            )),
+            home_override: Some(&home),
+            ..Default::default()
+        });
+        assert!(bad_mcc.is_err());
+        if let Err(error) = bad_mcc
+            && let Some(data) = error.data
+        {
+            assert_eq!(data["issues"][0]["code"], "invalid_mcc");
+        }
+    }
+}
+
+#[test]
+fn rules_apply_previews_then_updates_existing_rows_around_manual_edits() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", LEDGER);
+        let sf_id = query_rows(
+            &home,
+            "SELECT txn_id FROM v1_transactions WHERE description = 'SQ *BLUE BOTTLE SF'",
+        )[0][0]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        to_data(transactions::edit_with_options(TransactionEditOptions {
+            txn_id: sf_id.clone(),
+            category: Some("Work Meals".to_string()),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        let merchant_rule = rule_id(&home, blue_bottle());
+
+        let preview = apply(&home, true);
+        assert_eq!(preview["dry_run"], true);
+        assert_eq!(preview["rows_changed"], 3);
+        let sf_changes = preview["changes"]
+            .as_array()
+            .map(|changes| {
+                changes
+                    .iter()
+                    .filter(|change| change["txn_id"] == json!(sf_id))
+                    .map(|change| change["field"].clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        assert_eq!(sf_changes, vec![json!("merchant")]);
+        assert_eq!(
+            query_rows(&home, "SELECT COUNT(*) FROM v1_transaction_rule_hits"),
+            json!([[0]])
+        );
+        assert_eq!(
+            query_rows(&home, EFFECTIVE_QUERY)[0],
+            json!(["SQ *BLUE BOTTLE OAKLAND", null, "Restaurants", "5814"])
+        );
+
+        let applied = apply(&home, false);
+        assert_eq!(applied["rows_changed"], 3);
+        assert_eq!(applied["intelligence_refreshed"], true);
+        assert_eq!(
+            query_rows(&home, EFFECTIVE_QUERY),
+            json!([
+                [
+                    "SQ *BLUE BOTTLE OAKLAND",
+                    "Blue Bottle Coffee",
+                    "Coffee",
+                    "5814"
+                ],
+                [
+                    "SQ *BLUE BOTTLE SF",
+                    "Blue Bottle Coffee",
+                    "Work Meals",
+                    "5814"
+                ],
+                [
+                    "SQ *BLUE BOTTLE REFUND",
+                    "Blue Bottle Coffee",
+                    "Coffee",
+                    "5814"
+                ],
+                ["WHOLE FOODS MKT", null, "Groceries", "5411"]
+            ])
+        );
+        assert_eq!(
+            query_rows(
+                &home,
+                &format!(
+                    "SELECT fields FROM v1_transaction_rule_hits WHERE txn_id = '{sf_id}' AND rule_id = '{merchant_rule}'"
+                )
+            ),
+            json!([["merchant"]])
+        );
+        assert_eq!(apply(&home, false)["rows_changed"], 0);
+
+        // A rule placed ahead may take over a field an earlier rule set.
+        rule_id(
+            &home,
+            RuleDefinition {
+                description: Some("REFUND$".to_string()),
+                set_category: Some("Refunds".to_string()),
+                position: Some(1),
+                ..Default::default()
+            },
+        );
+        let reapplied = apply(&home, false);
+        assert_eq!(reapplied["rows_changed"], 1);
+        assert_eq!(
+            query_rows(&home, EFFECTIVE_QUERY)[2],
+            json!([
+                "SQ *BLUE BOTTLE REFUND",
+                "Blue Bottle Coffee",
+                "Refunds",
+                "5814"
+            ])
+        );
+    }
+}
+
+#[test]
+fn rules_apply_leaves_the_locked_one_of_two_identical_rows_alone() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(
+            &home,
+            "twins.csv",
+            "account_key,posted_at,amount,currency,description
+chase_card_1234,2026-01-05,-4.75,USD,SQ *BLUE BOTTLE SF
+chase_card_1234,2026-01-05,-4.75,USD,SQ *BLUE BOTTLE SF
+",
+        );
+        let twins = query_rows(&home, "SELECT txn_id FROM v1_transactions ORDER BY txn_id");
+        let locked_id = twins[1][0].as_str().unwrap_or_default().to_string();
+        to_data(transactions::edit_with_options(TransactionEditOptions {
+            txn_id: locked_id.clone(),
+            merchant: Some("Client Lunch".to_string()),
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        rule_id(&home, blue_bottle());
+
+        let applied = apply(&home, false);
+        assert_eq!(applied["rows_changed"], 2);
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT effective_merchant, effective_category
+                 FROM v1_transactions
+                 ORDER BY txn_id"
+            ),
+            json!([["Blue Bottle Coffee", "Coffee"], ["Client Lunch", "Coffee"]])
+        );
+        assert_eq!(
+            query_rows(
+                &home,
+                &format!(
+                    "SELECT fields FROM v1_transaction_rule_hits WHERE txn_id = '{locked_id}'"
+                )
+            ),
+            json!([["category"]])
+        );
+    }
+}
+
+#[test]
+fn rules_match_on_account_and_mcc() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(
+            &home,
+            "rides.csv",
+            "account_key,posted_at,amount,currency,description,mcc
+chase_card_1234,2026-02-01,-12.00,USD,UBER TRIP,4121
+amex_gold_9876,2026-02-02,-18.00,USD,UBER TRIP,4121
+amex_gold_9876,2026-02-03,-9.00,USD,UBER EATS,5812
+",
+        );
+        rule_id(
+            &home,
+            RuleDefinition {
+                account_key: Some("amex_gold_9876".to_string()),
+                mcc: Some("4121".to_string()),
+                set_category: Some("Business Travel".to_string()),
+                ..Default::default()
+            },
+        );
+        rule_id(
+            &home,
+            RuleDefinition {
+                mcc: Some("5812".to_string()),
+                set_category: Some("Dining".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(apply(&home, false)["rows_changed"], 2);
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT account_key, effective_category FROM v1_transactions ORDER BY posted_at"
+            ),
+            json!([
+                ["chase_card_1234", null],
+                ["amex_gold_9876", "Business Travel"],
+                ["amex_gold_9876", "Dining"]
+            ])
+        );
+    }
+}
+
+#[test]
+fn rules_apply_dry_run_writes_nothing() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", LEDGER);
+        rule_id(
+            &home,
+            RuleDefinition {
+                add_tags: vec!["coffee".to_string()],
+                ..blue_bottle()
+            },
+        );
+        let snapshot_query = "SELECT
+                (SELECT COUNT(*) FROM v1_transaction_rule_hits),
+                (SELECT COUNT(*) FROM v1_transaction_tags),
+                (SELECT COUNT(*) FROM v1_transactions WHERE effective_merchant IS NOT NULL)";
+        let before = query_rows(&home, snapshot_query);
+        assert_eq!(before, json!([[0, 0, 0]]));
+        let effective_before = query_rows(&home, EFFECTIVE_QUERY);
+
+        let preview = apply(&home, true);
+        assert_eq!(preview["dry_run"], true);
+        assert_eq!(preview["rows_changed"], 3);
+        assert_eq!(query_rows(&home, snapshot_query), before);
+        assert_eq!(query_rows(&home, EFFECTIVE_QUERY), effective_before);
+
+        assert_eq!(apply(&home, false)["rows_changed"], 3);
+        assert_eq!(query_rows(&home, snapshot_query), json!([[3, 3, 3]]));
+    }
+}
+
+#[test]
+fn rerunning_rules_apply_replaces_a_field_an_earlier_rule_set() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", LEDGER);
+        let first = rule_id(&home, blue_bottle());
+        assert_eq!(apply(&home, false)["rows_changed"], 3);
+
+        to_data(rules::remove_with_options(RuleRemoveOptions {
+            rule_id: first,
+            home_override: Some(&home),
+        }));
+        let second = rule_id(
+            &home,
+            RuleDefinition {
+                description: Some("BLUE BOTTLE".to_string()),
+                set_category: Some("Cafes".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(apply(&home, false)["rows_changed"], 3);
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT effective_merchant, effective_category
+                 FROM v1_transactions
+                 WHERE mcc = '5814'
+                 ORDER BY posted_at"
+            ),
+            json!([
+                ["Blue Bottle Coffee", "Cafes"],
+                ["Blue Bottle Coffee", "Cafes"],
+                ["Blue Bottle Coffee", "Cafes"]
+            ])
+        );
+        assert_eq!(
+            query_rows(
+                &home,
+                &format!(
+                    "SELECT COUNT(*) FROM v1_transaction_rule_hits WHERE rule_id = '{second}' AND fields = 'category'"
+                )
+            ),
+            json!([[3]])
+        );
+    }
+}
+
+#[test]
+fn unreadable_rule_tags_fail_instead_of_being_dropped() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(&home, "ledger.csv", LEDGER);
+        let broken = rule_id(&home, blue_bottle());
+        let connection = rusqlite::Connection::open(home.join("ledger.db"));
+        assert!(connection.is_ok());
+        if let Ok(connection) = connection {
+            let updated = connection.execute(
+                "UPDATE internal_rules SET add_tags = 'coffee' WHERE rule_id = ?1",
+                [&broken],
+            );
+            assert_eq!(updated.ok(), Some(1));
+        }
+
+        let applied = rules::apply_with_options(RulesApplyOptions {
+            dry_run: true,
+            home_override: Some(&home),
+        });
+        assert!(applied.is_err());
+        if let Err(error) = applied {
+            assert_eq!(error.code, "rule_invalid");
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::import::{self, ImportRunOptions, ImportUndoOptions};
+use driggsby_client::commands::sql::{self, SqlQueryOptions};
+use driggsby_client::commands::transactions::{
+    self, TransactionShowOptions, TransactionSplitOptions,
+};
+use rusqlite::Connection;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> Value {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }))
+}
+
+fn txn_id_for(db_path: &Path, description: &str) -> String {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection
+        && let Ok(txn_id) = conn.query_row(
+            "SELECT txn_id FROM v1_transactions WHERE description = ?1",
+            [description],
+            |row| row.get::<_, String>(0),
+        )
+    {
+        return txn_id;
+    }
+    String::new()
+}
+
+fn split(
+    home: &Path,
+    txn_id: &str,
+    parts: &[&str],
+    clear: bool,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    transactions::split_with_options(TransactionSplitOptions {
+        txn_id: txn_id.to_string(),
+        parts: parts.iter().map(|part| part.to_string()).collect(),
+        clear,
+        home_override: Some(home),
+    })
+}
+
+fn query_rows(home: &Path, query: &str) -> Value {
+    let data = to_data(sql::run_with_options(SqlQueryOptions {
+        query: Some(query.to_string()),
+        file: None,
+        home_override: Some(home),
+        stdin_override: None,
+        max_rows: None,
+    }));
+    data["rows"].clone()
+}
+
+const ROLLUP_QUERY: &str = "SELECT month, category, txn_count, amount
+     FROM v1_category_rollup
+     ORDER BY month, category";
+
+const LEDGER: &str = "account_key,posted_at,amount,currency,description,category
+chase_checking_1234,2026-01-03,-41.20,USD,WHOLE FOODS MKT,Groceries
+chase_checking_1234,2026-01-09,-120.00,USD,COSTCO WHOLESALE,Shopping
+chase_checking_1234,2026-02-02,-15.99,USD,NETFLIX.COM,Entertainment
+";
+
+#[test]
+fn splits_must_balance_and_feed_the_category_rollup() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        import_file(&home, "ledger.csv", LEDGER);
+        let costco_id = txn_id_for(&db_path, "COSTCO WHOLESALE");
+
+        let unbalanced = split(&home, &costco_id, &["80:Groceries", "30:Gifts"], false);
+        assert!(unbalanced.is_err());
+        if let Err(error) = unbalanced {
+            assert_eq!(error.code, "invalid_argument");
+            assert!(error.message.contains("-110.00"));
+        }
+        assert!(split(&home, &costco_id, &["120:Groceries"], false).is_err());
+        assert!(split(&home, &costco_id, &["Groceries"], false).is_err());
+        assert!(split(&home, &costco_id, &[], false).is_err());
+        assert!(split(&home, &costco_id, &["80:Groceries", "40:Gifts"], true).is_err());
+        let missing = split(&home, "txn_missing", &["80:Groceries", "40:Gifts"], false);
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "transaction_not_found");
+        }
+
+        let applied = to_data(split(
+            &home,
+            &costco_id,
+            &["80:Groceries", "25.50:Household", "14.50:Gifts"],
+            false,
+        ));
+        assert_eq!(applied["amount"], -120.0);
+        assert_eq!(
+            applied["splits"],
+            json!([
+                { "split_index": 1, "amount": -80.0, "category": "Groceries" },
+                { "split_index": 2, "amount": -25.5, "category": "Household" },
+                { "split_index": 3, "amount": -14.5, "category": "Gifts" }
+            ])
+        );
+        let shown = to_data(transactions::show_with_options(TransactionShowOptions {
+            txn_id: costco_id.clone(),
+            home_override: Some(&home),
+        }));
+        assert_eq!(shown["splits"].as_array().map(Vec::len), Some(3));
+
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT category, amount FROM v1_transaction_splits ORDER BY split_index"
+            ),
+            json!([["Groceries", -80.0], ["Household", -25.5], ["Gifts", -14.5]])
+        );
+        assert_eq!(
+            query_rows(&home, ROLLUP_QUERY),
+            json!([
+                ["2026-01", "Gifts", 1, -14.5],
+                ["2026-01", "Groceries", 2, -121.2],
+                ["2026-01", "Household", 1, -25.5],
+                ["2026-02", "Entertainment", 1, -15.99]
+            ])
+        );
+
+        let cleared = to_data(split(&home, &costco_id, &[], true));
+        assert_eq!(cleared["splits"], json!([]));
+        assert_eq!(
+            query_rows(&home, ROLLUP_QUERY),
+            json!([
+                ["2026-01", "Groceries", 1, -41.2],
+                ["2026-01", "Shopping", 1, -120.0],
+                ["2026-02", "Entertainment", 1, -15.99]
+            ])
+        );
+    }
+}
+
+#[test]
+fn splits_leave_dedupe_and_undo_of_the_parent_alone() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let first = import_file(&home, "ledger.csv", LEDGER);
+        let import_id = first["import_id"].as_str().unwrap_or_default().to_string();
+        let costco_id = txn_id_for(&db_path, "COSTCO WHOLESALE");
+        to_data(split(
+            &home,
+            &costco_id,
+            &["-80:Groceries", "-40:Gifts"],
+            false,
+        ));
+
+        let again = import_file(
+            &home,
+            "ledger-again.csv",
+            &format!("{LEDGER}chase_checking_1234,2026-02-09,-9.99,USD,SPOTIFY,Entertainment\n"),
+        );
+        assert_eq!(again["summary"]["inserted"], 1);
+        assert_eq!(again["duplicate_summary"]["existing_ledger"], 3);
+        to_data(import::undo_with_options(
+            again["import_id"].as_str().unwrap_or_default(),
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(
+            query_rows(&home, "SELECT txn_id FROM v1_transaction_splits"),
+            json!([[costco_id], [costco_id]])
+        );
+
+        to_data(import::undo_with_options(
+            &import_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(
+            query_rows(&home, "SELECT txn_id FROM v1_transaction_splits"),
+            json!([])
+        );
+        assert_eq!(query_rows(&home, ROLLUP_QUERY), json!([]));
+
+        import_file(&home, "ledger-third.csv", LEDGER);
+        let reimported_id = txn_id_for(&db_path, "COSTCO WHOLESALE");
+        assert_ne!(reimported_id, costco_id);
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT txn_id, category FROM v1_transaction_splits ORDER BY split_index"
+            ),
+            json!([[reimported_id, "Groceries"], [reimported_id, "Gifts"]])
+        );
+    }
+}
+
+#[test]
+fn splits_stay_on_one_of_two_identical_rows() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        import_file(
+            &home,
+            "twins.csv",
+            "account_key,posted_at,amount,currency,description,category
+chase_checking_1234,2026-01-09,-120.00,USD,COSTCO WHOLESALE,Shopping
+chase_checking_1234,2026-01-09,-120.00,USD,COSTCO WHOLESALE,Shopping
+",
+        );
+        let twins = query_rows(&home, "SELECT txn_id FROM v1_transactions ORDER BY txn_id");
+        let first_id = twins[0][0].as_str().unwrap_or_default().to_string();
+        to_data(split(
+            &home,
+            &first_id,
+            &["80:Groceries", "40:Gifts"],
+            false,
+        ));
+
+        assert_eq!(
+            query_rows(
+                &home,
+                "SELECT txn_id, COUNT(*) FROM v1_transaction_splits GROUP BY txn_id"
+            ),
+            json!([[first_id, 2]])
+        );
+        assert_eq!(
+            query_rows(&home, ROLLUP_QUERY),
+            json!([
+                ["2026-01", "Gifts", 1, -40.0],
+                ["2026-01", "Groceries", 1, -80.0],
+                ["2026-01", "Shopping", 1, -120.0]
+            ])
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use driggsby_client::commands::anomalies::{self, AnomaliesRunOptions};
+use driggsby_client::commands::import::{self, ImportRunOptions, ImportUndoOptions};
+use driggsby_client::commands::recurring::{self, RecurringRunOptions};
+use driggsby_client::commands::sql::{self, SqlQueryOptions};
+use driggsby_client::commands::tags::{self, TagChangeOptions, TagListOptions};
+use driggsby_client::commands::transactions::{self, TransactionExcludeOptions};
+use rusqlite::Connection;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+fn temp_home() -> std::io::Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempdir()?;
+    let home = dir.path().join("ledger-home");
+    fs::create_dir_all(&home)?;
+    Ok((dir, home))
+}
+
+fn to_data(result: driggsby_client::ClientResult<driggsby_client::SuccessEnvelope>) -> Value {
+    assert!(result.is_ok());
+    if let Ok(success) = result
+        && let Ok(value) = serde_json::to_value(success)
+    {
+        return value["data"].clone();
+    }
+    Value::Null
+}
+
+fn import_file(home: &Path, name: &str, body: &str) -> String {
+    let source = home.join(name);
+    let write = fs::write(&source, body);
+    assert!(write.is_ok());
+    let data = to_data(import::run_with_options(ImportRunOptions {
+        path: Some(source.display().to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["import_id"].as_str().unwrap_or_default().to_string()
+}
+
+fn txn_id_for(db_path: &Path, posted_at: &str) -> String {
+    let connection = Connection::open(db_path);
+    assert!(connection.is_ok());
+    if let Ok(conn) = connection
+        && let Ok(txn_id) = conn.query_row(
+            "SELECT txn_id FROM v1_transactions WHERE posted_at = ?1",
+            [posted_at],
+            |row| row.get::<_, String>(0),
+        )
+    {
+        return txn_id;
+    }
+    String::new()
+}
+
+fn tag_add(
+    home: &Path,
+    tag: &str,
+    txn_ids: &[&str],
+    filter: Option<&str>,
+) -> driggsby_client::ClientResult<driggsby_client::SuccessEnvelope> {
+    tags::add_with_options(TagChangeOptions {
+        tag: tag.to_string(),
+        txn_ids: txn_ids.iter().map(|value| value.to_string()).collect(),
+        filter: filter.map(str::to_string),
+        home_override: Some(home),
+    })
+}
+
+fn tag_counts(home: &Path, txn_id: Option<&str>) -> Value {
+    let data = to_data(tags::list_with_options(TagListOptions {
+        txn_id: txn_id.map(str::to_string),
+        home_override: Some(home),
+    }));
+    Value::Array(
+        data["rows"]
+            .as_array()
+            .map(|rows| {
+                rows.iter()
+                    .map(|row| json!([row["tag"], row["txn_count"]]))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    )
+}
+
+fn recurring_merchants(home: &Path, tag: &str) -> Vec<String> {
+    let data = to_data(recurring::run_with_options(RecurringRunOptions {
+        tag: Some(tag.to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["rows"]
+        .as_array()
+        .map(|rows| {
+            rows.iter()
+                .filter_map(|row| row["merchant"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn anomaly_count(home: &Path, tag: &str) -> usize {
+    let data = to_data(anomalies::run_with_options(AnomaliesRunOptions {
+        tag: Some(tag.to_string()),
+        home_override: Some(home),
+        ..Default::default()
+    }));
+    data["rows"].as_array().map(Vec::len).unwrap_or_default()
+}
+
+const LEDGER: &str = "account_key,posted_at,amount,currency,description,merchant
+chase_checking_1234,2026-01-03,-41.20,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-01-05,-15.99,USD,NETFLIX.COM,Netflix
+chase_checking_1234,2026-01-10,-38.75,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-01-17,-42.10,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-01-24,-39.60,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-01-31,-40.45,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-02-05,-15.99,USD,NETFLIX.COM,Netflix
+chase_checking_1234,2026-02-07,-41.05,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-02-14,-39.90,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-02-21,-400.00,USD,WHOLE FOODS MKT,Whole Foods
+chase_checking_1234,2026-03-05,-15.99,USD,NETFLIX.COM,Netflix
+chase_checking_1234,2026-03-09,-4.50,USD,BLUE BOTTLE COFFEE,Blue Bottle
+chase_checking_1234,2026-03-11,-60.00,USD,SHELL OIL,Shell
+";
+
+#[test]
+fn tags_select_by_id_and_filter_and_feed_sql_and_intelligence() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        import_file(&home, "ledger.csv", LEDGER);
+        let coffee_id = txn_id_for(&db_path, "2026-03-09");
+
+        let added = to_data(tag_add(
+            &home,
+            "#Reimbursable",
+            &[&coffee_id],
+            Some("description = 'SHELL OIL'"),
+        ));
+        assert_eq!(added["tag"], "reimbursable");
+        assert_eq!(added["changed"], 2);
+        assert_eq!(added["txn_ids"].as_array().map(Vec::len), Some(2));
+        let again = to_data(tag_add(&home, "reimbursable", &[&coffee_id], None));
+        assert_eq!(again["changed"], 0);
+
+        to_data(tag_add(
+            &home,
+            "subscriptions",
+            &[],
+            Some("merchant = 'Netflix'"),
+        ));
+        to_data(tag_add(
+            &home,
+            "review",
+            &[&txn_id_for(&db_path, "2026-02-21")],
+            None,
+        ));
+        assert_eq!(
+            tag_counts(&home, None),
+            json!([["reimbursable", 2], ["review", 1], ["subscriptions", 3]])
+        );
+        assert_eq!(
+            tag_counts(&home, Some(&coffee_id)),
+            json!([["reimbursable", 1]])
+        );
+
+        let joined = to_data(sql::run_with_options(SqlQueryOptions {
+            query: Some(
+                "SELECT t.description FROM v1_transactions t
+                 JOIN v1_transaction_tags g ON g.txn_id = t.txn_id
+                 WHERE g.tag = 'reimbursable'
+                 ORDER BY t.posted_at"
+                    .to_string(),
+            ),
+            file: None,
+            home_override: Some(&home),
+            stdin_override: None,
+            max_rows: None,
+        }));
+        assert_eq!(
+            joined["rows"],
+            json!([["BLUE BOTTLE COFFEE"], ["SHELL OIL"]])
+        );
+
+        assert_eq!(recurring_merchants(&home, "subscriptions"), vec!["NETFLIX"]);
+        assert!(recurring_merchants(&home, "reimbursable").is_empty());
+        assert_eq!(anomaly_count(&home, "review"), 1);
+        assert_eq!(anomaly_count(&home, "subscriptions"), 0);
+
+        let blocked = tag_add(
+            &home,
+            "reimbursable",
+            &[],
+            Some("txn_id IN (SELECT txn_id FROM internal_transactions)"),
+        );
+        assert!(blocked.is_err());
+        if let Err(error) = blocked {
+            assert_eq!(error.code, "invalid_argument");
+        }
+        let unselected = tag_add(&home, "reimbursable", &[], None);
+        assert!(unselected.is_err());
+        let invalid = tag_add(&home, "not a tag", &[&coffee_id], None);
+        assert!(invalid.is_err());
+        let missing = tag_add(&home, "reimbursable", &[&coffee_id, "txn_missing"], None);
+        assert!(missing.is_err());
+        if let Err(error) = missing {
+            assert_eq!(error.code, "transaction_not_found");
+        }
+
+        let removed = to_data(tags::remove_with_options(TagChangeOptions {
+            tag: "reimbursable".to_string(),
+            txn_ids: vec![coffee_id],
+            home_override: Some(&home),
+            ..Default::default()
+        }));
+        assert_eq!(removed["changed"], 1);
+    }
+}
+
+#[test]
+fn tags_reattach_when_rows_are_imported_again() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let import_id = import_file(&home, "ledger.csv", LEDGER);
+        to_data(tag_add(
+            &home,
+            "vacation-2026",
+            &[],
+            Some("posted_at >= '2026-03-09'"),
+        ));
+
+        to_data(import::undo_with_options(
+            &import_id,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(tag_counts(&home, None), json!([]));
+
+        import_file(&home, "ledger-again.csv", LEDGER);
+        assert_eq!(tag_counts(&home, None), json!([["vacation-2026", 2]]));
+        assert_eq!(
+            tag_counts(&home, Some(&txn_id_for(&db_path, "2026-03-11"))),
+            json!([["vacation-2026", 1]])
+        );
+    }
+}
+
+#[test]
+fn tags_stay_on_one_of_two_identical_rows() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        import_file(
+            &home,
+            "twins.csv",
+            "account_key,posted_at,amount,currency,description
+chase_checking_1234,2026-03-09,-4.50,USD,BLUE BOTTLE COFFEE
+chase_checking_1234,2026-03-09,-4.50,USD,BLUE BOTTLE COFFEE
+",
+        );
+        let mut twins = Vec::new();
+        let connection = Connection::open(&db_path);
+        assert!(connection.is_ok());
+        if let Ok(conn) = connection
+            && let Ok(mut statement) =
+                conn.prepare("SELECT txn_id FROM v1_transactions ORDER BY txn_id")
+            && let Ok(rows) = statement.query_map([], |row| row.get::<_, String>(0))
+        {
+            twins = rows.filter_map(Result::ok).collect();
+        }
+        assert_eq!(twins.len(), 2);
+
+        to_data(tag_add(&home, "reimbursable", &[twins[0].as_str()], None));
+        assert_eq!(tag_counts(&home, None), json!([["reimbursable", 1]]));
+        assert_eq!(
+            tag_counts(&home, Some(&twins[0])),
+            json!([["reimbursable", 1]])
+        );
+        assert_eq!(tag_counts(&home, Some(&twins[1])), json!([]));
+    }
+}
+
+#[test]
+fn annotations_do_not_pass_to_a_later_charge_under_a_date_tolerance_policy() {
+    let temp = temp_home();
+    assert!(temp.is_ok());
+    if let Ok((_temp, home)) = temp {
+        let db_path = home.join("ledger.db");
+        let policy = fs::write(
+            home.join("import_policy.json"),
+            r#"{"dedupe": {"acct_1": {"date_tolerance_days": 3}}}"#,
+        );
+        assert!(policy.is_ok());
+        let header = "account_key,posted_at,amount,currency,description\n";
+        let january = import_file(
+            &home,
+            "jan.csv",
+            &format!("{header}acct_1,2026-01-10,-15.49,USD,NETFLIX.COM\n"),
+        );
+        import_file(
+            &home,
+            "feb.csv",
+            &format!("{header}acct_1,2026-02-10,-15.49,USD,NETFLIX.COM\n"),
+        );
+        let january_id = txn_id_for(&db_path, "2026-01-10");
+        to_data(transactions::exclude_with_options(
+            TransactionExcludeOptions {
+                txn_id: january_id.clone(),
+                reason: "transfer".to_string(),
+                home_override: Some(&home),
+            },
+        ));
+        to_data(tag_add(&home, "vacation", &[&january_id], None));
+
+        let undone = import::undo_with_options(
+            &january,
+            ImportUndoOptions {
+                home_override: Some(&home),
+                ..Default::default()
+            },
+        );
+        assert!(undone.is_ok());
+        import_file(
+            &home,
+            "apr.csv",
+            &format!("{header}acct_1,2026-04-10,-15.49,USD,NETFLIX.COM\n"),
+        );
+
+        let data = to_data(sql::run_with_options(SqlQueryOptions {
+            query: Some(
+                "SELECT t.posted_at, t.excluded, COUNT(g.tag)
+                 FROM v1_transactions t
+                 LEFT JOIN v1_transaction_tags g ON g.txn_id = t.txn_id
+                 GROUP BY t.txn_id
+                 ORDER BY t.posted_at"
+                    .to_string(),
+            ),
+            file: None,
+            home_override: Some(&home),
+            stdin_override: None,
+            max_rows: None,
+        }));
+        assert_eq!(
+            data["rows"],
+            json!([["2026-02-10", 0, 0], ["2026-04-10", 0, 0]])
+        );
+    }
+}